@@ -1,11 +1,40 @@
 //! Easing functions for smooth animation transitions
 
-use crate::{Easing, SpringConfig};
+use crate::{Easing, SpringConfig, SpringSimulator};
 
 /// Easing function type
 pub type EasingFn = fn(f64) -> f64;
 
+/// Steps of the CSS `linear()` curve a spring is sampled into
+const SPRING_CSS_SAMPLES: usize = 40;
+
 impl Easing {
+    /// CSS timing function following this easing
+    ///
+    /// Springs are sampled into a `linear()` curve over their settling
+    /// duration, so they need a matching CSS duration.
+    pub fn to_css(&self) -> String {
+        match self {
+            Easing::Linear => "linear".to_string(),
+            Easing::EaseIn => "ease-in".to_string(),
+            Easing::EaseOut => "ease-out".to_string(),
+            Easing::EaseInOut => "ease-in-out".to_string(),
+            Easing::CircIn => "cubic-bezier(0.55, 0.055, 0.675, 0.19)".to_string(),
+            Easing::CircOut => "cubic-bezier(0.215, 0.61, 0.355, 1)".to_string(),
+            Easing::CircInOut => "cubic-bezier(0.645, 0.045, 0.355, 1)".to_string(),
+            Easing::BackIn => "cubic-bezier(0.6, -0.28, 0.735, 0.045)".to_string(),
+            Easing::BackOut => "cubic-bezier(0.175, 0.885, 0.32, 1.275)".to_string(),
+            Easing::BackInOut => "cubic-bezier(0.68, -0.55, 0.265, 1.55)".to_string(),
+            Easing::Spring(config) => SpringSimulator::new(config.clone())
+                .map(|spring| spring.css_linear_easing(SPRING_CSS_SAMPLES))
+                .unwrap_or_else(|_| "linear".to_string()),
+            Easing::Bezier(x1, y1, x2, y2) => format!("cubic-bezier({x1}, {y1}, {x2}, {y2})"),
+            Easing::CubicBezier(cb) => {
+                format!("cubic-bezier({}, {}, {}, {})", cb.0, cb.1, cb.2, cb.3)
+            }
+        }
+    }
+
     /// Evaluate the easing function at time t (0.0 to 1.0)
     pub fn evaluate(&self, t: f64) -> f64 {
         let t = t.clamp(0.0, 1.0);
@@ -21,12 +50,7 @@ impl Easing {
             Easing::BackIn => back_in(t),
             Easing::BackOut => back_out(t),
             Easing::BackInOut => back_in_out(t),
-            Easing::Spring(config) => {
-                // For UI purposes, we approximate spring with a ease-out curve
-                // Real spring physics is handled by the spring module
-                let factor = (config.stiffness / 100.0).clamp(0.5, 2.0);
-                ease_out_expo(t).powf(1.0 / factor)
-            }
+            Easing::Spring(config) => spring_progress(config, t),
             Easing::Bezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, t),
             Easing::CubicBezier(cb) => cubic_bezier(cb.0, cb.1, cb.2, cb.3, t),
         }
@@ -45,10 +69,10 @@ impl Easing {
             Easing::BackIn => Box::new(back_in),
             Easing::BackOut => Box::new(back_out),
             Easing::BackInOut => Box::new(back_in_out),
-            Easing::Spring(config) => {
-                let factor = (config.stiffness / 100.0).clamp(0.5, 2.0);
-                Box::new(move |t| ease_out_expo(t).powf(1.0 / factor))
-            }
+            Easing::Spring(config) => match SpringSimulator::new(config.clone()) {
+                Ok(spring) => Box::new(move |t| spring.progress_at(t)),
+                Err(_) => Box::new(linear),
+            },
             Easing::Bezier(x1, y1, x2, y2) => {
                 let x1 = *x1;
                 let y1 = *y1;
@@ -67,6 +91,18 @@ impl Easing {
     }
 }
 
+/// Progress of a spring with `config` at `t` of its settling duration
+///
+/// Follows the same physics as springs driven by time, so a spring eased
+/// animation and a spring simulated frame by frame move alike. Invalid
+/// configs fall back to linear progress.
+pub fn spring_progress(config: &SpringConfig, t: f64) -> f64 {
+    match SpringSimulator::new(config.clone()) {
+        Ok(spring) => spring.progress_at(t),
+        Err(_) => t,
+    }
+}
+
 // Basic easing functions
 
 /// Linear interpolation (no easing)
@@ -316,4 +352,18 @@ mod tests {
         assert_eq!(easing.evaluate(-0.1), 0.0);
         assert_eq!(easing.evaluate(1.1), 1.0);
     }
+
+    #[test]
+    fn test_easing_to_css() {
+        assert_eq!(Easing::EaseOut.to_css(), "ease-out");
+        assert_eq!(
+            Easing::Bezier(0.4, 0.0, 0.2, 1.0).to_css(),
+            "cubic-bezier(0.4, 0, 0.2, 1)"
+        );
+        assert!(
+            Easing::Spring(SpringConfig::default())
+                .to_css()
+                .starts_with("linear(")
+        );
+    }
 }
@@ -4,6 +4,9 @@
 use crate::performance::{
     AnimationPool, AnimationScheduler, GPULayerManager, PerformanceBudget, PerformanceMonitor,
 };
+use crate::svg::{AttributeTween, is_svg_attribute, svg_attribute_value};
+#[cfg(feature = "web-sys")]
+use crate::svg::{PATH_LENGTH_ATTRIBUTE, PathDrawing, is_path_drawing};
 use crate::{
     AnimationError, AnimationHandle, AnimationTarget, AnimationValue, Result, Transform, Transition,
 };
@@ -613,25 +616,79 @@ impl AnimationEngine for RafEngine {
     }
 }
 
+/// Sizes that can animate to and from `auto`
+#[cfg(feature = "web-sys")]
+const AUTO_SIZE_PROPERTIES: [&str; 2] = ["height", "width"];
+
+/// Measure the `auto` sizes of `config` so they animate in pixels
+///
+/// Returns the properties animating to `auto`, which are handed back to
+/// `auto` once the animation completes.
+fn resolve_auto_sizes(config: &mut AnimationConfig) -> Vec<String> {
+    let mut to_auto = Vec::new();
+    #[cfg(feature = "web-sys")]
+    for property in AUTO_SIZE_PROPERTIES {
+        if matches!(config.from.get(property), Some(AnimationValue::Auto))
+            && let Some(size) = crate::style::computed_size(&config.element, property)
+        {
+            config
+                .from
+                .insert(property.to_string(), AnimationValue::Pixels(size));
+        }
+        if matches!(config.to.get(property), Some(AnimationValue::Auto))
+            && let Some(size) = crate::style::natural_size(&config.element, property)
+        {
+            config
+                .to
+                .insert(property.to_string(), AnimationValue::Pixels(size));
+            to_auto.push(property.to_string());
+        }
+    }
+    #[cfg(not(feature = "web-sys"))]
+    let _ = config;
+    to_auto
+}
+
 /// RAF animation state
 struct RafAnimation {
     config: AnimationConfig,
     start_time: f64,
     state: PlaybackState,
+    /// Sizes animating to `auto` in pixels, set back to `auto` when complete
+    auto_sizes: Vec<String>,
+    /// Tweens of the animated SVG attributes
+    svg_tweens: HashMap<String, AttributeTween>,
 }
 
 impl RafAnimation {
-    fn new(config: AnimationConfig, start_time: f64) -> Self {
+    fn new(mut config: AnimationConfig, start_time: f64) -> Self {
+        let auto_sizes = resolve_auto_sizes(&mut config);
+        let svg_tweens = config
+            .from
+            .iter()
+            .filter(|(property, _)| is_svg_attribute(property))
+            .filter_map(|(property, from)| {
+                let to = config.to.get(property)?;
+                let tween = AttributeTween::new(
+                    property,
+                    &svg_attribute_value(from),
+                    &svg_attribute_value(to),
+                );
+                Some((property.clone(), tween))
+            })
+            .collect();
         Self {
             config,
             start_time,
             state: PlaybackState::Running,
+            auto_sizes,
+            svg_tweens,
         }
     }
 
     fn update(&mut self, timestamp: f64) {
         let elapsed = timestamp - self.start_time;
-        let duration = self.config.transition.duration.unwrap_or(1.0) * 1000.0; // Convert to ms
+        let duration = self.config.transition.effective_duration().unwrap_or(1.0) * 1000.0; // Convert to ms
 
         if elapsed >= duration {
             self.state = PlaybackState::Completed;
@@ -642,6 +699,9 @@ impl RafAnimation {
 
         // Apply animation values to element
         self.apply_values(eased_progress);
+        if self.state == PlaybackState::Completed {
+            self.restore_auto_sizes();
+        }
 
         if let Some(_callback_id) = self.config.on_update_id {
             // Call callback by ID (simplified for now)
@@ -649,6 +709,23 @@ impl RafAnimation {
         }
     }
 
+    /// Leave sizes that animated to `auto` free to follow their content
+    fn restore_auto_sizes(&self) {
+        #[cfg(feature = "web-sys")]
+        {
+            let style = self
+                .config
+                .element
+                .unchecked_ref::<web_sys::HtmlElement>()
+                .style();
+            for property in &self.auto_sizes {
+                let _ = style.set_property(property, "auto");
+            }
+        }
+        #[cfg(not(feature = "web-sys"))]
+        let _ = &self.auto_sizes;
+    }
+
     fn apply_values(&self, progress: f64) {
         // Interpolate between from and to values based on progress
         let mut current_values = HashMap::new();
@@ -656,7 +733,10 @@ impl RafAnimation {
         for (property, from_value) in &self.config.from {
             if let Some(to_value) = self.config.to.get(property) {
                 // Interpolate between from and to values
-                let interpolated = self.interpolate_values(from_value, to_value, progress);
+                let interpolated = match self.svg_tweens.get(property) {
+                    Some(tween) => AnimationValue::String(tween.at(progress)),
+                    None => self.interpolate_values(from_value, to_value, progress),
+                };
                 current_values.insert(property.clone(), interpolated);
             }
         }
@@ -741,13 +821,29 @@ impl RafAnimation {
     }
 
     fn apply_to_element(&self, values: &HashMap<String, AnimationValue>) {
-        // Apply values directly to element properties
-        for (property, value) in values {
-            let css_value = self.animation_value_to_css(property, value);
-            // For now, we'll use a simplified approach
-            // In a real implementation, this would update the element's style
-            let _ = css_value; // Suppress unused variable warning
+        // SVG geometry is written as attributes, everything else as CSS
+        #[cfg(feature = "web-sys")]
+        {
+            let element = &self.config.element;
+            let style = element.unchecked_ref::<web_sys::HtmlElement>().style();
+            for (property, value) in values {
+                if is_svg_attribute(property) {
+                    let _ = element.set_attribute(property, &svg_attribute_value(value));
+                } else if !is_path_drawing(property) {
+                    let css_value = self.animation_value_to_css(property, value);
+                    let _ = style.set_property(property, &css_value);
+                }
+            }
+            if let Some(drawing) = PathDrawing::from_target(values) {
+                let (name, value) = PATH_LENGTH_ATTRIBUTE;
+                let _ = element.set_attribute(name, value);
+                for (property, value) in drawing.css_properties() {
+                    let _ = style.set_property(property, &value);
+                }
+            }
         }
+        #[cfg(not(feature = "web-sys"))]
+        let _ = values;
     }
 
     fn animation_value_to_css(&self, _property: &str, value: &AnimationValue) -> String {
@@ -45,6 +45,10 @@ pub mod simplified_engine;
 pub mod minimal_serialization;
 #[cfg(feature = "approx")]
 pub mod spring;
+pub mod stagger;
+#[cfg(feature = "web-sys")]
+pub mod style;
+pub mod svg;
 
 #[cfg(test)]
 mod fuzz_tests_1;
@@ -126,7 +130,9 @@ pub use interpolation::Interpolate;
 #[cfg(feature = "approx")]
 pub use math::{clamp, distance_2d, map_range, smooth_step, smoother_step};
 #[cfg(feature = "web-sys")]
-pub use time::Timer;
+pub use style::{computed_size, inline_style, natural_size};
+#[cfg(feature = "web-sys")]
+pub use time::{FrameLoop, Timer, request_frame, run_frames};
 #[cfg(feature = "approx")]
 pub use types::SpringConfig;
 pub use types::{
@@ -148,6 +154,7 @@ pub use minimal_engine::MinimalEngine;
 pub use simplified_engine::SimplifiedAnimationEngine;
 #[cfg(feature = "approx")]
 pub use spring::{SpringSimulator, SpringState};
+pub use stagger::StaggerResolver;
 
 // Feature-specific re-exports
 #[cfg(feature = "performance-metrics")]
@@ -13,6 +13,9 @@ pub struct SpringState {
     pub time: f64,
 }
 
+/// Longest duration a spring animation driven by progress is stretched over
+pub const MAX_SPRING_DURATION: f64 = 10.0;
+
 /// Spring physics simulator
 #[derive(Debug, Clone)]
 pub struct SpringSimulator {
@@ -53,25 +56,13 @@ impl SpringSimulator {
     }
 
     /// Calculate spring position at given time
+    ///
+    /// The spring starts at `from` with the configured initial velocity.
     pub fn position(&self, from: f64, to: f64, time: f64) -> f64 {
         if time <= 0.0 {
             return from;
         }
-
-        let displacement = to - from;
-        let initial_velocity = self.config.velocity;
-
-        match self.spring_type() {
-            SpringType::Underdamped => {
-                self.underdamped_position(displacement, initial_velocity, time) + from
-            }
-            SpringType::CriticallyDamped => {
-                self.critically_damped_position(displacement, initial_velocity, time) + from
-            }
-            SpringType::Overdamped => {
-                self.overdamped_position(displacement, initial_velocity, time) + from
-            }
-        }
+        to + self.offset(from - to, time).0
     }
 
     /// Calculate spring velocity at given time
@@ -79,21 +70,7 @@ impl SpringSimulator {
         if time <= 0.0 {
             return self.config.velocity;
         }
-
-        let displacement = to - from;
-        let initial_velocity = self.config.velocity;
-
-        match self.spring_type() {
-            SpringType::Underdamped => {
-                self.underdamped_velocity(displacement, initial_velocity, time)
-            }
-            SpringType::CriticallyDamped => {
-                self.critically_damped_velocity(displacement, initial_velocity, time)
-            }
-            SpringType::Overdamped => {
-                self.overdamped_velocity(displacement, initial_velocity, time)
-            }
-        }
+        self.offset(from - to, time).1
     }
 
     /// Calculate complete spring state at given time
@@ -132,6 +109,35 @@ impl SpringSimulator {
         }
     }
 
+    /// Progress from 0 to 1 at `progress` through the spring's settling duration
+    ///
+    /// This is the curve an [`Easing::Spring`](crate::Easing::Spring) follows
+    /// when an animation is driven by progress rather than time.
+    pub fn progress_at(&self, progress: f64) -> f64 {
+        if progress >= 1.0 {
+            return 1.0;
+        }
+        self.position(0.0, 1.0, progress.max(0.0) * self.settling_duration())
+    }
+
+    /// Time in seconds for a spring from 0 to 1 to settle, at most [`MAX_SPRING_DURATION`]
+    pub fn settling_duration(&self) -> f64 {
+        self.estimate_duration(0.0, 1.0).min(MAX_SPRING_DURATION)
+    }
+
+    /// CSS `linear()` timing function following [`Self::progress_at`] in `samples` steps
+    pub fn css_linear_easing(&self, samples: usize) -> String {
+        let samples = samples.max(2);
+        let points: Vec<String> = (0..=samples)
+            .map(|step| {
+                let value = self.progress_at(step as f64 / samples as f64);
+                // Adding zero turns -0 into 0
+                format!("{}", (value * 10000.0).round() / 10000.0 + 0.0)
+            })
+            .collect();
+        format!("linear({})", points.join(", "))
+    }
+
     /// Check if spring has settled at given time
     pub fn is_settled(&self, from: f64, to: f64, time: f64) -> bool {
         let state = self.state(from, to, time);
@@ -165,74 +171,45 @@ impl SpringSimulator {
         }
     }
 
-    fn underdamped_position(&self, displacement: f64, _initial_velocity: f64, time: f64) -> f64 {
-        let exp_decay = (-self.zeta * self.omega * time).exp();
-        let omega_d = self.omega * (1.0 - self.zeta * self.zeta).sqrt();
-
-        // Correct formula for underdamped spring
-        displacement
-            * (1.0
-                - exp_decay
-                    * ((omega_d * time).cos()
-                        + (self.zeta * self.omega / omega_d) * (omega_d * time).sin()))
-    }
-
-    fn underdamped_velocity(&self, displacement: f64, _initial_velocity: f64, time: f64) -> f64 {
-        let exp_decay = (-self.zeta * self.omega * time).exp();
-        let omega_d = self.omega * (1.0 - self.zeta * self.zeta).sqrt();
-
-        // Correct formula for underdamped spring velocity
-        displacement
-            * self.omega
-            * self.omega
-            * exp_decay
-            * ((omega_d * time).sin() - (self.zeta * self.omega / omega_d) * (omega_d * time).cos())
-    }
-
-    fn critically_damped_position(
-        &self,
-        displacement: f64,
-        _initial_velocity: f64,
-        time: f64,
-    ) -> f64 {
-        let exp_decay = (-self.omega * time).exp();
-
-        // Correct formula for critically damped spring
-        displacement * (1.0 - exp_decay * (1.0 + self.omega * time))
-    }
-
-    fn critically_damped_velocity(
-        &self,
-        displacement: f64,
-        _initial_velocity: f64,
-        time: f64,
-    ) -> f64 {
-        let exp_decay = (-self.omega * time).exp();
-
-        // Correct formula for critically damped spring velocity
-        displacement * self.omega * self.omega * time * exp_decay
-    }
-
-    fn overdamped_position(&self, displacement: f64, initial_velocity: f64, time: f64) -> f64 {
-        let sqrt_term = (self.zeta * self.zeta - 1.0).sqrt();
-        let r1 = -self.omega * (self.zeta + sqrt_term);
-        let r2 = -self.omega * (self.zeta - sqrt_term);
-
-        let c1 = (initial_velocity - r2 * displacement) / (r1 - r2);
-        let c2 = displacement - c1;
-
-        c1 * (r1 * time).exp() + c2 * (r2 * time).exp()
-    }
-
-    fn overdamped_velocity(&self, displacement: f64, initial_velocity: f64, time: f64) -> f64 {
-        let sqrt_term = (self.zeta * self.zeta - 1.0).sqrt();
-        let r1 = -self.omega * (self.zeta + sqrt_term);
-        let r2 = -self.omega * (self.zeta - sqrt_term);
-
-        let c1 = (initial_velocity - r2 * displacement) / (r1 - r2);
-        let c2 = displacement - c1;
+    /// Offset from the target and velocity at `time`, starting `offset`
+    /// away from it at the configured velocity
+    fn offset(&self, offset: f64, time: f64) -> (f64, f64) {
+        let initial_velocity = self.config.velocity;
+        let omega = self.omega;
+        let zeta = self.zeta;
 
-        c1 * r1 * (r1 * time).exp() + c2 * r2 * (r2 * time).exp()
+        match self.spring_type() {
+            SpringType::Underdamped => {
+                let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+                let decay = (-zeta * omega * time).exp();
+                let (sin, cos) = (omega_d * time).sin_cos();
+                let sin_amplitude = (initial_velocity + zeta * omega * offset) / omega_d;
+                let velocity_sin_amplitude =
+                    (zeta * omega * initial_velocity + omega * omega * offset) / omega_d;
+                (
+                    decay * (offset * cos + sin_amplitude * sin),
+                    decay * (initial_velocity * cos - velocity_sin_amplitude * sin),
+                )
+            }
+            SpringType::CriticallyDamped => {
+                let decay = (-omega * time).exp();
+                let slope = initial_velocity + omega * offset;
+                (
+                    decay * (offset + slope * time),
+                    decay * (initial_velocity - omega * slope * time),
+                )
+            }
+            SpringType::Overdamped => {
+                let sqrt_term = (zeta * zeta - 1.0).sqrt();
+                let r1 = -omega * (zeta + sqrt_term);
+                let r2 = -omega * (zeta - sqrt_term);
+
+                let c1 = (initial_velocity - r2 * offset) / (r1 - r2);
+                let c2 = offset - c1;
+                let (e1, e2) = ((r1 * time).exp(), (r2 * time).exp());
+                (c1 * e1 + c2 * e2, c1 * r1 * e1 + c2 * r2 * e2)
+            }
+        }
     }
 }
 
@@ -468,6 +445,51 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "approx")]
+    #[test]
+    fn test_spring_initial_velocity() {
+        for damping in [5.0, 20.0, 40.0] {
+            let spring = SpringSimulator::new(SpringConfig {
+                damping,
+                velocity: 250.0,
+                ..Default::default()
+            })
+            .unwrap();
+
+            // Velocity is continuous from the start, whatever the damping
+            assert_relative_eq!(spring.velocity(0.0, 100.0, 1e-9), 250.0, epsilon = 1e-3);
+            assert!(spring.position(0.0, 100.0, 0.01) > spring.position(0.0, 0.0, 0.0));
+        }
+    }
+
+    #[cfg(feature = "approx")]
+    #[test]
+    fn test_overdamped_spring_converges() {
+        let spring = SpringSimulator::new(SpringConfig {
+            damping: 40.0,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_relative_eq!(spring.position(0.0, 100.0, 10.0), 100.0, epsilon = 0.01);
+        assert!(spring.is_settled(0.0, 100.0, 10.0));
+    }
+
+    #[cfg(feature = "approx")]
+    #[test]
+    fn test_spring_progress_and_css_easing() {
+        let spring = SpringSimulator::new(presets::BOUNCY).unwrap();
+
+        assert_relative_eq!(spring.progress_at(0.0), 0.0);
+        assert_relative_eq!(spring.progress_at(1.0), 1.0);
+        assert!(spring.settling_duration() <= MAX_SPRING_DURATION);
+
+        let css = spring.css_linear_easing(10);
+        assert!(css.starts_with("linear(0, "));
+        assert!(css.ends_with(", 1)"));
+        assert_eq!(css.matches(',').count(), 10);
+    }
+
     #[cfg(feature = "approx")]
     #[test]
     fn test_spring_presets() {
@@ -0,0 +1,219 @@
+//! Stagger delays for sets of elements
+//!
+//! [`StaggerResolver`] turns a [`StaggerConfig`] into per-element delays,
+//! either along a list or across a grid, optionally shaping how the delays
+//! are distributed with an [`Easing`].
+
+use crate::{Easing, StaggerConfig, StaggerFrom};
+
+/// Computes per-element delays from a [`StaggerConfig`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaggerResolver {
+    config: StaggerConfig,
+    ease: Option<Easing>,
+    columns: Option<usize>,
+}
+
+impl StaggerResolver {
+    /// Stagger elements in a list
+    pub fn new(config: StaggerConfig) -> Self {
+        Self {
+            config,
+            ease: None,
+            columns: None,
+        }
+    }
+
+    /// Distribute delays along `ease` instead of linearly
+    ///
+    /// The element furthest from the origin keeps the same delay; the
+    /// elements in between start earlier or later depending on the curve.
+    pub fn with_ease(mut self, ease: Easing) -> Self {
+        self.ease = Some(ease);
+        self
+    }
+
+    /// Treat elements as a row-major grid with `columns` columns
+    ///
+    /// Delays then grow with the distance to the origin cell.
+    pub fn with_grid(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// Stagger configuration
+    pub fn config(&self) -> &StaggerConfig {
+        &self.config
+    }
+
+    /// Delay in seconds of the element at `index` of `count`
+    pub fn delay(&self, index: usize, count: usize) -> f64 {
+        if count == 0 {
+            return 0.0;
+        }
+        self.delay_within(index, count, self.max_distance(count))
+    }
+
+    /// Delays in seconds of all `count` elements
+    pub fn delays(&self, count: usize) -> Vec<f64> {
+        let max_distance = self.max_distance(count);
+        (0..count)
+            .map(|index| self.delay_within(index, count, max_distance))
+            .collect()
+    }
+
+    /// Delay of the element at `index`, given the largest distance of any element
+    fn delay_within(&self, index: usize, count: usize, max_distance: f64) -> f64 {
+        let distance = self.distance(index, count);
+        let distance = match &self.ease {
+            Some(ease) if max_distance > 0.0 => {
+                ease.basic_evaluate(distance / max_distance) * max_distance
+            }
+            _ => distance,
+        };
+        distance * self.config.delay
+    }
+
+    /// Largest distance of any of the `count` elements from the origin
+    fn max_distance(&self, count: usize) -> f64 {
+        (0..count)
+            .map(|index| self.distance(index, count))
+            .fold(0.0, f64::max)
+    }
+
+    /// Distance of the element at `index` from the stagger origin
+    fn distance(&self, index: usize, count: usize) -> f64 {
+        match self.columns {
+            Some(columns) => {
+                let (x, y) = cell(index, columns);
+                let (origin_x, origin_y) = self.grid_origin(count, columns);
+                ((x - origin_x).powi(2) + (y - origin_y).powi(2)).sqrt()
+            }
+            None => (index as f64 - self.list_origin(count)).abs(),
+        }
+    }
+
+    fn list_origin(&self, count: usize) -> f64 {
+        let last = count.saturating_sub(1) as f64;
+        match self.config.from {
+            StaggerFrom::First => 0.0,
+            StaggerFrom::Last => last,
+            StaggerFrom::Center => last / 2.0,
+            StaggerFrom::Index(index) => index as f64,
+        }
+    }
+
+    fn grid_origin(&self, count: usize, columns: usize) -> (f64, f64) {
+        let last = count.saturating_sub(1);
+        match self.config.from {
+            StaggerFrom::First => (0.0, 0.0),
+            StaggerFrom::Last => cell(last, columns),
+            StaggerFrom::Center => {
+                let rows = count.div_ceil(columns);
+                let used_columns = columns.min(count);
+                (
+                    used_columns.saturating_sub(1) as f64 / 2.0,
+                    rows.saturating_sub(1) as f64 / 2.0,
+                )
+            }
+            StaggerFrom::Index(index) => cell(index, columns),
+        }
+    }
+}
+
+impl From<StaggerConfig> for StaggerResolver {
+    fn from(config: StaggerConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+/// Column and row of `index` in a row-major grid
+fn cell(index: usize, columns: usize) -> (f64, f64) {
+    ((index % columns) as f64, (index / columns) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stagger(from: StaggerFrom) -> StaggerConfig {
+        StaggerConfig { delay: 0.1, from }
+    }
+
+    fn assert_delays(actual: Vec<f64>, expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-9,
+                "expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_origins() {
+        let first = StaggerResolver::new(stagger(StaggerFrom::First));
+        assert_delays(first.delays(4), &[0.0, 0.1, 0.2, 0.3]);
+
+        let last = StaggerResolver::new(stagger(StaggerFrom::Last));
+        assert_delays(last.delays(4), &[0.3, 0.2, 0.1, 0.0]);
+
+        let center = StaggerResolver::new(stagger(StaggerFrom::Center));
+        assert_delays(center.delays(5), &[0.2, 0.1, 0.0, 0.1, 0.2]);
+
+        let index = StaggerResolver::new(stagger(StaggerFrom::Index(1)));
+        assert_delays(index.delays(4), &[0.1, 0.0, 0.1, 0.2]);
+    }
+
+    #[test]
+    fn test_eased_distribution_keeps_total_duration() {
+        let resolver = StaggerResolver::new(stagger(StaggerFrom::First)).with_ease(Easing::EaseIn);
+        let delays = resolver.delays(5);
+
+        assert_eq!(delays[0], 0.0);
+        assert!((delays[4] - 0.4).abs() < 1e-9);
+        // Ease-in bunches the early elements together
+        assert!(delays[1] < 0.1);
+        assert!(delays[3] - delays[2] > delays[2] - delays[1]);
+    }
+
+    #[test]
+    fn test_grid_distance_from_origin() {
+        // 3x3 grid staggered out from the centre cell
+        let resolver = StaggerResolver::new(stagger(StaggerFrom::Center)).with_grid(3);
+        let diagonal = 2.0_f64.sqrt() * 0.1;
+        assert_delays(
+            resolver.delays(9),
+            &[
+                diagonal, 0.1, diagonal, 0.1, 0.0, 0.1, diagonal, 0.1, diagonal,
+            ],
+        );
+
+        // From the first cell delays grow with Euclidean distance
+        let resolver = StaggerResolver::new(stagger(StaggerFrom::First)).with_grid(2);
+        assert_delays(resolver.delays(4), &[0.0, 0.1, 0.1, diagonal]);
+    }
+
+    #[test]
+    fn test_grid_origin_at_index() {
+        let resolver = StaggerResolver::new(stagger(StaggerFrom::Index(5))).with_grid(3);
+        assert_eq!(resolver.delay(5, 6), 0.0);
+        assert!((resolver.delay(2, 6) - 0.1).abs() < 1e-9);
+        assert!((resolver.delay(3, 6) - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_delay_matches_delays() {
+        let resolver = StaggerResolver::new(stagger(StaggerFrom::Center))
+            .with_grid(3)
+            .with_ease(Easing::EaseOut);
+        let delays = resolver.delays(7);
+        for (index, delay) in delays.iter().enumerate() {
+            assert!((resolver.delay(index, 7) - delay).abs() < 1e-9);
+        }
+        assert_eq!(
+            StaggerResolver::new(stagger(StaggerFrom::First)).delay(0, 0),
+            0.0
+        );
+    }
+}
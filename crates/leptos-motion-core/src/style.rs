@@ -0,0 +1,44 @@
+//! Style helpers shared by the DOM animation crates
+
+use wasm_bindgen::JsCast;
+use web_sys::{CssStyleDeclaration, Element};
+
+/// Inline style declaration of `element`
+///
+/// HTML and SVG elements both carry one, so any element animated by motion
+/// components can be styled through it.
+pub fn inline_style(element: &Element) -> CssStyleDeclaration {
+    element.unchecked_ref::<web_sys::HtmlElement>().style()
+}
+
+/// Computed `property` of `element` in pixels, such as its `height`
+///
+/// `None` when the element isn't rendered and the size has no pixel value.
+pub fn computed_size(element: &Element, property: &str) -> Option<f64> {
+    web_sys::window()?
+        .get_computed_style(element)
+        .ok()??
+        .get_property_value(property)
+        .ok()?
+        .strip_suffix("px")?
+        .parse()
+        .ok()
+}
+
+/// Pixel size `property` of `element` would have at `auto`
+///
+/// The inline value is swapped for `auto` while measuring and then put back,
+/// so a collapsed element reports the size of its content.
+pub fn natural_size(element: &Element, property: &str) -> Option<f64> {
+    let style = inline_style(element);
+    let value = style.get_property_value(property).unwrap_or_default();
+    let priority = style.get_property_priority(property);
+    let _ = style.set_property(property, "auto");
+    let size = computed_size(element, property);
+    if value.is_empty() {
+        let _ = style.remove_property(property);
+    } else {
+        let _ = style.set_property_with_priority(property, &value, &priority);
+    }
+    size
+}
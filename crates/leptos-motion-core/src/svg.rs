@@ -0,0 +1,361 @@
+//! SVG attributes and path drawing
+//!
+//! Some keys of an [`AnimationTarget`] don't map to CSS properties on SVG
+//! elements. Geometry such as `cx`, `r`, `d`, `points` or `viewBox` is
+//! written as attributes, and `path_length`, `path_offset` and
+//! `path_spacing` draw a stroke as fractions of its total length through
+//! `stroke-dasharray` and `stroke-dashoffset`. [`PathMorpher`] morphs `d`
+//! between paths whose commands don't line up.
+
+mod path;
+
+use crate::{AnimationTarget, AnimationValue};
+pub use path::{BoundingBox, MorphConfig, PathCommand, PathMorpher, Point, SvgPath};
+
+/// Keys animated as SVG attributes rather than CSS properties
+pub const SVG_ATTRIBUTES: &[&str] = &[
+    "cx", "cy", "r", "rx", "ry", "fx", "fy", "x1", "y1", "x2", "y2", "d", "points", "viewBox",
+];
+
+/// Visible fraction of the stroke, from 0 to 1
+pub const PATH_LENGTH: &str = "path_length";
+/// Fraction of the stroke the visible dash is shifted by
+pub const PATH_OFFSET: &str = "path_offset";
+/// Fraction of the stroke between dashes (defaults to 1)
+pub const PATH_SPACING: &str = "path_spacing";
+
+/// `pathLength` attribute that makes dash lengths fractions of the stroke
+pub const PATH_LENGTH_ATTRIBUTE: (&str, &str) = ("pathLength", "1");
+
+/// Whether `key` is animated as an SVG attribute
+pub fn is_svg_attribute(key: &str) -> bool {
+    SVG_ATTRIBUTES.contains(&key)
+}
+
+/// Whether `key` is one of the path drawing values
+pub fn is_path_drawing(key: &str) -> bool {
+    matches!(key, PATH_LENGTH | PATH_OFFSET | PATH_SPACING)
+}
+
+/// Attribute value of `value`; plain numbers and pixels are user units
+pub fn svg_attribute_value(value: &AnimationValue) -> String {
+    match value {
+        AnimationValue::Number(number) | AnimationValue::Pixels(number) => number.to_string(),
+        value => value.to_string_value(),
+    }
+}
+
+/// Path drawing values of a target
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PathDrawing {
+    /// Visible fraction of the stroke
+    pub length: Option<f64>,
+    /// Fraction of the stroke the dash is shifted by
+    pub offset: Option<f64>,
+    /// Fraction of the stroke between dashes
+    pub spacing: Option<f64>,
+}
+
+impl PathDrawing {
+    /// Path drawing values of `target`, if it has any
+    pub fn from_target(target: &AnimationTarget) -> Option<Self> {
+        let value = |key: &str| match target.get(key)? {
+            AnimationValue::Number(number) => Some(*number),
+            AnimationValue::Percentage(percentage) => Some(percentage / 100.0),
+            _ => None,
+        };
+        let drawing = Self {
+            length: value(PATH_LENGTH),
+            offset: value(PATH_OFFSET),
+            spacing: value(PATH_SPACING),
+        };
+        (drawing != Self::default()).then_some(drawing)
+    }
+
+    /// CSS properties drawing the stroke, relative to [`PATH_LENGTH_ATTRIBUTE`]
+    ///
+    /// The dash array is only written when the length or spacing is set, so
+    /// animating the offset alone keeps the current dash.
+    pub fn css_properties(&self) -> Vec<(&'static str, String)> {
+        let mut properties = Vec::new();
+        if self.length.is_some() || self.spacing.is_some() {
+            let length = self.length.unwrap_or(1.0).clamp(0.0, 1.0);
+            let spacing = self.spacing.unwrap_or(1.0).max(0.0);
+            properties.push((
+                "stroke-dasharray",
+                format!("{} {}", format_number(length), format_number(spacing)),
+            ));
+        }
+        if let Some(offset) = self.offset {
+            properties.push(("stroke-dashoffset", format_number(-offset)));
+        }
+        properties
+    }
+}
+
+/// Tween of an SVG attribute, prepared once for the frames of an animation
+///
+/// Numbers are interpolated pairwise when both values have the same shape,
+/// like `"0 0 100 100"` and `"0 0 200 50"`. `d` attributes whose commands
+/// don't line up are morphed through cubic segments, and any other value
+/// switches halfway through.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeTween {
+    from: String,
+    to: String,
+    kind: TweenKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TweenKind {
+    Numbers {
+        from: Vec<f64>,
+        to: Vec<f64>,
+        separators: Vec<String>,
+    },
+    Path(PathMorpher),
+    Discrete,
+}
+
+impl AttributeTween {
+    /// Tween SVG `attribute` from `from` to `to`
+    pub fn new(attribute: &str, from: &str, to: &str) -> Self {
+        let kind = match number_tween(from, to) {
+            Some(kind) => kind,
+            None if attribute == "d" => match PathMorpher::new(from, to) {
+                Ok(mut morpher) => {
+                    morpher.prepare();
+                    TweenKind::Path(morpher)
+                }
+                Err(_) => TweenKind::Discrete,
+            },
+            None => TweenKind::Discrete,
+        };
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        }
+    }
+
+    /// Value at `progress`, exactly the start and end values at 0 and 1
+    pub fn at(&self, progress: f64) -> String {
+        if progress == 0.0 {
+            return self.from.clone();
+        }
+        if progress == 1.0 {
+            return self.to.clone();
+        }
+        match &self.kind {
+            TweenKind::Numbers {
+                from,
+                to,
+                separators,
+            } => {
+                let mut value = String::new();
+                for (index, separator) in separators.iter().enumerate() {
+                    value.push_str(separator);
+                    if let (Some(from), Some(to)) = (from.get(index), to.get(index)) {
+                        value.push_str(&format_number(from + (to - from) * progress));
+                    }
+                }
+                value
+            }
+            TweenKind::Path(morpher) => morpher.interpolate(progress).data,
+            TweenKind::Discrete if progress < 0.5 => self.from.clone(),
+            TweenKind::Discrete => self.to.clone(),
+        }
+    }
+}
+
+/// Value of SVG `attribute` at `progress` between `from` and `to`
+///
+/// Animations should prepare an [`AttributeTween`] once instead of calling
+/// this on every frame.
+pub fn interpolate_svg_attribute(attribute: &str, from: &str, to: &str, progress: f64) -> String {
+    AttributeTween::new(attribute, from, to).at(progress)
+}
+
+/// Interpolate the numbers of two values with the same text between them
+pub fn interpolate_numbers(from: &str, to: &str, progress: f64) -> Option<String> {
+    number_tween(from, to).map(|kind| {
+        AttributeTween {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        }
+        .at(progress)
+    })
+}
+
+/// Pairwise number tween, if `from` and `to` have the same text between numbers
+fn number_tween(from: &str, to: &str) -> Option<TweenKind> {
+    let (from_numbers, from_separators) = split_numbers(from);
+    let (to_numbers, to_separators) = split_numbers(to);
+    if from_separators != to_separators {
+        return None;
+    }
+    Some(TweenKind::Numbers {
+        from: from_numbers,
+        to: to_numbers,
+        separators: from_separators.into_iter().map(str::to_string).collect(),
+    })
+}
+
+/// Numbers in `value` and the text around them (one more than the numbers)
+fn split_numbers(value: &str) -> (Vec<f64>, Vec<&str>) {
+    let bytes = value.as_bytes();
+    let mut numbers = Vec::new();
+    let mut separators = Vec::new();
+    let mut separator_start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        match number_end(bytes, index) {
+            Some(end) => {
+                separators.push(&value[separator_start..index]);
+                numbers.push(value[index..end].parse().unwrap_or(0.0));
+                separator_start = end;
+                index = end;
+            }
+            None => index += 1,
+        }
+    }
+    separators.push(&value[separator_start..]);
+    (numbers, separators)
+}
+
+/// End of the number starting at `start`, if one does
+fn number_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let digits = |mut index: usize| {
+        while bytes.get(index).is_some_and(u8::is_ascii_digit) {
+            index += 1;
+        }
+        index
+    };
+
+    let mut index = start;
+    if matches!(bytes.get(index), Some(b'-' | b'+')) {
+        index += 1;
+    }
+    let integer_end = digits(index);
+    let mut end = integer_end;
+    if bytes.get(end) == Some(&b'.') {
+        let fraction_end = digits(end + 1);
+        if fraction_end > end + 1 || integer_end > index {
+            end = fraction_end;
+        }
+    }
+    if end == index {
+        return None;
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exponent = end + 1;
+        if matches!(bytes.get(exponent), Some(b'-' | b'+')) {
+            exponent += 1;
+        }
+        let exponent_end = digits(exponent);
+        if exponent_end > exponent {
+            end = exponent_end;
+        }
+    }
+    Some(end)
+}
+
+/// Format `number` with at most three decimals
+fn format_number(number: f64) -> String {
+    let rounded = (number * 1000.0).round() / 1000.0;
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_keys() {
+        assert!(is_svg_attribute("cx"));
+        assert!(is_svg_attribute("viewBox"));
+        assert!(!is_svg_attribute("opacity"));
+        assert!(is_path_drawing("path_length"));
+        assert!(!is_path_drawing("stroke-dasharray"));
+    }
+
+    #[test]
+    fn test_path_drawing_css() {
+        let mut target = AnimationTarget::new();
+        target.insert(PATH_LENGTH.to_string(), AnimationValue::Number(0.25));
+        target.insert(PATH_OFFSET.to_string(), AnimationValue::Number(0.5));
+        let drawing = PathDrawing::from_target(&target).unwrap();
+        assert_eq!(
+            drawing.css_properties(),
+            vec![
+                ("stroke-dasharray", "0.25 1".to_string()),
+                ("stroke-dashoffset", "-0.5".to_string()),
+            ]
+        );
+
+        // Offset alone keeps the current dash
+        let offset_only = PathDrawing {
+            offset: Some(0.1),
+            ..Default::default()
+        };
+        assert_eq!(
+            offset_only.css_properties(),
+            vec![("stroke-dashoffset", "-0.1".to_string())]
+        );
+
+        let mut opacity = AnimationTarget::new();
+        opacity.insert("opacity".to_string(), AnimationValue::Number(1.0));
+        assert_eq!(PathDrawing::from_target(&opacity), None);
+    }
+
+    #[test]
+    fn test_interpolate_numbers_with_matching_shape() {
+        assert_eq!(
+            interpolate_svg_attribute("viewBox", "0 0 100 100", "0 0 200 50", 0.5),
+            "0 0 150 75"
+        );
+        assert_eq!(
+            interpolate_svg_attribute("d", "M0,0 L10,-10", "M10,10 L20,10", 0.25),
+            "M2.5,2.5 L12.5,-5"
+        );
+        assert_eq!(interpolate_svg_attribute("r", "1e1", "20", 0.5), "15");
+        assert_eq!(
+            interpolate_svg_attribute("points", ".5,1 2-3", "1.5,1 4-5", 0.5),
+            "1,1 3-4"
+        );
+    }
+
+    #[test]
+    fn test_mismatched_shapes_switch_halfway() {
+        assert_eq!(
+            interpolate_svg_attribute("points", "0,0 1,1", "0,0 1,1 2,2", 0.4),
+            "0,0 1,1"
+        );
+        assert_eq!(
+            interpolate_svg_attribute("points", "0,0 1,1", "0,0 1,1 2,2", 0.6),
+            "0,0 1,1 2,2"
+        );
+    }
+
+    #[test]
+    fn test_mismatched_paths_morph() {
+        let tween = AttributeTween::new("d", "M0 0 L10 0", "M0 0 L10 0 L10 10 Z");
+        assert_eq!(tween.at(0.0), "M0 0 L10 0");
+        assert_eq!(tween.at(1.0), "M0 0 L10 0 L10 10 Z");
+        let middle = tween.at(0.5);
+        assert!(middle.starts_with("M0 0 C"));
+        assert!(middle.ends_with(" Z"));
+
+        // Only `d` is morphed; unparseable paths switch halfway
+        assert_eq!(
+            interpolate_svg_attribute("points", "0 0", "0 0 1 1", 0.3),
+            "0 0"
+        );
+        assert_eq!(interpolate_svg_attribute("d", "M0 0", "oops", 0.3), "M0 0");
+    }
+}
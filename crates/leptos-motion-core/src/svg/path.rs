@@ -0,0 +1,911 @@
+//! SVG path data and morphing between paths whose commands don't line up
+//!
+//! [`SvgPath`] reads path data into commands with absolute coordinates.
+//! [`PathMorpher`] normalises both paths to cubic Bézier segments, then pads
+//! subpaths and segments to the same count, splitting the longest segments,
+//! so every control point has a partner to move towards.
+
+use super::{format_number, number_end};
+use crate::{AnimationError, Result};
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+/// 2D point for path coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde-support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Point {
+    /// Horizontal coordinate
+    pub x: f64,
+    /// Vertical coordinate
+    pub y: f64,
+}
+
+impl Point {
+    /// Create a point
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Point at the origin
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    /// Distance to `other`
+    pub fn distance_to(self, other: Self) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+
+    /// Point at `t` on the way to `other`
+    pub fn lerp(self, other: Self, t: f64) -> Self {
+        Self::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
+    /// `self` mirrored through `center`
+    fn reflect(self, center: Point) -> Point {
+        Point::new(2.0 * center.x - self.x, 2.0 * center.y - self.y)
+    }
+}
+
+/// Path command with absolute coordinates
+///
+/// `H` and `V` are read as lines, `S` and `T` with their reflected control
+/// point, and arcs as cubic curves of at most a quarter turn.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde-support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum PathCommand {
+    /// Start a subpath at `point`
+    MoveTo {
+        /// Start of the subpath
+        point: Point,
+        /// Whether the command was written with absolute coordinates
+        absolute: bool,
+    },
+    /// Straight line to `point`
+    LineTo {
+        /// End of the line
+        point: Point,
+        /// Whether the command was written with absolute coordinates
+        absolute: bool,
+    },
+    /// Cubic Bézier curve to `point`
+    CurveTo {
+        /// Control point near the start
+        control1: Point,
+        /// Control point near the end
+        control2: Point,
+        /// End of the curve
+        point: Point,
+        /// Whether the command was written with absolute coordinates
+        absolute: bool,
+    },
+    /// Quadratic Bézier curve to `point`
+    QuadTo {
+        /// Control point
+        control: Point,
+        /// End of the curve
+        point: Point,
+        /// Whether the command was written with absolute coordinates
+        absolute: bool,
+    },
+    /// Line back to the start of the subpath
+    ClosePath,
+}
+
+impl PathCommand {
+    /// Point the command ends at, if it moves the pen
+    fn end(&self) -> Option<Point> {
+        match self {
+            PathCommand::MoveTo { point, .. }
+            | PathCommand::LineTo { point, .. }
+            | PathCommand::CurveTo { point, .. }
+            | PathCommand::QuadTo { point, .. } => Some(*point),
+            PathCommand::ClosePath => None,
+        }
+    }
+}
+
+/// Bounding box of the end points of a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde-support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct BoundingBox {
+    /// Left edge
+    pub min_x: f64,
+    /// Top edge
+    pub min_y: f64,
+    /// Right edge
+    pub max_x: f64,
+    /// Bottom edge
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    /// Width of the box
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+
+    /// Height of the box
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+
+    /// Center of the box
+    pub fn center(&self) -> Point {
+        Point::new(
+            (self.min_x + self.max_x) / 2.0,
+            (self.min_y + self.max_y) / 2.0,
+        )
+    }
+}
+
+/// Parsed SVG path data
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde-support",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct SvgPath {
+    /// Path data string (`d` attribute)
+    pub data: String,
+    /// Parsed path commands
+    pub commands: Vec<PathCommand>,
+    /// Path bounding box
+    pub bounds: Option<BoundingBox>,
+    /// Path length (approximate)
+    pub length: f64,
+}
+
+impl SvgPath {
+    /// Parse SVG path data
+    pub fn from_data(data: &str) -> Result<Self> {
+        let commands = parse_commands(data)
+            .filter(|commands| !commands.is_empty())
+            .ok_or_else(|| AnimationError::InvalidValue(format!("invalid path data `{data}`")))?;
+        Ok(Self {
+            data: data.to_string(),
+            bounds: Some(calculate_bounds(&commands)),
+            length: calculate_length(&commands),
+            commands,
+        })
+    }
+
+    /// Path of `commands`, writing its data from them
+    fn from_commands(commands: Vec<PathCommand>) -> Self {
+        let mut path = Self {
+            data: String::new(),
+            bounds: Some(calculate_bounds(&commands)),
+            length: calculate_length(&commands),
+            commands,
+        };
+        path.data = path.to_data();
+        path
+    }
+
+    /// Path data of the commands, in absolute coordinates
+    pub fn to_data(&self) -> String {
+        let commands: Vec<String> = self
+            .commands
+            .iter()
+            .map(|command| match command {
+                PathCommand::MoveTo { point, .. } => format!("M{}", format_point(*point)),
+                PathCommand::LineTo { point, .. } => format!("L{}", format_point(*point)),
+                PathCommand::CurveTo {
+                    control1,
+                    control2,
+                    point,
+                    ..
+                } => format!(
+                    "C{} {} {}",
+                    format_point(*control1),
+                    format_point(*control2),
+                    format_point(*point)
+                ),
+                PathCommand::QuadTo { control, point, .. } => {
+                    format!("Q{} {}", format_point(*control), format_point(*point))
+                }
+                PathCommand::ClosePath => "Z".to_string(),
+            })
+            .collect();
+        commands.join(" ")
+    }
+}
+
+/// Bounding box of the end points of `commands`
+fn calculate_bounds(commands: &[PathCommand]) -> BoundingBox {
+    let mut bounds = BoundingBox {
+        min_x: f64::INFINITY,
+        min_y: f64::INFINITY,
+        max_x: f64::NEG_INFINITY,
+        max_y: f64::NEG_INFINITY,
+    };
+    for point in commands.iter().filter_map(PathCommand::end) {
+        bounds.min_x = bounds.min_x.min(point.x);
+        bounds.min_y = bounds.min_y.min(point.y);
+        bounds.max_x = bounds.max_x.max(point.x);
+        bounds.max_y = bounds.max_y.max(point.y);
+    }
+    bounds
+}
+
+/// Approximate length of `commands`
+fn calculate_length(commands: &[PathCommand]) -> f64 {
+    let mut length = 0.0;
+    let mut current = Point::zero();
+    let mut start = Point::zero();
+    for command in commands {
+        match command {
+            PathCommand::MoveTo { point, .. } => start = *point,
+            PathCommand::LineTo { point, .. } => length += current.distance_to(*point),
+            PathCommand::QuadTo { control, point, .. } => {
+                // Rough approximation from the control polygon
+                length += (current.distance_to(*control) + control.distance_to(*point)) * 0.75;
+            }
+            PathCommand::CurveTo {
+                control1,
+                control2,
+                point,
+                ..
+            } => {
+                length += (current.distance_to(*control1)
+                    + control1.distance_to(*control2)
+                    + control2.distance_to(*point))
+                    * 0.6;
+            }
+            PathCommand::ClosePath => length += current.distance_to(start),
+        }
+        current = command.end().unwrap_or(start);
+    }
+    length
+}
+
+/// Options of a path morph
+#[derive(Debug, Clone, PartialEq)]
+pub struct MorphConfig {
+    /// Smoothing factor (0.0 to 1.0)
+    pub smoothing: f64,
+    /// Whether to optimize for performance
+    pub optimize: bool,
+    /// Maximum subdivisions for path normalization
+    pub max_subdivisions: usize,
+    /// Tolerance for path simplification
+    pub tolerance: f64,
+}
+
+impl Default for MorphConfig {
+    fn default() -> Self {
+        Self {
+            smoothing: 0.5,
+            optimize: true,
+            max_subdivisions: 100,
+            tolerance: 1.0,
+        }
+    }
+}
+
+/// Morph between two paths whose commands don't need to line up
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMorpher {
+    /// Source path
+    pub source: SvgPath,
+    /// Target path
+    pub target: SvgPath,
+    /// Morphing configuration
+    pub config: MorphConfig,
+    /// Subpaths of both paths padded to the same segments, once prepared
+    normalized: Option<(Vec<Subpath>, Vec<Subpath>)>,
+}
+
+impl PathMorpher {
+    /// Morph from `source_data` to `target_data`
+    pub fn new(source_data: &str, target_data: &str) -> Result<Self> {
+        Ok(Self {
+            source: SvgPath::from_data(source_data)?,
+            target: SvgPath::from_data(target_data)?,
+            config: MorphConfig::default(),
+            normalized: None,
+        })
+    }
+
+    /// Normalize both paths once, for morphs interpolated on every frame
+    pub fn prepare(&mut self) {
+        self.normalized = Some(self.normalize());
+    }
+
+    /// Path at `t` between the source and the target, exactly them at 0 and 1
+    pub fn interpolate(&self, t: f64) -> SvgPath {
+        let t = t.clamp(0.0, 1.0);
+        if t == 0.0 {
+            return self.source.clone();
+        }
+        if t == 1.0 {
+            return self.target.clone();
+        }
+        match &self.normalized {
+            Some((from, to)) => SvgPath::from_commands(morph_commands(from, to, t)),
+            None => {
+                let (from, to) = self.normalize();
+                SvgPath::from_commands(morph_commands(&from, &to, t))
+            }
+        }
+    }
+
+    /// Subpaths of both paths, padded to the same subpaths and segments
+    fn normalize(&self) -> (Vec<Subpath>, Vec<Subpath>) {
+        let mut from = subpaths(&self.source.commands);
+        let mut to = subpaths(&self.target.commands);
+
+        // Missing subpaths grow out of the last point of the other path
+        let pad = |subpaths: &mut Vec<Subpath>, count: usize| {
+            let point = subpaths.last().map_or(Point::zero(), Subpath::end);
+            subpaths.resize(count, Subpath::at(point));
+        };
+        let count = from.len().max(to.len());
+        pad(&mut from, count);
+        pad(&mut to, count);
+
+        for (from, to) in from.iter_mut().zip(to.iter_mut()) {
+            let segments = from.segments.len().max(to.segments.len());
+            from.subdivide(segments);
+            to.subdivide(segments);
+        }
+        (from, to)
+    }
+}
+
+/// Commands at `progress` between normalized subpaths
+fn morph_commands(from: &[Subpath], to: &[Subpath], progress: f64) -> Vec<PathCommand> {
+    let mut commands = Vec::new();
+    for (from, to) in from.iter().zip(to) {
+        commands.push(PathCommand::MoveTo {
+            point: from.start.lerp(to.start, progress),
+            absolute: true,
+        });
+        for (from, to) in from.segments.iter().zip(&to.segments) {
+            let segment = from.lerp(to, progress);
+            commands.push(PathCommand::CurveTo {
+                control1: segment.c1,
+                control2: segment.c2,
+                point: segment.end,
+                absolute: true,
+            });
+        }
+        let closed = if progress < 0.5 {
+            from.closed
+        } else {
+            to.closed
+        };
+        if closed {
+            commands.push(PathCommand::ClosePath);
+        }
+    }
+    commands
+}
+
+fn format_point(point: Point) -> String {
+    format!("{} {}", format_number(point.x), format_number(point.y))
+}
+
+/// Cubic Bézier segment starting where the previous one ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cubic {
+    c1: Point,
+    c2: Point,
+    end: Point,
+}
+
+impl Cubic {
+    fn line(from: Point, to: Point) -> Self {
+        Self {
+            c1: from.lerp(to, 1.0 / 3.0),
+            c2: from.lerp(to, 2.0 / 3.0),
+            end: to,
+        }
+    }
+
+    fn quadratic(from: Point, control: Point, to: Point) -> Self {
+        Self {
+            c1: from.lerp(control, 2.0 / 3.0),
+            c2: to.lerp(control, 2.0 / 3.0),
+            end: to,
+        }
+    }
+
+    /// Halves of the segment starting at `start`
+    fn split(&self, start: Point) -> (Cubic, Cubic) {
+        let a = start.lerp(self.c1, 0.5);
+        let b = self.c1.lerp(self.c2, 0.5);
+        let c = self.c2.lerp(self.end, 0.5);
+        let ab = a.lerp(b, 0.5);
+        let bc = b.lerp(c, 0.5);
+        let middle = ab.lerp(bc, 0.5);
+        (
+            Cubic {
+                c1: a,
+                c2: ab,
+                end: middle,
+            },
+            Cubic {
+                c1: bc,
+                c2: c,
+                end: self.end,
+            },
+        )
+    }
+
+    fn lerp(&self, other: &Cubic, t: f64) -> Cubic {
+        Cubic {
+            c1: self.c1.lerp(other.c1, t),
+            c2: self.c2.lerp(other.c2, t),
+            end: self.end.lerp(other.end, t),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Subpath {
+    start: Point,
+    segments: Vec<Cubic>,
+    closed: bool,
+}
+
+impl Subpath {
+    fn at(start: Point) -> Self {
+        Self {
+            start,
+            segments: Vec::new(),
+            closed: false,
+        }
+    }
+
+    fn end(&self) -> Point {
+        self.segments
+            .last()
+            .map_or(self.start, |segment| segment.end)
+    }
+
+    /// Split the longest segments until there are `count` of them
+    fn subdivide(&mut self, count: usize) {
+        if count > 0 && self.segments.is_empty() {
+            self.segments.push(Cubic::line(self.start, self.start));
+        }
+        while self.segments.len() < count {
+            let starts: Vec<Point> = std::iter::once(self.start)
+                .chain(self.segments.iter().map(|segment| segment.end))
+                .collect();
+            let Some(index) = (0..self.segments.len()).max_by(|&a, &b| {
+                let length = |index: usize| starts[index].distance_to(self.segments[index].end);
+                length(a).total_cmp(&length(b))
+            }) else {
+                return;
+            };
+            let (first, second) = self.segments[index].split(starts[index]);
+            self.segments[index] = first;
+            self.segments.insert(index + 1, second);
+        }
+    }
+}
+
+/// Reads the commands, numbers and flags of path data
+struct Parser<'a> {
+    data: &'a str,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a str) -> Self {
+        Self { data, index: 0 }
+    }
+
+    fn skip_separators(&mut self) {
+        let bytes = self.data.as_bytes();
+        while bytes
+            .get(self.index)
+            .is_some_and(|byte| byte.is_ascii_whitespace() || *byte == b',')
+        {
+            self.index += 1;
+        }
+    }
+
+    fn is_done(&mut self) -> bool {
+        self.skip_separators();
+        self.index >= self.data.len()
+    }
+
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let byte = *self.data.as_bytes().get(self.index)?;
+        byte.is_ascii_alphabetic().then(|| {
+            self.index += 1;
+            byte
+        })
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let end = number_end(self.data.as_bytes(), self.index)?;
+        let number = self.data[self.index..end].parse().ok()?;
+        self.index = end;
+        Some(number)
+    }
+
+    /// Arc flags may be written without separators, like `a1 1 0 011 1`
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.data.as_bytes().get(self.index)? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.index += 1;
+        Some(flag)
+    }
+
+    fn point(&mut self, origin: Point) -> Option<Point> {
+        Some(Point::new(
+            origin.x + self.number()?,
+            origin.y + self.number()?,
+        ))
+    }
+}
+
+/// Commands of path data, or `None` if it isn't valid
+fn parse_commands(data: &str) -> Option<Vec<PathCommand>> {
+    let mut parser = Parser::new(data);
+    let mut commands = Vec::new();
+    let mut current = Point::zero();
+    let mut start = Point::zero();
+    let mut command = None::<u8>;
+    let mut last_cubic = None::<Point>;
+    let mut last_quadratic = None::<Point>;
+
+    while !parser.is_done() {
+        let letter = match parser.command() {
+            Some(letter) => letter,
+            // Repeated arguments continue the previous command
+            None => match command? {
+                b'M' => b'L',
+                b'm' => b'l',
+                b'Z' | b'z' => return None,
+                previous => previous,
+            },
+        };
+        command = Some(letter);
+        let absolute = letter.is_ascii_uppercase();
+        let origin = if absolute { Point::zero() } else { current };
+        let upper = letter.to_ascii_uppercase();
+        // Path data starts with a move
+        if commands.is_empty() && upper != b'M' {
+            return None;
+        }
+
+        let mut cubic = None;
+        let mut quadratic = None;
+        match upper {
+            b'M' => {
+                current = parser.point(origin)?;
+                start = current;
+                commands.push(PathCommand::MoveTo {
+                    point: current,
+                    absolute,
+                });
+            }
+            b'L' | b'H' | b'V' => {
+                current = match upper {
+                    b'L' => parser.point(origin)?,
+                    b'H' => Point::new(origin.x + parser.number()?, current.y),
+                    _ => Point::new(current.x, origin.y + parser.number()?),
+                };
+                commands.push(PathCommand::LineTo {
+                    point: current,
+                    absolute,
+                });
+            }
+            b'C' | b'S' => {
+                let control1 = if upper == b'C' {
+                    parser.point(origin)?
+                } else {
+                    last_cubic.map_or(current, |control| control.reflect(current))
+                };
+                let control2 = parser.point(origin)?;
+                current = parser.point(origin)?;
+                commands.push(PathCommand::CurveTo {
+                    control1,
+                    control2,
+                    point: current,
+                    absolute,
+                });
+                cubic = Some(control2);
+            }
+            b'Q' | b'T' => {
+                let control = if upper == b'Q' {
+                    parser.point(origin)?
+                } else {
+                    last_quadratic.map_or(current, |control| control.reflect(current))
+                };
+                current = parser.point(origin)?;
+                commands.push(PathCommand::QuadTo {
+                    control,
+                    point: current,
+                    absolute,
+                });
+                quadratic = Some(control);
+            }
+            b'A' => {
+                let rx = parser.number()?;
+                let ry = parser.number()?;
+                let rotation = parser.number()?;
+                let large_arc = parser.flag()?;
+                let sweep = parser.flag()?;
+                let end = parser.point(origin)?;
+                let segments = arc(current, rx, ry, rotation, large_arc, sweep, end);
+                commands.extend(segments.into_iter().map(|segment| PathCommand::CurveTo {
+                    control1: segment.c1,
+                    control2: segment.c2,
+                    point: segment.end,
+                    absolute,
+                }));
+                current = end;
+            }
+            b'Z' => {
+                commands.push(PathCommand::ClosePath);
+                current = start;
+            }
+            _ => return None,
+        }
+        last_cubic = cubic;
+        last_quadratic = quadratic;
+    }
+    Some(commands)
+}
+
+/// Subpaths of `commands` as cubic segments
+fn subpaths(commands: &[PathCommand]) -> Vec<Subpath> {
+    let mut subpaths: Vec<Subpath> = Vec::new();
+    for command in commands {
+        if let PathCommand::MoveTo { point, .. } = command {
+            subpaths.push(Subpath::at(*point));
+            continue;
+        }
+        // Drawing after a close starts a new subpath at the same point
+        if subpaths.last().is_none_or(|subpath| subpath.closed) {
+            let start = subpaths
+                .last()
+                .map_or(Point::zero(), |subpath| subpath.start);
+            subpaths.push(Subpath::at(start));
+        }
+        let Some(subpath) = subpaths.last_mut() else {
+            continue;
+        };
+        let from = subpath.end();
+        match command {
+            PathCommand::LineTo { point, .. } => subpath.segments.push(Cubic::line(from, *point)),
+            PathCommand::CurveTo {
+                control1,
+                control2,
+                point,
+                ..
+            } => subpath.segments.push(Cubic {
+                c1: *control1,
+                c2: *control2,
+                end: *point,
+            }),
+            PathCommand::QuadTo { control, point, .. } => subpath
+                .segments
+                .push(Cubic::quadratic(from, *control, *point)),
+            PathCommand::ClosePath => {
+                if from != subpath.start {
+                    subpath.segments.push(Cubic::line(from, subpath.start));
+                }
+                subpath.closed = true;
+            }
+            PathCommand::MoveTo { .. } => {}
+        }
+    }
+    subpaths
+}
+
+/// Elliptical arc from `from` to `to` as cubic segments of at most a quarter turn
+fn arc(
+    from: Point,
+    rx: f64,
+    ry: f64,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) -> Vec<Cubic> {
+    if from == to {
+        return Vec::new();
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return vec![Cubic::line(from, to)];
+    }
+
+    // Centre parameterisation, following the SVG implementation notes
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let dx = (from.x - to.x) / 2.0;
+    let dy = (from.y - to.y) / 2.0;
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center_x1 = coefficient * rx * y1 / ry;
+    let center_y1 = -coefficient * ry * x1 / rx;
+    let center = Point::new(
+        cos * center_x1 - sin * center_y1 + (from.x + to.x) / 2.0,
+        sin * center_x1 + cos * center_y1 + (from.y + to.y) / 2.0,
+    );
+
+    let angle = |ux: f64, uy: f64, vx: f64, vy: f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let start_angle = angle(1.0, 0.0, (x1 - center_x1) / rx, (y1 - center_y1) / ry);
+    let mut sweep_angle = angle(
+        (x1 - center_x1) / rx,
+        (y1 - center_y1) / ry,
+        (-x1 - center_x1) / rx,
+        (-y1 - center_y1) / ry,
+    );
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= TAU;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += TAU;
+    }
+
+    // Unit circle points mapped onto the rotated ellipse
+    let map = |u: f64, v: f64| {
+        Point::new(
+            center.x + rx * u * cos - ry * v * sin,
+            center.y + rx * u * sin + ry * v * cos,
+        )
+    };
+    let count = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep_angle / count as f64;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    let mut segments: Vec<Cubic> = (0..count)
+        .map(|index| {
+            let (sin0, cos0) = (start_angle + step * index as f64).sin_cos();
+            let (sin1, cos1) = (start_angle + step * (index + 1) as f64).sin_cos();
+            Cubic {
+                c1: map(cos0 - handle * sin0, sin0 + handle * cos0),
+                c2: map(cos1 + handle * sin1, sin1 - handle * cos1),
+                end: map(cos1, sin1),
+            }
+        })
+        .collect();
+    if let Some(last) = segments.last_mut() {
+        last.end = to;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_relative_and_shorthand_commands() {
+        let path = SvgPath::from_data("m10 10 h10 v10 l-10 0 z").unwrap();
+        let parsed = subpaths(&path.commands);
+        assert_eq!(parsed.len(), 1);
+        let square = &parsed[0];
+        assert!(square.closed);
+        assert_eq!(square.start, Point::new(10.0, 10.0));
+        let ends: Vec<Point> = square.segments.iter().map(|segment| segment.end).collect();
+        assert_eq!(
+            ends,
+            [
+                Point::new(20.0, 10.0),
+                Point::new(20.0, 20.0),
+                Point::new(10.0, 20.0),
+                Point::new(10.0, 10.0),
+            ]
+        );
+
+        // Implicit line-tos after a move and compact arc flags
+        let path = SvgPath::from_data("M0 0 10 0 A5 5 0 0120 0").unwrap();
+        let parsed = subpaths(&path.commands);
+        assert_eq!(parsed[0].segments[0].end, Point::new(10.0, 0.0));
+        assert_eq!(parsed[0].end(), Point::new(20.0, 0.0));
+
+        assert!(SvgPath::from_data("L10 10").is_err());
+        assert!(SvgPath::from_data("M0 0 X").is_err());
+        assert!(SvgPath::from_data("INVALID PATH DATA").is_err());
+        assert!(SvgPath::from_data("").is_err());
+    }
+
+    #[test]
+    fn test_parses_command_kinds() {
+        let path =
+            SvgPath::from_data("M 10 10 C 20 20 30 30 40 40 Q 50 50 60 60 L 70 70 Z").unwrap();
+        assert_eq!(path.commands.len(), 5);
+        assert!(matches!(path.commands[0], PathCommand::MoveTo { .. }));
+        assert!(matches!(path.commands[1], PathCommand::CurveTo { .. }));
+        assert!(matches!(path.commands[2], PathCommand::QuadTo { .. }));
+        assert!(matches!(path.commands[3], PathCommand::LineTo { .. }));
+        assert!(matches!(path.commands[4], PathCommand::ClosePath));
+
+        let reparsed = SvgPath::from_data(&path.to_data()).unwrap();
+        assert_eq!(reparsed.commands, path.commands);
+    }
+
+    #[test]
+    fn test_bounds_and_length() {
+        let path = SvgPath::from_data("M10 10 L90 10 L90 90 Z").unwrap();
+        let bounds = path.bounds.unwrap();
+        assert_eq!((bounds.min_x, bounds.min_y), (10.0, 10.0));
+        assert_eq!((bounds.width(), bounds.height()), (80.0, 80.0));
+        assert_eq!(bounds.center(), Point::new(50.0, 50.0));
+        assert!((path.length - (160.0 + 80.0 * 2f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_stays_on_the_circle() {
+        // Half circle of radius 10 around (10, 0)
+        let segments = arc(
+            Point::new(0.0, 0.0),
+            10.0,
+            10.0,
+            0.0,
+            false,
+            true,
+            Point::new(20.0, 0.0),
+        );
+        assert_eq!(segments.len(), 2);
+        let top = segments[0].end;
+        assert!((top.x - 10.0).abs() < 1e-9);
+        assert!((top.y + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_morph_pads_segments_and_subpaths() {
+        let morpher = PathMorpher::new("M0 0 L10 0", "M0 0 L10 0 L10 10 Z M20 20 L30 30").unwrap();
+        let (from, to) = morpher.normalize();
+        assert_eq!(from.len(), 2);
+        for (from, to) in from.iter().zip(&to) {
+            assert_eq!(from.segments.len(), to.segments.len());
+        }
+
+        // The padded line still ends where it did
+        assert_eq!(from[0].end(), Point::new(10.0, 0.0));
+        let start = SvgPath::from_commands(morph_commands(&from, &to, 0.0)).data;
+        assert_eq!(start.matches(" C").count(), 4);
+        assert!(start.ends_with("M10 0 C10 0 10 0 10 0"));
+        let end = SvgPath::from_commands(morph_commands(&from, &to, 1.0)).data;
+        assert!(end.starts_with("M0 0 C3.333 0 6.667 0 10 0"));
+        assert!(end.contains(" Z M20 20 C"));
+    }
+
+    #[test]
+    fn test_interpolate_returns_the_paths_at_the_ends() {
+        let mut morpher = PathMorpher::new("M0 0 L100 0 L100 100 Z", "M0 0 L50 50 Z").unwrap();
+        assert_eq!(morpher.interpolate(0.0), morpher.source);
+        assert_eq!(morpher.interpolate(1.0), morpher.target);
+
+        let unprepared = morpher.interpolate(0.5);
+        morpher.prepare();
+        assert_eq!(morpher.interpolate(0.5), unprepared);
+        assert!(unprepared.data.starts_with("M0 0 C"));
+        assert!(unprepared.data.ends_with(" Z"));
+    }
+}
@@ -1,5 +1,13 @@
 //! Time and scheduling utilities
 
+#[cfg(feature = "web-sys")]
+use std::cell::{Cell, OnceCell, RefCell};
+#[cfg(feature = "web-sys")]
+use std::rc::{Rc, Weak};
+#[cfg(feature = "web-sys")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "web-sys")]
+use wasm_bindgen::closure::Closure;
 #[cfg(feature = "web-sys")]
 use web_sys::{Performance, window};
 
@@ -50,6 +58,146 @@ impl Default for Timer {
     }
 }
 
+/// Animation frame callback that can be requested again and again
+///
+/// Each requested frame calls the callback with the frame timestamp in
+/// milliseconds; the callback asks for the next frame by returning `true`.
+/// A frame is requested at most once at a time, so callers can request one
+/// whenever they have work for it. Dropping the last handle cancels the
+/// pending frame.
+#[cfg(feature = "web-sys")]
+#[derive(Clone)]
+pub struct FrameLoop {
+    inner: Rc<FrameLoopInner>,
+}
+
+#[cfg(feature = "web-sys")]
+struct FrameLoopInner {
+    on_frame: RefCell<Box<dyn FnMut(f64) -> bool>>,
+    /// Created on the first request, so loops can be set up off the browser
+    callback: OnceCell<Closure<dyn FnMut(f64)>>,
+    request: Cell<Option<i32>>,
+    this: Weak<FrameLoopInner>,
+}
+
+#[cfg(feature = "web-sys")]
+impl FrameLoop {
+    /// Loop calling `on_frame` on requested frames, none requested yet
+    pub fn new(on_frame: impl FnMut(f64) -> bool + 'static) -> Self {
+        let inner = Rc::new_cyclic(|this| FrameLoopInner {
+            on_frame: RefCell::new(Box::new(on_frame)),
+            callback: OnceCell::new(),
+            request: Cell::new(None),
+            this: this.clone(),
+        });
+        Self { inner }
+    }
+
+    /// Ask for a frame unless one is already pending
+    ///
+    /// `false` when there is no window to ask.
+    pub fn request(&self) -> bool {
+        self.inner.request()
+    }
+
+    /// Whether a frame is pending
+    pub fn is_pending(&self) -> bool {
+        self.inner.request.get().is_some()
+    }
+
+    /// Cancel the pending frame, if any
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+}
+
+#[cfg(feature = "web-sys")]
+impl FrameLoopInner {
+    fn request(&self) -> bool {
+        if self.request.get().is_some() {
+            return true;
+        }
+        let Some(window) = window() else {
+            return false;
+        };
+        let callback = self.callback.get_or_init(|| {
+            let this = self.this.clone();
+            Closure::wrap(Box::new(move |timestamp: f64| {
+                let Some(inner) = this.upgrade() else {
+                    return;
+                };
+                inner.request.set(None);
+                let more = (inner.on_frame.borrow_mut())(timestamp);
+                if more {
+                    inner.request();
+                }
+            }) as Box<dyn FnMut(f64)>)
+        });
+        let Ok(request) = window.request_animation_frame(callback.as_ref().unchecked_ref()) else {
+            return false;
+        };
+        self.request.set(Some(request));
+        true
+    }
+
+    fn cancel(&self) {
+        if let Some(request) = self.request.take()
+            && let Some(window) = window()
+        {
+            let _ = window.cancel_animation_frame(request);
+        }
+    }
+}
+
+#[cfg(feature = "web-sys")]
+impl Drop for FrameLoopInner {
+    fn drop(&mut self) {
+        // The browser must not call the callback once it is gone
+        self.cancel();
+    }
+}
+
+#[cfg(feature = "web-sys")]
+impl std::fmt::Debug for FrameLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameLoop")
+            .field("pending", &self.is_pending())
+            .finish()
+    }
+}
+
+/// Call `on_frame` on animation frames, starting with the next, until it returns `false`
+///
+/// The loop keeps itself alive while it runs. `false` when no frame could be
+/// requested, in which case `on_frame` is never called.
+#[cfg(feature = "web-sys")]
+pub fn run_frames(mut on_frame: impl FnMut(f64) -> bool + 'static) -> bool {
+    let running: Rc<RefCell<Option<FrameLoop>>> = Rc::default();
+    let frame_running = running.clone();
+    let frame_loop = FrameLoop::new(move |timestamp| {
+        let more = on_frame(timestamp);
+        if !more {
+            frame_running.borrow_mut().take();
+        }
+        more
+    });
+    if !frame_loop.request() {
+        return false;
+    }
+    *running.borrow_mut() = Some(frame_loop);
+    true
+}
+
+/// Run `callback` once on the next animation frame
+#[cfg(feature = "web-sys")]
+pub fn request_frame(callback: impl FnOnce() + 'static) {
+    let Some(window) = window() else {
+        return;
+    };
+    let closure = Closure::once_into_js(callback);
+    let _ = window.request_animation_frame(closure.unchecked_ref());
+}
+
 /// Frame rate utilities
 pub mod fps {
     /// Target frame rate for smooth animations
@@ -31,6 +31,11 @@ pub enum AnimationValue {
     String(String),
     /// Complex value with custom interpolation
     Complex(ComplexValue),
+    /// The `auto` keyword, such as the natural height of an element
+    ///
+    /// Sizes are measured when an animation starts so they can animate in
+    /// pixels, then handed back to `auto`.
+    Auto,
 }
 
 impl AnimationValue {
@@ -84,6 +89,7 @@ impl AnimationValue {
             }
             AnimationValue::String(s) => s.clone(),
             AnimationValue::Complex(_) => "complex".to_string(),
+            AnimationValue::Auto => "auto".to_string(),
         }
     }
 
@@ -339,11 +345,7 @@ impl Easing {
                 }
             }
             #[cfg(feature = "approx")]
-            Easing::Spring(_) => {
-                // For now, return linear interpolation for spring
-                // This should be replaced with proper spring physics when available
-                t
-            }
+            Easing::Spring(config) => crate::easing::spring_progress(config, t),
             Easing::Bezier(_x1, y1, _x2, y2) => {
                 // Simple cubic bezier implementation
                 // This is a simplified version - for production use, consider a more robust implementation
@@ -433,6 +435,21 @@ impl Default for Transition {
     }
 }
 
+impl Transition {
+    /// Duration in seconds
+    ///
+    /// Springs without an explicit duration take as long as they need to settle.
+    pub fn effective_duration(&self) -> Option<f64> {
+        self.duration.or_else(|| match &self.ease {
+            #[cfg(feature = "approx")]
+            Easing::Spring(config) => crate::SpringSimulator::new(config.clone())
+                .ok()
+                .map(|spring| spring.settling_duration()),
+            _ => None,
+        })
+    }
+}
+
 #[cfg(feature = "approx")]
 impl Default for SpringConfig {
     fn default() -> Self {
@@ -597,6 +614,18 @@ mod tests {
         assert_eq!(pixels.unit(), "px");
     }
 
+    #[test]
+    fn test_animation_value_auto() {
+        let auto = AnimationValue::Auto;
+        assert_eq!(auto.to_string_value(), "auto");
+        assert!(!auto.is_numeric());
+        assert_eq!(auto.as_number(), None);
+
+        let collapsed = AnimationValue::Pixels(0.0);
+        assert_eq!(collapsed.interpolate(&auto, 0.25), collapsed);
+        assert_eq!(collapsed.interpolate(&auto, 1.0), auto);
+    }
+
     #[test]
     fn test_transform_identity() {
         let identity = Transform::default();
@@ -647,7 +676,7 @@ pub mod leptos_helpers {
     pub fn transition_to_css_properties(transition: &Transition) -> String {
         let mut properties = Vec::new();
 
-        if let Some(duration) = transition.duration {
+        if let Some(duration) = transition.effective_duration() {
             properties.push(format!("transition-duration: {}s", duration));
         }
 
@@ -655,27 +684,10 @@ pub mod leptos_helpers {
             properties.push(format!("transition-delay: {}s", delay));
         }
 
-        let ease_string = match transition.ease {
-            Easing::Linear => "linear",
-            Easing::EaseIn => "ease-in",
-            Easing::EaseOut => "ease-out",
-            Easing::EaseInOut => "ease-in-out",
-            Easing::CircIn => "cubic-bezier(0.55, 0.055, 0.675, 0.19)",
-            Easing::CircOut => "cubic-bezier(0.215, 0.61, 0.355, 1)",
-            Easing::CircInOut => "cubic-bezier(0.645, 0.045, 0.355, 1)",
-            Easing::BackIn => "cubic-bezier(0.6, -0.28, 0.735, 0.045)",
-            Easing::BackOut => "cubic-bezier(0.175, 0.885, 0.32, 1.275)",
-            Easing::BackInOut => "cubic-bezier(0.68, -0.55, 0.265, 1.55)",
-            Easing::Spring(_) => "cubic-bezier(0.68, -0.55, 0.265, 1.55)", // Simplified
-            Easing::Bezier(a, b, c, d) => {
-                return format!("cubic-bezier({}, {}, {}, {})", a, b, c, d);
-            }
-            Easing::CubicBezier(cb) => {
-                return format!("cubic-bezier({}, {}, {}, {})", cb.0, cb.1, cb.2, cb.3);
-            }
-        };
-
-        properties.push(format!("transition-timing-function: {}", ease_string));
+        properties.push(format!(
+            "transition-timing-function: {}",
+            transition.ease.to_css()
+        ));
         properties.join("; ")
     }
 }
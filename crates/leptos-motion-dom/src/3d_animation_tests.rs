@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use crate::ReactiveMotionDiv;
+use crate::reactive_motion_div::ReactiveMotionDiv;
 use crate::core::{AnimationValue, Easing, RepeatConfig, Transition};
 use leptos::prelude::*;
 use std::collections::HashMap;
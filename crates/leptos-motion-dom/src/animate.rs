@@ -0,0 +1,171 @@
+//! CSS transition animations of motion elements
+//!
+//! [`animate_target`] is the primitive behind mount, gesture, variant and
+//! presence animations: it transitions the properties of an
+//! [`AnimationTarget`] and hands the element its own transitions back once
+//! the animation completes.
+
+use crate::auto_size::AutoSizes;
+use crate::svg_animation::animate_svg_attributes;
+use crate::utils::{
+    is_current_animation, next_animation_generation, remember_style, restore_style,
+};
+use leptos_motion_core::svg::{
+    PATH_LENGTH_ATTRIBUTE, PathDrawing, is_path_drawing, is_svg_attribute, svg_attribute_value,
+};
+use leptos_motion_core::{AnimationTarget, Transition};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+/// Property of an element tracking its latest CSS transition animation
+const TRANSITION_ANIMATION_KEY: &str = "__motionTransition";
+
+/// Declarations set while an animation transitions an element
+const TRANSITION_DECLARATIONS: [&str; 4] = [
+    "transition-property",
+    "transition-duration",
+    "transition-delay",
+    "transition-timing-function",
+];
+
+/// Property of an element holding its own `declaration` while it is animated
+fn saved_transition_key(declaration: &str) -> String {
+    format!("__motionTransition_{declaration}")
+}
+
+/// `transition-property` of an animation of `properties` over the `current` one
+///
+/// Properties still transitioning for an earlier animation keep going.
+pub(crate) fn transition_property_list(current: &str, properties: &[&str]) -> String {
+    let mut list: Vec<&str> = current
+        .split(',')
+        .map(str::trim)
+        .filter(|property| !matches!(*property, "" | "all" | "none"))
+        .collect();
+    for property in properties {
+        if !list.contains(property) {
+            list.push(property);
+        }
+    }
+    if list.is_empty() {
+        "none".to_string()
+    } else {
+        list.join(", ")
+    }
+}
+
+/// Set CSS transitions for `transition` and apply `target` to `element`
+///
+/// Only the properties of `target` transition, and the element gets its
+/// own transition declarations back once the last animation completes, so
+/// later style writes, such as drag offsets, apply right away.
+///
+/// SVG attributes such as `d` or `cx` are tweened on animation frames and
+/// path drawing values become `stroke-dasharray`/`stroke-dashoffset`.
+/// A `height` or `width` of
+/// [`AnimationValue::Auto`](leptos_motion_core::AnimationValue::Auto) animates
+/// to the measured natural size and is set back to `auto` at the end.
+/// `on_complete` runs once the transition (including its delay) has finished.
+pub fn animate_target(
+    element: &web_sys::HtmlElement,
+    target: &AnimationTarget,
+    transition: &Transition,
+    on_complete: impl FnOnce() + 'static,
+) {
+    let sizes = AutoSizes::resolve(element, target);
+    let drawing = PathDrawing::from_target(target);
+    let mut properties: Vec<&str> = target
+        .keys()
+        .map(String::as_str)
+        .filter(|property| !is_svg_attribute(property) && !is_path_drawing(property))
+        .collect();
+    let drawing_properties = drawing
+        .as_ref()
+        .map(PathDrawing::css_properties)
+        .unwrap_or_default();
+    properties.extend(drawing_properties.iter().map(|(property, _)| *property));
+
+    let generation = next_animation_generation(element, TRANSITION_ANIMATION_KEY);
+    for declaration in TRANSITION_DECLARATIONS {
+        remember_style(element, &saved_transition_key(declaration), declaration);
+    }
+    let style = element.style();
+    let current = style
+        .get_property_value("transition-property")
+        .unwrap_or_default();
+    let _ = style.set_property(
+        "transition-property",
+        &transition_property_list(&current, &properties),
+    );
+    for declaration in leptos_motion_core::transition_to_css_properties(transition).split("; ") {
+        if let Some((property, value)) = declaration.split_once(": ") {
+            let _ = style.set_property(property, value);
+        }
+    }
+
+    // Flush the current styles so the change below transitions
+    let _ = element.get_bounding_client_rect();
+    let mut attributes = Vec::new();
+    for (property, value) in target {
+        if is_svg_attribute(property) {
+            attributes.push((property.clone(), svg_attribute_value(value)));
+        } else if !is_path_drawing(property) {
+            let value = sizes
+                .value(property)
+                .unwrap_or_else(|| value.to_string_value());
+            let _ = style.set_property(property, &value);
+        }
+    }
+    if drawing.is_some() {
+        let (name, value) = PATH_LENGTH_ATTRIBUTE;
+        let _ = element.set_attribute(name, value);
+        for (property, value) in &drawing_properties {
+            let _ = style.set_property(property, value);
+        }
+    }
+    if !attributes.is_empty() {
+        animate_svg_attributes(element, attributes, transition);
+    }
+
+    let duration = transition.effective_duration().unwrap_or(0.0);
+    let total_ms = (duration + transition.delay.unwrap_or(0.0)) * 1000.0;
+    let element = element.clone();
+    let on_complete = move || {
+        sizes.restore(&element);
+        if is_current_animation(&element, TRANSITION_ANIMATION_KEY, generation) {
+            for declaration in TRANSITION_DECLARATIONS {
+                restore_style(&element, &saved_transition_key(declaration), declaration);
+            }
+        }
+        on_complete();
+    };
+    let Some(window) = web_sys::window() else {
+        on_complete();
+        return;
+    };
+    let callback = Closure::once_into_js(on_complete);
+    let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        callback.unchecked_ref(),
+        total_ms.ceil() as i32,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_property_list_keeps_running_properties() {
+        assert_eq!(
+            transition_property_list("", &["opacity", "height"]),
+            "opacity, height"
+        );
+        assert_eq!(
+            transition_property_list("opacity", &["transform", "opacity"]),
+            "opacity, transform"
+        );
+        // Lists not set by an animation don't add to its properties
+        assert_eq!(transition_property_list("all", &["opacity"]), "opacity");
+        assert_eq!(transition_property_list("none", &[]), "none");
+    }
+}
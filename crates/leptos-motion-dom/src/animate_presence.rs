@@ -2,10 +2,18 @@
 //!
 //! A component for handling enter and exit animations of conditionally rendered elements
 
+use crate::animate::animate_target;
+use crate::motion_element::html_element;
+use crate::presence_flow::PresenceItemContext;
+use crate::shared_layout::replace_layout_element;
+use crate::variants::{Custom, Variants};
+use leptos::html::ElementType;
 use leptos::prelude::*;
 use leptos::reactive::signal::signal;
 use leptos_motion_core::{AnimationTarget, Transition};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use wasm_bindgen::JsCast;
 
 /// Type alias for animate presence hook return type
 type UseAnimatePresenceReturn = (
@@ -18,14 +26,14 @@ type UseAnimatePresenceReturn = (
 /// Mode for handling presence animations
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum PresenceMode {
-    /// Synchronous mode - all animations complete before next state
+    /// Enter new children while old ones exit
     #[default]
     Sync,
-    /// Wait mode - wait for exit animations before entering new elements
+    /// Wait for exit animations to finish before entering new children
     Wait,
-    /// Immediate mode - start enter animations immediately
+    /// Enter new children immediately (same as `Sync`)
     Immediate,
-    /// Pop layout mode - handle layout changes
+    /// Take exiting children out of the layout so siblings reflow immediately
     PopLayout,
 }
 
@@ -40,7 +48,125 @@ pub struct AnimatePresenceConfig {
     pub enter_transition: Option<Transition>,
 }
 
+/// Presence state shared with the children of an [`AnimatePresence`]
+#[derive(Clone, Copy)]
+pub struct PresenceContext {
+    config: StoredValue<AnimatePresenceConfig>,
+    manager: StoredValue<PresenceManager>,
+    exiting: RwSignal<Vec<String>>,
+    play_initial: bool,
+    first_render: StoredValue<bool>,
+    next_id: StoredValue<u64>,
+    on_exit_complete: Option<Callback<()>>,
+    custom: Option<Signal<Custom>>,
+}
+
+impl PresenceContext {
+    /// Presence mode of the enclosing [`AnimatePresence`]
+    pub fn mode(&self) -> PresenceMode {
+        self.config.with_value(|config| config.mode.clone())
+    }
+
+    /// Keys of the children currently running their exit animation
+    pub fn exiting(&self) -> RwSignal<Vec<String>> {
+        self.exiting
+    }
+
+    /// Whether a child mounting now should skip its `initial` state
+    pub fn skips_initial(&self) -> bool {
+        !self.play_initial && self.first_render.get_value()
+    }
+
+    /// Latest custom data, passed to the exit variants of removed children
+    pub fn custom(&self) -> Option<Custom> {
+        self.custom.and_then(|custom| custom.try_get_untracked())
+    }
+
+    /// Register a mounted child, generating a key if it has none
+    pub fn register(&self, key: Option<String>) -> String {
+        let id = key.unwrap_or_else(|| {
+            let id = self.next_id.get_value();
+            self.next_id.set_value(id + 1);
+            format!("presence-child-{}", id)
+        });
+        self.manager
+            .update_value(|manager| manager.add_child(id.clone(), None));
+        self.sync_exiting();
+        id
+    }
+
+    /// Move a child into the exiting list
+    pub fn start_exit(&self, id: &str) {
+        let _ = self
+            .manager
+            .try_update_value(|manager| manager.remove_child(id));
+        self.sync_exiting();
+    }
+
+    /// Mark a child's exit as finished, firing `on_exit_complete` once none are left
+    pub fn finish_exit(&self, id: &str) {
+        let all_exited = self
+            .manager
+            .try_update_value(|manager| {
+                manager.complete_exit(id);
+                manager.update_exiting_children()
+            })
+            .unwrap_or(false);
+        self.sync_exiting();
+        if all_exited && let Some(on_exit_complete) = self.on_exit_complete {
+            on_exit_complete.run(());
+        }
+    }
+
+    /// Exit transition, falling back to the presence default
+    fn exit_transition(&self, transition: Option<Transition>) -> Transition {
+        transition
+            .or_else(|| {
+                self.config
+                    .try_with_value(|config| config.exit_transition.clone())
+                    .flatten()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Enter transition, falling back to the presence default
+    fn enter_transition(&self, transition: Option<Transition>) -> Transition {
+        transition
+            .or_else(|| {
+                self.config
+                    .try_with_value(|config| config.enter_transition.clone())
+                    .flatten()
+            })
+            .unwrap_or_default()
+    }
+
+    fn sync_exiting(&self) {
+        if let Some(exiting) = self
+            .manager
+            .try_with_value(|manager| manager.current_exiting_children().clone())
+        {
+            self.exiting.try_set(exiting);
+        }
+    }
+}
+
+/// Access the closest [`AnimatePresence`]
+pub fn use_presence_context() -> Option<PresenceContext> {
+    use_context::<PresenceContext>()
+}
+
 /// AnimatePresence component for handling enter/exit animations
+///
+/// Motion children with an `exit` target stay in the document after Leptos
+/// removes them: a snapshot of the element animates to the exit target and
+/// is only then unmounted. Children are tracked by their `key`.
+///
+/// - [`PresenceMode::Sync`] and [`PresenceMode::Immediate`] enter new
+///   children while old ones exit.
+/// - [`PresenceMode::Wait`] keeps entering children hidden until every
+///   exit has finished.
+/// - [`PresenceMode::PopLayout`] takes exiting children out of the layout
+///   so their siblings reflow immediately.
 #[component]
 pub fn AnimatePresence(
     /// Presence mode
@@ -52,15 +178,39 @@ pub fn AnimatePresence(
     /// Default enter transition
     #[prop(optional)]
     enter_transition: Option<Transition>,
+    /// Play the `initial` state of children present on first render (defaults to true)
+    #[prop(optional)]
+    initial: Option<bool>,
+    /// Called once every exiting child has finished its exit animation
+    #[prop(optional, into)]
+    on_exit_complete: Option<Callback<()>>,
+    /// Custom data for the exit variants of removed children, read when they exit
+    #[prop(optional, into)]
+    custom: Option<Signal<Custom>>,
     /// Children to animate
     children: Children,
 ) -> impl IntoView {
-    let _config = AnimatePresenceConfig {
+    let config = AnimatePresenceConfig {
         mode: mode.unwrap_or_default(),
         exit_transition,
         enter_transition,
     };
 
+    let first_render = StoredValue::new(true);
+    provide_context(PresenceContext {
+        manager: StoredValue::new(PresenceManager::new(config.clone())),
+        config: StoredValue::new(config),
+        exiting: RwSignal::new(Vec::new()),
+        play_initial: initial.unwrap_or(true),
+        first_render,
+        next_id: StoredValue::new(0),
+        on_exit_complete,
+        custom,
+    });
+
+    // Effects run after the first render is mounted
+    Effect::new(move |_| first_render.set_value(false));
+
     view! {
         <div class="animate-presence">
             {children()}
@@ -77,8 +227,213 @@ pub fn use_animate_presence() -> UseAnimatePresenceReturn {
 }
 
 /// Hook for managing exiting children
+///
+/// Inside an [`AnimatePresence`] this tracks the keys of the children
+/// currently running their exit animation.
 pub fn use_exiting_children() -> (ReadSignal<Vec<String>>, WriteSignal<Vec<String>>) {
-    signal(Vec::<String>::new())
+    match use_presence_context() {
+        Some(context) => context.exiting.split(),
+        None => signal(Vec::<String>::new()),
+    }
+}
+
+/// Exit state of a presence child
+#[derive(Debug, Clone)]
+pub enum PresenceExit {
+    /// Fixed exit target
+    Target(AnimationTarget),
+    /// Variant resolved when the exit starts
+    Variant {
+        /// Variants of the child
+        variants: Variants,
+        /// Label of the exit variant
+        label: String,
+        /// Custom data of the child
+        custom: Option<Custom>,
+    },
+}
+
+impl PresenceExit {
+    /// Resolve the exit target, preferring the presence's latest custom data over the child's
+    pub fn resolve(&self, presence_custom: Option<&Custom>) -> Option<AnimationTarget> {
+        match self {
+            Self::Target(target) => Some(target.clone()),
+            Self::Variant {
+                variants,
+                label,
+                custom,
+            } => variants.resolve_variant(label, presence_custom.or(custom.as_ref())),
+        }
+    }
+}
+
+impl From<AnimationTarget> for PresenceExit {
+    fn from(target: AnimationTarget) -> Self {
+        Self::Target(target)
+    }
+}
+
+/// Enter and exit a motion element inside an enclosing [`AnimatePresence`]
+///
+/// Call from a motion component body; returns `true` if the component
+/// should render without its `initial` state (`initial={false}`). Inside
+/// [`PresenceFor`](crate::presence_flow::PresenceFor) or
+/// [`PresenceShow`](crate::presence_flow::PresenceShow) the element itself
+/// exits and holds back its removal. Without an enclosing presence this
+/// does nothing.
+pub fn use_presence_child<E>(
+    node_ref: NodeRef<E>,
+    key: Option<String>,
+    animate: Option<AnimationTarget>,
+    exit: Option<PresenceExit>,
+    transition: Option<Transition>,
+) -> bool
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    if let Some(item) = use_context::<PresenceItemContext>() {
+        return use_presence_item(item, node_ref, animate, exit, transition);
+    }
+    let Some(context) = use_presence_context() else {
+        return false;
+    };
+    let skip_initial = context.skips_initial();
+    let id = context.register(key);
+
+    // Enter, waiting for running exits first in `Wait` mode
+    let wait = context.mode() == PresenceMode::Wait;
+    let entered = Cell::new(false);
+    let hidden_display = RefCell::new(None::<String>);
+    let enter_transition = context.enter_transition(transition.clone());
+    Effect::new(move |_| {
+        let exits_pending = context.exiting.with(|exiting| !exiting.is_empty());
+        if entered.get() {
+            return;
+        }
+        let Some(element) = html_element(node_ref) else {
+            return;
+        };
+        let style = element.style();
+        if wait && exits_pending {
+            if hidden_display.borrow().is_none() {
+                let display = style.get_property_value("display").unwrap_or_default();
+                *hidden_display.borrow_mut() = Some(display);
+                let _ = style.set_property("display", "none");
+            }
+            return;
+        }
+
+        entered.set(true);
+        if let Some(display) = hidden_display.borrow_mut().take() {
+            let _ = style.set_property("display", &display);
+        }
+        if !skip_initial && let Some(animate) = &animate {
+            animate_target(&element, animate, &enter_transition, || {});
+        }
+    });
+
+    // Exit: snapshot the element before Leptos unmounts it
+    let exit_transition = context.exit_transition(transition);
+    let mode = context.mode();
+    on_cleanup(move || {
+        context.start_exit(&id);
+        let exit = exit.and_then(|exit| exit.resolve(context.custom().as_ref()));
+        // An element already detached can't be measured or stood in for
+        let ghost = exit.as_ref().and_then(|_| {
+            let element = html_element(node_ref).filter(|element| element.is_connected())?;
+            let parent = element.parent_node()?;
+            let offset_box = (
+                element.offset_left(),
+                element.offset_top(),
+                element.offset_width(),
+                element.offset_height(),
+            );
+            let ghost = element
+                .clone_node_with_deep(true)
+                .ok()?
+                .unchecked_into::<web_sys::HtmlElement>();
+            parent.insert_before(&ghost, Some(&element)).ok()?;
+            replace_layout_element(&element, &ghost);
+            Some((ghost, offset_box))
+        });
+        let (Some(exit), Some((ghost, (left, top, width, height)))) = (exit, ghost) else {
+            context.finish_exit(&id);
+            return;
+        };
+
+        let style = ghost.style();
+        let _ = style.set_property("pointer-events", "none");
+        if mode == PresenceMode::PopLayout {
+            let _ = style.set_property("position", "absolute");
+            let _ = style.set_property("margin", "0");
+            let _ = style.set_property("left", &format!("{}px", left));
+            let _ = style.set_property("top", &format!("{}px", top));
+            let _ = style.set_property("width", &format!("{}px", width));
+            let _ = style.set_property("height", &format!("{}px", height));
+        }
+
+        let exiting_ghost = ghost.clone();
+        animate_target(&ghost, &exit, &exit_transition, move || {
+            exiting_ghost.remove();
+            context.finish_exit(&id);
+        });
+    });
+
+    skip_initial
+}
+
+/// Enter and exit a motion element kept mounted by presence-aware control flow
+fn use_presence_item<E>(
+    item: PresenceItemContext,
+    node_ref: NodeRef<E>,
+    animate: Option<AnimationTarget>,
+    exit: Option<PresenceExit>,
+    transition: Option<Transition>,
+) -> bool
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let skip_initial = item.skips_initial();
+    let transition = transition.unwrap_or_default();
+    let blocker = exit.as_ref().map(|_| item.register());
+    if let Some(id) = blocker {
+        on_cleanup(move || item.unregister(id));
+    }
+
+    let was_present = Cell::new(None::<bool>);
+    Effect::new(move |_| {
+        let present = item.is_present();
+        let previous = was_present.replace(Some(present));
+        let Some(element) = html_element(node_ref) else {
+            return;
+        };
+        match (previous, present) {
+            // Enter, or come back while exiting
+            (None, true) | (Some(false), true) => {
+                if (previous.is_some() || !skip_initial)
+                    && let Some(animate) = &animate
+                {
+                    animate_target(&element, animate, &transition, || {});
+                }
+            }
+            (_, false) => {
+                let Some(id) = blocker else {
+                    return;
+                };
+                match exit.as_ref().and_then(|exit| exit.resolve(None)) {
+                    Some(target) => animate_target(&element, &target, &transition, move || {
+                        item.safe_to_remove(id)
+                    }),
+                    None => item.safe_to_remove(id),
+                }
+            }
+            _ => {}
+        }
+    });
+
+    skip_initial
 }
 
 /// Presence animation manager
@@ -88,6 +443,7 @@ pub struct PresenceManager {
     config: AnimatePresenceConfig,
     children: Vec<String>,
     exiting_children: Vec<String>,
+    exit_completed: Vec<String>,
     animations: HashMap<String, AnimationTarget>,
 }
 
@@ -98,6 +454,7 @@ impl PresenceManager {
             config,
             children: Vec::new(),
             exiting_children: Vec::new(),
+            exit_completed: Vec::new(),
             animations: HashMap::new(),
         }
     }
@@ -120,10 +477,26 @@ impl PresenceManager {
         }
     }
 
-    /// Update exiting children
-    pub fn update_exiting_children(&mut self) {
-        // In a real implementation, this would handle exit animation completion
-        self.exiting_children.clear();
+    /// Mark a child's exit animation as finished
+    pub fn complete_exit(&mut self, id: &str) {
+        if self.is_child_exiting(id) && !self.exit_completed.iter().any(|x| x == id) {
+            self.exit_completed.push(id.to_string());
+        }
+    }
+
+    /// Drop exiting children whose exit animation has finished
+    ///
+    /// Returns `true` once no child is left exiting.
+    pub fn update_exiting_children(&mut self) -> bool {
+        for id in std::mem::take(&mut self.exit_completed) {
+            if let Some(pos) = self.exiting_children.iter().position(|x| *x == id) {
+                self.exiting_children.remove(pos);
+            }
+            if !self.children.contains(&id) {
+                self.animations.remove(&id);
+            }
+        }
+        self.exiting_children.is_empty()
     }
 
     /// Get current children
@@ -160,6 +533,7 @@ impl PresenceManager {
     pub fn clear(&mut self) {
         self.children.clear();
         self.exiting_children.clear();
+        self.exit_completed.clear();
         self.animations.clear();
     }
 
@@ -226,6 +600,30 @@ pub fn create_initial_animation() -> AnimationTarget {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_presence_exit_prefers_presence_custom() {
+        let mut variants = Variants::new();
+        variants.add_dynamic_variant("exit".to_string(), |direction: &f64| {
+            crate::variants::common_variants::translate(direction * 50.0, 0.0)
+        });
+        let exit = PresenceExit::Variant {
+            variants,
+            label: "exit".to_string(),
+            custom: Some(Custom::new(1.0_f64)),
+        };
+
+        let own = exit.resolve(None).unwrap();
+        assert_eq!(
+            own.get("x"),
+            Some(&leptos_motion_core::AnimationValue::Number(50.0))
+        );
+        let latest = exit.resolve(Some(&Custom::new(-1.0_f64))).unwrap();
+        assert_eq!(
+            latest.get("x"),
+            Some(&leptos_motion_core::AnimationValue::Number(-50.0))
+        );
+    }
+
     #[test]
     fn test_presence_manager_basic() {
         let mut manager = PresenceManager::default();
@@ -278,6 +676,38 @@ mod tests {
         assert_eq!(manager.exiting_child_count(), 0);
     }
 
+    #[test]
+    fn test_presence_manager_exit_completion() {
+        let mut manager = PresenceManager::default();
+        manager.add_child("modal".to_string(), None);
+        manager.add_child("toast".to_string(), None);
+        manager.remove_child("modal");
+        manager.remove_child("toast");
+
+        // Exits stay tracked until their animation completes
+        assert!(!manager.update_exiting_children());
+        assert_eq!(manager.exiting_child_count(), 2);
+
+        manager.complete_exit("modal");
+        assert!(!manager.update_exiting_children());
+        assert!(!manager.is_child_exiting("modal"));
+        assert!(manager.is_child_exiting("toast"));
+
+        manager.complete_exit("toast");
+        assert!(manager.update_exiting_children());
+        assert_eq!(manager.exiting_child_count(), 0);
+    }
+
+    #[test]
+    fn test_presence_manager_ignores_unknown_exit() {
+        let mut manager = PresenceManager::default();
+        manager.add_child("modal".to_string(), None);
+
+        manager.complete_exit("modal");
+        assert!(manager.update_exiting_children());
+        assert!(manager.is_child_present("modal"));
+    }
+
     #[test]
     fn test_animation_utilities() {
         let exit_anim = create_exit_animation();
@@ -1,6 +1,5 @@
 #![cfg(test)]
 
-use crate::ReactiveMotionDiv;
 use leptos::prelude::*;
 use leptos_motion_core::{
     AnimationValue, Easing, RepeatConfig, StaggerConfig, StaggerFrom, Transition,
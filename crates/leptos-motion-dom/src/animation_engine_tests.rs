@@ -205,49 +205,20 @@ fn test_cubic_bezier() {
 /// Test spring physics
 #[test]
 fn test_spring_physics() {
-    use crate::easing_functions::spring::*;
+    use leptos_motion_core::{SpringConfig, SpringSimulator};
 
-    let config = SpringConfig::default();
-    let mut state = SpringState::new(0.0, 100.0);
+    let spring = SpringSimulator::new(SpringConfig::default()).unwrap();
 
     // Test initial state
-    assert_eq!(state.position, 0.0);
-    assert_eq!(state.velocity, 0.0);
-    assert_eq!(state.target, 100.0);
-    assert!(!state.is_complete);
+    assert_eq!(spring.position(0.0, 100.0, 0.0), 0.0);
+    assert_eq!(spring.velocity(0.0, 100.0, 0.0), 0.0);
 
     // Test spring update
-    update_spring(&mut state, &config, 0.016); // 60fps
+    let position = spring.position(0.0, 100.0, 0.016); // 60fps
 
     // Position should be valid (spring physics can have negative values initially)
-    assert!(state.position.is_finite());
-    assert!(state.position <= 100.0);
-}
-
-/// Test spring presets
-#[test]
-fn test_spring_presets() {
-    use crate::easing_functions::spring::presets::*;
-
-    let gentle = gentle();
-    assert_eq!(gentle.stiffness, 120.0);
-    assert_eq!(gentle.damping, 14.0);
-
-    let wobbly = wobbly();
-    assert_eq!(wobbly.stiffness, 180.0);
-    assert_eq!(wobbly.damping, 12.0);
-
-    let stiff = stiff();
-    assert_eq!(stiff.stiffness, 210.0);
-    assert_eq!(stiff.damping, 20.0);
-
-    let slow = slow();
-    assert_eq!(slow.stiffness, 280.0);
-    assert_eq!(slow.damping, 60.0);
-
-    let bouncy = bouncy();
-    assert_eq!(bouncy.stiffness, 400.0);
-    assert_eq!(bouncy.damping, 10.0);
+    assert!(position.is_finite());
+    assert!(position <= 100.0);
 }
 
 /// Test repeat configuration
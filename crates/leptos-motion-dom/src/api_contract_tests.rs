@@ -25,9 +25,9 @@ fn test_motion_div_api_contract_signature() {
                 transition=Transition::default()
                 while_hover=HashMap::new()
                 while_tap=HashMap::new()
-                _layout=true
+                layout=true
                 drag=DragConfig::default()
-                _drag_constraints=DragConstraints::default()
+                drag_constraints=DragConstraints::default()
             >
                 "Test Content"
             </MotionDiv>
@@ -154,9 +154,9 @@ fn test_prop_type_consistency_contract() {
                 transition=transition.unwrap_or_default()
                 while_hover=while_hover.unwrap_or_default()
                 while_tap=while_tap.unwrap_or_default()
-                _layout=layout.unwrap_or(false)
+                layout=layout.unwrap_or(false)
                 drag=drag.unwrap_or_default()
-                _drag_constraints=drag_constraints.unwrap_or_default()
+                drag_constraints=drag_constraints.unwrap_or_default()
             >
                 "Type Test Content"
             </MotionDiv>
@@ -263,9 +263,9 @@ fn test_api_stability_contract() {
                 transition=Transition::default()
                 while_hover=HashMap::new()
                 while_tap=HashMap::new()
-                _layout=true
+                layout=true
                 drag=DragConfig::default()
-                _drag_constraints=DragConstraints::default()
+                drag_constraints=DragConstraints::default()
             >
                 "Stability Test"
             </MotionDiv>
@@ -0,0 +1,144 @@
+//! Size animations to and from `auto`
+//!
+//! CSS transitions can't interpolate `auto`, so [`AutoSizes`] pins animated
+//! sizes to pixels, measures the natural size of those animating to `auto`
+//! and hands `auto` back once the animation completes, leaving the element
+//! free to follow later content changes.
+
+use crate::utils::{is_current_animation, next_animation_generation};
+use leptos_motion_core::{AnimationTarget, AnimationValue, computed_size, natural_size};
+
+/// Properties that can animate to and from `auto`
+const SIZE_PROPERTIES: [&str; 2] = ["height", "width"];
+
+/// Property of the element tracking the latest animation of a size
+fn generation_key(property: &str) -> String {
+    format!("__motionSize_{property}")
+}
+
+/// Sizes of an animation target resolved from `auto` to pixels
+pub(crate) struct AutoSizes {
+    /// Property, natural size in pixels and animation generation
+    sizes: Vec<(&'static str, f64, f64)>,
+}
+
+impl AutoSizes {
+    /// Pin the sizes `target` animates to pixels and measure those going to `auto`
+    ///
+    /// Must run before the target is applied so the transition starts from
+    /// the current size.
+    pub(crate) fn resolve(element: &web_sys::HtmlElement, target: &AnimationTarget) -> Self {
+        let style = element.style();
+        let mut sizes = Vec::new();
+        for property in SIZE_PROPERTIES {
+            let Some(value) = target.get(property) else {
+                continue;
+            };
+            let generation = next_animation_generation(element, &generation_key(property));
+            let to_auto = matches!(value, AnimationValue::Auto);
+            let inline = style.get_property_value(property).unwrap_or_default();
+            if (to_auto || inline.is_empty() || inline == "auto")
+                && let Some(current) = computed_size(element, property)
+            {
+                let _ = style.set_property(property, &format!("{current}px"));
+            }
+            if to_auto && let Some(natural) = natural_size(element, property) {
+                sizes.push((property, natural, generation));
+            }
+        }
+        Self { sizes }
+    }
+
+    /// Pixel value to animate `property` to in place of `auto`
+    pub(crate) fn value(&self, property: &str) -> Option<String> {
+        self.sizes
+            .iter()
+            .find(|(size, _, _)| *size == property)
+            .map(|(_, natural, _)| format!("{natural}px"))
+    }
+
+    /// Hand the resolved sizes back to `auto` unless a later animation took them over
+    pub(crate) fn restore(&self, element: &web_sys::HtmlElement) {
+        for (property, _, generation) in &self.sizes {
+            if is_current_animation(element, &generation_key(property), *generation) {
+                let _ = element.style().set_property(property, "auto");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Element in the page with 50px of content and the inline `style`
+    fn element_with_content(style: &str) -> web_sys::HtmlElement {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let element: web_sys::HtmlElement =
+            document.create_element("div").unwrap().unchecked_into();
+        element.set_attribute("style", style).unwrap();
+        let content = document.create_element("div").unwrap();
+        content.set_attribute("style", "height: 50px").unwrap();
+        element.append_child(&content).unwrap();
+        document.body().unwrap().append_child(&element).unwrap();
+        element
+    }
+
+    fn height(value: AnimationValue) -> AnimationTarget {
+        [("height".to_string(), value)].into_iter().collect()
+    }
+
+    fn inline_height(element: &web_sys::HtmlElement) -> String {
+        element.style().get_property_value("height").unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn test_resolve_from_zero_to_auto() {
+        let element = element_with_content("height: 0px; overflow: hidden");
+
+        let sizes = AutoSizes::resolve(&element, &height(AnimationValue::Auto));
+        assert_eq!(inline_height(&element), "0px");
+        assert_eq!(sizes.value("height").as_deref(), Some("50px"));
+        assert_eq!(sizes.value("width"), None);
+
+        sizes.restore(&element);
+        assert_eq!(inline_height(&element), "auto");
+        element.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_resolve_from_auto_to_zero() {
+        let element = element_with_content("overflow: hidden");
+
+        // The transition starts from the measured size rather than `auto`
+        let sizes = AutoSizes::resolve(&element, &height(AnimationValue::Pixels(0.0)));
+        assert_eq!(inline_height(&element), "50px");
+        assert_eq!(sizes.value("height"), None);
+
+        let _ = element.style().set_property("height", "0px");
+        sizes.restore(&element);
+        assert_eq!(inline_height(&element), "0px");
+        element.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_newer_animation_keeps_its_size() {
+        let element = element_with_content("height: 0px; overflow: hidden");
+
+        let expanding = AutoSizes::resolve(&element, &height(AnimationValue::Auto));
+        let _ = element.style().set_property("height", "50px");
+        let collapsing = AutoSizes::resolve(&element, &height(AnimationValue::Pixels(0.0)));
+        let _ = element.style().set_property("height", "0px");
+
+        // The interrupted expansion completes without handing `auto` back
+        expanding.restore(&element);
+        assert_eq!(inline_height(&element), "0px");
+        collapsing.restore(&element);
+        assert_eq!(inline_height(&element), "0px");
+        element.remove();
+    }
+}
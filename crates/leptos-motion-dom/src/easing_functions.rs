@@ -1,7 +1,9 @@
 //! Easing Functions for Leptos Motion
 //!
 //! This module provides comprehensive easing functions for smooth animations.
-//! Includes standard easing curves, cubic bezier curves, and spring physics.
+//! Includes standard easing curves and cubic bezier curves. Springs are
+//! [`leptos_motion_core::Easing::Spring`], simulated by
+//! [`leptos_motion_core::SpringSimulator`].
 
 // Global PI import removed - now imported in specific modules where needed
 
@@ -332,6 +334,12 @@ impl CubicBezier {
     }
 }
 
+impl From<CubicBezier> for leptos_motion_core::Easing {
+    fn from(curve: CubicBezier) -> Self {
+        Self::Bezier(curve.x1, curve.y1, curve.x2, curve.y2)
+    }
+}
+
 /// Predefined cubic bezier curves
 pub mod bezier {
     use super::CubicBezier;
@@ -362,152 +370,6 @@ pub mod bezier {
     }
 }
 
-/// Spring physics implementation
-pub mod spring {
-    // use super::*; // Unused
-
-    /// Spring configuration
-    #[derive(Debug, Clone)]
-    pub struct SpringConfig {
-        /// Spring stiffness (higher = snappier)
-        pub stiffness: f64,
-        /// Damping (higher = less bouncy)
-        pub damping: f64,
-        /// Mass of the animated object
-        pub mass: f64,
-        /// Initial velocity
-        pub velocity: f64,
-        /// Rest delta threshold
-        pub rest_delta: f64,
-        /// Rest speed threshold
-        pub rest_speed: f64,
-    }
-
-    impl Default for SpringConfig {
-        fn default() -> Self {
-            Self {
-                stiffness: 100.0,
-                damping: 10.0,
-                mass: 1.0,
-                velocity: 0.0,
-                rest_delta: 0.01,
-                rest_speed: 0.01,
-            }
-        }
-    }
-
-    /// Spring animation state
-    #[derive(Debug, Clone)]
-    pub struct SpringState {
-        /// Current position of the spring
-        pub position: f64,
-        /// Current velocity of the spring
-        pub velocity: f64,
-        /// Target position for the spring
-        pub target: f64,
-        /// Whether the spring animation is complete
-        pub is_complete: bool,
-    }
-
-    impl SpringState {
-        /// Create a new spring state with initial position and target
-        pub fn new(initial: f64, target: f64) -> Self {
-            Self {
-                position: initial,
-                velocity: 0.0,
-                target,
-                is_complete: false,
-            }
-        }
-    }
-
-    /// Update spring physics
-    pub fn update_spring(state: &mut SpringState, config: &SpringConfig, delta_time: f64) {
-        let distance = state.target - state.position;
-        let spring_force = -config.stiffness * distance;
-        let damping_force = -config.damping * state.velocity;
-        let acceleration = (spring_force + damping_force) / config.mass;
-
-        state.velocity += acceleration * delta_time;
-        state.position += state.velocity * delta_time;
-
-        // Check if animation is complete
-        let velocity_threshold = config.rest_speed;
-        let distance_threshold = config.rest_delta;
-
-        if state.velocity.abs() < velocity_threshold && distance.abs() < distance_threshold {
-            state.position = state.target;
-            state.velocity = 0.0;
-            state.is_complete = true;
-        }
-    }
-
-    /// Predefined spring configurations
-    pub mod presets {
-        use super::SpringConfig;
-
-        /// Gentle spring
-        pub fn gentle() -> SpringConfig {
-            SpringConfig {
-                stiffness: 120.0,
-                damping: 14.0,
-                mass: 1.0,
-                velocity: 0.0,
-                rest_delta: 0.01,
-                rest_speed: 0.01,
-            }
-        }
-
-        /// Wobbly spring
-        pub fn wobbly() -> SpringConfig {
-            SpringConfig {
-                stiffness: 180.0,
-                damping: 12.0,
-                mass: 1.0,
-                velocity: 0.0,
-                rest_delta: 0.01,
-                rest_speed: 0.01,
-            }
-        }
-
-        /// Stiff spring
-        pub fn stiff() -> SpringConfig {
-            SpringConfig {
-                stiffness: 210.0,
-                damping: 20.0,
-                mass: 1.0,
-                velocity: 0.0,
-                rest_delta: 0.01,
-                rest_speed: 0.01,
-            }
-        }
-
-        /// Slow spring
-        pub fn slow() -> SpringConfig {
-            SpringConfig {
-                stiffness: 280.0,
-                damping: 60.0,
-                mass: 1.0,
-                velocity: 0.0,
-                rest_delta: 0.01,
-                rest_speed: 0.01,
-            }
-        }
-
-        /// Bouncy spring
-        pub fn bouncy() -> SpringConfig {
-            SpringConfig {
-                stiffness: 400.0,
-                damping: 10.0,
-                mass: 1.0,
-                velocity: 0.0,
-                rest_delta: 0.01,
-                rest_speed: 0.01,
-            }
-        }
-    }
-}
-
 /// Easing function trait for extensibility
 pub trait EasingFunction {
     /// Evaluate the easing function at time t (0.0 to 1.0), returning the eased value
@@ -526,6 +388,12 @@ impl EasingFunction for CubicBezier {
     }
 }
 
+impl EasingFunction for leptos_motion_core::Easing {
+    fn evaluate(&self, t: f64) -> f64 {
+        leptos_motion_core::Easing::evaluate(self, t)
+    }
+}
+
 /// Apply any easing function to a progress value
 pub fn apply_easing<F: EasingFunction>(progress: f64, easing: &F) -> f64 {
     easing.evaluate(progress)
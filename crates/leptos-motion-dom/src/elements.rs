@@ -1,8 +1,7 @@
 //! Additional motion elements
-
-// Placeholder for additional motion elements
-// This module will be expanded with more motion components
+//!
+//! Motion components for every HTML and SVG element (`MotionButton`,
+//! `MotionImg`, `MotionSvg`, `MotionPath`, ...) are generated in
+//! [`components`](crate::components) and re-exported here.
 
 pub use crate::components::*;
-
-// Future: MotionButton, MotionImg, MotionSection, etc.
@@ -2,6 +2,10 @@
 //!
 //! This module provides MotionDiv components that actually use the sophisticated
 //! animation engine instead of basic style setting.
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use crate::{DragConfig, DragConstraints};
 use leptos::prelude::*;
@@ -2,6 +2,10 @@
 //!
 //! This module provides an enhanced MotionDiv component that integrates with the
 //! animation engine for smooth, performant animations.
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use crate::{
     // animation_engine::{AnimationEngineBuilder}, // Unused
@@ -1,6 +1,10 @@
 //! Fixed MotionDiv component using proven WASM + signals patterns
 //!
 //! This implements the solutions from the Leptos Motion WASM + Signals Guide
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use leptos::prelude::*;
 use leptos_motion_core::*;
@@ -0,0 +1,112 @@
+//! FLIP animations for groups of sibling elements
+//!
+//! Shared by components that move keyed siblings around (reordering,
+//! presence lists): measure the siblings, change the DOM, then let
+//! [`FLIPAnimator`] play each sibling from its old box into its new one.
+
+use leptos::prelude::*;
+use leptos_motion_core::{inline_style, run_frames};
+use leptos_motion_layout::{FLIPAnimator, LayoutAnimationConfig};
+
+/// Visual box of a sibling before a layout change
+pub(crate) struct FlipSnapshot {
+    id: String,
+    node_ref: NodeRef<leptos::html::Div>,
+    first: web_sys::DomRect,
+}
+
+#[derive(Default)]
+struct FlipGroupState {
+    animator: FLIPAnimator,
+    running: bool,
+}
+
+/// FLIP animations of a group of siblings, driven on animation frames
+#[derive(Clone, Copy)]
+pub(crate) struct FlipGroup {
+    state: StoredValue<FlipGroupState, LocalStorage>,
+    config: StoredValue<LayoutAnimationConfig>,
+}
+
+impl FlipGroup {
+    /// Create a group animating with `config`
+    pub(crate) fn new(config: LayoutAnimationConfig) -> Self {
+        Self {
+            state: StoredValue::new_local(FlipGroupState::default()),
+            config: StoredValue::new(config),
+        }
+    }
+
+    /// Measure the current visual boxes, including running FLIP transforms
+    pub(crate) fn snapshot(
+        nodes: impl IntoIterator<Item = (String, NodeRef<leptos::html::Div>)>,
+    ) -> Vec<FlipSnapshot> {
+        nodes
+            .into_iter()
+            .filter_map(|(id, node_ref)| {
+                let first = node_ref.get_untracked()?.get_bounding_client_rect();
+                Some(FlipSnapshot {
+                    id,
+                    node_ref,
+                    first,
+                })
+            })
+            .collect()
+    }
+
+    /// Animate each snapshotted sibling from its old box into its current layout
+    pub(crate) fn play(&self, snapshots: Vec<FlipSnapshot>) {
+        let Some(config) = self.config.try_get_value() else {
+            return;
+        };
+        let _ = self.state.try_update_value(|state| {
+            for snapshot in snapshots {
+                let Some(element) = snapshot.node_ref.get_untracked() else {
+                    continue;
+                };
+                state.animator.cancel(&snapshot.id);
+                let _ = inline_style(&element).remove_property("transform");
+
+                let last = element.get_bounding_client_rect();
+                if snapshot.first.x() == last.x() && snapshot.first.y() == last.y() {
+                    continue;
+                }
+                let _ = state.animator.animate(
+                    snapshot.id,
+                    element.into(),
+                    snapshot.first,
+                    last,
+                    config.clone(),
+                );
+            }
+        });
+        self.run();
+    }
+
+    /// Drive the animations until they have all finished
+    fn run(&self) {
+        let state = self.state;
+        let should_start = state
+            .try_update_value(|state| {
+                let start = !state.running && state.animator.active_count() > 0;
+                state.running |= start;
+                start
+            })
+            .unwrap_or(false);
+        if !should_start {
+            return;
+        }
+        let started = run_frames(move |_timestamp| {
+            state
+                .try_update_value(|state| {
+                    state.animator.update();
+                    state.running = state.animator.active_count() > 0;
+                    state.running
+                })
+                .unwrap_or(false)
+        });
+        if !started {
+            let _ = state.try_update_value(|state| state.running = false);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Gesture animation states
+//!
+//! Motion components animate towards their `while_*` targets while a
+//! gesture is active. When several gestures are active at once, each
+//! property comes from the active state with the highest [`GestureState`]
+//! priority; when a gesture ends the element animates back to its `animate`
+//! state.
+//!
+//! Pointer taps and drags are arbitrated by a
+//! [`GestureArbiter`]: once the pointer moves far enough to start a drag,
+//! the press no longer counts as a tap.
+
+use crate::variants::{Custom, Variants};
+use leptos_motion_core::AnimationTarget;
+use leptos_motion_gestures::arbitration::{
+    GestureArbiter, GestureTransition, PanRecognizer, RecognizerState, TapRecognizer,
+};
+use leptos_motion_gestures::{GestureEvent, PAN_RECOGNIZER, TAP_RECOGNIZER, TouchPoint};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
+use wasm_bindgen::closure::Closure;
+
+/// Gesture states, in increasing priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GestureState {
+    /// The element is inside the viewport
+    InView,
+    /// The element has focus
+    Focus,
+    /// A pointer hovers the element
+    Hover,
+    /// The element is pressed by a pointer or the keyboard
+    Tap,
+    /// The element is being dragged
+    Drag,
+}
+
+/// State animated to while a gesture is active
+#[derive(Debug, Clone, PartialEq)]
+pub enum GestureTarget {
+    /// Fixed target
+    Target(AnimationTarget),
+    /// Label of one of the component's variants
+    Variant(String),
+}
+
+impl GestureTarget {
+    /// Resolve to a target, looking variant labels up in `variants`
+    pub fn resolve(
+        &self,
+        variants: Option<&Variants>,
+        custom: Option<&Custom>,
+    ) -> Option<AnimationTarget> {
+        match self {
+            Self::Target(target) => Some(target.clone()),
+            Self::Variant(label) => variants?.resolve_variant(label, custom),
+        }
+    }
+}
+
+impl From<AnimationTarget> for GestureTarget {
+    fn from(target: AnimationTarget) -> Self {
+        Self::Target(target)
+    }
+}
+
+impl From<&str> for GestureTarget {
+    fn from(label: &str) -> Self {
+        Self::Variant(label.to_string())
+    }
+}
+
+impl From<String> for GestureTarget {
+    fn from(label: String) -> Self {
+        Self::Variant(label)
+    }
+}
+
+/// Target for the `active` gesture states layered over the `base` state
+///
+/// Higher priority states override the properties of lower ones regardless
+/// of the order they are listed in.
+pub fn resolve_gesture_target(
+    base: &AnimationTarget,
+    active: &[(GestureState, AnimationTarget)],
+) -> AnimationTarget {
+    let mut layers: Vec<&(GestureState, AnimationTarget)> = active.iter().collect();
+    layers.sort_by_key(|(state, _)| *state);
+
+    let mut target = base.clone();
+    for (_, layer) in layers {
+        target.extend(
+            layer
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+    target
+}
+
+/// Whether `key` (a `KeyboardEvent.key`) presses an element like a tap
+pub fn is_tap_key(key: &str) -> bool {
+    matches!(key, "Enter" | " " | "Spacebar")
+}
+
+/// Pointer movement in pixels before a press turns into a drag
+pub const DRAG_START_DISTANCE: f64 = 3.0;
+
+/// Interval at which undecided taps are re-evaluated, in milliseconds
+const TAP_SETTLE_INTERVAL_MS: i32 = 50;
+
+/// Adds recognizers and relationships to a motion element's arbiter
+///
+/// The arbiter starts with [`TAP_RECOGNIZER`] and, for draggable elements,
+/// [`PAN_RECOGNIZER`]. A setup can for example make single taps wait for a
+/// double tap with [`GestureArbiter::require_to_fail`].
+#[derive(Clone)]
+pub struct GestureSetup(Rc<dyn Fn(&mut GestureArbiter)>);
+
+impl GestureSetup {
+    /// Create a setup from a function configuring the arbiter
+    pub fn new(setup: impl Fn(&mut GestureArbiter) + 'static) -> Self {
+        Self(Rc::new(setup))
+    }
+}
+
+impl<F> From<F> for GestureSetup
+where
+    F: Fn(&mut GestureArbiter) + 'static,
+{
+    fn from(setup: F) -> Self {
+        Self::new(setup)
+    }
+}
+
+/// Arbiter of a motion element's pointer taps and drags
+///
+/// Taps have no distance or duration limit of their own: a press is a tap
+/// until the pan recognizer starts a drag or the pointer leaves the element.
+pub fn pointer_arbiter(draggable: bool, setup: Option<&GestureSetup>) -> GestureArbiter {
+    let mut arbiter = GestureArbiter::new();
+    arbiter
+        .add(
+            TAP_RECOGNIZER,
+            TapRecognizer::new()
+                .max_distance(f64::INFINITY)
+                .max_duration(u64::MAX),
+        )
+        .add(
+            PAN_RECOGNIZER,
+            PanRecognizer::new().min_distance(DRAG_START_DISTANCE),
+        );
+    arbiter.set_enabled(PAN_RECOGNIZER, draggable);
+    if let Some(GestureSetup(setup)) = setup {
+        setup(&mut arbiter);
+    }
+    arbiter
+}
+
+/// Whether the tap recognizer recognized (`Some(true)`) or failed in `transitions`
+pub fn tap_outcome(transitions: &[GestureTransition]) -> Option<bool> {
+    transitions
+        .iter()
+        .filter(|transition| transition.id == TAP_RECOGNIZER)
+        .find_map(|transition| match transition.state {
+            RecognizerState::Ended => Some(true),
+            RecognizerState::Failed | RecognizerState::Cancelled => Some(false),
+            _ => None,
+        })
+}
+
+/// Arbiter input of a `pointerdown`, `pointermove` or `pointerup` event
+pub(crate) fn pointer_gesture_event(event: &web_sys::PointerEvent) -> Option<GestureEvent> {
+    let touches = vec![TouchPoint {
+        id: event.pointer_id() as u64,
+        x: event.client_x() as f64,
+        y: event.client_y() as f64,
+        pressure: event.pressure() as f64,
+        timestamp: event.time_stamp() as u64,
+    }];
+    match event.type_().as_str() {
+        "pointerdown" => Some(GestureEvent::TouchStart { touches }),
+        "pointermove" => Some(GestureEvent::TouchMove { touches }),
+        "pointerup" => Some(GestureEvent::TouchEnd { touches }),
+        _ => None,
+    }
+}
+
+/// Tick `arbiter` until its tap recognizer settles, e.g. once a double tap
+/// it waits for times out, then report the outcome
+///
+/// Stops early once `pending` is cleared by another input.
+pub(crate) fn settle_tap(
+    arbiter: Rc<RefCell<GestureArbiter>>,
+    pending: Rc<Cell<bool>>,
+    on_settled: Rc<dyn Fn(bool)>,
+) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let tick = Closure::once_into_js(move || {
+        if !pending.get() {
+            return;
+        }
+        let now = web_sys::window()
+            .and_then(|window| window.performance())
+            .map(|performance| performance.now())
+            .unwrap_or_default();
+        let transitions = arbiter.borrow_mut().tick(now as u64);
+        match tap_outcome(&transitions) {
+            Some(tapped) => on_settled(tapped),
+            None => settle_tap(arbiter, pending, on_settled),
+        }
+    });
+    let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        tick.unchecked_ref(),
+        TAP_SETTLE_INTERVAL_MS,
+    );
+}
+
+/// Callback receiving intersection entries and the observer
+type IntersectionCallback = Closure<dyn FnMut(js_sys::Array, web_sys::IntersectionObserver)>;
+
+/// Keeps an `IntersectionObserver` alive, disconnecting it on drop
+pub(crate) struct InViewObserver {
+    observer: web_sys::IntersectionObserver,
+    _callback: IntersectionCallback,
+}
+
+impl Drop for InViewObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// Report whether `element` is in the viewport once `amount` of it is visible
+///
+/// With `once` the observer stops after the element first enters.
+pub(crate) fn observe_in_view(
+    element: &web_sys::Element,
+    amount: f64,
+    once: bool,
+    on_change: impl Fn(bool) + 'static,
+) -> Option<InViewObserver> {
+    let threshold_ratio = amount.clamp(0.0, 1.0);
+    let stopped = Rc::new(Cell::new(false));
+    let callback: IntersectionCallback = Closure::wrap(Box::new(
+        move |entries: js_sys::Array, observer: web_sys::IntersectionObserver| {
+            if stopped.get() {
+                return;
+            }
+            let Some(entry) = entries
+                .iter()
+                .last()
+                .and_then(|entry| entry.dyn_into::<web_sys::IntersectionObserverEntry>().ok())
+            else {
+                return;
+            };
+            let in_view = entry.is_intersecting() && entry.intersection_ratio() >= threshold_ratio;
+            on_change(in_view);
+            if once && in_view {
+                stopped.set(true);
+                observer.disconnect();
+            }
+        },
+    )
+        as Box<dyn FnMut(js_sys::Array, web_sys::IntersectionObserver)>);
+
+    let init = web_sys::IntersectionObserverInit::new();
+    let threshold = js_sys::Array::new();
+    threshold.push(&JsValue::from_f64(threshold_ratio));
+    init.set_threshold(&threshold);
+
+    let observer =
+        web_sys::IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &init)
+            .ok()?;
+    observer.observe(element);
+
+    Some(InViewObserver {
+        observer,
+        _callback: callback,
+    })
+}
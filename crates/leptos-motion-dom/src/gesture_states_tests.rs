@@ -0,0 +1,148 @@
+// Gesture States Tests
+//
+// These tests verify how active gesture states layer over the animate state,
+// how gesture targets resolve variant labels, which keys press a tap, and how
+// pointer presses are arbitrated between taps and drags.
+
+use crate::gesture_states::{
+    GestureSetup, GestureState, GestureTarget, is_tap_key, pointer_arbiter, resolve_gesture_target,
+    tap_outcome,
+};
+use crate::variants::{Variants, common_variants};
+use leptos_motion_core::{AnimationTarget, AnimationValue};
+use leptos_motion_gestures::arbitration::{GestureArbiter, GestureTransition, TapRecognizer};
+use leptos_motion_gestures::{GestureEvent, TAP_RECOGNIZER, TouchPoint};
+
+fn target(pairs: &[(&str, f64)]) -> AnimationTarget {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), AnimationValue::Number(*value)))
+        .collect()
+}
+
+#[test]
+fn test_no_active_gestures_returns_base() {
+    let base = target(&[("opacity", 1.0)]);
+    assert_eq!(resolve_gesture_target(&base, &[]), base);
+}
+
+#[test]
+fn test_higher_priority_gesture_wins() {
+    let base = target(&[("opacity", 1.0), ("scale", 1.0)]);
+    // Listed out of order: tap must still override hover
+    let active = vec![
+        (GestureState::Tap, target(&[("scale", 0.9)])),
+        (
+            GestureState::Hover,
+            target(&[("scale", 1.1), ("rotate", 5.0)]),
+        ),
+    ];
+
+    let resolved = resolve_gesture_target(&base, &active);
+    assert_eq!(resolved.get("scale"), Some(&AnimationValue::Number(0.9)));
+    assert_eq!(resolved.get("rotate"), Some(&AnimationValue::Number(5.0)));
+    assert_eq!(resolved.get("opacity"), Some(&AnimationValue::Number(1.0)));
+}
+
+#[test]
+fn test_gesture_priority_order() {
+    assert!(GestureState::InView < GestureState::Focus);
+    assert!(GestureState::Focus < GestureState::Hover);
+    assert!(GestureState::Hover < GestureState::Tap);
+    assert!(GestureState::Tap < GestureState::Drag);
+}
+
+#[test]
+fn test_gesture_target_resolves_variant_labels() {
+    let mut variants = Variants::new();
+    variants.add_variant("pressed".to_string(), common_variants::scale(0.95));
+
+    let label = GestureTarget::from("pressed");
+    assert_eq!(
+        label.resolve(Some(&variants), None),
+        Some(common_variants::scale(0.95))
+    );
+    assert_eq!(label.resolve(None, None), None);
+    assert_eq!(
+        GestureTarget::from("missing").resolve(Some(&variants), None),
+        None
+    );
+
+    let fixed = GestureTarget::from(common_variants::fade(0.5));
+    assert_eq!(fixed.resolve(None, None), Some(common_variants::fade(0.5)));
+}
+
+#[test]
+fn test_tap_keys() {
+    assert!(is_tap_key("Enter"));
+    assert!(is_tap_key(" "));
+    assert!(!is_tap_key("Tab"));
+    assert!(!is_tap_key("a"));
+}
+
+fn pointer(x: f64, time_ms: u64) -> Vec<TouchPoint> {
+    vec![TouchPoint {
+        id: 1,
+        x,
+        y: 0.0,
+        pressure: 0.5,
+        timestamp: time_ms,
+    }]
+}
+
+/// Press at 0, move to `x` and release there
+fn press_and_release(arbiter: &mut GestureArbiter, x: f64) -> Vec<GestureTransition> {
+    let mut transitions = arbiter.handle_event(
+        &GestureEvent::TouchStart {
+            touches: pointer(0.0, 0),
+        },
+        0,
+    );
+    transitions.extend(arbiter.handle_event(
+        &GestureEvent::TouchMove {
+            touches: pointer(x, 16),
+        },
+        16,
+    ));
+    transitions.extend(arbiter.handle_event(
+        &GestureEvent::TouchEnd {
+            touches: pointer(x, 100),
+        },
+        100,
+    ));
+    transitions
+}
+
+#[test]
+fn test_drag_fails_the_tap_of_draggable_elements() {
+    let mut arbiter = pointer_arbiter(true, None);
+    assert_eq!(
+        tap_outcome(&press_and_release(&mut arbiter, 50.0)),
+        Some(false)
+    );
+    assert_eq!(
+        tap_outcome(&press_and_release(&mut arbiter, 1.0)),
+        Some(true)
+    );
+
+    // Without dragging, a press that moved still taps
+    let mut arbiter = pointer_arbiter(false, None);
+    assert_eq!(
+        tap_outcome(&press_and_release(&mut arbiter, 50.0)),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_gesture_setup_adds_relationships() {
+    let setup = GestureSetup::from(|arbiter: &mut GestureArbiter| {
+        arbiter
+            .add("double_tap", TapRecognizer::new().taps_required(2))
+            .require_to_fail(TAP_RECOGNIZER, "double_tap");
+    });
+    let mut arbiter = pointer_arbiter(false, Some(&setup));
+
+    // The single tap waits for the double tap to fail
+    assert_eq!(tap_outcome(&press_and_release(&mut arbiter, 0.0)), None);
+    assert_eq!(tap_outcome(&arbiter.tick(2000)), Some(true));
+}
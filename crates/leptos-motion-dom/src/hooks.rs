@@ -1,7 +1,10 @@
 //! Animation hooks for Leptos components
 
+use leptos::html::ElementType;
 use leptos::prelude::*;
 use leptos::reactive::signal::signal;
+use leptos_motion_layout::{LayoutAnimationConfig, LayoutChange, LayoutObserver};
+use wasm_bindgen::JsCast;
 
 // Include modern TDD tests
 #[cfg(test)]
@@ -19,3 +22,44 @@ pub fn use_in_view(_element: NodeRef<leptos::html::Div>) -> ReadSignal<bool> {
     // Placeholder implementation
     signal(true).0
 }
+
+/// Hook reporting layout changes of the marked elements inside `scope`
+///
+/// Elements carrying [`leptos_motion_layout::LAYOUT_ATTRIBUTE`] are measured
+/// only when the browser reports they may have moved, and when `config` is
+/// enabled every change plays a FLIP animation on its own.
+pub fn use_layout_changes<E>(
+    scope: NodeRef<E>,
+    config: LayoutAnimationConfig,
+) -> ReadSignal<Vec<LayoutChange>, LocalStorage>
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let (changes, set_changes) = signal_local(Vec::new());
+    let observer = StoredValue::new_local(None::<LayoutObserver>);
+
+    Effect::new(move |_| {
+        let Some(scope) = scope.get() else {
+            return;
+        };
+        let scope: web_sys::Element = scope.unchecked_into();
+        let Ok(layout_observer) = LayoutObserver::new(&scope) else {
+            return;
+        };
+        layout_observer.animate_changes(config.clone());
+        layout_observer.subscribe(move |layout_changes| set_changes.set(layout_changes.to_vec()));
+        // Replacing an earlier observer disconnects it
+        observer.set_value(Some(layout_observer));
+    });
+
+    on_cleanup(move || {
+        observer.try_update_value(|layout_observer| {
+            if let Some(layout_observer) = layout_observer.take() {
+                layout_observer.disconnect();
+            }
+        });
+    });
+
+    changes
+}
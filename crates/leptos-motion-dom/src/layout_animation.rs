@@ -0,0 +1,264 @@
+//! Layout animations of motion elements
+//!
+//! Motion elements with the `layout` prop animate from their previous box
+//! whenever a render moves or resizes them. They are tracked by a
+//! [`LayoutObserver`], which remembers each element's last box and measures
+//! it again once the browser reports a change that may have moved it: the
+//! observer of the nearest [`LayoutGroup`], or one watching the whole page.
+//!
+//! [`attach_layout_debug_overlay`] draws the layout animations of all these
+//! observers and the shared layout animations of the page.
+
+use crate::shared_layout::{forget_shared_layout_snapshots, shared_layout_snapshots};
+use leptos::html::ElementType;
+use leptos::prelude::*;
+use leptos_motion_core::Transition;
+use leptos_motion_layout::{
+    LAYOUT_ATTRIBUTE, LayoutAnimationConfig, LayoutDebugOverlay, LayoutMode, LayoutObserver,
+};
+use std::cell::RefCell;
+use wasm_bindgen::JsCast;
+
+thread_local! {
+    /// Observer of the `layout` elements outside of any group
+    static PAGE_LAYOUT: RefCell<Option<LayoutObserver>> = const { RefCell::new(None) };
+    /// Observers of the connected layout groups
+    static GROUP_LAYOUTS: RefCell<Vec<LayoutObserver>> = const { RefCell::new(Vec::new()) };
+    /// Overlay drawing the layout animations of the page, once attached
+    static DEBUG_OVERLAY: RefCell<Option<LayoutDebugOverlay>> = const { RefCell::new(None) };
+}
+
+/// Add the layout debug overlay to the page, hidden, or get the one added
+///
+/// The overlay draws the animations of `layout` elements, in and outside of
+/// layout groups, and of elements with a `layout_id`. It stays attached
+/// until [`detach_layout_debug_overlay`], whatever happens to the handles.
+pub fn attach_layout_debug_overlay() -> Result<LayoutDebugOverlay, String> {
+    if let Some(overlay) = DEBUG_OVERLAY.with(|attached| attached.borrow().clone()) {
+        return Ok(overlay);
+    }
+    let overlay = LayoutDebugOverlay::new()?;
+    with_page_layout(|observer| overlay.watch(observer));
+    GROUP_LAYOUTS.with(|groups| {
+        for observer in groups.borrow().iter() {
+            overlay.watch(observer);
+        }
+    });
+    overlay.add_source(shared_layout_snapshots);
+    DEBUG_OVERLAY.with(|attached| *attached.borrow_mut() = Some(overlay.clone()));
+    Ok(overlay)
+}
+
+/// Remove the overlay added by [`attach_layout_debug_overlay`] once its last handle is dropped
+pub fn detach_layout_debug_overlay() {
+    DEBUG_OVERLAY.with(|attached| attached.borrow_mut().take());
+    forget_shared_layout_snapshots();
+}
+
+/// Whether a layout debug overlay is attached to draw animations
+pub(crate) fn is_debug_overlay_attached() -> bool {
+    DEBUG_OVERLAY.with(|attached| attached.borrow().is_some())
+}
+
+/// Run `f` with the attached layout debug overlay, if any
+fn with_debug_overlay(f: impl FnOnce(&LayoutDebugOverlay)) {
+    DEBUG_OVERLAY.with(|attached| {
+        if let Some(overlay) = attached.borrow().as_ref() {
+            f(overlay);
+        }
+    });
+}
+
+/// Layout animation of a member, derived from its transition
+pub(crate) fn layout_animation_config(transition: &Transition) -> LayoutAnimationConfig {
+    LayoutAnimationConfig::from(transition)
+}
+
+/// Observer shared by the members of a [`LayoutGroup`]
+#[derive(Default)]
+struct LayoutGroupState {
+    observer: Option<LayoutObserver>,
+    config: LayoutAnimationConfig,
+    /// Members that mounted before the group element did
+    pending: Vec<(web_sys::Element, Option<LayoutAnimationConfig>)>,
+}
+
+/// Context provided by [`LayoutGroup`] to its `layout` elements
+#[derive(Clone, Copy)]
+pub struct LayoutGroupContext {
+    state: StoredValue<LayoutGroupState, LocalStorage>,
+}
+
+impl LayoutGroupContext {
+    fn new(config: LayoutAnimationConfig) -> Self {
+        Self {
+            state: StoredValue::new_local(LayoutGroupState {
+                config,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Start observing the group element and the members waiting for it
+    fn connect(&self, scope: &web_sys::Element) {
+        self.state.update_value(|state| {
+            let Ok(observer) = LayoutObserver::for_group(scope) else {
+                return;
+            };
+            observer.animate_changes(state.config.clone());
+            for (element, config) in state.pending.drain(..) {
+                track_member(&observer, &element, config);
+            }
+            GROUP_LAYOUTS.with(|groups| groups.borrow_mut().push(observer.clone()));
+            with_debug_overlay(|overlay| overlay.watch(&observer));
+            state.observer = Some(observer);
+        });
+    }
+
+    fn disconnect(&self) {
+        self.state.try_update_value(|state| {
+            if let Some(observer) = state.observer.take() {
+                GROUP_LAYOUTS.with(|groups| groups.borrow_mut().retain(|group| group != &observer));
+                with_debug_overlay(|overlay| overlay.unwatch(&observer));
+                observer.disconnect();
+            }
+        });
+    }
+
+    fn track(&self, element: &web_sys::Element, config: Option<LayoutAnimationConfig>) {
+        self.state.update_value(|state| match &state.observer {
+            Some(observer) => track_member(observer, element, config),
+            None => state.pending.push((element.clone(), config)),
+        });
+    }
+
+    fn untrack(&self, element: &web_sys::Element) {
+        self.state.try_update_value(|state| {
+            state.pending.retain(|(pending, _)| pending != element);
+            if let Some(observer) = &state.observer {
+                untrack_member(observer, element);
+            }
+        });
+    }
+}
+
+fn track_member(
+    observer: &LayoutObserver,
+    element: &web_sys::Element,
+    config: Option<LayoutAnimationConfig>,
+) {
+    match config {
+        Some(config) => observer.track_animated(element, config),
+        None => observer.track(element),
+    };
+}
+
+fn untrack_member(observer: &LayoutObserver, element: &web_sys::Element) {
+    if let Some(element_id) = observer.element_id(element) {
+        let _ = observer.untrack(&element_id);
+    }
+}
+
+/// Run `f` with the page observer, created on first use
+fn with_page_layout(f: impl FnOnce(&LayoutObserver)) {
+    PAGE_LAYOUT.with(|page| {
+        let mut page = page.borrow_mut();
+        if page.is_none() {
+            let body = web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.body());
+            let Some(body) = body else {
+                return;
+            };
+            let Ok(observer) = LayoutObserver::for_group(&body) else {
+                return;
+            };
+            observer.animate_changes(LayoutAnimationConfig::default());
+            *page = Some(observer);
+        }
+        if let Some(observer) = page.as_ref() {
+            f(observer);
+        }
+    });
+}
+
+/// Animate the element of `node_ref` into its new box whenever its layout changes
+pub(crate) fn use_layout_animation<E>(
+    node_ref: NodeRef<E>,
+    layout: Option<bool>,
+    mode: Option<LayoutMode>,
+    transition: Option<Transition>,
+) where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    if layout != Some(true) {
+        return;
+    }
+    let group = use_context::<LayoutGroupContext>();
+    let config = transition.as_ref().map(layout_animation_config);
+    let mounted = StoredValue::new_local(None::<web_sys::Element>);
+
+    Effect::new(move |_| {
+        let Some(element) = node_ref.get() else {
+            return;
+        };
+        if mounted.with_value(Option::is_some) {
+            return;
+        }
+        let element: web_sys::Element = element.unchecked_into();
+        let _ = element.set_attribute(LAYOUT_ATTRIBUTE, mode.unwrap_or_default().as_str());
+        match group {
+            Some(group) => group.track(&element, config.clone()),
+            None => with_page_layout(|observer| track_member(observer, &element, config.clone())),
+        }
+        mounted.set_value(Some(element));
+    });
+
+    on_cleanup(move || {
+        let Some(element) = mounted.try_update_value(Option::take).flatten() else {
+            return;
+        };
+        match group {
+            Some(group) => group.untrack(&element),
+            None => with_page_layout(|observer| untrack_member(observer, &element)),
+        }
+    });
+}
+
+/// Animates the layout of the `layout` elements inside it together
+///
+/// Every member is measured whenever the layout of any of them may have
+/// changed, so siblings that do not re-render themselves, such as the
+/// sections below an expanding accordion item, still animate into place.
+#[component]
+pub fn LayoutGroup(
+    /// Animation of members without a transition of their own
+    #[prop(optional)]
+    transition: Option<LayoutAnimationConfig>,
+    /// CSS class
+    #[prop(optional)]
+    class: Option<String>,
+    /// Inline style
+    #[prop(optional)]
+    style: Option<String>,
+    /// Children
+    children: Children,
+) -> impl IntoView {
+    let node_ref = NodeRef::<leptos::html::Div>::new();
+    let context = LayoutGroupContext::new(transition.unwrap_or_default());
+    provide_context(context);
+
+    Effect::new(move |_| {
+        if let Some(scope) = node_ref.get() {
+            context.connect(&scope);
+        }
+    });
+    on_cleanup(move || context.disconnect());
+
+    view! {
+        <div node_ref=node_ref class=class style=style>
+            {children()}
+        </div>
+    }
+}
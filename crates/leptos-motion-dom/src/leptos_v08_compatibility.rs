@@ -78,6 +78,7 @@ impl IntoAttributeValue for AnimationValue {
                 #[cfg(not(feature = "serde-support"))]
                 data => data.clone(),
             }),
+            AnimationValue::Auto => AttributeValue::String("auto".to_string()),
         }
     }
 }
@@ -208,6 +209,7 @@ impl IntoProperty for AnimationTarget {
                         #[cfg(not(feature = "serde-support"))]
                         data => data.clone(),
                     },
+                    AnimationValue::Auto => "auto".to_string(),
                 };
 
                 format!("{}: {}", css_property, css_value)
@@ -346,6 +348,7 @@ impl AnimationValueExt for AnimationValue {
                 #[cfg(not(feature = "serde-support"))]
                 data => data.clone(),
             },
+            AnimationValue::Auto => "auto".to_string(),
         }
     }
 }
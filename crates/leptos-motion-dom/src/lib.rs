@@ -5,18 +5,34 @@
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
+// Lets generated motion components refer to this crate by name
+extern crate self as leptos_motion_dom;
+
+pub mod animate;
+mod auto_size;
 pub mod components;
 pub mod elements;
 pub mod fixed_motion_div;
+mod flip_group;
+pub mod gesture_states;
 pub mod hooks;
 /// Improved motion div implementation with enhanced features
 pub mod improved_motion_div;
+pub mod layout_animation;
 pub mod minimal_motion_div;
+pub mod motion_element;
 pub mod performance;
+pub mod pointer_drag;
 pub mod presence;
+pub mod presence_flow;
 pub mod reactive_motion_div;
+pub mod reorder;
+#[cfg(feature = "router")]
+pub mod route_transitions;
+mod shared_layout;
 // pub mod reactive_motion_div_fixed; // Disabled due to threading issues
 pub mod simplified_event_handling;
+mod svg_animation;
 pub mod utils;
 
 // Phase 2: Animation Engine Integration
@@ -70,6 +86,7 @@ mod integration_tests;
 mod animation_engine_tests;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod enhanced_motion_div_tests;
 
 // Include the DOM integration TDD tests
@@ -87,9 +104,11 @@ mod enhanced_motion_div_tests;
 mod signal_based_controller_tests;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tdd_animation_reactivity_tests;
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod phase4a_function_props_tests;
 
 // #[cfg(test)]
@@ -116,6 +135,31 @@ mod drag_constraint_tests {
     include!("drag_constraint_tests.rs");
 }
 
+#[cfg(test)]
+mod pointer_drag_tests {
+    include!("pointer_drag_tests.rs");
+}
+
+#[cfg(test)]
+mod reorder_tests {
+    include!("reorder_tests.rs");
+}
+
+#[cfg(test)]
+mod presence_flow_tests {
+    include!("presence_flow_tests.rs");
+}
+
+#[cfg(test)]
+mod gesture_states_tests {
+    include!("gesture_states_tests.rs");
+}
+
+#[cfg(test)]
+mod motion_element_tests {
+    include!("motion_element_tests.rs");
+}
+
 #[cfg(test)]
 mod performance_benchmark_tests {
     include!("performance_benchmark_tests.rs");
@@ -234,6 +278,7 @@ pub use hooks::*;
 pub use utils::*;
 
 // Re-export new v0.7 features
+pub use animate::animate_target;
 pub use animate_presence::*;
 pub use performance_optimizations::*;
 pub use spring_physics::*;
@@ -244,15 +289,45 @@ pub use variants::*;
 pub use css_animations::*;
 
 // Re-export components
-pub use components::{MotionDiv, MotionSpan};
-pub use minimal_motion_div::MinimalMotionDiv;
-pub use reactive_motion_div::{ReactiveMotionDiv, ReactiveMotionDiv as ReactiveMotionDivNew};
+pub use components::{
+    MotionButton, MotionDiv, MotionImg, MotionInput, MotionPath, MotionSpan, MotionSvg, motion,
+};
+pub use layout_animation::{
+    LayoutGroup, LayoutGroupContext, attach_layout_debug_overlay, detach_layout_debug_overlay,
+};
+pub use motion_element::{MotionElement, MotionOptions};
 // pub use reactive_motion_div_fixed::ReactiveMotionDivFixed; // Disabled due to threading issues
 // Improved motion div module is not yet implemented
 // pub use improved_motion_div::{
 //     ImprovedMotionDiv, use_animation_state, use_drag_state, use_in_view, use_layout_animation,
 // };
 
+// Re-export pointer drag
+pub use pointer_drag::{
+    DragConstraintsRef, DragControls, DragOptions, DragSession, DragStartOptions, DragStartRequest,
+    SnapBackAnimation, apply_elastic, use_drag_controls,
+};
+
+// Re-export gesture states
+pub use gesture_states::{
+    DRAG_START_DISTANCE, GestureSetup, GestureState, GestureTarget, is_tap_key, pointer_arbiter,
+    resolve_gesture_target, tap_outcome,
+};
+
+// Re-export presence components and hooks
+pub use presence_flow::{
+    PresenceFor, PresenceItemContext, PresenceShow, merge_presence_order, use_is_present,
+    use_presence,
+};
+
+// Re-export drag-to-reorder
+pub use reorder::{ReorderContext, ReorderGroup, ReorderItem, use_reorder_context};
+
+// Re-export shared elements and route transitions
+#[cfg(feature = "router")]
+pub use route_transitions::{ROUTE_SNAPSHOT_LIFETIME_MS, RoutePage, RouteTransitions};
+pub use shared_layout::{register_shared_element, unregister_shared_element};
+
 // Re-export simplified event handling (new public API)
 pub use simplified_event_handling::{
     DragAxis, DragConfig, DragConstraints, EventHandlers, MotionProps, SimplifiedDragConfig,
@@ -266,6 +341,12 @@ pub use leptos_motion_core::{
     SpringConfig, StaggerConfig, StaggerFrom, Transform, Transition, Variants,
 };
 
+// Re-export layout types taken by motion props and route transitions
+pub use leptos_motion_layout::{
+    LAYOUT_ID_ATTRIBUTE, LayoutAnimationConfig, LayoutDebugOverlay, LayoutMode,
+    SharedElementConfig, ViewTransitionEngine, ZIndexStrategy,
+};
+
 // Include simplified event handling tests
 #[cfg(test)]
 mod simplified_event_handling_tests {
@@ -1,6 +1,10 @@
 //! Minimal MotionDiv Component
 //!
 //! This is a completely minimal version to test if the issue is with the complex logic
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use leptos::prelude::*;
 use leptos_motion_core::{AnimationTarget, Transition};
@@ -0,0 +1,851 @@
+//! Shared core of the motion components
+//!
+//! Every motion component ([`MotionDiv`](crate::MotionDiv),
+//! [`MotionButton`](crate::MotionButton), [`MotionPath`](crate::MotionPath),
+//! ...) takes the same props. They are collected in [`MotionOptions`] and
+//! turned into the element's initial style and event handlers by
+//! [`MotionElement`], whatever the element's tag.
+
+use crate::animate::animate_target;
+use crate::animate_presence::{PresenceExit, use_presence_child, use_presence_context};
+use crate::gesture_states::{
+    GestureSetup, GestureState, GestureTarget, InViewObserver, is_tap_key, observe_in_view,
+    pointer_arbiter, pointer_gesture_event, resolve_gesture_target, settle_tap, tap_outcome,
+};
+use crate::layout_animation::use_layout_animation;
+use crate::pointer_drag::{
+    DragConstraintsRef, DragControls, DragOptions, DragSession, DragStartRequest,
+    SnapBackAnimation, measure_constraints, run_snap_back, snap_offset_to_cursor, touch_action,
+};
+use crate::presence_flow::PresenceItemContext;
+use crate::shared_layout::use_layout_id;
+use crate::variants::{
+    Custom, Orchestration, VariantContext, Variants, use_variant_context, variant_duration,
+};
+use crate::{DragConfig, DragConstraints};
+use leptos::html::ElementType;
+use leptos::prelude::{
+    Callable, Callback, Effect, Get, GetUntracked, NodeRef, Set, SetValue, Signal, StoredValue,
+    on_cleanup, provide_context, untrack, use_context,
+};
+use leptos::reactive::signal::signal;
+use leptos_motion_core::svg::{
+    PATH_LENGTH_ATTRIBUTE, PathDrawing, is_path_drawing, is_svg_attribute, svg_attribute_value,
+};
+use leptos_motion_core::{AnimationTarget, SpringConfig, Transition};
+use leptos_motion_gestures::arbitration::RecognizerState;
+use leptos_motion_gestures::{PAN_RECOGNIZER, TAP_RECOGNIZER};
+use leptos_motion_layout::{LayoutMode, exclude_from_transition};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Handler of pointer events on a motion element
+pub type PointerHandler = Box<dyn FnMut(web_sys::PointerEvent)>;
+/// Handler of keyboard events on a motion element
+pub type KeyboardHandler = Box<dyn FnMut(web_sys::KeyboardEvent)>;
+/// Handler of focus events on a motion element
+pub type FocusHandler = Box<dyn FnMut(web_sys::FocusEvent)>;
+
+/// Props shared by all motion components, except `class`, `node_ref` and `children`
+#[derive(Default)]
+pub struct MotionOptions {
+    /// CSS styles
+    pub style: Option<String>,
+    /// Key for React-like re-rendering
+    pub key: Option<String>,
+    /// Initial animation state
+    pub initial: Option<AnimationTarget>,
+    /// Target animation state
+    pub animate: Option<AnimationTarget>,
+    /// State animated to before unmounting inside an `AnimatePresence`
+    pub exit: Option<AnimationTarget>,
+    /// Transition configuration
+    pub transition: Option<Transition>,
+    /// Named animation states, selected by variant labels
+    pub variants: Option<Variants>,
+    /// Label of the initial variant, inherited from the parent if unset
+    pub initial_variant: Option<String>,
+    /// Label of the animated variant, inherited from the parent if unset
+    pub animate_variant: Option<Signal<String>>,
+    /// Label of the variant animated to before unmounting, used without `exit`
+    pub exit_variant: Option<String>,
+    /// Data passed to dynamic variants
+    pub custom: Option<Custom>,
+    /// How variant animations of children are delayed and staggered
+    pub orchestration: Option<Orchestration>,
+    /// Inherit variant labels from the parent (defaults to true)
+    pub inherit: Option<bool>,
+    /// State animated to while hovered
+    pub while_hover: Option<GestureTarget>,
+    /// State animated to while pressed by a pointer or Enter/Space
+    pub while_tap: Option<GestureTarget>,
+    /// State animated to while focused
+    pub while_focus: Option<GestureTarget>,
+    /// State animated to while dragged
+    pub while_drag: Option<GestureTarget>,
+    /// State animated to while inside the viewport
+    pub while_in_view: Option<GestureTarget>,
+    /// Fraction of the element that must be visible to be in view (defaults to 0)
+    pub viewport_amount: Option<f64>,
+    /// Keep the in-view state once the element first enters the viewport
+    pub viewport_once: Option<bool>,
+    /// Called when a pointer starts hovering the element
+    pub on_hover_start: Option<Callback<()>>,
+    /// Called when a pointer stops hovering the element
+    pub on_hover_end: Option<Callback<()>>,
+    /// Called when a press is released over the element
+    pub on_tap: Option<Callback<()>>,
+    /// Called when a press ends without a tap
+    pub on_tap_cancel: Option<Callback<()>>,
+    /// Called when the element gains focus
+    pub on_focus: Option<Callback<()>>,
+    /// Called when the element loses focus
+    pub on_blur: Option<Callback<()>>,
+    /// Extra recognizers and relationships arbitrated with taps and drags
+    pub gestures: Option<GestureSetup>,
+    /// Animate from the previous box whenever a render moves or resizes the element
+    pub layout: Option<bool>,
+    /// Restrict `layout` animations to the position or the size
+    pub layout_mode: Option<LayoutMode>,
+    /// Animate between elements with the same id as they mount and unmount
+    pub layout_id: Option<String>,
+    /// Drag configuration
+    pub drag: Option<DragConfig>,
+    /// Drag constraints, overriding those of `drag`
+    pub drag_constraints: Option<DragConstraints>,
+    /// Element whose bounds constrain the drag, measured at each drag start
+    pub drag_constraints_ref: Option<DragConstraintsRef>,
+    /// Return to the origin when the drag ends
+    pub drag_snap_to_origin: Option<bool>,
+    /// Lock dragging to the first axis the pointer moves along
+    pub drag_direction_lock: Option<bool>,
+    /// Let drags start inside this element also drag draggable ancestors
+    pub drag_propagation: Option<bool>,
+    /// Spring used to settle the element when the drag ends
+    pub drag_transition: Option<SpringConfig>,
+    /// Controls that start this element's drag from other elements
+    pub drag_controls: Option<DragControls>,
+    /// Start dragging on this element's own pointerdown (defaults to true)
+    pub drag_listener: Option<bool>,
+}
+
+/// Style and event handlers to render a motion element with
+pub struct MotionElement {
+    /// Inline style of the first render
+    pub style: String,
+    /// `tabindex`, set when the element reacts to taps or focus
+    pub tab_index: Option<&'static str>,
+    /// `pointerdown` handler
+    pub on_pointer_down: PointerHandler,
+    /// `pointermove` handler
+    pub on_pointer_move: PointerHandler,
+    /// `pointerup` handler
+    pub on_pointer_up: PointerHandler,
+    /// `pointercancel` handler
+    pub on_pointer_cancel: PointerHandler,
+    /// `pointerenter` handler
+    pub on_pointer_enter: PointerHandler,
+    /// `pointerleave` handler
+    pub on_pointer_leave: PointerHandler,
+    /// `keydown` handler
+    pub on_key_down: KeyboardHandler,
+    /// `keyup` handler
+    pub on_key_up: KeyboardHandler,
+    /// `focus` handler
+    pub on_focus: FocusHandler,
+    /// `blur` handler
+    pub on_blur: FocusHandler,
+}
+
+/// Element of `node_ref`, if mounted
+///
+/// SVG elements are accessed through the same `HtmlElement` bindings: the
+/// properties motion elements use, such as `style`, are looked up on the
+/// object at runtime.
+pub(crate) fn html_element<E>(node_ref: NodeRef<E>) -> Option<web_sys::HtmlElement>
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    node_ref.get_untracked().map(JsCast::unchecked_into)
+}
+
+/// CSS properties and SVG attributes of an initial target
+///
+/// Path drawing values become `stroke-dasharray`/`stroke-dashoffset` along
+/// with the `pathLength` attribute they are relative to.
+pub(crate) fn split_initial_target(
+    target: AnimationTarget,
+) -> (HashMap<String, String>, Vec<(String, String)>) {
+    let mut styles = HashMap::new();
+    let mut attributes = Vec::new();
+    if let Some(drawing) = PathDrawing::from_target(&target) {
+        let (name, value) = PATH_LENGTH_ATTRIBUTE;
+        attributes.push((name.to_string(), value.to_string()));
+        for (property, value) in drawing.css_properties() {
+            styles.insert(property.to_string(), value);
+        }
+    }
+    for (key, value) in target {
+        if is_svg_attribute(&key) {
+            attributes.push((key, svg_attribute_value(&value)));
+        } else if !is_path_drawing(&key) {
+            styles.insert(key, value.to_string_value());
+        }
+    }
+    (styles, attributes)
+}
+
+impl MotionElement {
+    /// Set up animations, gestures and dragging for the element of `node_ref`
+    ///
+    /// Call from a motion component body, then render the element with
+    /// `node_ref` and the returned style and handlers.
+    pub fn new<E>(node_ref: NodeRef<E>, options: MotionOptions) -> Self
+    where
+        E: ElementType,
+        E::Output: JsCast + Clone + 'static,
+    {
+        let MotionOptions {
+            style,
+            key,
+            initial,
+            animate,
+            exit,
+            transition,
+            variants,
+            initial_variant,
+            animate_variant,
+            exit_variant,
+            custom,
+            orchestration,
+            inherit,
+            while_hover,
+            while_tap,
+            while_focus,
+            while_drag,
+            while_in_view,
+            viewport_amount,
+            viewport_once,
+            on_hover_start,
+            on_hover_end,
+            on_tap,
+            on_tap_cancel,
+            on_focus,
+            on_blur,
+            gestures,
+            layout,
+            layout_mode,
+            layout_id,
+            drag,
+            drag_constraints,
+            drag_constraints_ref,
+            drag_snap_to_origin,
+            drag_direction_lock,
+            drag_propagation,
+            drag_transition,
+            drag_controls,
+            drag_listener,
+        } = options;
+
+        // Create signals for animation state
+        let (is_hovered, set_hovered) = signal(false);
+        let (is_tapped, set_tapped) = signal(false);
+        let (is_focused, set_focused) = signal(false);
+        let (is_in_view, set_in_view) = signal(false);
+        let (current_styles, set_styles) = signal(HashMap::<String, String>::new());
+
+        // Create signals for drag state
+        let (is_dragging, set_dragging) = signal(false);
+        let (drag_position, set_drag_position) = signal((0.0, 0.0));
+
+        // An explicit `drag_constraints` prop overrides the config's constraints
+        let drag = drag.map(|mut config| {
+            if let Some(constraints) = drag_constraints {
+                config.constraints = Some(constraints);
+            }
+            config
+        });
+        let default_drag_options = DragOptions::default();
+        let drag_options = DragOptions {
+            constraints_ref: drag_constraints_ref,
+            snap_to_origin: drag_snap_to_origin.unwrap_or(false),
+            direction_lock: drag_direction_lock.unwrap_or(false),
+            propagation: drag_propagation.unwrap_or(false),
+            transition: drag_transition.unwrap_or(default_drag_options.transition),
+        };
+
+        // Variant labels propagate to descendants that define the same labels
+        let parent_variants = if inherit.unwrap_or(true) {
+            use_variant_context()
+        } else {
+            None
+        };
+        let variant_transition = transition.clone().unwrap_or_default();
+        let variant_context = variants.as_ref().map(|_| {
+            let initial_label = initial_variant
+                .or_else(|| parent_variants.and_then(|parent| parent.initial_label()));
+            let animate_label = match (animate_variant, parent_variants) {
+                (Some(label), _) => Signal::derive(move || Some(label.get())),
+                (None, Some(parent)) => parent.animate_signal(),
+                (None, None) => Signal::stored(None),
+            };
+            // Without explicit stagger_children, the transition's stagger applies to children
+            let mut orchestration = orchestration.unwrap_or_default();
+            if orchestration.stagger_children.is_none() {
+                orchestration.stagger_children = variant_transition.stagger.clone();
+            }
+            let context = VariantContext::new(
+                initial_label,
+                animate_label,
+                orchestration,
+                variant_transition.clone(),
+            );
+            provide_context(context);
+            context
+        });
+        let exit = exit.map(PresenceExit::Target).or_else(|| {
+            Some(PresenceExit::Variant {
+                variants: variants.clone()?,
+                label: exit_variant?,
+                custom: custom.clone(),
+            })
+        });
+        let gesture_variants = variants.clone();
+        let gesture_custom = custom.clone();
+        let variant_target = move |label: Option<String>| {
+            let variants = variants.as_ref()?;
+            variants.resolve_variant(&label?, custom.as_ref())
+        };
+        let base_variant_target = variant_target.clone();
+        let initial = initial.or_else(|| {
+            variant_context.and_then(|context| variant_target(context.initial_label()))
+        });
+        let animate_variant_untracked =
+            variant_context.and_then(|context| variant_target(untrack(|| context.animate_label())));
+
+        // Registered first so the element is measured before a presence ghost replaces it
+        use_layout_id(node_ref, layout_id, transition.clone().unwrap_or_default());
+        use_layout_animation(node_ref, layout, layout_mode, transition.clone());
+
+        // Enter and exit through an enclosing AnimatePresence
+        let in_presence =
+            use_context::<PresenceItemContext>().is_some() || use_presence_context().is_some();
+        let mount_transition = transition.clone().unwrap_or_default();
+        let skip_initial = use_presence_child(node_ref, key, animate.clone(), exit, transition);
+        let initial = if skip_initial {
+            animate.clone().or(animate_variant_untracked)
+        } else {
+            initial
+        };
+
+        let gesture_transition = variant_transition.clone();
+
+        // Animate to the current variant, delayed by the parent's orchestration
+        if let Some(context) = variant_context {
+            let child_id = parent_variants.map(|parent| {
+                let id = parent.register_child(variant_duration(&variant_transition));
+                on_cleanup(move || parent.unregister_child(id));
+                id
+            });
+            Effect::new(move |_| {
+                let Some(target) = variant_target(context.animate_label()) else {
+                    return;
+                };
+                let Some(element) = html_element(node_ref) else {
+                    return;
+                };
+                let inherited_delay = match (parent_variants, child_id) {
+                    (Some(parent), Some(id)) => parent.child_delay(id),
+                    _ => 0.0,
+                };
+                let delay = variant_transition.delay.unwrap_or(0.0)
+                    + inherited_delay
+                    + context.parent_delay();
+                let transition = Transition {
+                    delay: Some(delay),
+                    ..variant_transition.clone()
+                };
+                animate_target(&element, &target, &transition, || {});
+            });
+        }
+
+        let gesture_animate = animate.clone();
+
+        // Outside of a presence, animate from the initial state once mounted.
+        // Without one the element is rendered in its `animate` state, so
+        // only elements that really animate in get a transition.
+        let (initial, mount_animate) = match (initial, animate.filter(|_| !in_presence)) {
+            (None, Some(animate)) => (Some(animate), None),
+            states => states,
+        };
+
+        // Initialize with initial styles; SVG attributes are set once mounted
+        let mut initial_attributes = Vec::new();
+        if let Some(initial_target) = initial {
+            let (styles, attributes) = split_initial_target(initial_target);
+            initial_attributes = attributes;
+            set_styles.set(styles);
+        }
+
+        if !initial_attributes.is_empty() || mount_animate.is_some() {
+            let mounted = Cell::new(false);
+            Effect::new(move |_| {
+                let Some(element) = node_ref.get() else {
+                    return;
+                };
+                if mounted.replace(true) {
+                    return;
+                }
+                let element: web_sys::HtmlElement = element.unchecked_into();
+                for (name, value) in &initial_attributes {
+                    let _ = element.set_attribute(name, value);
+                }
+                if let Some(animate) = &mount_animate {
+                    animate_target(&element, animate, &mount_transition, || {});
+                }
+            });
+        }
+
+        // Pressing needs focus for keyboard taps
+        let tab_index = (while_tap.is_some()
+            || while_focus.is_some()
+            || on_tap.is_some()
+            || on_focus.is_some())
+        .then_some("0");
+
+        // Gesture states animate over the `animate` state in priority order
+        let observes_in_view = while_in_view.is_some();
+        let gesture_targets: Vec<(GestureState, GestureTarget)> = [
+            (GestureState::InView, while_in_view),
+            (GestureState::Focus, while_focus),
+            (GestureState::Hover, while_hover),
+            (GestureState::Tap, while_tap),
+            (GestureState::Drag, while_drag),
+        ]
+        .into_iter()
+        .filter_map(|(state, target)| Some((state, target?)))
+        .collect();
+        if !gesture_targets.is_empty() {
+            let active_gestures = RefCell::new(Vec::<GestureState>::new());
+            let gesture_keys = RefCell::new(Vec::<String>::new());
+            Effect::new(move |_| {
+                let active: Vec<GestureState> = [
+                    (GestureState::InView, is_in_view.get()),
+                    (GestureState::Focus, is_focused.get()),
+                    (GestureState::Hover, is_hovered.get()),
+                    (GestureState::Tap, is_tapped.get()),
+                    (GestureState::Drag, is_dragging.get()),
+                ]
+                .into_iter()
+                .filter_map(|(state, active)| active.then_some(state))
+                .collect();
+                if *active_gestures.borrow() == active {
+                    return;
+                }
+                *active_gestures.borrow_mut() = active.clone();
+                let Some(element) = html_element(node_ref) else {
+                    return;
+                };
+
+                let layers: Vec<(GestureState, AnimationTarget)> = gesture_targets
+                    .iter()
+                    .filter(|(state, _)| active.contains(state))
+                    .filter_map(|(state, target)| {
+                        let target =
+                            target.resolve(gesture_variants.as_ref(), gesture_custom.as_ref())?;
+                        Some((*state, target))
+                    })
+                    .collect();
+                let base = gesture_animate
+                    .clone()
+                    .or_else(|| {
+                        variant_context.and_then(|context| {
+                            base_variant_target(untrack(|| context.animate_label()))
+                        })
+                    })
+                    .unwrap_or_default();
+                let target = resolve_gesture_target(&base, &layers);
+
+                // Properties only an ended gesture animated fall back to the element's styles
+                let style = element.style();
+                for key in gesture_keys.borrow().iter() {
+                    if !target.contains_key(key) {
+                        let _ = style.remove_property(key);
+                    }
+                }
+                *gesture_keys.borrow_mut() = layers
+                    .iter()
+                    .flat_map(|(_, layer)| layer.keys().cloned())
+                    .collect();
+                animate_target(&element, &target, &gesture_transition, || {});
+            });
+        }
+
+        // Track viewport visibility for `while_in_view`
+        if observes_in_view {
+            let observer = StoredValue::new_local(None::<InViewObserver>);
+            let amount = viewport_amount.unwrap_or(0.0);
+            let once = viewport_once.unwrap_or(false);
+            Effect::new(move |_| {
+                let Some(element) = node_ref.get() else {
+                    return;
+                };
+                observer.set_value(observe_in_view(
+                    element.unchecked_ref(),
+                    amount,
+                    once,
+                    move |in_view| set_in_view.set(in_view),
+                ));
+            });
+        }
+
+        // Convert styles to CSS string
+        let style_string = {
+            let mut styles = current_styles.get_untracked();
+
+            // Keep touch scrolling from stealing pointer events on the drag axis
+            if let Some(drag_config) = &drag {
+                styles.insert(
+                    "touch-action".to_string(),
+                    touch_action(drag_config).to_string(),
+                );
+            }
+
+            // Combine with the style prop if provided
+            let mut style_parts = styles
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key, value))
+                .collect::<Vec<_>>();
+
+            // Add the style prop if provided
+            if let Some(style_prop) = &style {
+                style_parts.push(style_prop.clone());
+            }
+
+            style_parts.join("; ")
+        };
+
+        // Pointer Events drive dragging so mouse, touch and pen behave identically
+        let drag_session: Rc<RefCell<Option<DragSession>>> = Rc::new(RefCell::new(None));
+        let drag_generation = Rc::new(Cell::new(0u32));
+
+        let apply_drag_offset = move |(x, y): (f64, f64)| {
+            set_drag_position.set((x, y));
+            if let Some(element) = html_element(node_ref) {
+                // Offsets follow the pointer, so they must not be transitioned
+                exclude_from_transition(&element, &["transform"]);
+                let _ = element
+                    .style()
+                    .set_property("transform", &format!("translate({}px, {}px)", x, y));
+            }
+        };
+
+        let drag_config_start = drag.clone();
+        let drag_options_start = drag_options.clone();
+        let drag_session_start = drag_session.clone();
+        let drag_generation_start = drag_generation.clone();
+        // Own presses drag once the pan recognizer begins, controls drag at once
+        let begin_drag = move |request: DragStartRequest, active: bool| {
+            let Some(drag_config) = &drag_config_start else {
+                return;
+            };
+            let Some(element) = html_element(node_ref) else {
+                return;
+            };
+
+            // Interrupt any running snap-back and continue from the current offset
+            drag_generation_start.set(drag_generation_start.get().wrapping_add(1));
+            let mut offset = drag_position.get_untracked();
+            if request.snap_to_cursor {
+                offset = snap_offset_to_cursor(&element, offset, request.pointer);
+                apply_drag_offset(offset);
+            }
+            let constraints = measure_constraints(&drag_options_start, &element, offset);
+
+            let _ = element.set_pointer_capture(request.pointer_id);
+            *drag_session_start.borrow_mut() = Some(DragSession::start(
+                drag_config,
+                &drag_options_start,
+                constraints,
+                request.pointer,
+                offset,
+                request.time_ms,
+            ));
+            if active {
+                set_dragging.set(true);
+            }
+        };
+
+        // Drags started from another element through `DragControls`
+        if let Some(controls) = drag_controls {
+            let begin_drag = begin_drag.clone();
+            let last_sequence = Cell::new(controls.sequence_untracked());
+            Effect::new(move |_| {
+                let Some(request) = controls.request() else {
+                    return;
+                };
+                if last_sequence.replace(request.sequence) != request.sequence {
+                    begin_drag(request, true);
+                }
+            });
+        }
+
+        let listens_for_drag = drag.is_some() && drag_listener.unwrap_or(true);
+        let drag_options_pointerdown = drag_options.clone();
+        let on_pointer_down = move |event: web_sys::PointerEvent| {
+            if !listens_for_drag || event.button() != 0 {
+                return;
+            }
+            if !drag_options_pointerdown.propagation {
+                event.stop_propagation();
+            }
+            begin_drag(DragStartRequest::from_event(&event, false), false);
+        };
+
+        let drag_session_pointermove = drag_session.clone();
+        let on_pointer_move = move |event: web_sys::PointerEvent| {
+            let mut session = drag_session_pointermove.borrow_mut();
+            let Some(session) = session.as_mut() else {
+                return;
+            };
+            let offset = session.update(
+                (event.client_x() as f64, event.client_y() as f64),
+                event.time_stamp(),
+            );
+            if is_dragging.get_untracked() {
+                apply_drag_offset(offset);
+            }
+        };
+
+        let drag_config_pointerup = drag.clone();
+        let drag_options_pointerup = drag_options.clone();
+        let drag_session_pointerup = drag_session.clone();
+        let drag_generation_pointerup = drag_generation.clone();
+        let on_pointer_up = move |event: web_sys::PointerEvent| {
+            let Some(mut session) = drag_session_pointerup.borrow_mut().take() else {
+                return;
+            };
+            session.release(event.time_stamp());
+            if let Some(element) = html_element(node_ref) {
+                let _ = element.release_pointer_capture(event.pointer_id());
+            }
+            // Released before the pan recognizer started the drag
+            if !is_dragging.get_untracked() {
+                return;
+            }
+            set_dragging.set(false);
+
+            let momentum = drag_config_pointerup
+                .as_ref()
+                .and_then(|config| config.momentum)
+                .unwrap_or(false);
+            let target = session.release_target(&drag_options_pointerup, momentum);
+            if target == session.position() {
+                return;
+            }
+
+            let animation = SnapBackAnimation::new(
+                session.position(),
+                session.velocity(),
+                target,
+                &drag_options_pointerup.transition,
+            );
+            run_snap_back(
+                animation,
+                drag_generation_pointerup.clone(),
+                apply_drag_offset,
+            );
+        };
+        let on_pointer_cancel = on_pointer_up.clone();
+
+        // A tap is a press released over the element that the arbiter didn't
+        // hand to a drag, or Enter/Space pressed and released while the
+        // element has focus
+        let run_callback = |callback: Option<Callback<()>>| {
+            if let Some(callback) = callback {
+                callback.run(());
+            }
+        };
+        let end_tap = move |tapped: bool| {
+            set_tapped.set(false);
+            run_callback(if tapped { on_tap } else { on_tap_cancel });
+        };
+        let arbiter = Rc::new(RefCell::new(pointer_arbiter(
+            listens_for_drag,
+            gestures.as_ref(),
+        )));
+        // The pointer is down on the element
+        let pressed = Rc::new(Cell::new(false));
+        // A pointer press waits for the arbiter to decide whether it tapped
+        let tap_pending = Rc::new(Cell::new(false));
+        let key_pressed = Rc::new(Cell::new(false));
+
+        let tap_pending_settled = tap_pending.clone();
+        let finish_tap: Rc<dyn Fn(bool)> = Rc::new(move |tapped: bool| {
+            if tap_pending_settled.replace(false) {
+                end_tap(tapped);
+            }
+        });
+
+        let arbiter_down = arbiter.clone();
+        let pressed_down = pressed.clone();
+        let tap_pending_down = tap_pending.clone();
+        let handle_pointer_down = move |event: web_sys::PointerEvent| {
+            if event.button() == 0
+                && let Some(input) = pointer_gesture_event(&event)
+            {
+                arbiter_down
+                    .borrow_mut()
+                    .handle_event(&input, event.time_stamp() as u64);
+                pressed_down.set(true);
+                tap_pending_down.set(true);
+                set_tapped.set(true);
+            }
+            on_pointer_down(event);
+        };
+
+        let arbiter_move = arbiter.clone();
+        let pressed_move = pressed.clone();
+        let finish_tap_move = finish_tap.clone();
+        let drag_session_move = drag_session.clone();
+        let handle_pointer_move = move |event: web_sys::PointerEvent| {
+            if pressed_move.get()
+                && let Some(input) = pointer_gesture_event(&event)
+            {
+                let transitions = arbiter_move
+                    .borrow_mut()
+                    .handle_event(&input, event.time_stamp() as u64);
+                if tap_outcome(&transitions) == Some(false) {
+                    finish_tap_move(false);
+                }
+                let panning = transitions.iter().any(|transition| {
+                    transition.id == PAN_RECOGNIZER && transition.state == RecognizerState::Began
+                });
+                if panning && drag_session_move.borrow().is_some() {
+                    set_dragging.set(true);
+                }
+            }
+            on_pointer_move(event);
+        };
+
+        let arbiter_up = arbiter.clone();
+        let pressed_up = pressed.clone();
+        let tap_pending_up = tap_pending.clone();
+        let finish_tap_up = finish_tap.clone();
+        let handle_pointer_up = move |event: web_sys::PointerEvent| {
+            if pressed_up.replace(false)
+                && let Some(input) = pointer_gesture_event(&event)
+            {
+                let (x, y) = (event.client_x() as f64, event.client_y() as f64);
+                let over_element = html_element(node_ref).is_some_and(|element| {
+                    let rect = element.get_bounding_client_rect();
+                    x >= rect.left() && x <= rect.right() && y >= rect.top() && y <= rect.bottom()
+                });
+                let transitions = arbiter_up
+                    .borrow_mut()
+                    .handle_event(&input, event.time_stamp() as u64);
+                match tap_outcome(&transitions) {
+                    Some(tapped) => finish_tap_up(tapped && over_element),
+                    // Waiting on another recognizer, e.g. a double tap
+                    None if tap_pending_up.get()
+                        && arbiter_up.borrow().is_enabled(TAP_RECOGNIZER) =>
+                    {
+                        set_tapped.set(false);
+                        let finish_tap = finish_tap_up.clone();
+                        settle_tap(
+                            arbiter_up.clone(),
+                            tap_pending_up.clone(),
+                            Rc::new(move |tapped| finish_tap(tapped && over_element)),
+                        );
+                    }
+                    None => finish_tap_up(false),
+                }
+            }
+            on_pointer_up(event);
+        };
+
+        let arbiter_cancel = arbiter.clone();
+        let pressed_cancel = pressed.clone();
+        let finish_tap_cancel = finish_tap.clone();
+        let handle_pointer_cancel = move |event: web_sys::PointerEvent| {
+            if pressed_cancel.replace(false) {
+                arbiter_cancel.borrow_mut().reset();
+                finish_tap_cancel(false);
+            }
+            on_pointer_cancel(event);
+        };
+
+        let handle_pointer_enter = move |event: web_sys::PointerEvent| {
+            // Touch pointers press rather than hover
+            if event.pointer_type() == "touch" {
+                return;
+            }
+            set_hovered.set(true);
+            run_callback(on_hover_start);
+        };
+
+        let handle_pointer_leave = move |_event: web_sys::PointerEvent| {
+            // Without pointer capture the release would happen outside the element
+            if !is_dragging.get_untracked() && pressed.replace(false) {
+                arbiter.borrow_mut().reset();
+                finish_tap(false);
+            }
+            if is_hovered.get_untracked() {
+                set_hovered.set(false);
+                run_callback(on_hover_end);
+            }
+        };
+
+        let is_own_event = move |event: &web_sys::KeyboardEvent| {
+            let target = event.target().map(JsValue::from);
+            let element = html_element(node_ref).map(JsValue::from);
+            target.is_some() && target == element
+        };
+        let key_pressed_down = key_pressed.clone();
+        let handle_key_down = move |event: web_sys::KeyboardEvent| {
+            if !is_tap_key(&event.key()) || event.repeat() || !is_own_event(&event) {
+                return;
+            }
+            // Keep Space from scrolling the page
+            event.prevent_default();
+            key_pressed_down.set(true);
+            set_tapped.set(true);
+        };
+        let key_pressed_up = key_pressed.clone();
+        let handle_key_up = move |event: web_sys::KeyboardEvent| {
+            if is_tap_key(&event.key()) && key_pressed_up.replace(false) {
+                end_tap(true);
+            }
+        };
+
+        let handle_focus = move |_event: web_sys::FocusEvent| {
+            set_focused.set(true);
+            run_callback(on_focus);
+        };
+        let handle_blur = move |_event: web_sys::FocusEvent| {
+            set_focused.set(false);
+            if key_pressed.replace(false) {
+                end_tap(false);
+            }
+            run_callback(on_blur);
+        };
+
+        Self {
+            style: style_string,
+            tab_index,
+            on_pointer_down: Box::new(handle_pointer_down),
+            on_pointer_move: Box::new(handle_pointer_move),
+            on_pointer_up: Box::new(handle_pointer_up),
+            on_pointer_cancel: Box::new(handle_pointer_cancel),
+            on_pointer_enter: Box::new(handle_pointer_enter),
+            on_pointer_leave: Box::new(handle_pointer_leave),
+            on_key_down: Box::new(handle_key_down),
+            on_key_up: Box::new(handle_key_up),
+            on_focus: Box::new(handle_focus),
+            on_blur: Box::new(handle_blur),
+        }
+    }
+}
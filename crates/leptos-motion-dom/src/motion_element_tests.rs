@@ -0,0 +1,163 @@
+// Motion Element Tests
+//
+// These tests verify the style and focusability the shared motion element
+// core derives from its options, and that the generated components for
+// HTML, void and SVG elements accept the shared props.
+
+use crate::components::{MotionButtonProps, MotionImgProps, MotionPathProps, MotionSvgProps};
+use crate::layout_animation::layout_animation_config;
+use crate::motion;
+use crate::motion_element::{MotionElement, MotionOptions, split_initial_target};
+use crate::variants::Variants;
+use crate::{DragConfig, LayoutMode};
+use leptos::prelude::*;
+use leptos_motion_core::{AnimationTarget, AnimationValue, Easing, SpringConfig, Transition};
+
+fn target(pairs: &[(&str, f64)]) -> AnimationTarget {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), AnimationValue::Number(*value)))
+        .collect()
+}
+
+#[test]
+fn test_initial_style_combines_initial_drag_and_style_prop() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let motion = MotionElement::new(
+            NodeRef::<leptos::html::Button>::new(),
+            MotionOptions {
+                style: Some("color: red".to_string()),
+                initial: Some(target(&[("opacity", 0.0)])),
+                drag: Some(DragConfig::default()),
+                ..Default::default()
+            },
+        );
+
+        assert!(motion.style.contains("opacity: 0"));
+        assert!(motion.style.contains("touch-action: none"));
+        assert!(motion.style.ends_with("color: red"));
+    });
+}
+
+#[test]
+fn test_animate_without_initial_renders_animate_state() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let motion = MotionElement::new(
+            NodeRef::<leptos::html::Div>::new(),
+            MotionOptions {
+                animate: Some(target(&[("opacity", 0.5)])),
+                ..Default::default()
+            },
+        );
+        assert!(motion.style.contains("opacity: 0.5"));
+    });
+}
+
+#[test]
+fn test_tab_index_only_for_tap_and_focus() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let plain = MotionElement::new(NodeRef::<leptos::svg::Path>::new(), Default::default());
+        assert_eq!(plain.tab_index, None);
+
+        let tappable = MotionElement::new(
+            NodeRef::<leptos::svg::Path>::new(),
+            MotionOptions {
+                on_tap: Some(Callback::new(|_| {})),
+                ..Default::default()
+            },
+        );
+        assert_eq!(tappable.tab_index, Some("0"));
+
+        let focusable = MotionElement::new(
+            NodeRef::<leptos::html::Img>::new(),
+            MotionOptions {
+                on_focus: Some(Callback::new(|_| {})),
+                ..Default::default()
+            },
+        );
+        assert_eq!(focusable.tab_index, Some("0"));
+    });
+}
+
+#[test]
+fn test_generated_elements_share_props() {
+    let owner = Owner::new();
+    owner.with(|| {
+        let _ = MotionButtonProps::builder()
+            .while_tap("pressed")
+            .on_tap(|_| {})
+            .children(Box::new(|| "Press".into_any()))
+            .build();
+        let _ = MotionImgProps::builder()
+            .initial(target(&[("opacity", 0.0)]))
+            .layout(true)
+            .layout_mode(LayoutMode::Position)
+            .build();
+        let _ = MotionPathProps::builder()
+            .while_hover(target(&[("opacity", 0.5)]))
+            .drag(DragConfig::default())
+            .children(Box::new(|| ().into_any()))
+            .build();
+        let _ = MotionSvgProps::builder()
+            .variants(Variants::new())
+            .layout_id("logo")
+            .children(Box::new(|| ().into_any()))
+            .build();
+    });
+
+    // Components are also reachable by element name
+    let _ = motion::Div;
+    let _ = motion::ClipPath;
+}
+
+#[test]
+fn test_initial_svg_attributes_and_path_drawing() {
+    let (styles, attributes) = split_initial_target(target(&[
+        ("opacity", 0.0),
+        ("r", 10.0),
+        ("path_length", 0.0),
+    ]));
+
+    assert_eq!(styles.get("opacity").map(String::as_str), Some("0"));
+    assert_eq!(
+        styles.get("stroke-dasharray").map(String::as_str),
+        Some("0 1")
+    );
+    assert!(!styles.contains_key("r"));
+    assert!(!styles.contains_key("path_length"));
+    assert!(attributes.contains(&("pathLength".to_string(), "1".to_string())));
+    assert!(attributes.contains(&("r".to_string(), "10".to_string())));
+}
+
+#[test]
+fn test_layout_animation_follows_transition_duration() {
+    let config = layout_animation_config(&Transition {
+        duration: Some(0.6),
+        ..Default::default()
+    });
+    assert!(config.enabled);
+    assert_eq!(config.duration, 0.6);
+
+    let config = layout_animation_config(&Transition {
+        duration: None,
+        ..Default::default()
+    });
+    assert_eq!(config.duration, 0.3);
+}
+
+#[test]
+fn test_layout_animation_follows_transition_spring() {
+    let spring = SpringConfig {
+        stiffness: 300.0,
+        damping: 20.0,
+        ..Default::default()
+    };
+    let config = layout_animation_config(&Transition {
+        ease: Easing::Spring(spring.clone()),
+        ..Default::default()
+    });
+    assert_eq!(config.easing, Easing::Spring(spring));
+}
@@ -7,7 +7,10 @@ use leptos_motion_core::{AnimationTarget, AnimationValue, Transition};
 use std::collections::HashMap;
 use wasm_bindgen_test::*;
 
-use crate::{ReactiveMotionDiv, signal_based_animation_controller::SignalBasedAnimationController};
+use crate::{
+    reactive_motion_div::ReactiveMotionDiv,
+    signal_based_animation_controller::SignalBasedAnimationController,
+};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -0,0 +1,619 @@
+//! Pointer Events based drag
+//!
+//! Unified drag handling for mouse, touch and pen built on Pointer Events,
+//! with live constraints measured from a container element, rubber-band
+//! elastic overflow, direction locking and spring snap-back on release.
+//! [`DragControls`] start a drag from a pointerdown on another element.
+
+use crate::{DragAxis, DragConfig, DragConstraints};
+use leptos::html::ElementType;
+use leptos::prelude::{
+    Get, GetUntracked, LocalStorage, NodeRef, RwSignal, Set, Signal, WithUntracked,
+};
+use leptos_motion_core::{SpringConfig, SpringSimulator, run_frames};
+use leptos_motion_layout::LayoutInfo;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+
+/// Distance in pixels the pointer must travel before a direction lock is decided
+pub const DIRECTION_LOCK_THRESHOLD: f64 = 3.0;
+
+/// Distance in pixels under which a snap-back spring is considered settled
+const SNAP_BACK_REST_DELTA: f64 = 0.5;
+
+/// Velocity in pixels/sec under which a snap-back spring is considered settled
+const SNAP_BACK_REST_SPEED: f64 = 5.0;
+
+/// Seconds of release velocity projected forward when momentum is enabled
+const MOMENTUM_PROJECTION: f64 = 0.35;
+
+/// Milliseconds of pointer movement the drag velocity is measured over
+///
+/// A pointer held still for longer than this before release has no velocity.
+const VELOCITY_WINDOW_MS: f64 = 100.0;
+
+/// Element whose bounds constrain a drag, built from a `NodeRef` of any element
+#[derive(Clone, Copy, Debug)]
+pub struct DragConstraintsRef(Signal<Option<web_sys::Element>, LocalStorage>);
+
+impl DragConstraintsRef {
+    /// The constraining element, if it is mounted
+    pub fn element(&self) -> Option<web_sys::Element> {
+        self.0.get_untracked()
+    }
+}
+
+impl<E> From<NodeRef<E>> for DragConstraintsRef
+where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    fn from(node_ref: NodeRef<E>) -> Self {
+        Self(Signal::derive_local(move || {
+            node_ref.get().map(JsCast::unchecked_into)
+        }))
+    }
+}
+
+/// Drag options that complement [`DragConfig`]
+#[derive(Clone, Debug)]
+pub struct DragOptions {
+    /// Element whose bounds constrain the drag, measured at each drag start
+    pub constraints_ref: Option<DragConstraintsRef>,
+    /// Return to the origin on release
+    pub snap_to_origin: bool,
+    /// Lock movement to the first axis the pointer moves along
+    pub direction_lock: bool,
+    /// Let pointer events reach draggable ancestors
+    pub propagation: bool,
+    /// Spring used to settle the element on release
+    pub transition: SpringConfig,
+}
+
+impl Default for DragOptions {
+    fn default() -> Self {
+        Self {
+            constraints_ref: None,
+            snap_to_origin: false,
+            direction_lock: false,
+            propagation: false,
+            transition: SpringConfig {
+                stiffness: 400.0,
+                damping: 40.0,
+                ..SpringConfig::default()
+            },
+        }
+    }
+}
+
+impl DragOptions {
+    /// Create new drag options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constrain the drag to the bounds of another element
+    pub fn constraints_ref(mut self, constraints_ref: impl Into<DragConstraintsRef>) -> Self {
+        self.constraints_ref = Some(constraints_ref.into());
+        self
+    }
+
+    /// Set snap to origin
+    pub fn snap_to_origin(mut self, snap_to_origin: bool) -> Self {
+        self.snap_to_origin = snap_to_origin;
+        self
+    }
+
+    /// Set direction lock
+    pub fn direction_lock(mut self, direction_lock: bool) -> Self {
+        self.direction_lock = direction_lock;
+        self
+    }
+
+    /// Set propagation to draggable ancestors
+    pub fn propagation(mut self, propagation: bool) -> Self {
+        self.propagation = propagation;
+        self
+    }
+
+    /// Set the release spring
+    pub fn transition(mut self, transition: SpringConfig) -> Self {
+        self.transition = transition;
+        self
+    }
+}
+
+impl DragConstraints {
+    /// Derive offset constraints that keep `element` inside `container`
+    ///
+    /// `element` is the element's measured box and `offset` the drag offset
+    /// already applied to it, so the result is relative to its origin.
+    pub fn from_container(
+        container: &LayoutInfo,
+        element: &LayoutInfo,
+        offset: (f64, f64),
+    ) -> Self {
+        let origin_x = element.x - offset.0;
+        let origin_y = element.y - offset.1;
+
+        let left = container.x - origin_x;
+        let top = container.y - origin_y;
+        let right = (container.x + container.width) - (origin_x + element.width);
+        let bottom = (container.y + container.height) - (origin_y + element.height);
+
+        // An element larger than its container can only move inside the overlap
+        Self {
+            left: Some(left.min(right)),
+            right: Some(left.max(right)),
+            top: Some(top.min(bottom)),
+            bottom: Some(top.max(bottom)),
+        }
+    }
+
+    /// Clamp an x offset into the horizontal bounds
+    pub fn clamp_x(&self, x: f64) -> f64 {
+        clamp_optional(x, self.left, self.right)
+    }
+
+    /// Clamp a y offset into the vertical bounds
+    pub fn clamp_y(&self, y: f64) -> f64 {
+        clamp_optional(y, self.top, self.bottom)
+    }
+}
+
+/// Apply rubber-band overflow to `value` outside `[min, max]`
+///
+/// `elastic` is the fraction of the overflow that is kept: `0.0` pins the
+/// value to the bound, `1.0` ignores the bound entirely.
+pub fn apply_elastic(value: f64, min: Option<f64>, max: Option<f64>, elastic: f64) -> f64 {
+    let elastic = elastic.clamp(0.0, 1.0);
+    if let Some(min) = min
+        && value < min
+    {
+        return min - (min - value) * elastic;
+    }
+    if let Some(max) = max
+        && value > max
+    {
+        return max + (value - max) * elastic;
+    }
+    value
+}
+
+fn clamp_optional(value: f64, min: Option<f64>, max: Option<f64>) -> f64 {
+    apply_elastic(value, min, max, 0.0)
+}
+
+/// State of a single pointer drag gesture
+#[derive(Clone, Debug)]
+pub struct DragSession {
+    axis: DragAxis,
+    direction_lock: bool,
+    locked_axis: Option<DragAxis>,
+    constraints: Option<DragConstraints>,
+    elastic: f64,
+    pointer_origin: (f64, f64),
+    offset_origin: (f64, f64),
+    position: (f64, f64),
+    velocity: (f64, f64),
+    /// Pointer positions and times, from the last one before the velocity window
+    samples: VecDeque<(f64, f64, f64)>,
+}
+
+impl DragSession {
+    /// Start a drag at `pointer` (client coordinates) with the element at `offset`
+    pub fn start(
+        config: &DragConfig,
+        options: &DragOptions,
+        constraints: Option<DragConstraints>,
+        pointer: (f64, f64),
+        offset: (f64, f64),
+        time_ms: f64,
+    ) -> Self {
+        Self {
+            axis: config.axis.clone().unwrap_or(DragAxis::Both),
+            direction_lock: options.direction_lock,
+            locked_axis: None,
+            constraints: constraints.or_else(|| config.constraints.clone()),
+            elastic: config.elastic.unwrap_or(0.0),
+            pointer_origin: pointer,
+            offset_origin: offset,
+            position: offset,
+            velocity: (0.0, 0.0),
+            samples: VecDeque::from([(pointer.0, pointer.1, time_ms)]),
+        }
+    }
+
+    /// Move the pointer and return the new element offset
+    pub fn update(&mut self, pointer: (f64, f64), time_ms: f64) -> (f64, f64) {
+        let delta_x = pointer.0 - self.pointer_origin.0;
+        let delta_y = pointer.1 - self.pointer_origin.1;
+
+        if self.direction_lock && self.locked_axis.is_none() {
+            if delta_x.abs().max(delta_y.abs()) < DIRECTION_LOCK_THRESHOLD {
+                return self.position;
+            }
+            self.locked_axis = Some(if delta_x.abs() >= delta_y.abs() {
+                DragAxis::X
+            } else {
+                DragAxis::Y
+            });
+        }
+
+        let (allow_x, allow_y) = self.allowed_axes();
+        let mut x = if allow_x {
+            self.offset_origin.0 + delta_x
+        } else {
+            self.offset_origin.0
+        };
+        let mut y = if allow_y {
+            self.offset_origin.1 + delta_y
+        } else {
+            self.offset_origin.1
+        };
+
+        if let Some(constraints) = &self.constraints {
+            x = apply_elastic(x, constraints.left, constraints.right, self.elastic);
+            y = apply_elastic(y, constraints.top, constraints.bottom, self.elastic);
+        }
+
+        self.samples.push_back((pointer.0, pointer.1, time_ms));
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|sample| sample.2 <= time_ms - VELOCITY_WINDOW_MS)
+        {
+            self.samples.pop_front();
+        }
+        self.velocity = self.velocity_at(time_ms);
+
+        self.position = (x, y);
+        self.position
+    }
+
+    /// Release the pointer at `time_ms`, settling the release velocity
+    ///
+    /// Time passed since the last move slows the velocity down, so a pointer
+    /// that paused before it was lifted doesn't fling the element.
+    pub fn release(&mut self, time_ms: f64) {
+        self.velocity = self.velocity_at(time_ms);
+    }
+
+    /// Pointer velocity in pixels/sec over the window ending at `time_ms`
+    fn velocity_at(&self, time_ms: f64) -> (f64, f64) {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return (0.0, 0.0);
+        };
+        let dt = (time_ms - first.2) / 1000.0;
+        if time_ms - last.2 > VELOCITY_WINDOW_MS || dt <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let (allow_x, allow_y) = self.allowed_axes();
+        (
+            if allow_x {
+                (last.0 - first.0) / dt
+            } else {
+                0.0
+            },
+            if allow_y {
+                (last.1 - first.1) / dt
+            } else {
+                0.0
+            },
+        )
+    }
+
+    /// Offset the element should settle at once released
+    pub fn release_target(&self, options: &DragOptions, momentum: bool) -> (f64, f64) {
+        if options.snap_to_origin {
+            return (0.0, 0.0);
+        }
+
+        let (mut x, mut y) = self.position;
+        if momentum {
+            x += self.velocity.0 * MOMENTUM_PROJECTION;
+            y += self.velocity.1 * MOMENTUM_PROJECTION;
+        }
+
+        match &self.constraints {
+            Some(constraints) => (constraints.clamp_x(x), constraints.clamp_y(y)),
+            None => (x, y),
+        }
+    }
+
+    /// Compensate for the element's layout box moving by `delta` mid-drag
+    ///
+    /// Keeps the element visually under the pointer when it is moved in the
+    /// document, e.g. after a reorder.
+    pub fn rebase(&mut self, delta: (f64, f64)) {
+        self.offset_origin.0 -= delta.0;
+        self.offset_origin.1 -= delta.1;
+        self.position.0 -= delta.0;
+        self.position.1 -= delta.1;
+    }
+
+    /// Current element offset
+    pub fn position(&self) -> (f64, f64) {
+        self.position
+    }
+
+    /// Pointer velocity in pixels/sec, measured over the last moves or at release
+    pub fn velocity(&self) -> (f64, f64) {
+        self.velocity
+    }
+
+    /// Axis chosen by the direction lock, if any
+    pub fn locked_axis(&self) -> Option<&DragAxis> {
+        self.locked_axis.as_ref()
+    }
+
+    fn allowed_axes(&self) -> (bool, bool) {
+        let allow_x = self.axis != DragAxis::Y;
+        let allow_y = self.axis != DragAxis::X;
+        match self.locked_axis {
+            Some(DragAxis::X) => (allow_x, false),
+            Some(DragAxis::Y) => (false, allow_y),
+            _ => (allow_x, allow_y),
+        }
+    }
+}
+
+/// Spring animation that settles a released element on its target offset
+#[derive(Debug, Clone)]
+pub struct SnapBackAnimation {
+    x: SnapBackAxis,
+    y: SnapBackAxis,
+    elapsed: f64,
+    target: (f64, f64),
+}
+
+/// Spring of one axis of a [`SnapBackAnimation`]
+#[derive(Debug, Clone)]
+struct SnapBackAxis {
+    /// `None` for a spring that can't be simulated, which lands right away
+    spring: Option<SpringSimulator>,
+    from: f64,
+    to: f64,
+}
+
+impl SnapBackAxis {
+    fn position(&self, time: f64) -> f64 {
+        self.spring
+            .as_ref()
+            .map_or(self.to, |spring| spring.position(self.from, self.to, time))
+    }
+
+    fn is_settled(&self, time: f64) -> bool {
+        self.spring
+            .as_ref()
+            .is_none_or(|spring| spring.is_settled(self.from, self.to, time))
+    }
+}
+
+impl SnapBackAnimation {
+    /// Start from `position`, carrying over the release `velocity` (pixels/sec)
+    pub fn new(
+        position: (f64, f64),
+        velocity: (f64, f64),
+        target: (f64, f64),
+        spring: &SpringConfig,
+    ) -> Self {
+        let axis = |from: f64, to: f64, velocity: f64| SnapBackAxis {
+            // Offsets are in pixels, so the spring rests within pixel thresholds
+            spring: SpringSimulator::new(SpringConfig {
+                velocity,
+                rest_delta: SNAP_BACK_REST_DELTA,
+                rest_speed: SNAP_BACK_REST_SPEED,
+                ..spring.clone()
+            })
+            .ok(),
+            from,
+            to,
+        };
+
+        Self {
+            x: axis(position.0, target.0, velocity.0),
+            y: axis(position.1, target.1, velocity.1),
+            elapsed: 0.0,
+            target,
+        }
+    }
+
+    /// Advance by `dt` seconds and return the new offset
+    pub fn step(&mut self, dt: f64) -> (f64, f64) {
+        self.elapsed += dt.max(0.0);
+        if self.is_settled() {
+            return self.target;
+        }
+        (self.x.position(self.elapsed), self.y.position(self.elapsed))
+    }
+
+    /// Check if both axes have settled on the target
+    pub fn is_settled(&self) -> bool {
+        self.x.is_settled(self.elapsed) && self.y.is_settled(self.elapsed)
+    }
+
+    /// Target offset
+    pub fn target(&self) -> (f64, f64) {
+        self.target
+    }
+}
+
+/// Drive `animation` on animation frames, calling `on_frame` with each offset
+///
+/// The loop stops once the spring settles or `generation` no longer matches
+/// the value it had when the loop started (a new drag began).
+pub fn run_snap_back(
+    mut animation: SnapBackAnimation,
+    generation: Rc<Cell<u32>>,
+    mut on_frame: impl FnMut((f64, f64)) + 'static,
+) {
+    if web_sys::window().is_none() {
+        on_frame(animation.target());
+        return;
+    }
+
+    let started_generation = generation.get();
+    let mut last_timestamp = None::<f64>;
+    run_frames(move |timestamp| {
+        if generation.get() != started_generation {
+            return false;
+        }
+
+        let dt = last_timestamp
+            .replace(timestamp)
+            .map_or(0.0, |last| (timestamp - last) / 1000.0);
+        on_frame(animation.step(dt));
+        !animation.is_settled()
+    });
+}
+
+/// Measure the drag constraints implied by `options.constraints_ref`
+pub fn measure_constraints(
+    options: &DragOptions,
+    element: &web_sys::Element,
+    offset: (f64, f64),
+) -> Option<DragConstraints> {
+    let container = options.constraints_ref.as_ref()?.element()?;
+    Some(DragConstraints::from_container(
+        &layout_info(&container.get_bounding_client_rect()),
+        &layout_info(&element.get_bounding_client_rect()),
+        offset,
+    ))
+}
+
+/// CSS `touch-action` that leaves the non-dragged axis to the browser
+pub fn touch_action(config: &DragConfig) -> &'static str {
+    match config.axis {
+        Some(DragAxis::X) => "pan-y",
+        Some(DragAxis::Y) => "pan-x",
+        _ => "none",
+    }
+}
+
+fn layout_info(rect: &web_sys::DomRect) -> LayoutInfo {
+    LayoutInfo::new(rect.left(), rect.top(), rect.width(), rect.height())
+}
+
+/// Request to start a drag, captured from a pointerdown on any element
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragStartRequest {
+    /// Pointer that started the drag
+    pub pointer_id: i32,
+    /// Pointer position in client coordinates
+    pub pointer: (f64, f64),
+    /// Event timestamp in milliseconds
+    pub time_ms: f64,
+    /// Center the dragged element on the pointer before dragging
+    pub snap_to_cursor: bool,
+    /// Increments with every request so repeated starts are distinguishable
+    pub sequence: u64,
+}
+
+impl DragStartRequest {
+    /// Capture a request from a pointer event
+    pub fn from_event(event: &web_sys::PointerEvent, snap_to_cursor: bool) -> Self {
+        Self {
+            pointer_id: event.pointer_id(),
+            pointer: (event.client_x() as f64, event.client_y() as f64),
+            time_ms: event.time_stamp(),
+            snap_to_cursor,
+            sequence: 0,
+        }
+    }
+}
+
+/// Options for [`DragControls::start`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DragStartOptions {
+    /// Center the dragged element on the pointer before dragging
+    pub snap_to_cursor: bool,
+}
+
+/// Starts drags of a `MotionDiv` from pointer events on other elements
+///
+/// ```ignore
+/// let controls = use_drag_controls();
+/// view! {
+///     <div on:pointerdown=move |event| controls.start(&event, DragStartOptions::default())>
+///         "Grip"
+///     </div>
+///     <MotionDiv drag=DragConfig::default() drag_controls=controls drag_listener=false>
+///         "Sheet"
+///     </MotionDiv>
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DragControls {
+    request: RwSignal<Option<DragStartRequest>>,
+}
+
+impl DragControls {
+    /// Create new drag controls
+    pub fn new() -> Self {
+        Self {
+            request: RwSignal::new(None),
+        }
+    }
+
+    /// Start dragging every element bound to these controls
+    pub fn start(&self, event: &web_sys::PointerEvent, options: DragStartOptions) {
+        self.start_with(DragStartRequest::from_event(event, options.snap_to_cursor));
+    }
+
+    /// Start dragging from an already captured request
+    pub fn start_with(&self, mut request: DragStartRequest) {
+        request.sequence = self.sequence_untracked() + 1;
+        self.request.set(Some(request));
+    }
+
+    /// Latest start request (tracked)
+    pub fn request(&self) -> Option<DragStartRequest> {
+        self.request.get()
+    }
+
+    /// Sequence number of the latest start request, without tracking
+    pub fn sequence_untracked(&self) -> u64 {
+        self.request
+            .with_untracked(|request| request.as_ref().map_or(0, |request| request.sequence))
+    }
+}
+
+impl Default for DragControls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hook creating [`DragControls`] for the current component
+pub fn use_drag_controls() -> DragControls {
+    DragControls::new()
+}
+
+/// Offset that centers `element` on `pointer`, given its current drag `offset`
+pub fn snap_offset_to_cursor(
+    element: &web_sys::Element,
+    offset: (f64, f64),
+    pointer: (f64, f64),
+) -> (f64, f64) {
+    centered_offset(
+        &layout_info(&element.get_bounding_client_rect()),
+        offset,
+        pointer,
+    )
+}
+
+/// Offset that moves the center of `element` onto `pointer`
+pub fn centered_offset(
+    element: &LayoutInfo,
+    offset: (f64, f64),
+    pointer: (f64, f64),
+) -> (f64, f64) {
+    let (center_x, center_y) = element.center();
+    (
+        offset.0 + pointer.0 - center_x,
+        offset.1 + pointer.1 - center_y,
+    )
+}
@@ -0,0 +1,261 @@
+// Pointer Drag Tests
+//
+// These tests verify the pointer drag session: rubber-band overflow,
+// constraints measured from a container, direction locking and the
+// spring that settles the element on release.
+
+use crate::pointer_drag::{
+    DragControls, DragOptions, DragSession, DragStartRequest, SnapBackAnimation, apply_elastic,
+    centered_offset,
+};
+use crate::{DragAxis, DragConfig, DragConstraints};
+use leptos_motion_layout::LayoutInfo;
+
+fn bounded_config(elastic: f64) -> DragConfig {
+    DragConfig {
+        axis: Some(DragAxis::Both),
+        constraints: Some(DragConstraints {
+            left: Some(-100.0),
+            right: Some(100.0),
+            top: Some(-50.0),
+            bottom: Some(50.0),
+        }),
+        elastic: Some(elastic),
+        momentum: Some(false),
+    }
+}
+
+#[test]
+fn test_apply_elastic_inside_bounds_is_unchanged() {
+    assert_eq!(apply_elastic(25.0, Some(-100.0), Some(100.0), 0.5), 25.0);
+}
+
+#[test]
+fn test_apply_elastic_overflow_coefficient() {
+    assert_eq!(apply_elastic(140.0, Some(-100.0), Some(100.0), 0.0), 100.0);
+    assert_eq!(apply_elastic(140.0, Some(-100.0), Some(100.0), 0.5), 120.0);
+    assert_eq!(
+        apply_elastic(-140.0, Some(-100.0), Some(100.0), 0.25),
+        -110.0
+    );
+    assert_eq!(apply_elastic(140.0, Some(-100.0), Some(100.0), 1.0), 140.0);
+}
+
+#[test]
+fn test_constraints_from_container() {
+    let container = LayoutInfo::new(0.0, 0.0, 400.0, 300.0);
+    let element = LayoutInfo::new(100.0, 50.0, 100.0, 100.0);
+
+    let constraints = DragConstraints::from_container(&container, &element, (0.0, 0.0));
+    assert_eq!(constraints.left, Some(-100.0));
+    assert_eq!(constraints.right, Some(200.0));
+    assert_eq!(constraints.top, Some(-50.0));
+    assert_eq!(constraints.bottom, Some(150.0));
+
+    // Measuring mid-drag subtracts the offset already applied
+    let moved = LayoutInfo::new(130.0, 70.0, 100.0, 100.0);
+    let constraints = DragConstraints::from_container(&container, &moved, (30.0, 20.0));
+    assert_eq!(constraints.left, Some(-100.0));
+    assert_eq!(constraints.bottom, Some(150.0));
+}
+
+#[test]
+fn test_drag_session_follows_pointer_with_elastic_overflow() {
+    let config = bounded_config(0.5);
+    let options = DragOptions::default();
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+
+    assert_eq!(session.update((40.0, 20.0), 16.0), (40.0, 20.0));
+    assert_eq!(session.update((140.0, 20.0), 32.0), (120.0, 20.0));
+}
+
+#[test]
+fn test_drag_session_respects_axis() {
+    let config = DragConfig {
+        axis: Some(DragAxis::X),
+        ..bounded_config(0.0)
+    };
+    let options = DragOptions::default();
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+
+    assert_eq!(session.update((30.0, 40.0), 16.0), (30.0, 0.0));
+    assert_eq!(session.velocity().1, 0.0);
+}
+
+#[test]
+fn test_drag_session_direction_lock() {
+    let config = bounded_config(0.0);
+    let options = DragOptions::default().direction_lock(true);
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+
+    // Below the threshold nothing moves and no axis is chosen
+    assert_eq!(session.update((1.0, 2.0), 8.0), (0.0, 0.0));
+    assert!(session.locked_axis().is_none());
+
+    assert_eq!(session.update((2.0, 10.0), 16.0), (0.0, 10.0));
+    assert_eq!(session.locked_axis(), Some(&DragAxis::Y));
+
+    // Horizontal movement is ignored for the rest of the gesture
+    assert_eq!(session.update((60.0, 20.0), 32.0), (0.0, 20.0));
+}
+
+#[test]
+fn test_measured_constraints_override_config() {
+    let config = bounded_config(0.0);
+    let options = DragOptions::default();
+    let measured = DragConstraints {
+        left: Some(-10.0),
+        right: Some(10.0),
+        top: None,
+        bottom: None,
+    };
+    let mut session = DragSession::start(
+        &config,
+        &options,
+        Some(measured),
+        (0.0, 0.0),
+        (0.0, 0.0),
+        0.0,
+    );
+
+    assert_eq!(session.update((50.0, 80.0), 16.0), (10.0, 80.0));
+}
+
+#[test]
+fn test_release_target_snaps_back_inside_bounds() {
+    let config = bounded_config(0.5);
+    let options = DragOptions::default();
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+    session.update((300.0, 0.0), 16.0);
+
+    assert_eq!(session.release_target(&options, false), (100.0, 0.0));
+}
+
+#[test]
+fn test_release_target_snap_to_origin() {
+    let config = bounded_config(0.0);
+    let options = DragOptions::default().snap_to_origin(true);
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+    session.update((30.0, 30.0), 16.0);
+
+    assert_eq!(session.release_target(&options, false), (0.0, 0.0));
+}
+
+#[test]
+fn test_release_target_projects_momentum() {
+    let config = bounded_config(0.0);
+    let options = DragOptions::default();
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+    session.update((10.0, 0.0), 100.0);
+
+    // 100px/s projected forward, still inside the bounds
+    let (x, y) = session.release_target(&options, true);
+    assert!(x > 10.0 && x <= 100.0);
+    assert_eq!(y, 0.0);
+}
+
+#[test]
+fn test_release_velocity_covers_recent_moves() {
+    let config = bounded_config(0.0);
+    let options = DragOptions::default();
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+
+    // Held still first, then moved at 1000px/s
+    session.update((0.0, 0.0), 200.0);
+    for step in 1..=10 {
+        session.update((step as f64 * 10.0, 0.0), 200.0 + step as f64 * 10.0);
+    }
+    session.release(300.0);
+    assert!((session.velocity().0 - 1000.0).abs() < 1e-9);
+
+    // Time passed since the last move slows the release down
+    session.release(350.0);
+    assert!(session.velocity().0 > 0.0 && session.velocity().0 < 1000.0);
+}
+
+#[test]
+fn test_release_after_pause_has_no_velocity() {
+    let config = bounded_config(0.0);
+    let options = DragOptions::default();
+    let mut session = DragSession::start(&config, &options, None, (0.0, 0.0), (0.0, 0.0), 0.0);
+    session.update((20.0, 0.0), 16.0);
+    session.update((40.0, 0.0), 32.0);
+    assert!(session.velocity().0 > 0.0);
+
+    session.release(500.0);
+    assert_eq!(session.velocity(), (0.0, 0.0));
+    assert_eq!(session.release_target(&options, true), (40.0, 0.0));
+}
+
+#[test]
+fn test_snap_back_animation_settles_on_target() {
+    let options = DragOptions::default();
+    let mut animation = SnapBackAnimation::new(
+        (150.0, 0.0),
+        (800.0, 0.0),
+        (100.0, 0.0),
+        &options.transition,
+    );
+
+    let (first_x, _) = animation.step(1.0 / 60.0);
+    // Release velocity carries the element further out before it returns
+    assert!(first_x > 150.0);
+
+    for _ in 0..300 {
+        animation.step(1.0 / 60.0);
+    }
+    assert!(animation.is_settled());
+    assert_eq!(animation.step(1.0 / 60.0), (100.0, 0.0));
+}
+
+fn start_request(pointer: (f64, f64)) -> DragStartRequest {
+    DragStartRequest {
+        pointer_id: 1,
+        pointer,
+        time_ms: 0.0,
+        snap_to_cursor: false,
+        sequence: 0,
+    }
+}
+
+#[test]
+fn test_drag_controls_sequence_increments() {
+    let controls = DragControls::new();
+    assert_eq!(controls.sequence_untracked(), 0);
+    assert!(controls.request().is_none());
+
+    controls.start_with(start_request((10.0, 20.0)));
+    assert_eq!(controls.sequence_untracked(), 1);
+
+    // Starting again from the same point is still a new request
+    controls.start_with(start_request((10.0, 20.0)));
+    let request = controls.request().unwrap();
+    assert_eq!(request.sequence, 2);
+    assert_eq!(request.pointer, (10.0, 20.0));
+}
+
+#[test]
+fn test_drag_controls_are_shared_between_copies() {
+    let controls = DragControls::new();
+    let handle = controls;
+
+    handle.start_with(DragStartRequest {
+        snap_to_cursor: true,
+        ..start_request((5.0, 5.0))
+    });
+    assert!(controls.request().unwrap().snap_to_cursor);
+}
+
+#[test]
+fn test_centered_offset_moves_center_onto_pointer() {
+    let element = LayoutInfo::new(100.0, 100.0, 50.0, 20.0);
+
+    assert_eq!(
+        centered_offset(&element, (0.0, 0.0), (125.0, 110.0)),
+        (0.0, 0.0)
+    );
+    assert_eq!(
+        centered_offset(&element, (10.0, 0.0), (200.0, 150.0)),
+        (85.0, 40.0)
+    );
+}
@@ -0,0 +1,389 @@
+//! Presence-aware control flow
+//!
+//! [`PresenceFor`] and [`PresenceShow`] are drop-in variants of Leptos' `For`
+//! and `Show` that keep removed content mounted until its exit has finished.
+//! Children observe their presence with [`use_is_present`], and can hold
+//! removal back with [`use_presence`] until they call `safe_to_remove`.
+//! Motion children with an `exit` target do this automatically.
+
+use crate::flip_group::FlipGroup;
+use leptos::context::Provider;
+use leptos::prelude::*;
+use leptos_motion_core::request_frame;
+use leptos_motion_layout::LayoutAnimationConfig;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Presence of a single piece of content inside [`PresenceFor`] or [`PresenceShow`]
+#[derive(Clone, Copy)]
+pub struct PresenceItemContext {
+    present: RwSignal<bool>,
+    blockers: RwSignal<Vec<u64>>,
+    released: RwSignal<Vec<u64>>,
+    next_id: StoredValue<u64>,
+    skip_initial: StoredValue<bool>,
+}
+
+impl PresenceItemContext {
+    /// Create a present item; `skip_initial` renders children without their `initial` state
+    pub fn new(skip_initial: bool) -> Self {
+        Self {
+            present: RwSignal::new(true),
+            blockers: RwSignal::new(Vec::new()),
+            released: RwSignal::new(Vec::new()),
+            next_id: StoredValue::new(0),
+            skip_initial: StoredValue::new(skip_initial),
+        }
+    }
+
+    /// Whether the content is present (tracked)
+    pub fn is_present(&self) -> bool {
+        self.present.try_get().unwrap_or(false)
+    }
+
+    /// Whether children mounting now should skip their `initial` state
+    pub fn skips_initial(&self) -> bool {
+        self.skip_initial.try_get_value().unwrap_or(false)
+    }
+
+    /// Start or cancel the exit of the content
+    pub fn set_present(&self, present: bool) {
+        if self.present.try_get_untracked() == Some(present) {
+            return;
+        }
+        // Every exit needs fresh `safe_to_remove` calls
+        self.released.try_set(Vec::new());
+        self.present.try_set(present);
+    }
+
+    /// Hold removal back until [`safe_to_remove`](Self::safe_to_remove) is called with the returned id
+    pub fn register(&self) -> u64 {
+        let id = self.next_id.try_get_value().unwrap_or_default();
+        self.next_id.try_set_value(id + 1);
+        self.blockers.try_update(|blockers| blockers.push(id));
+        id
+    }
+
+    /// Stop holding removal back, e.g. when the child unmounts
+    pub fn unregister(&self, id: u64) {
+        self.blockers
+            .try_update(|blockers| blockers.retain(|blocker| *blocker != id));
+        self.released
+            .try_update(|released| released.retain(|blocker| *blocker != id));
+    }
+
+    /// Allow removal once the exit of blocker `id` has finished
+    pub fn safe_to_remove(&self, id: u64) {
+        if self.present.try_get_untracked() != Some(false) {
+            return;
+        }
+        self.released.try_update(|released| {
+            if !released.contains(&id) {
+                released.push(id);
+            }
+        });
+    }
+
+    /// Whether the content has exited and every blocker allowed removal (tracked)
+    pub fn is_removable(&self) -> bool {
+        if self.is_present() {
+            return false;
+        }
+        self.blockers
+            .try_with(|blockers| {
+                self.released
+                    .with(|released| blockers.iter().all(|id| released.contains(id)))
+            })
+            .unwrap_or(true)
+    }
+
+    fn dispose(self) {
+        self.present.dispose();
+        self.blockers.dispose();
+        self.released.dispose();
+        self.next_id.dispose();
+        self.skip_initial.dispose();
+    }
+}
+
+/// Whether the closest [`PresenceFor`] item or [`PresenceShow`] content is present
+///
+/// Always `true` outside of presence-aware control flow.
+pub fn use_is_present() -> Signal<bool> {
+    match use_context::<PresenceItemContext>() {
+        Some(presence) => Signal::derive(move || presence.is_present()),
+        None => Signal::stored(true),
+    }
+}
+
+/// Presence state plus a `safe_to_remove` callback
+///
+/// Once the content stops being present it stays mounted until
+/// `safe_to_remove` has been run, letting custom children finish their own
+/// exit first.
+pub fn use_presence() -> (Signal<bool>, Callback<()>) {
+    let Some(presence) = use_context::<PresenceItemContext>() else {
+        return (Signal::stored(true), Callback::new(|_| {}));
+    };
+    let id = presence.register();
+    on_cleanup(move || presence.unregister(id));
+
+    (
+        Signal::derive(move || presence.is_present()),
+        Callback::new(move |_| presence.safe_to_remove(id)),
+    )
+}
+
+/// Order of keys after an update, keeping exiting keys where they were
+///
+/// Keys missing from `next` are reinserted at their index in `previous`, so
+/// exiting content stays in place while the rest of the list updates.
+pub fn merge_presence_order<K>(previous: &[K], next: &[K]) -> Vec<K>
+where
+    K: Eq + Hash + Clone,
+{
+    let next_keys: HashSet<&K> = next.iter().collect();
+    let mut merged = next.to_vec();
+    for (index, key) in previous.iter().enumerate() {
+        if !next_keys.contains(key) {
+            merged.insert(index.min(merged.len()), key.clone());
+        }
+    }
+    merged
+}
+
+/// Item rendered by [`PresenceFor`]
+#[derive(Clone)]
+struct PresenceEntry<K, T> {
+    id: u64,
+    key: K,
+    item: T,
+    presence: PresenceItemContext,
+    node_ref: NodeRef<leptos::html::Div>,
+}
+
+impl<K, T> PresenceEntry<K, T> {
+    fn flip_id(&self) -> String {
+        format!("presence-{}", self.id)
+    }
+}
+
+/// Keyed list that keeps removed items mounted through their exit
+///
+/// Each item is wrapped in a `div`; remaining siblings FLIP into their new
+/// positions when items enter or finish exiting.
+#[component]
+pub fn PresenceFor<IF, I, T, EF, N, KF, K>(
+    /// Items to render
+    each: IF,
+    /// Unique key of an item
+    key: KF,
+    /// Renders an item
+    children: EF,
+    /// Play the `initial` state of items present on first render (defaults to true)
+    #[prop(optional)]
+    initial: Option<bool>,
+    /// Animation used by siblings moving into their new positions
+    #[prop(optional)]
+    layout_transition: Option<LayoutAnimationConfig>,
+    /// Called once every removed item has finished exiting
+    #[prop(optional, into)]
+    on_exit_complete: Option<Callback<()>>,
+    /// CSS class of the wrapper around each item
+    #[prop(optional)]
+    item_class: Option<String>,
+) -> impl IntoView
+where
+    IF: Fn() -> I + Send + Sync + 'static,
+    I: IntoIterator<Item = T>,
+    EF: Fn(T) -> N + Clone + Send + Sync + 'static,
+    N: IntoView + 'static,
+    KF: Fn(&T) -> K + Send + Sync + 'static,
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    let skip_initial = !initial.unwrap_or(true);
+    let flip = FlipGroup::new(layout_transition.unwrap_or_default());
+    let next_id = StoredValue::new(0u64);
+    let owner = Owner::current();
+
+    // Item contexts outlive the effect run that creates them
+    let new_entry = move |key: K, item: T, skip_initial: bool| {
+        let id = next_id.get_value();
+        next_id.set_value(id + 1);
+        let presence = match &owner {
+            Some(owner) => owner.with(|| PresenceItemContext::new(skip_initial)),
+            None => PresenceItemContext::new(skip_initial),
+        };
+        PresenceEntry {
+            id,
+            key,
+            item,
+            presence,
+            node_ref: NodeRef::new(),
+        }
+    };
+
+    let initial_entries = untrack(|| {
+        each()
+            .into_iter()
+            .map(|item| new_entry(key(&item), item, skip_initial))
+            .collect::<Vec<_>>()
+    });
+    let entries = RwSignal::new(initial_entries);
+
+    // Merge updates, starting exits for removed keys and re-entering returning ones
+    Effect::new(move |_| {
+        let items: Vec<T> = each().into_iter().collect();
+        let next_keys: Vec<K> = items.iter().map(&key).collect();
+        let mut items_by_key: HashMap<K, T> =
+            items.into_iter().map(|item| (key(&item), item)).collect();
+
+        let previous = entries.get_untracked();
+        let previous_keys: Vec<K> = previous.iter().map(|entry| entry.key.clone()).collect();
+        let mut previous_by_key: HashMap<K, PresenceEntry<K, T>> = previous
+            .iter()
+            .cloned()
+            .map(|entry| (entry.key.clone(), entry))
+            .collect();
+
+        let merged: Vec<_> = merge_presence_order(&previous_keys, &next_keys)
+            .into_iter()
+            .filter_map(|entry_key| {
+                match (
+                    previous_by_key.remove(&entry_key),
+                    items_by_key.remove(&entry_key),
+                ) {
+                    (Some(mut entry), Some(item)) => {
+                        entry.presence.set_present(true);
+                        entry.item = item;
+                        Some(entry)
+                    }
+                    (Some(entry), None) => {
+                        entry.presence.set_present(false);
+                        Some(entry)
+                    }
+                    (None, Some(item)) => Some(new_entry(entry_key, item, false)),
+                    (None, None) => None,
+                }
+            })
+            .collect();
+
+        let unchanged = merged.len() == previous.len()
+            && merged
+                .iter()
+                .zip(&previous)
+                .all(|(entry, previous)| entry.id == previous.id);
+        if unchanged {
+            return;
+        }
+
+        let firsts = FlipGroup::snapshot(
+            previous
+                .iter()
+                .map(|entry| (entry.flip_id(), entry.node_ref)),
+        );
+        entries.set(merged);
+        request_frame(move || flip.play(firsts));
+    });
+
+    // Unmount items whose exit has finished
+    Effect::new(move |_| {
+        let removable: Vec<u64> = entries.with(|entries| {
+            entries
+                .iter()
+                .filter(|entry| entry.presence.is_removable())
+                .map(|entry| entry.id)
+                .collect()
+        });
+        if removable.is_empty() {
+            return;
+        }
+
+        let (removed, kept): (Vec<_>, Vec<_>) = entries
+            .get_untracked()
+            .into_iter()
+            .partition(|entry| removable.contains(&entry.id));
+        let firsts =
+            FlipGroup::snapshot(kept.iter().map(|entry| (entry.flip_id(), entry.node_ref)));
+        let all_exited = kept
+            .iter()
+            .all(|entry| entry.presence.present.get_untracked());
+        entries.set(kept);
+
+        request_frame(move || {
+            flip.play(firsts);
+            for entry in removed {
+                entry.presence.dispose();
+            }
+        });
+        if all_exited && let Some(on_exit_complete) = on_exit_complete {
+            on_exit_complete.run(());
+        }
+    });
+
+    view! {
+        <For
+            each=move || entries.get()
+            key=|entry| entry.id
+            children=move |entry| {
+                let children = children.clone();
+                let class = item_class.clone();
+                view! {
+                    <Provider value=entry.presence>
+                        <div node_ref=entry.node_ref class=class>
+                            {children(entry.item)}
+                        </div>
+                    </Provider>
+                }
+            }
+        />
+    }
+}
+
+/// Conditional content that animates out instead of disappearing instantly
+#[component]
+pub fn PresenceShow(
+    /// Whether the content is shown
+    #[prop(into)]
+    when: Signal<bool>,
+    /// Play the `initial` state if shown on first render (defaults to true)
+    #[prop(optional)]
+    initial: Option<bool>,
+    /// Called once the content has finished exiting
+    #[prop(optional, into)]
+    on_exit_complete: Option<Callback<()>>,
+    /// Content to show
+    children: ChildrenFn,
+) -> impl IntoView {
+    let presence = PresenceItemContext::new(!initial.unwrap_or(true));
+    let mounted = RwSignal::new(when.get_untracked());
+    presence.set_present(mounted.get_untracked());
+
+    Effect::new(move |_| {
+        let show = when.get();
+        presence.set_present(show);
+        if show && !mounted.get_untracked() {
+            mounted.set(true);
+        }
+    });
+
+    // `initial` only applies to the first render
+    Effect::new(move |_| presence.skip_initial.set_value(false));
+
+    Effect::new(move |_| {
+        if mounted.get() && presence.is_removable() {
+            mounted.set(false);
+            if let Some(on_exit_complete) = on_exit_complete {
+                on_exit_complete.run(());
+            }
+        }
+    });
+
+    move || {
+        mounted.get().then(|| {
+            let children = children.clone();
+            view! { <Provider value=presence>{children()}</Provider> }
+        })
+    }
+}
@@ -0,0 +1,72 @@
+// Presence Flow Tests
+//
+// These tests verify where exiting keys stay while a presence list updates,
+// and when an exiting item becomes safe to remove.
+
+use crate::presence_flow::{PresenceItemContext, merge_presence_order};
+
+#[test]
+fn test_merge_keeps_exiting_keys_in_place() {
+    let merged = merge_presence_order(&["a", "b", "c"], &["a", "c"]);
+    assert_eq!(merged, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_merge_with_insertions_and_removals() {
+    let merged = merge_presence_order(&["a", "b", "c"], &["c", "d"]);
+    assert_eq!(merged, vec!["a", "b", "c", "d"]);
+
+    // Exiting keys past the end of the new list go last
+    let merged = merge_presence_order(&["a", "b", "c", "d"], &["a"]);
+    assert_eq!(merged, vec!["a", "b", "c", "d"]);
+}
+
+#[test]
+fn test_merge_returning_keys_take_new_position() {
+    let merged = merge_presence_order(&["a", "b"], &["b", "a"]);
+    assert_eq!(merged, vec!["b", "a"]);
+}
+
+#[test]
+fn test_item_without_blockers_is_removable_once_absent() {
+    let presence = PresenceItemContext::new(false);
+    assert!(presence.is_present());
+    assert!(!presence.is_removable());
+
+    presence.set_present(false);
+    assert!(presence.is_removable());
+}
+
+#[test]
+fn test_item_waits_for_every_blocker() {
+    let presence = PresenceItemContext::new(false);
+    let first = presence.register();
+    let second = presence.register();
+
+    // Releasing while present is ignored
+    presence.safe_to_remove(first);
+    presence.set_present(false);
+    assert!(!presence.is_removable());
+
+    presence.safe_to_remove(first);
+    assert!(!presence.is_removable());
+
+    // Unmounted blockers no longer hold removal back
+    presence.unregister(second);
+    assert!(presence.is_removable());
+}
+
+#[test]
+fn test_reentering_resets_released_blockers() {
+    let presence = PresenceItemContext::new(true);
+    assert!(presence.skips_initial());
+    let id = presence.register();
+
+    presence.set_present(false);
+    presence.safe_to_remove(id);
+    assert!(presence.is_removable());
+
+    presence.set_present(true);
+    presence.set_present(false);
+    assert!(!presence.is_removable());
+}
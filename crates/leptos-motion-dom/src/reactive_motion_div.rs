@@ -2,6 +2,10 @@
 //!
 //! This module provides a clean, minimal ReactiveMotionDiv component that
 //! doesn't interfere with browser right-click functionality.
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use leptos::prelude::*;
 use leptos_motion_core::{AnimationTarget, Transition};
@@ -241,7 +241,7 @@ fn test_prop_combinations_regression() {
                 transition=transition
                 while_hover=hover_target
                 while_tap=tap_target
-                _layout=true
+                layout=true
                 drag=drag_config
             >
                 "Complex Combination"
@@ -344,9 +344,9 @@ fn test_type_safety_regression() {
                 transition=correct_transition.unwrap_or_default()
                 while_hover=correct_while_hover.unwrap_or_default()
                 while_tap=correct_while_tap.unwrap_or_default()
-                _layout=correct_layout.unwrap_or(false)
+                layout=correct_layout.unwrap_or(false)
                 drag=correct_drag.unwrap_or_default()
-                _drag_constraints=correct_drag_constraints.unwrap_or_default()
+                drag_constraints=correct_drag_constraints.unwrap_or_default()
             >
                 "Type Safety Test"
             </MotionDiv>
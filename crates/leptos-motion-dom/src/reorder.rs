@@ -0,0 +1,459 @@
+//! Drag-to-reorder lists
+//!
+//! [`ReorderGroup`] binds a `RwSignal<Vec<T>>` and [`ReorderItem`] makes each
+//! value draggable along the group axis. Crossing a sibling's midpoint moves
+//! the value inside the signal, siblings FLIP into their new slots through
+//! [`leptos_motion_layout::FLIPAnimator`] and the dragged item springs into its slot on release.
+
+use crate::flip_group::FlipGroup;
+use crate::pointer_drag::{DragOptions, DragSession, SnapBackAnimation, run_snap_back};
+use crate::{DragAxis, DragConfig};
+use leptos::prelude::*;
+use leptos_motion_core::{SpringConfig, inline_style, request_frame};
+use leptos_motion_layout::{LayoutAnimationConfig, LayoutInfo, exclude_from_transition};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Start and end of `layout` along `axis`
+///
+/// [`DragAxis::Both`] is treated as a vertical list.
+fn axis_span(layout: &LayoutInfo, axis: &DragAxis) -> (f64, f64) {
+    match axis {
+        DragAxis::X => (layout.x, layout.x + layout.width),
+        DragAxis::Y | DragAxis::Both => (layout.y, layout.y + layout.height),
+    }
+}
+
+/// Component of `value` along `axis`
+fn axis_component(value: (f64, f64), axis: &DragAxis) -> f64 {
+    match axis {
+        DragAxis::X => value.0,
+        DragAxis::Y | DragAxis::Both => value.1,
+    }
+}
+
+/// New index for the item at `index` dragged by `offset` along `axis`
+///
+/// `layouts` are the untransformed boxes of the items in their current
+/// order. Only the neighbour in the direction of `velocity` is considered,
+/// and the item moves into its slot once the leading edge of the dragged
+/// box crosses that neighbour's midpoint.
+pub fn check_reorder(
+    layouts: &[LayoutInfo],
+    index: usize,
+    offset: f64,
+    velocity: f64,
+    axis: &DragAxis,
+) -> Option<usize> {
+    if velocity == 0.0 {
+        return None;
+    }
+    let item = layouts.get(index)?;
+    let next_index = if velocity > 0.0 {
+        index + 1
+    } else {
+        index.checked_sub(1)?
+    };
+    let next = layouts.get(next_index)?;
+
+    let (item_min, item_max) = axis_span(item, axis);
+    let (next_min, next_max) = axis_span(next, axis);
+    let next_center = (next_min + next_max) / 2.0;
+
+    let crossed = if velocity > 0.0 {
+        item_max + offset > next_center
+    } else {
+        item_min + offset < next_center
+    };
+    crossed.then_some(next_index)
+}
+
+/// Move the value at `from` to `to`, shifting the values in between
+pub fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    if from >= items.len() || to >= items.len() || from == to {
+        return;
+    }
+    let item = items.remove(from);
+    items.insert(to, item);
+}
+
+/// Untransformed layout box of `element` relative to its offset parent
+pub fn layout_of(element: &web_sys::HtmlElement) -> LayoutInfo {
+    LayoutInfo::new(
+        element.offset_left() as f64,
+        element.offset_top() as f64,
+        element.offset_width() as f64,
+        element.offset_height() as f64,
+    )
+}
+
+/// Item registered with a [`ReorderGroup`]
+#[derive(Clone, Debug)]
+struct ReorderEntry<T> {
+    id: u64,
+    value: T,
+    node_ref: NodeRef<leptos::html::Div>,
+}
+
+/// Context shared between a [`ReorderGroup`] and its [`ReorderItem`]s
+#[derive(Clone)]
+pub struct ReorderContext<T: Send + Sync + 'static> {
+    values: RwSignal<Vec<T>>,
+    axis: DragAxis,
+    key: Option<Callback<T, String>>,
+    items: StoredValue<Vec<ReorderEntry<T>>>,
+    next_id: StoredValue<u64>,
+    flip: FlipGroup,
+}
+
+impl<T> ReorderContext<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Bound values in their current order
+    pub fn values(&self) -> RwSignal<Vec<T>> {
+        self.values
+    }
+
+    /// Axis items are dragged along
+    pub fn axis(&self) -> &DragAxis {
+        &self.axis
+    }
+
+    /// Whether `a` and `b` identify the same item
+    fn same_item(&self, a: &T, b: &T) -> bool {
+        match &self.key {
+            Some(key) => key.run(a.clone()) == key.run(b.clone()),
+            None => a == b,
+        }
+    }
+
+    fn register(&self, value: T, node_ref: NodeRef<leptos::html::Div>) -> u64 {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        self.items.update_value(|items| {
+            items.push(ReorderEntry {
+                id,
+                value,
+                node_ref,
+            })
+        });
+        id
+    }
+
+    fn unregister(&self, id: u64) {
+        let _ = self
+            .items
+            .try_update_value(|items| items.retain(|entry| entry.id != id));
+    }
+
+    /// Registered entries in the order of the bound values
+    ///
+    /// Each entry is matched to at most one value, so equal values without
+    /// a key still map to distinct items.
+    fn ordered_entries(&self) -> Vec<ReorderEntry<T>> {
+        let mut items = self.items.get_value();
+        self.values.with_untracked(|values| {
+            values
+                .iter()
+                .filter_map(|value| {
+                    let index = items
+                        .iter()
+                        .position(|entry| self.same_item(&entry.value, value))?;
+                    Some(items.remove(index))
+                })
+                .collect()
+        })
+    }
+
+    /// Move the item registered as `id` to `to`, FLIP-animating every other
+    /// item into its slot
+    ///
+    /// `on_layout` receives the dragged item's layout delta once the DOM has
+    /// been updated.
+    fn reorder(&self, id: u64, to: usize, on_layout: impl FnOnce((f64, f64)) + 'static) {
+        let entries = self.ordered_entries();
+        let Some(from) = entries.iter().position(|entry| entry.id == id) else {
+            return;
+        };
+
+        // First: visual positions, including any running FLIP transform
+        let firsts = FlipGroup::snapshot(
+            entries
+                .iter()
+                .filter(|entry| entry.id != id)
+                .map(|entry| (format!("reorder-{}", entry.id), entry.node_ref)),
+        );
+        let Some(dragged_before) = entries[from]
+            .node_ref
+            .get_untracked()
+            .map(|element| layout_of(&element))
+        else {
+            return;
+        };
+        let dragged = entries[from].node_ref;
+
+        self.values.update(|values| move_item(values, from, to));
+
+        // Last and Invert once the keyed list has been re-rendered
+        let context = self.clone();
+        request_frame(move || {
+            if let Some(element) = dragged.get_untracked() {
+                let after = layout_of(&element);
+                on_layout((after.x - dragged_before.x, after.y - dragged_before.y));
+            }
+
+            context.flip.play(firsts);
+        });
+    }
+}
+
+/// Access the closest [`ReorderGroup`] for values of type `T`
+pub fn use_reorder_context<T>() -> Option<ReorderContext<T>>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    use_context::<ReorderContext<T>>()
+}
+
+/// Container for a list whose items can be reordered by dragging
+///
+/// Render one [`ReorderItem`] per value of `values`, typically with a keyed
+/// `<For>`, and the order of `values` follows the drag. Pass the same `key`
+/// as the `<For>` when values can compare equal; without it items are
+/// matched to values by equality.
+#[component]
+pub fn ReorderGroup<T>(
+    /// Values in display order, updated as items are dragged
+    values: RwSignal<Vec<T>>,
+    /// Stable identity of a value (defaults to the value itself)
+    #[prop(optional, into)]
+    key: Option<Callback<T, String>>,
+    /// Axis items are dragged along (defaults to `DragAxis::Y`)
+    #[prop(optional)]
+    axis: Option<DragAxis>,
+    /// Animation used by siblings moving into their new slots
+    #[prop(optional)]
+    layout_transition: Option<LayoutAnimationConfig>,
+    /// CSS class
+    #[prop(optional)]
+    class: Option<String>,
+    /// Inline style
+    #[prop(optional)]
+    style: Option<String>,
+    /// Children
+    children: Children,
+) -> impl IntoView
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    provide_context(ReorderContext {
+        values,
+        axis: axis.unwrap_or(DragAxis::Y),
+        key,
+        items: StoredValue::new(Vec::new()),
+        next_id: StoredValue::new(0),
+        flip: FlipGroup::new(
+            layout_transition.unwrap_or_else(|| LayoutAnimationConfig::new().with_duration(0.25)),
+        ),
+    });
+
+    view! {
+        <div class=class style=style>
+            {children()}
+        </div>
+    }
+}
+
+/// Draggable item of a [`ReorderGroup`]
+#[component]
+pub fn ReorderItem<T>(
+    /// Value this item represents in the group's values
+    value: T,
+    /// Spring used to settle into the slot on release
+    #[prop(optional)]
+    drag_transition: Option<SpringConfig>,
+    /// CSS class
+    #[prop(optional)]
+    class: Option<String>,
+    /// Inline style
+    #[prop(optional)]
+    style: Option<String>,
+    /// Children
+    children: Children,
+) -> impl IntoView
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    let context =
+        use_reorder_context::<T>().expect("ReorderItem must be used inside a ReorderGroup");
+    let node_ref = NodeRef::<leptos::html::Div>::new();
+    let id = context.register(value.clone(), node_ref);
+    let cleanup_context = context.clone();
+    on_cleanup(move || cleanup_context.unregister(id));
+
+    let drag_config = DragConfig {
+        axis: Some(context.axis.clone()),
+        constraints: None,
+        elastic: None,
+        momentum: None,
+    };
+    let mut drag_options = DragOptions::new();
+    if let Some(transition) = drag_transition {
+        drag_options = drag_options.transition(transition);
+    }
+
+    let drag_session: Rc<RefCell<Option<DragSession>>> = Rc::new(RefCell::new(None));
+    let drag_generation = Rc::new(Cell::new(0u32));
+    let offset = Rc::new(Cell::new((0.0, 0.0)));
+    // Reorders wait for the previous one to be laid out before re-checking
+    let layout_pending = Rc::new(Cell::new(false));
+
+    let apply_offset = {
+        let offset = offset.clone();
+        move |(x, y): (f64, f64)| {
+            offset.set((x, y));
+            if let Some(element) = node_ref.get_untracked() {
+                exclude_from_transition(&element, &["transform"]);
+                let style = inline_style(&element);
+                if x == 0.0 && y == 0.0 {
+                    let _ = style.remove_property("transform");
+                    let _ = style.remove_property("z-index");
+                } else {
+                    let _ =
+                        style.set_property("transform", &format!("translate({}px, {}px)", x, y));
+                }
+            }
+        }
+    };
+
+    let session_down = drag_session.clone();
+    let generation_down = drag_generation.clone();
+    let offset_down = offset.clone();
+    let options_down = drag_options.clone();
+    let on_pointer_down = move |event: web_sys::PointerEvent| {
+        if event.button() != 0 {
+            return;
+        }
+        let Some(element) = node_ref.get_untracked() else {
+            return;
+        };
+        event.stop_propagation();
+
+        // Interrupt any running settle animation and continue from there
+        generation_down.set(generation_down.get().wrapping_add(1));
+        let _ = element.set_pointer_capture(event.pointer_id());
+        let _ = inline_style(&element).set_property("position", "relative");
+        let _ = inline_style(&element).set_property("z-index", "1");
+        *session_down.borrow_mut() = Some(DragSession::start(
+            &drag_config,
+            &options_down,
+            None,
+            (event.client_x() as f64, event.client_y() as f64),
+            offset_down.get(),
+            event.time_stamp(),
+        ));
+    };
+
+    let session_move = drag_session.clone();
+    let apply_move = apply_offset.clone();
+    let move_context = context.clone();
+    let on_pointer_move = move |event: web_sys::PointerEvent| {
+        let (position, velocity) = {
+            let mut session = session_move.borrow_mut();
+            let Some(session) = session.as_mut() else {
+                return;
+            };
+            let position = session.update(
+                (event.client_x() as f64, event.client_y() as f64),
+                event.time_stamp(),
+            );
+            (position, session.velocity())
+        };
+        apply_move(position);
+
+        if layout_pending.get() {
+            return;
+        }
+        let entries = move_context.ordered_entries();
+        let layouts: Option<Vec<_>> = entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .node_ref
+                    .get_untracked()
+                    .map(|element| layout_of(&element))
+            })
+            .collect();
+        let (Some(layouts), Some(index)) =
+            (layouts, entries.iter().position(|entry| entry.id == id))
+        else {
+            return;
+        };
+
+        let axis = &move_context.axis;
+        let Some(to) = check_reorder(
+            &layouts,
+            index,
+            axis_component(position, axis),
+            axis_component(velocity, axis),
+            axis,
+        ) else {
+            return;
+        };
+
+        layout_pending.set(true);
+        let pending = layout_pending.clone();
+        let session = session_move.clone();
+        let apply_layout = apply_move.clone();
+        move_context.reorder(id, to, move |delta| {
+            pending.set(false);
+            if let Some(session) = session.borrow_mut().as_mut() {
+                session.rebase(delta);
+                apply_layout(session.position());
+            }
+        });
+    };
+
+    let session_up = drag_session.clone();
+    let generation_up = drag_generation.clone();
+    let options_up = drag_options.clone();
+    let on_pointer_up = move |event: web_sys::PointerEvent| {
+        let Some(mut session) = session_up.borrow_mut().take() else {
+            return;
+        };
+        session.release(event.time_stamp());
+        if let Some(element) = node_ref.get_untracked() {
+            let _ = element.release_pointer_capture(event.pointer_id());
+        }
+
+        // The slot is the item's untransformed layout position
+        let animation = SnapBackAnimation::new(
+            session.position(),
+            session.velocity(),
+            (0.0, 0.0),
+            &options_up.transition,
+        );
+        run_snap_back(animation, generation_up.clone(), apply_offset.clone());
+    };
+    let on_pointer_cancel = on_pointer_up.clone();
+
+    let style = format!(
+        "touch-action: none; user-select: none;{}",
+        style.map(|style| format!(" {}", style)).unwrap_or_default()
+    );
+
+    view! {
+        <div
+            node_ref=node_ref
+            class=class
+            style=style
+            on:pointerdown=on_pointer_down
+            on:pointermove=on_pointer_move
+            on:pointerup=on_pointer_up
+            on:pointercancel=on_pointer_cancel
+        >
+            {children()}
+        </div>
+    }
+}
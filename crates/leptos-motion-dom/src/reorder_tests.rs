@@ -0,0 +1,108 @@
+// Reorder Tests
+//
+// These tests verify when a dragged item takes a neighbour's slot and how
+// the bound values move, plus rebasing a drag after the item is re-laid out.
+
+use crate::pointer_drag::{DragOptions, DragSession};
+use crate::reorder::{check_reorder, move_item};
+use crate::{DragAxis, DragConfig};
+use leptos_motion_layout::LayoutInfo;
+
+/// Three 50px tall rows stacked vertically
+fn rows() -> Vec<LayoutInfo> {
+    vec![
+        LayoutInfo::new(0.0, 0.0, 200.0, 50.0),
+        LayoutInfo::new(0.0, 50.0, 200.0, 50.0),
+        LayoutInfo::new(0.0, 100.0, 200.0, 50.0),
+    ]
+}
+
+#[test]
+fn test_reorder_waits_for_midpoint() {
+    // The next row's midpoint is at 75px, the dragged bottom edge at 50px
+    assert_eq!(check_reorder(&rows(), 0, 20.0, 300.0, &DragAxis::Y), None);
+    assert_eq!(
+        check_reorder(&rows(), 0, 30.0, 300.0, &DragAxis::Y),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_reorder_upwards_uses_leading_edge() {
+    // The previous row's midpoint is at 75px, the dragged top edge at 100px
+    assert_eq!(check_reorder(&rows(), 2, -20.0, -300.0, &DragAxis::Y), None);
+    assert_eq!(
+        check_reorder(&rows(), 2, -30.0, -300.0, &DragAxis::Y),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_reorder_follows_velocity_direction() {
+    // Past the next midpoint but moving back up: no reorder downwards
+    assert_eq!(check_reorder(&rows(), 0, 30.0, -300.0, &DragAxis::Y), None);
+    assert_eq!(check_reorder(&rows(), 1, 30.0, 0.0, &DragAxis::Y), None);
+}
+
+#[test]
+fn test_reorder_stops_at_list_ends() {
+    assert_eq!(
+        check_reorder(&rows(), 0, -200.0, -300.0, &DragAxis::Y),
+        None
+    );
+    assert_eq!(check_reorder(&rows(), 2, 200.0, 300.0, &DragAxis::Y), None);
+    assert_eq!(check_reorder(&rows(), 5, 200.0, 300.0, &DragAxis::Y), None);
+}
+
+#[test]
+fn test_reorder_horizontal_axis() {
+    let columns = vec![
+        LayoutInfo::new(0.0, 0.0, 80.0, 40.0),
+        LayoutInfo::new(80.0, 0.0, 120.0, 40.0),
+    ];
+
+    // The next column's midpoint is at 140px, the dragged right edge at 80px
+    assert_eq!(check_reorder(&columns, 0, 50.0, 300.0, &DragAxis::X), None);
+    assert_eq!(
+        check_reorder(&columns, 0, 70.0, 300.0, &DragAxis::X),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_move_item() {
+    let mut items = vec!["a", "b", "c", "d"];
+    move_item(&mut items, 0, 2);
+    assert_eq!(items, vec!["b", "c", "a", "d"]);
+
+    move_item(&mut items, 3, 0);
+    assert_eq!(items, vec!["d", "b", "c", "a"]);
+
+    // Out of range moves are ignored
+    move_item(&mut items, 1, 9);
+    assert_eq!(items, vec!["d", "b", "c", "a"]);
+}
+
+#[test]
+fn test_session_rebase_keeps_item_under_pointer() {
+    let config = DragConfig {
+        axis: Some(DragAxis::Y),
+        constraints: None,
+        elastic: None,
+        momentum: None,
+    };
+    let mut session = DragSession::start(
+        &config,
+        &DragOptions::default(),
+        None,
+        (0.0, 0.0),
+        (0.0, 0.0),
+        0.0,
+    );
+    assert_eq!(session.update((0.0, 60.0), 16.0), (0.0, 60.0));
+
+    // The item moved 50px down in the document, so its offset shrinks by 50px
+    session.rebase((0.0, 50.0));
+    assert_eq!(session.position(), (0.0, 10.0));
+    assert_eq!(session.update((0.0, 70.0), 32.0), (0.0, 20.0));
+}
@@ -0,0 +1,372 @@
+//! Transitions between `leptos_router` routes
+//!
+//! [`RouteTransitions`] goes around the `<Routes>` of a router and each
+//! route view is wrapped in a [`RoutePage`], which enters and exits through
+//! [`AnimatePresence`]. When a page leaves, its shared elements are
+//! measured: motion elements with a `layout_id` and plain elements tagged
+//! with [`LAYOUT_ID_ATTRIBUTE`]. Their counterparts on the next page then
+//! animate from there, by default flying in an overlay above both pages
+//! ([`ZIndexStrategy::Elevate`]).
+//!
+//! ```rust,ignore
+//! <Router>
+//!     <RouteTransitions>
+//!         <Routes fallback=|| "Not found">
+//!             <Route path=path!("/") view=|| view! { <RoutePage><Gallery/></RoutePage> }/>
+//!             <Route path=path!("/photo/:id") view=|| view! { <RoutePage><Photo/></RoutePage> }/>
+//!         </Routes>
+//!     </RouteTransitions>
+//! </Router>
+//! ```
+//!
+//! With a [`ViewTransitionEngine`], link navigations run through
+//! `document.startViewTransition` instead: the browser crossfades the pages
+//! and morphs the tagged elements, and pages swap without presence.
+
+use crate::animate_presence::{AnimatePresence, PresenceMode};
+use crate::components::MotionDiv;
+use crate::motion_element::html_element;
+use crate::shared_layout::{play_shared_layout, track_crossfade, with_shared_elements};
+use leptos::prelude::*;
+use leptos_motion_core::{AnimationTarget, AnimationValue, Transition};
+use leptos_motion_layout::{
+    Crossfade, LAYOUT_ID_ATTRIBUTE, SharedElementConfig, ViewTransitionEngine, ZIndexStrategy,
+    element_layout,
+};
+use leptos_router::NavigateOptions;
+use leptos_router::hooks::{use_location, use_navigate};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+/// How long the shared elements of a leaving page stay available to the
+/// next page, in milliseconds
+///
+/// Covers routes that load their data before rendering shared elements.
+pub const ROUTE_SNAPSHOT_LIFETIME_MS: f64 = 1000.0;
+
+/// Page and shared element transitions of a [`RouteTransitions`]
+#[derive(Debug, Clone)]
+struct RouteTransitionConfig {
+    initial: AnimationTarget,
+    animate: AnimationTarget,
+    exit: AnimationTarget,
+    transition: Transition,
+    shared: SharedElementConfig,
+    z_index: ZIndexStrategy,
+}
+
+impl Default for RouteTransitionConfig {
+    fn default() -> Self {
+        Self {
+            initial: opacity(0.0),
+            animate: opacity(1.0),
+            exit: opacity(0.0),
+            transition: Transition::default(),
+            shared: SharedElementConfig::default(),
+            z_index: ZIndexStrategy::Elevate,
+        }
+    }
+}
+
+/// Target setting only the opacity
+fn opacity(value: f64) -> AnimationTarget {
+    AnimationTarget::from([("opacity".to_string(), AnimationValue::Number(value))])
+}
+
+/// Context provided by [`RouteTransitions`] to its pages
+#[derive(Clone, Copy)]
+struct RouteTransitionContext {
+    config: StoredValue<RouteTransitionConfig, LocalStorage>,
+    /// Set while a navigation runs through the view transition engine,
+    /// which animates the shared elements itself
+    view_transition: StoredValue<bool, LocalStorage>,
+}
+
+/// Animates navigations between the [`RoutePage`]s inside it
+///
+/// Pages fade by default. With [`PresenceMode::PopLayout`], the default,
+/// the leaving page is taken out of the layout so shared elements are
+/// measured at their final place on the next page.
+#[component]
+pub fn RouteTransitions(
+    /// How the next page enters while the previous one exits
+    #[prop(optional)]
+    mode: Option<PresenceMode>,
+    /// State pages enter from (defaults to transparent)
+    #[prop(optional)]
+    initial: Option<AnimationTarget>,
+    /// State pages enter to (defaults to opaque)
+    #[prop(optional)]
+    animate: Option<AnimationTarget>,
+    /// State pages exit to (defaults to transparent)
+    #[prop(optional)]
+    exit: Option<AnimationTarget>,
+    /// Transition of pages entering and exiting
+    #[prop(optional)]
+    transition: Option<Transition>,
+    /// Transition of tagged shared elements without a registered configuration
+    #[prop(optional)]
+    shared: Option<SharedElementConfig>,
+    /// Stacking of shared elements in flight (defaults to [`ZIndexStrategy::Elevate`])
+    #[prop(optional)]
+    z_index: Option<ZIndexStrategy>,
+    /// Run link navigations as view transitions of the tagged elements
+    ///
+    /// Browsers without view transitions get the engine's FLIP fallback.
+    /// Pages then swap without their enter and exit animations.
+    #[prop(optional)]
+    view_transitions: Option<ViewTransitionEngine>,
+    /// Routes, usually a `<Routes>`
+    children: Children,
+) -> impl IntoView {
+    let defaults = RouteTransitionConfig::default();
+    let config = RouteTransitionConfig {
+        initial: initial.unwrap_or(defaults.initial),
+        animate: animate.unwrap_or(defaults.animate),
+        exit: exit.unwrap_or(defaults.exit),
+        transition: transition.unwrap_or(defaults.transition),
+        shared: shared.unwrap_or(defaults.shared),
+        z_index: z_index.unwrap_or(defaults.z_index),
+    };
+    let context = RouteTransitionContext {
+        config: StoredValue::new_local(config),
+        view_transition: StoredValue::new_local(false),
+    };
+    provide_context(context);
+
+    match view_transitions {
+        Some(engine) => {
+            intercept_links(engine, context);
+            children().into_any()
+        }
+        None => view! {
+            <AnimatePresence mode=mode.unwrap_or(PresenceMode::PopLayout)>
+                {children()}
+            </AnimatePresence>
+        }
+        .into_any(),
+    }
+}
+
+/// Run the router's link navigations inside view transitions of `engine`
+///
+/// Listens for clicks in the capture phase, ahead of the router, and
+/// navigates from inside [`ViewTransitionEngine::start`] instead.
+fn intercept_links(engine: ViewTransitionEngine, context: RouteTransitionContext) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let navigate = use_navigate();
+    let click = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+        let Some(path) = router_link_path(&event) else {
+            return;
+        };
+        event.prevent_default();
+        let update = {
+            let navigate = navigate.clone();
+            let path = path.clone();
+            move || {
+                context.view_transition.try_set_value(true);
+                navigate(&path, NavigateOptions::default());
+            }
+        };
+        if engine.start(update).is_err() {
+            navigate(&path, NavigateOptions::default());
+        }
+    }) as Box<dyn FnMut(web_sys::MouseEvent)>);
+    let _ = window.add_event_listener_with_callback_and_bool(
+        "click",
+        click.as_ref().unchecked_ref(),
+        true,
+    );
+
+    let listener = StoredValue::new_local(Some((window, click)));
+    on_cleanup(move || {
+        listener.try_update_value(|listener| {
+            if let Some((window, click)) = listener.take() {
+                let _ = window.remove_event_listener_with_callback_and_bool(
+                    "click",
+                    click.as_ref().unchecked_ref(),
+                    true,
+                );
+            }
+        });
+    });
+}
+
+/// Path of the same-origin link a plain left click navigates the router to
+fn router_link_path(event: &web_sys::MouseEvent) -> Option<String> {
+    if event.default_prevented()
+        || event.button() != 0
+        || event.meta_key()
+        || event.alt_key()
+        || event.ctrl_key()
+        || event.shift_key()
+    {
+        return None;
+    }
+    let anchor = event
+        .target()?
+        .dyn_into::<web_sys::Element>()
+        .ok()?
+        .closest("a[href]")
+        .ok()??
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .ok()?;
+    let external = anchor
+        .get_attribute("rel")
+        .is_some_and(|rel| rel.split_whitespace().any(|rel| rel == "external"));
+    if !anchor.target().is_empty() || anchor.has_attribute("download") || external {
+        return None;
+    }
+    let origin = web_sys::window()?.location().origin().ok()?;
+    (anchor.origin() == origin)
+        .then(|| format!("{}{}{}", anchor.pathname(), anchor.search(), anchor.hash()))
+}
+
+/// Page of a route inside a [`RouteTransitions`]
+///
+/// Enters and exits with the page transition. Shared elements of the
+/// previous page animate into their counterparts on this page once it
+/// mounts, and this page's are measured when it leaves.
+#[component]
+pub fn RoutePage(
+    /// CSS class of the page
+    #[prop(optional)]
+    class: Option<String>,
+    /// Page content
+    children: Children,
+) -> impl IntoView {
+    let key = use_location().pathname.get_untracked();
+    let context = use_context::<RouteTransitionContext>();
+    let config = context
+        .map(|context| context.config.get_value())
+        .unwrap_or_default();
+    let node_ref = NodeRef::<leptos::html::Div>::new();
+
+    // Registered before the page's exit, which copies it for the presence
+    if let Some(context) = context {
+        on_cleanup(move || {
+            if context.view_transition.try_get_value().unwrap_or(false) {
+                return;
+            }
+            let (Some(page), Some(z_index)) = (
+                html_element(node_ref),
+                context
+                    .config
+                    .try_with_value(|config| config.z_index.clone()),
+            ) else {
+                return;
+            };
+            leave_page(&page, z_index);
+        });
+        let shared = config.shared.clone();
+        Effect::new(move |_| {
+            if let Some(page) = node_ref.get() {
+                let view_transition = context
+                    .view_transition
+                    .try_update_value(std::mem::take)
+                    .unwrap_or(false);
+                if !view_transition {
+                    enter_page(&page, &shared);
+                }
+            }
+        });
+    }
+
+    view! {
+        <MotionDiv
+            node_ref=node_ref
+            key=key
+            class=class.unwrap_or_default()
+            initial=config.initial
+            animate=config.animate
+            exit=config.exit
+            transition=config.transition
+        >
+            {children()}
+        </MotionDiv>
+    }
+}
+
+/// Elements inside `page` tagged with [`LAYOUT_ID_ATTRIBUTE`] and their ids
+fn tagged_elements(page: &web_sys::Element) -> Vec<(web_sys::Element, String)> {
+    let Ok(nodes) = page.query_selector_all(&format!("[{LAYOUT_ID_ATTRIBUTE}]")) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index)?.dyn_into::<web_sys::Element>().ok())
+        .filter_map(|element| {
+            let layout_id = element.get_attribute(LAYOUT_ID_ATTRIBUTE)?;
+            Some((element, layout_id))
+        })
+        .collect()
+}
+
+/// Measure the tagged elements of a leaving page and hold the shared layout snapshots
+///
+/// A page already detached has no boxes left to measure and is skipped.
+fn leave_page(page: &web_sys::Element, z_index: ZIndexStrategy) {
+    if !page.is_connected() {
+        return;
+    }
+    let now = js_sys::Date::now();
+    with_shared_elements(|manager| {
+        manager.hold_layout_snapshots(now, ROUTE_SNAPSHOT_LIFETIME_MS, z_index);
+        for (element, layout_id) in tagged_elements(page) {
+            manager.snapshot_layout_id(&layout_id, &element, element_layout(&element), now);
+        }
+    });
+}
+
+/// Animate the tagged elements of an entering page from their counterparts
+///
+/// Counterparts still in the page crossfade with them when the shared
+/// element configuration asks for a crossfade.
+fn enter_page(page: &web_sys::Element, shared: &SharedElementConfig) {
+    let now = js_sys::Date::now();
+    for (element, layout_id) in tagged_elements(page) {
+        let entering = with_shared_elements(|manager| {
+            let enter = manager.enter_layout_id(&layout_id, now)?;
+            Some((enter, manager.element_config(&element).cloned()))
+        });
+        let Some((enter, config)) = entering else {
+            continue;
+        };
+        let config = config.unwrap_or_else(|| shared.clone());
+        if config.crossfade.is_some()
+            && let Some(previous) = &enter.crossfade
+            && let Ok(crossfade) = Crossfade::start(
+                previous,
+                &element,
+                &enter.from,
+                &element_layout(&element),
+                &config,
+            )
+        {
+            track_crossfade(&layout_id, crossfade);
+            continue;
+        }
+        play_shared_layout(&layout_id, &element, enter, &Transition::from(&config));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_transitions_fade_and_elevate_by_default() {
+        let config = RouteTransitionConfig::default();
+        assert_eq!(
+            config.initial.get("opacity"),
+            Some(&AnimationValue::Number(0.0))
+        );
+        assert_eq!(
+            config.animate.get("opacity"),
+            Some(&AnimationValue::Number(1.0))
+        );
+        assert_eq!(config.exit, config.initial);
+        assert_eq!(config.z_index, ZIndexStrategy::Elevate);
+    }
+}
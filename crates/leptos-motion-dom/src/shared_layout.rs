@@ -0,0 +1,423 @@
+//! Shared layout animations
+//!
+//! Motion elements with the same `layout_id` are treated as one element
+//! moving between places in the page: when one mounts while another is
+//! leading, or right after another unmounted, it animates from the other's
+//! box into its own and the two are crossfaded. The bookkeeping lives in a
+//! [`SharedElementManager`] shared by the whole page.
+//!
+//! Leads entering with [`ZIndexStrategy::Elevate`] stay hidden while a copy
+//! flies in an overlay layer above the page, landing on them.
+
+use crate::layout_animation::is_debug_overlay_attached;
+use crate::motion_element::html_element;
+use crate::utils::{
+    is_current_animation, next_animation_generation, remember_style, restore_style,
+};
+use leptos::html::ElementType;
+use leptos::prelude::{Effect, Get, NodeRef, on_cleanup};
+use leptos_motion_core::{Transition, run_frames};
+use leptos_motion_layout::{
+    AnimationSnapshot, Crossfade, InlineStyles, LayoutInfo, ScaleCorrection, SharedElementConfig,
+    SharedElementManager, SharedLayoutEnter, TransformValues, TransitionPriority, ZIndexStrategy,
+    element_layout, elevated_copy, exclude_from_transition, shared_layout_at,
+    shared_layout_transform, transform_css_at,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+/// Property of an element tracking its latest shared layout animation
+const LAYOUT_ANIMATION_KEY: &str = "__motionLayout";
+/// Property of an element holding its own opacity while it is faded by a shared layout
+const LAYOUT_OPACITY_KEY: &str = "__motionLayoutOpacity";
+/// Property of an element holding its own visibility while an elevated copy flies to it
+const LAYOUT_VISIBILITY_KEY: &str = "__motionLayoutVisibility";
+
+thread_local! {
+    static SHARED_ELEMENTS: RefCell<SharedElementManager> =
+        RefCell::new(SharedElementManager::default());
+    /// Styles of leads under scale correction, restored when their animation ends
+    static CORRECTED_STYLES: RefCell<Vec<(web_sys::Element, InlineStyles)>> =
+        const { RefCell::new(Vec::new()) };
+    /// Shared layout animations playing while the layout debug overlay is attached
+    static RUNNING_LAYOUTS: RefCell<Vec<RunningLayout>> = const { RefCell::new(Vec::new()) };
+    /// Crossfades of shared elements in route transitions while the layout debug overlay is attached
+    static RUNNING_CROSSFADES: RefCell<Vec<(String, Crossfade)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Shared layout animation of a lead, as far as it has played
+struct RunningLayout {
+    layout_id: String,
+    element: web_sys::Element,
+    generation: f64,
+    from: LayoutInfo,
+    to: LayoutInfo,
+    transform: TransformValues,
+    correction: Option<ScaleCorrection>,
+    /// Eased progress of the last frame, 1 once it landed
+    progress: Rc<Cell<f64>>,
+}
+
+impl RunningLayout {
+    fn is_running(&self) -> bool {
+        self.progress.get() < 1.0
+            && is_current_animation(&self.element, LAYOUT_ANIMATION_KEY, self.generation)
+    }
+
+    fn snapshot(&self) -> AnimationSnapshot {
+        let progress = self.progress.get();
+        let current = shared_layout_at(&self.to, &self.transform, progress);
+        let mut snapshot = AnimationSnapshot::between(
+            &self.layout_id,
+            self.from.clone(),
+            self.to.clone(),
+            &current,
+        );
+        if let Some(correction) = &self.correction {
+            snapshot.corrections =
+                correction.styles(snapshot.transform.scale_x, snapshot.transform.scale_y);
+        }
+        snapshot
+    }
+}
+
+/// Let the layout debug overlay draw the crossfade of `layout_id` while it flies
+#[cfg(feature = "router")]
+pub(crate) fn track_crossfade(layout_id: &str, crossfade: Crossfade) {
+    if !is_debug_overlay_attached() {
+        return;
+    }
+    RUNNING_CROSSFADES.with(|running| {
+        let mut running = running.borrow_mut();
+        running.retain(|(_, crossfade)| crossfade.is_running());
+        running.push((layout_id.to_string(), crossfade));
+    });
+}
+
+/// Drop the shared layout animations recorded for a detached overlay
+pub(crate) fn forget_shared_layout_snapshots() {
+    RUNNING_LAYOUTS.with(|running| running.borrow_mut().clear());
+    RUNNING_CROSSFADES.with(|running| running.borrow_mut().clear());
+}
+
+/// Snapshots of the shared layout animations and crossfades playing
+pub(crate) fn shared_layout_snapshots() -> Vec<AnimationSnapshot> {
+    let mut snapshots: Vec<AnimationSnapshot> = RUNNING_LAYOUTS.with(|running| {
+        let mut running = running.borrow_mut();
+        running.retain(RunningLayout::is_running);
+        running.iter().map(RunningLayout::snapshot).collect()
+    });
+    RUNNING_CROSSFADES.with(|running| {
+        let mut running = running.borrow_mut();
+        running.retain(|(_, crossfade)| crossfade.is_running());
+        snapshots.extend(running.iter().filter_map(|(layout_id, crossfade)| {
+            let (from, to, current) = crossfade.layouts()?;
+            Some(AnimationSnapshot::between(layout_id, from, to, &current))
+        }));
+    });
+    snapshots
+}
+
+/// Restore the styles an earlier shared layout animation of `element` corrected
+fn restore_corrected_styles(element: &web_sys::Element) {
+    CORRECTED_STYLES.with(|corrected| {
+        corrected.borrow_mut().retain(|(corrected, styles)| {
+            if corrected == element {
+                styles.restore();
+            }
+            corrected != element
+        })
+    });
+}
+
+/// Animate the element of `node_ref` between the places of its `layout_id`
+pub(crate) fn use_layout_id<E>(
+    node_ref: NodeRef<E>,
+    layout_id: Option<String>,
+    transition: Transition,
+) where
+    E: ElementType,
+    E::Output: JsCast + Clone + 'static,
+{
+    let Some(layout_id) = layout_id else {
+        return;
+    };
+
+    let mount_id = layout_id.clone();
+    let mount_transition = transition.clone();
+    let mounted = Cell::new(false);
+    Effect::new(move |_| {
+        let Some(element) = node_ref.get() else {
+            return;
+        };
+        if mounted.replace(true) {
+            return;
+        }
+        let element: web_sys::Element = element.unchecked_into();
+        let enter = SHARED_ELEMENTS.with(|manager| {
+            manager
+                .borrow_mut()
+                .mount_layout_id(&mount_id, &element, js_sys::Date::now())
+        });
+        if let Some(enter) = enter {
+            play_shared_layout(&mount_id, &element, enter, &mount_transition);
+        }
+    });
+
+    // Measure before Leptos unmounts the element
+    on_cleanup(move || {
+        let Some(element) = html_element(node_ref) else {
+            return;
+        };
+        // A detached element has no box left to hand on
+        let layout = element.is_connected().then(|| element_layout(&element));
+        let resumed = SHARED_ELEMENTS.with(|manager| {
+            manager.borrow_mut().unmount_layout_id(
+                &layout_id,
+                &element,
+                layout,
+                js_sys::Date::now(),
+            )
+        });
+        if let Some((lead, enter)) = resumed {
+            play_shared_layout(&layout_id, &lead, enter, &transition);
+        }
+    });
+}
+
+/// Let the presence ghost of an exiting element stand in for its `layout_id`
+pub(crate) fn replace_layout_element(element: &web_sys::Element, ghost: &web_sys::Element) {
+    SHARED_ELEMENTS.with(|manager| manager.borrow_mut().replace_layout_element(element, ghost));
+}
+
+/// Run `f` with the page's shared element manager
+pub(crate) fn with_shared_elements<R>(f: impl FnOnce(&mut SharedElementManager) -> R) -> R {
+    SHARED_ELEMENTS.with(|manager| f(&mut manager.borrow_mut()))
+}
+
+/// Animate `element` with `config` when it enters as a shared element
+///
+/// Applies to elements tagged with [`LAYOUT_ID_ATTRIBUTE`] in route
+/// transitions, which otherwise share the transition of the routes.
+///
+/// [`LAYOUT_ID_ATTRIBUTE`]: leptos_motion_layout::LAYOUT_ID_ATTRIBUTE
+pub fn register_shared_element(element: &web_sys::Element, config: &SharedElementConfig) {
+    with_shared_elements(|manager| {
+        let _ = manager.register_element(element, config);
+    });
+}
+
+/// Forget the configuration of an element registered with [`register_shared_element`]
+pub fn unregister_shared_element(element: &web_sys::Element) {
+    with_shared_elements(|manager| {
+        let _ = manager.unregister_element(element);
+    });
+}
+
+/// Animate `element` into its layout as the lead of `layout_id`
+///
+/// The page's shared element manager decides how the animation treats one
+/// of `layout_id` that is still running, at the default priority: it may
+/// wait for it, play back for the time it ran or be skipped.
+pub(crate) fn play_shared_layout(
+    layout_id: &str,
+    element: &web_sys::Element,
+    enter: SharedLayoutEnter,
+    transition: &Transition,
+) {
+    let duration_ms = transition.effective_duration().unwrap_or(0.0) * 1000.0;
+    let delay_ms = transition.delay.unwrap_or(0.0) * 1000.0;
+    let slot = with_shared_elements(|manager| {
+        manager.schedule_layout_animation(
+            layout_id,
+            js_sys::Date::now() + delay_ms,
+            duration_ms,
+            TransitionPriority::default(),
+        )
+    });
+    let transition = Transition {
+        duration: Some(slot.duration / 1000.0),
+        ..transition.clone()
+    };
+    if slot.delay <= 0.0 {
+        animate_shared_layout(layout_id, element, enter, &transition);
+        return;
+    }
+
+    // Queued behind the running animation, which keeps its elements until then
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let (layout_id, element) = (layout_id.to_string(), element.clone());
+    let start = Closure::once_into_js(move || {
+        animate_shared_layout(&layout_id, &element, enter, &transition)
+    });
+    let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        start.unchecked_ref(),
+        slot.delay as i32,
+    );
+}
+
+/// Animate `element` from `enter.from` into its layout, crossfading the previous element
+///
+/// The previous element follows the lead into its box and stays hidden
+/// afterwards if it remains mounted, until it leads again.
+fn animate_shared_layout(
+    layout_id: &str,
+    element: &web_sys::Element,
+    enter: SharedLayoutEnter,
+    transition: &Transition,
+) {
+    let lead: web_sys::HtmlElement = element.clone().unchecked_into();
+    let generation = next_animation_generation(element, LAYOUT_ANIMATION_KEY);
+    restore_corrected_styles(element);
+    restore_style(&lead, LAYOUT_VISIBILITY_KEY, "visibility");
+    let _ = lead.style().remove_property("transform");
+    let to = element_layout(element);
+    let transform = shared_layout_transform(&enter.from, &to);
+
+    // An elevated lead stays hidden while its copy flies in the overlay layer
+    let copy = if enter.z_index == ZIndexStrategy::Elevate {
+        elevated_copy(element, &to)
+    } else {
+        None
+    };
+    if copy.is_some() {
+        remember_style(&lead, LAYOUT_VISIBILITY_KEY, "visibility");
+        let _ = lead.style().set_property("visibility", "hidden");
+    }
+    let flying = copy.clone().unwrap_or_else(|| lead.clone());
+    // Frames set every step, so property transitions would only lag behind
+    exclude_from_transition(&flying, &["transform", "opacity"]);
+
+    // Keep corners, shadow and layout children from stretching with the scale
+    let correction = if transform.scale_x != 1.0 || transform.scale_y != 1.0 {
+        let correction = ScaleCorrection::measure(&flying);
+        // The copy is discarded when it lands, so only the lead needs its styles back
+        if copy.is_none() {
+            let styles = InlineStyles::save(element, &correction);
+            CORRECTED_STYLES
+                .with(|corrected| corrected.borrow_mut().push((element.clone(), styles)));
+        }
+        Some(correction)
+    } else {
+        None
+    };
+    if enter.fade_in && copy.is_none() {
+        remember_style(&lead, LAYOUT_OPACITY_KEY, "opacity");
+    }
+    let crossfade = enter.crossfade.map(|previous| {
+        let previous_generation = next_animation_generation(&previous, LAYOUT_ANIMATION_KEY);
+        let previous: web_sys::HtmlElement = previous.unchecked_into();
+        remember_style(&previous, LAYOUT_OPACITY_KEY, "opacity");
+        exclude_from_transition(&previous, &["transform", "opacity"]);
+        // The previous element is laid out at `from` and follows the lead to `to`
+        (
+            previous,
+            previous_generation,
+            shared_layout_transform(&to, &enter.from),
+        )
+    });
+
+    if web_sys::window().is_none() {
+        return;
+    }
+    let duration_ms = transition.effective_duration().unwrap_or(0.0) * 1000.0;
+    let delay_ms = transition.delay.unwrap_or(0.0) * 1000.0;
+    let ease = transition.ease.clone();
+    let fade_in = enter.fade_in;
+    let landing_copy = copy.clone();
+    let dropped = crossfade.clone();
+    let played = Rc::new(Cell::new(0.0));
+    if is_debug_overlay_attached() {
+        RUNNING_LAYOUTS.with(|running| {
+            let mut running = running.borrow_mut();
+            running.retain(RunningLayout::is_running);
+            running.push(RunningLayout {
+                layout_id: layout_id.to_string(),
+                element: element.clone(),
+                generation,
+                from: enter.from.clone(),
+                to: to.clone(),
+                transform: transform.clone(),
+                correction: correction.clone(),
+                progress: played.clone(),
+            });
+        });
+    }
+    let apply = move |progress: f64| {
+        let style = flying.style();
+        let eased = ease.evaluate(progress);
+        played.set(if progress >= 1.0 { 1.0 } else { eased });
+        if progress >= 1.0 {
+            if let Some(copy) = &landing_copy {
+                copy.remove();
+                restore_style(&lead, LAYOUT_VISIBILITY_KEY, "visibility");
+            } else {
+                let _ = style.remove_property("transform");
+                restore_corrected_styles(&lead);
+                if fade_in {
+                    restore_style(&lead, LAYOUT_OPACITY_KEY, "opacity");
+                }
+            }
+        } else {
+            let _ = style.set_property("transform", &transform_css_at(&transform, eased));
+            if let Some(correction) = &correction {
+                let remaining = 1.0 - eased;
+                correction.apply(
+                    &flying,
+                    1.0 + (transform.scale_x - 1.0) * remaining,
+                    1.0 + (transform.scale_y - 1.0) * remaining,
+                );
+            }
+            if fade_in {
+                let _ = style.set_property("opacity", &eased.to_string());
+            }
+        }
+
+        if let Some((previous, previous_generation, previous_transform)) = &crossfade
+            && is_current_animation(previous, LAYOUT_ANIMATION_KEY, *previous_generation)
+        {
+            let style = previous.style();
+            if progress >= 1.0 {
+                let _ = style.remove_property("transform");
+            } else {
+                let transform = transform_css_at(previous_transform, 1.0 - eased);
+                let _ = style.set_property("transform", &transform);
+            }
+            let _ = style.set_property("opacity", &(1.0 - eased).to_string());
+        }
+    };
+    apply(0.0);
+
+    let frame_element = element.clone();
+    let mut start = None::<f64>;
+    run_frames(move |timestamp| {
+        if !is_current_animation(&frame_element, LAYOUT_ANIMATION_KEY, generation) {
+            // The animation taking over restores the lead itself
+            if let Some(copy) = &copy {
+                copy.remove();
+            }
+            // A previous element it leaves behind doesn't stay half faded
+            if let Some((previous, previous_generation, _)) = &dropped
+                && is_current_animation(previous, LAYOUT_ANIMATION_KEY, *previous_generation)
+            {
+                let style = previous.style();
+                let _ = style.remove_property("transform");
+                let _ = style.set_property("opacity", "0");
+            }
+            return false;
+        }
+        let start = *start.get_or_insert(timestamp + delay_ms);
+        let progress = if duration_ms > 0.0 {
+            ((timestamp - start) / duration_ms).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        apply(progress);
+        progress < 1.0
+    });
+}
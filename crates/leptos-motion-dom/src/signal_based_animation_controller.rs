@@ -2,6 +2,10 @@
 //!
 //! This module implements the proven signal-based animation patterns from our guide.
 //! It provides proper signal tracking, WASM memory management, and effect dependencies.
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use leptos::prelude::*;
 use leptos_motion_core::types::AnimationValue;
@@ -205,6 +209,7 @@ pub fn animation_value_to_css(value: &AnimationValue) -> String {
         AnimationValue::Radians(r) => format!("{}rad", r),
         AnimationValue::Transform(t) => format!("transform({:?})", t),
         AnimationValue::Complex(c) => format!("complex({:?})", c),
+        AnimationValue::Auto => "auto".to_string(),
     }
 }
 
@@ -294,6 +294,7 @@ pub fn animation_value_to_css(value: &AnimationValue) -> String {
         AnimationValue::String(val) => val.clone(),
         AnimationValue::Transform(_) => "matrix(1,0,0,1,0,0)".to_string(),
         AnimationValue::Complex(_) => "0".to_string(),
+        AnimationValue::Auto => "auto".to_string(),
     }
 }
 
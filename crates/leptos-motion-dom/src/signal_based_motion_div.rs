@@ -2,6 +2,10 @@
 //!
 //! This component implements the proven patterns from the user's guide for
 //! proper signal tracking, WASM memory management, and effect dependencies.
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use crate::signal_based_controller::*;
 use leptos::prelude::*;
@@ -3,6 +3,10 @@
 //! This component implements the proven patterns from the user's guide for
 //! proper signal tracking, WASM memory management, and effect dependencies.
 //! Simplified version without complex WASM bindings.
+//!
+//! Superseded by [`MotionDiv`](crate::MotionDiv).
+
+#![deprecated(note = "use MotionDiv")]
 
 use leptos::prelude::*;
 use leptos_motion_core::*;
@@ -74,6 +78,7 @@ pub fn animation_value_to_css(value: &AnimationValue) -> String {
         AnimationValue::String(val) => val.clone(),
         AnimationValue::Transform(_) => "matrix(1,0,0,1,0,0)".to_string(),
         AnimationValue::Complex(_) => "0".to_string(),
+        AnimationValue::Auto => "auto".to_string(),
     }
 }
 
@@ -0,0 +1,66 @@
+//! SVG attribute animations
+//!
+//! CSS transitions can't reach SVG geometry such as `d`, `points` or
+//! `viewBox`, so motion elements tween those attributes on animation frames
+//! with an [`AttributeTween`] each.
+
+use crate::utils::{is_current_animation, next_animation_generation};
+use leptos_motion_core::svg::AttributeTween;
+use leptos_motion_core::{Transition, run_frames};
+
+/// Property of the element tracking the latest animation of an attribute
+fn generation_key(attribute: &str) -> String {
+    format!("__motionAttribute_{attribute}")
+}
+
+/// Animate `attributes` of `element` from their current values with `transition`
+///
+/// Attributes without a current value are set right away. A later call for
+/// the same attribute takes over from the running animation.
+pub(crate) fn animate_svg_attributes(
+    element: &web_sys::Element,
+    attributes: Vec<(String, String)>,
+    transition: &Transition,
+) {
+    let duration_ms = transition.effective_duration().unwrap_or(0.0) * 1000.0;
+    let delay_ms = transition.delay.unwrap_or(0.0) * 1000.0;
+    let has_window = web_sys::window().is_some();
+
+    let mut tweens = Vec::new();
+    for (attribute, to) in attributes {
+        let generation = next_animation_generation(element, &generation_key(&attribute));
+        match element.get_attribute(&attribute) {
+            Some(from) if from != to && duration_ms > 0.0 && has_window => {
+                let tween = AttributeTween::new(&attribute, &from, &to);
+                tweens.push((attribute, tween, generation))
+            }
+            _ => {
+                let _ = element.set_attribute(&attribute, &to);
+            }
+        }
+    }
+    if tweens.is_empty() {
+        return;
+    }
+
+    let element = element.clone();
+    let ease = transition.ease.clone();
+    let mut start = None::<f64>;
+    run_frames(move |timestamp| {
+        let start = *start.get_or_insert(timestamp + delay_ms);
+        let progress = ((timestamp - start) / duration_ms).clamp(0.0, 1.0);
+        let eased = ease.evaluate(progress);
+
+        let mut running = false;
+        for (attribute, tween, generation) in &tweens {
+            if !is_current_animation(&element, &generation_key(attribute), *generation) {
+                continue;
+            }
+            running = true;
+            let eased = if progress >= 1.0 { 1.0 } else { eased };
+            let _ = element.set_attribute(attribute, &tween.at(eased));
+        }
+
+        running && progress < 1.0
+    });
+}
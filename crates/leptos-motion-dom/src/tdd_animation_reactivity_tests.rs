@@ -8,7 +8,8 @@
 //! REFACTOR PHASE: Improve implementation while keeping tests green
 
 use crate::{
-    ReactiveMotionDivNew, animation_engine::AnimationEngine,
+    animation_engine::AnimationEngine,
+    reactive_motion_div::ReactiveMotionDiv as ReactiveMotionDivNew,
     signal_based_animation_controller::SignalBasedAnimationController,
 };
 use leptos::prelude::*;
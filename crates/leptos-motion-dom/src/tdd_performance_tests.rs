@@ -174,6 +174,9 @@ fn test_complex_animations_60fps_performance() {
                     AnimationValue::Complex(_) => {
                         // Simulate complex value processing
                     }
+                    AnimationValue::Auto => {
+                        // Simulate measuring the natural size
+                    }
                 }
             }
         }
@@ -443,6 +446,9 @@ fn test_concurrent_animations_60fps_performance() {
                     AnimationValue::Complex(_) => {
                         // Simulate complex value processing
                     }
+                    AnimationValue::Auto => {
+                        // Simulate measuring the natural size
+                    }
                 }
             }
         }
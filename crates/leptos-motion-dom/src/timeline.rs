@@ -4,7 +4,7 @@
 
 use leptos::prelude::*;
 use leptos::reactive::signal::signal;
-use leptos_motion_core::{AnimationTarget, Transition};
+use leptos_motion_core::{AnimationTarget, StaggerResolver, Transition};
 use std::collections::HashMap;
 
 /// Type alias for timeline hook return type
@@ -88,6 +88,21 @@ impl TimelineSequence {
         self.calculate_total_duration();
     }
 
+    /// Add steps that start staggered by `stagger`, on top of their own delays
+    pub fn add_staggered_steps(
+        &mut self,
+        steps: impl IntoIterator<Item = TimelineStep>,
+        stagger: &StaggerResolver,
+    ) {
+        let steps: Vec<TimelineStep> = steps.into_iter().collect();
+        let delays = stagger.delays(steps.len());
+        for (mut step, delay) in steps.into_iter().zip(delays) {
+            step.delay += delay;
+            self.steps.push(step);
+        }
+        self.calculate_total_duration();
+    }
+
     /// Calculate total duration based on steps
     fn calculate_total_duration(&mut self) {
         self.total_duration = self
@@ -542,6 +557,30 @@ mod tests {
         assert!(sequence.repeat);
     }
 
+    #[test]
+    fn test_staggered_steps() {
+        let mut sequence = TimelineSequence::new("cards".to_string());
+        let steps = (0..3).map(|index| {
+            TimelineStep::new(
+                format!("card{}", index),
+                leptos_motion_core::AnimationTarget::new(),
+                0.5,
+            )
+            .with_delay(0.1)
+        });
+        let stagger = StaggerResolver::new(leptos_motion_core::StaggerConfig {
+            delay: 0.2,
+            from: leptos_motion_core::StaggerFrom::Last,
+        });
+        sequence.add_staggered_steps(steps, &stagger);
+
+        let delays: Vec<f64> = sequence.steps.iter().map(|step| step.delay).collect();
+        assert!((delays[0] - 0.5).abs() < 1e-9);
+        assert!((delays[1] - 0.3).abs() < 1e-9);
+        assert!((delays[2] - 0.1).abs() < 1e-9);
+        assert!((sequence.total_duration - 1.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_timeline_player() {
         let mut sequence = TimelineSequence::new("player_test".to_string());
@@ -26,3 +26,57 @@ pub fn apply_css_properties(element: &HtmlElement, properties: &[(String, String
         let _ = style.set_property(property, value);
     }
 }
+
+/// Start a new animation tracked under `key` on `element`, superseding the running one
+///
+/// Animation loops check [`is_current_animation`] each frame and stop once
+/// another animation of the same key has started.
+pub(crate) fn next_animation_generation(element: &Element, key: &str) -> f64 {
+    let key = wasm_bindgen::JsValue::from_str(key);
+    let generation = js_sys::Reflect::get(element, &key)
+        .ok()
+        .and_then(|generation| generation.as_f64())
+        .unwrap_or(0.0)
+        + 1.0;
+    let _ = js_sys::Reflect::set(element, &key, &generation.into());
+    generation
+}
+
+/// Remember the inline `property` of `element` under `key`, unless already remembered
+pub(crate) fn remember_style(element: &HtmlElement, key: &str, property: &str) {
+    let key = wasm_bindgen::JsValue::from_str(key);
+    if js_sys::Reflect::has(element, &key).unwrap_or(false) {
+        return;
+    }
+    let value = element
+        .style()
+        .get_property_value(property)
+        .unwrap_or_default();
+    let _ = js_sys::Reflect::set(element, &key, &wasm_bindgen::JsValue::from_str(&value));
+}
+
+/// Put back the inline `property` saved by [`remember_style`]
+pub(crate) fn restore_style(element: &HtmlElement, key: &str, property: &str) {
+    let key = wasm_bindgen::JsValue::from_str(key);
+    let Some(value) = js_sys::Reflect::get(element, &key)
+        .ok()
+        .and_then(|value| value.as_string())
+    else {
+        return;
+    };
+    let _ = js_sys::Reflect::delete_property(element, &key);
+    let style = element.style();
+    let _ = if value.is_empty() {
+        style.remove_property(property).map(|_| ())
+    } else {
+        style.set_property(property, &value)
+    };
+}
+
+/// Whether `generation` is still the latest animation tracked under `key`
+pub(crate) fn is_current_animation(element: &Element, key: &str, generation: f64) -> bool {
+    js_sys::Reflect::get(element, &wasm_bindgen::JsValue::from_str(key))
+        .ok()
+        .and_then(|current| current.as_f64())
+        == Some(generation)
+}
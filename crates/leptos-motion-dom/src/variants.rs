@@ -4,8 +4,11 @@
 
 use leptos::prelude::*;
 use leptos::reactive::signal::signal;
-use leptos_motion_core::{AnimationTarget, Transition};
+use leptos_motion_core::{AnimationTarget, Easing, StaggerConfig, StaggerResolver, Transition};
+use std::any::Any;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
 /// Type alias for variants hook return type
 type UseVariantsReturn = (
@@ -15,10 +18,61 @@ type UseVariantsReturn = (
     WriteSignal<HashMap<String, AnimationTarget>>,
 );
 
+/// Per-element data passed to dynamic variants, e.g. a swipe direction
+#[derive(Clone)]
+pub struct Custom(Arc<dyn Any + Send + Sync>);
+
+impl Custom {
+    /// Wrap `value` as custom data
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// The wrapped value, if it is a `T`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Debug for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Custom").finish_non_exhaustive()
+    }
+}
+
+/// Resolves a dynamic variant from custom data
+type ResolveVariant = dyn Fn(&Custom) -> Option<AnimationTarget> + Send + Sync;
+
+/// Variant resolved from custom data when it is applied
+#[derive(Clone)]
+struct DynamicVariant(Arc<ResolveVariant>);
+
+impl fmt::Debug for DynamicVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DynamicVariant")
+    }
+}
+
+/// Report custom data that doesn't match the type dynamic variant `name` expects
+fn warn_custom_type<C>(name: &str) {
+    #[cfg(target_arch = "wasm32")]
+    web_sys::console::warn_1(
+        &format!(
+            "Variant `{}` expects custom data of type `{}`",
+            name,
+            std::any::type_name::<C>()
+        )
+        .into(),
+    );
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = (name, std::any::type_name::<C>());
+}
+
 /// Variants manager for named animation states
 #[derive(Debug, Clone)]
 pub struct Variants {
     states: HashMap<String, AnimationTarget>,
+    dynamic_states: HashMap<String, DynamicVariant>,
     current_state: Option<String>,
 }
 
@@ -27,18 +81,52 @@ impl Variants {
     pub fn new() -> Self {
         Self {
             states: HashMap::new(),
+            dynamic_states: HashMap::new(),
             current_state: None,
         }
     }
 
     /// Add a variant with the given name and animation target
     pub fn add_variant(&mut self, name: String, target: AnimationTarget) {
+        self.dynamic_states.remove(&name);
         self.states.insert(name, target);
     }
 
+    /// Add a variant computed from custom data of type `C` when it is applied
+    ///
+    /// The variant resolves to nothing without custom data, and warns in the
+    /// browser console when the custom data is of another type.
+    pub fn add_dynamic_variant<C, F>(&mut self, name: String, variant: F)
+    where
+        C: Any,
+        F: Fn(&C) -> AnimationTarget + Send + Sync + 'static,
+    {
+        self.states.remove(&name);
+        let variant_name = name.clone();
+        self.dynamic_states.insert(
+            name,
+            DynamicVariant(Arc::new(move |custom: &Custom| {
+                let target = custom.downcast_ref::<C>().map(&variant);
+                if target.is_none() {
+                    warn_custom_type::<C>(&variant_name);
+                }
+                target
+            })),
+        );
+    }
+
+    /// Resolve the variant `name`, passing `custom` to dynamic variants
+    pub fn resolve_variant(&self, name: &str, custom: Option<&Custom>) -> Option<AnimationTarget> {
+        if let Some(target) = self.states.get(name) {
+            return Some(target.clone());
+        }
+        let variant = self.dynamic_states.get(name)?;
+        (variant.0)(custom?)
+    }
+
     /// Set the current variant
     pub fn set_variant(&mut self, name: &str) -> bool {
-        if self.states.contains_key(name) {
+        if self.has_variant(name) {
             self.current_state = Some(name.to_string());
             true
         } else {
@@ -52,6 +140,9 @@ impl Variants {
     }
 
     /// Get the current animation target
+    ///
+    /// Only static variants are returned; see
+    /// [`resolve_current_animation_target`](Self::resolve_current_animation_target).
     pub fn current_animation_target(&self) -> Option<&AnimationTarget> {
         if let Some(ref state) = self.current_state {
             self.states.get(state)
@@ -60,43 +151,58 @@ impl Variants {
         }
     }
 
-    /// Remove a variant
+    /// Resolve the current animation target, passing `custom` to dynamic variants
+    pub fn resolve_current_animation_target(
+        &self,
+        custom: Option<&Custom>,
+    ) -> Option<AnimationTarget> {
+        let state = self.current_state.as_ref()?;
+        self.resolve_variant(state, custom)
+    }
+
+    /// Remove a variant, returning its target if it was static
     pub fn remove_variant(&mut self, name: &str) -> Option<AnimationTarget> {
         if self.current_state.as_ref() == Some(&name.to_string()) {
             self.current_state = None;
         }
+        self.dynamic_states.remove(name);
         self.states.remove(name)
     }
 
     /// Clear all variants
     pub fn clear_variants(&mut self) {
         self.states.clear();
+        self.dynamic_states.clear();
         self.current_state = None;
     }
 
     /// Get variant names
     pub fn variant_names(&self) -> Vec<String> {
-        self.states.keys().cloned().collect()
+        self.states
+            .keys()
+            .chain(self.dynamic_states.keys())
+            .cloned()
+            .collect()
     }
 
     /// Check if a variant exists
     pub fn has_variant(&self, name: &str) -> bool {
-        self.states.contains_key(name)
+        self.states.contains_key(name) || self.dynamic_states.contains_key(name)
     }
 
-    /// Get a variant by name
+    /// Get a static variant by name
     pub fn get_variant(&self, name: &str) -> Option<&AnimationTarget> {
         self.states.get(name)
     }
 
-    /// Get all variants
+    /// Get all static variants
     pub fn get_all_variants(&self) -> &HashMap<String, AnimationTarget> {
         &self.states
     }
 
     /// Get variant count
     pub fn variant_count(&self) -> usize {
-        self.states.len()
+        self.states.len() + self.dynamic_states.len()
     }
 
     /// Check if any variant is currently active
@@ -192,6 +298,210 @@ impl Default for VariantsWithTransitions {
     }
 }
 
+/// When a parent animates relative to its children
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum When {
+    /// Finish the parent's animation before children start
+    BeforeChildren,
+    /// Start the parent's animation once every child has finished
+    AfterChildren,
+}
+
+/// How a parent orchestrates the variant animations of its children
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Orchestration {
+    /// Delay in seconds before the first child starts
+    pub delay_children: f64,
+    /// Delay between consecutive children
+    pub stagger_children: Option<StaggerConfig>,
+    /// Easing of the stagger delay distribution
+    pub stagger_ease: Option<Easing>,
+    /// Stagger children as a grid with this many columns
+    pub stagger_columns: Option<usize>,
+    /// Order of the parent's animation relative to its children
+    pub when: Option<When>,
+}
+
+impl Orchestration {
+    /// Create an orchestration that starts every child together
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delay children by `delay` seconds
+    pub fn with_delay_children(mut self, delay: f64) -> Self {
+        self.delay_children = delay;
+        self
+    }
+
+    /// Stagger children by `stagger`
+    pub fn with_stagger_children(mut self, stagger: StaggerConfig) -> Self {
+        self.stagger_children = Some(stagger);
+        self
+    }
+
+    /// Distribute stagger delays along `ease`
+    pub fn with_stagger_ease(mut self, ease: Easing) -> Self {
+        self.stagger_ease = Some(ease);
+        self
+    }
+
+    /// Stagger children by their distance in a grid with `columns` columns
+    pub fn with_stagger_grid(mut self, columns: usize) -> Self {
+        self.stagger_columns = Some(columns);
+        self
+    }
+
+    /// Resolver for the children's stagger delays
+    pub fn stagger_resolver(&self) -> Option<StaggerResolver> {
+        let mut resolver = StaggerResolver::new(self.stagger_children.clone()?);
+        if let Some(ease) = &self.stagger_ease {
+            resolver = resolver.with_ease(ease.clone());
+        }
+        if let Some(columns) = self.stagger_columns {
+            resolver = resolver.with_grid(columns);
+        }
+        Some(resolver)
+    }
+
+    /// Order the parent's animation relative to its children
+    pub fn with_when(mut self, when: When) -> Self {
+        self.when = Some(when);
+        self
+    }
+
+    /// Delay in seconds of the child at `index` of `count` children
+    pub fn child_delay(&self, index: usize, count: usize, parent: &Transition) -> f64 {
+        let stagger = self
+            .stagger_resolver()
+            .map(|resolver| resolver.delay(index, count))
+            .unwrap_or(0.0);
+        let parent_first = if self.when == Some(When::BeforeChildren) {
+            variant_duration(parent)
+        } else {
+            0.0
+        };
+        parent_first + self.delay_children + stagger
+    }
+
+    /// Delay in seconds of the parent, given each child's total duration
+    pub fn parent_delay(&self, child_durations: &[f64]) -> f64 {
+        if self.when != Some(When::AfterChildren) {
+            return 0.0;
+        }
+        let count = child_durations.len();
+        child_durations
+            .iter()
+            .enumerate()
+            .map(|(index, duration)| {
+                self.child_delay(index, count, &Transition::default()) + duration
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Delay plus duration of `transition` in seconds
+pub fn variant_duration(transition: &Transition) -> f64 {
+    transition.delay.unwrap_or(0.0) + transition.effective_duration().unwrap_or(0.0)
+}
+
+/// Variant labels a motion component propagates to its descendants
+#[derive(Clone, Copy)]
+pub struct VariantContext {
+    initial: StoredValue<Option<String>>,
+    animate: Signal<Option<String>>,
+    orchestration: StoredValue<Orchestration>,
+    transition: StoredValue<Transition>,
+    children: RwSignal<Vec<(u64, f64)>>,
+    next_id: StoredValue<u64>,
+}
+
+impl VariantContext {
+    /// Create a context propagating `initial` and `animate` labels
+    pub fn new(
+        initial: Option<String>,
+        animate: Signal<Option<String>>,
+        orchestration: Orchestration,
+        transition: Transition,
+    ) -> Self {
+        Self {
+            initial: StoredValue::new(initial),
+            animate,
+            orchestration: StoredValue::new(orchestration),
+            transition: StoredValue::new(transition),
+            children: RwSignal::new(Vec::new()),
+            next_id: StoredValue::new(0),
+        }
+    }
+
+    /// Label of the initial variant
+    pub fn initial_label(&self) -> Option<String> {
+        self.initial.try_get_value().flatten()
+    }
+
+    /// Label of the animated variant (tracked)
+    pub fn animate_label(&self) -> Option<String> {
+        self.animate.try_get().flatten()
+    }
+
+    /// Signal of the animated variant label
+    pub fn animate_signal(&self) -> Signal<Option<String>> {
+        self.animate
+    }
+
+    /// Register a child whose animation takes `duration` seconds, in document order
+    pub fn register_child(&self, duration: f64) -> u64 {
+        let id = self.next_id.try_get_value().unwrap_or_default();
+        self.next_id.try_set_value(id + 1);
+        self.children
+            .try_update(|children| children.push((id, duration)));
+        id
+    }
+
+    /// Remove a child, e.g. when it unmounts
+    pub fn unregister_child(&self, id: u64) {
+        self.children
+            .try_update(|children| children.retain(|(child, _)| *child != id));
+    }
+
+    /// Delay in seconds the parent gives child `id`
+    pub fn child_delay(&self, id: u64) -> f64 {
+        let (Some(orchestration), Some(transition)) = (
+            self.orchestration.try_get_value(),
+            self.transition.try_get_value(),
+        ) else {
+            return 0.0;
+        };
+        self.children
+            .try_with_untracked(|children| {
+                children
+                    .iter()
+                    .position(|(child, _)| *child == id)
+                    .map(|index| orchestration.child_delay(index, children.len(), &transition))
+            })
+            .flatten()
+            .unwrap_or(0.0)
+    }
+
+    /// Delay in seconds of the parent's own animation
+    pub fn parent_delay(&self) -> f64 {
+        let Some(orchestration) = self.orchestration.try_get_value() else {
+            return 0.0;
+        };
+        self.children
+            .try_with_untracked(|children| {
+                let durations: Vec<f64> = children.iter().map(|(_, duration)| *duration).collect();
+                orchestration.parent_delay(&durations)
+            })
+            .unwrap_or(0.0)
+    }
+}
+
+/// Variant labels propagated by the closest motion ancestor with variants
+pub fn use_variant_context() -> Option<VariantContext> {
+    use_context::<VariantContext>()
+}
+
 /// Hook for using variants in Leptos components
 pub fn use_variants() -> UseVariantsReturn {
     let (current_variant, set_current_variant) = signal(None::<String>);
@@ -301,6 +611,7 @@ pub mod common_variants {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use leptos_motion_core::StaggerFrom;
 
     #[test]
     fn test_variants_basic() {
@@ -356,6 +667,142 @@ mod tests {
         assert!(translate_target.contains_key("y"));
     }
 
+    #[test]
+    fn test_orchestration_child_delays() {
+        let orchestration = Orchestration::new()
+            .with_delay_children(0.5)
+            .with_stagger_children(StaggerConfig {
+                delay: 0.1,
+                from: StaggerFrom::First,
+            });
+        let parent = Transition::default();
+
+        assert!((orchestration.child_delay(0, 3, &parent) - 0.5).abs() < 1e-9);
+        assert!((orchestration.child_delay(2, 3, &parent) - 0.7).abs() < 1e-9);
+        assert_eq!(orchestration.parent_delay(&[0.3, 0.3, 0.3]), 0.0);
+    }
+
+    #[test]
+    fn test_orchestration_grid_stagger() {
+        let orchestration = Orchestration::new()
+            .with_stagger_children(StaggerConfig {
+                delay: 0.1,
+                from: StaggerFrom::Center,
+            })
+            .with_stagger_grid(3);
+        let parent = Transition::default();
+
+        assert_eq!(orchestration.child_delay(4, 9, &parent), 0.0);
+        assert!((orchestration.child_delay(1, 9, &parent) - 0.1).abs() < 1e-9);
+        assert!((orchestration.child_delay(0, 9, &parent) - 2.0_f64.sqrt() * 0.1).abs() < 1e-9);
+
+        // Easing reshapes the distribution but keeps the furthest delay
+        let eased = orchestration.with_stagger_ease(leptos_motion_core::Easing::EaseIn);
+        assert!(eased.child_delay(1, 9, &parent) < 0.1);
+        assert!((eased.child_delay(0, 9, &parent) - 2.0_f64.sqrt() * 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_orchestration_when() {
+        let parent = Transition {
+            duration: Some(0.4),
+            delay: Some(0.1),
+            ..Default::default()
+        };
+
+        // Children start once the parent has finished
+        let before = Orchestration::new().with_when(When::BeforeChildren);
+        assert!((before.child_delay(0, 2, &parent) - 0.5).abs() < 1e-9);
+
+        // The parent starts once the last child has finished
+        let after = Orchestration::new()
+            .with_when(When::AfterChildren)
+            .with_stagger_children(StaggerConfig {
+                delay: 0.2,
+                from: StaggerFrom::First,
+            });
+        assert_eq!(after.child_delay(1, 2, &parent), 0.2);
+        assert!((after.parent_delay(&[0.3, 0.3]) - 0.5).abs() < 1e-9);
+        assert_eq!(after.parent_delay(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_variant_context_orders_children() {
+        let context = VariantContext::new(
+            Some("hidden".to_string()),
+            Signal::stored(Some("visible".to_string())),
+            Orchestration::new().with_stagger_children(StaggerConfig {
+                delay: 0.1,
+                from: StaggerFrom::First,
+            }),
+            Transition::default(),
+        );
+        assert_eq!(context.initial_label().as_deref(), Some("hidden"));
+        assert_eq!(context.animate_label().as_deref(), Some("visible"));
+
+        let first = context.register_child(0.3);
+        let second = context.register_child(0.3);
+        let third = context.register_child(0.3);
+        assert!((context.child_delay(third) - 0.2).abs() < 1e-9);
+
+        // Later children move up once an earlier one unmounts
+        context.unregister_child(first);
+        assert_eq!(context.child_delay(second), 0.0);
+        assert!((context.child_delay(third) - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dynamic_variants_use_custom_data() {
+        let mut variants = Variants::new();
+        variants.add_dynamic_variant("exit".to_string(), |direction: &f64| {
+            common_variants::translate(direction * 100.0, 0.0)
+        });
+
+        assert!(variants.has_variant("exit"));
+        assert_eq!(variants.variant_count(), 1);
+        assert!(variants.get_variant("exit").is_none());
+
+        let target = variants
+            .resolve_variant("exit", Some(&Custom::new(-1.0_f64)))
+            .unwrap();
+        assert_eq!(
+            target.get("x"),
+            Some(&leptos_motion_core::AnimationValue::Number(-100.0))
+        );
+
+        // Missing or mistyped custom data does not resolve
+        assert!(variants.resolve_variant("exit", None).is_none());
+        assert!(
+            variants
+                .resolve_variant("exit", Some(&Custom::new("left")))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_current_dynamic_variant() {
+        let mut variants = Variants::new();
+        variants.add_variant("hidden".to_string(), common_variants::fade(0.0));
+        variants.add_dynamic_variant("visible".to_string(), |opacity: &f64| {
+            common_variants::fade(*opacity)
+        });
+
+        assert!(variants.set_variant("visible"));
+        assert!(variants.current_animation_target().is_none());
+        let target = variants
+            .resolve_current_animation_target(Some(&Custom::new(0.5_f64)))
+            .unwrap();
+        assert_eq!(
+            target.get("opacity"),
+            Some(&leptos_motion_core::AnimationValue::Number(0.5))
+        );
+
+        // Static variants resolve without custom data and replace dynamic ones
+        variants.add_variant("visible".to_string(), common_variants::fade(1.0));
+        assert_eq!(variants.variant_count(), 2);
+        assert!(variants.resolve_current_animation_target(None).is_some());
+    }
+
     #[test]
     fn test_variants_removal() {
         let mut variants = Variants::new();
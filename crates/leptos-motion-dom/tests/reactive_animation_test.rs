@@ -1,5 +1,8 @@
+#![allow(deprecated)]
+
 use leptos::prelude::*;
 use leptos_motion_core::*;
+use leptos_motion_dom::reactive_motion_div::ReactiveMotionDiv;
 use leptos_motion_dom::*;
 use std::collections::HashMap;
 use wasm_bindgen::JsCast;
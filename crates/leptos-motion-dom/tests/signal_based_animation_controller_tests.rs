@@ -2,6 +2,8 @@
 //!
 //! These tests verify the proven signal-based patterns work correctly.
 
+#![allow(deprecated)]
+
 use leptos::prelude::*;
 use leptos_motion_core::types::AnimationValue;
 use leptos_motion_dom::signal_based_animation_controller::*;
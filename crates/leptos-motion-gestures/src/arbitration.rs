@@ -0,0 +1,1289 @@
+//! Gesture recognizer arbitration
+//!
+//! A [`GestureArbiter`] runs a set of named recognizers against the same input
+//! and decides which of them may recognize. Each recognizer moves through
+//! [`RecognizerState`]s; relationships between recognizers control how they
+//! interact:
+//!
+//! - By default the first recognizer to begin wins and every other recognizer
+//!   still in [`RecognizerState::Possible`] fails.
+//! - [`GestureArbiter::simultaneous_with`] lets two recognizers run together.
+//! - [`GestureArbiter::require_to_fail`] holds a recognizer back until another
+//!   one has failed (a single tap waiting for a double tap to fail).
+//! - [`GestureArbiter::exclusive`] gives one recognizer priority over another:
+//!   it cancels the other when it begins and the two never run together.
+//!
+//! Once every enabled recognizer has finished, all of them are reset to
+//! [`RecognizerState::Possible`] for the next gesture.
+
+use crate::{GestureEvent, TouchPoint};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Type alias for recognizer transition callbacks
+type TransitionCallback = Box<dyn Fn(&GestureTransition) + Send + Sync>;
+
+/// State of a gesture recognizer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecognizerState {
+    /// Waiting for input that may turn into the gesture
+    Possible,
+    /// A continuous gesture has started
+    Began,
+    /// A continuous gesture has updated
+    Changed,
+    /// The gesture finished, or a discrete gesture was recognized
+    Ended,
+    /// The input is not this gesture
+    Failed,
+    /// A running gesture was interrupted
+    Cancelled,
+}
+
+impl RecognizerState {
+    /// Check if the gesture is running
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Began | Self::Changed)
+    }
+
+    /// Check if the recognizer is done until the next reset
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Ended | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Values reported by a recognizer
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureValues {
+    /// Location of the gesture (touch centroid)
+    pub location: (f64, f64),
+    /// Translation since the gesture started
+    pub translation: (f64, f64),
+    /// Scale since the gesture started (1.0 = no change)
+    pub scale: f64,
+    /// Rotation since the gesture started, in radians
+    pub rotation: f64,
+    /// Number of taps recognized
+    pub tap_count: u32,
+}
+
+impl Default for GestureValues {
+    fn default() -> Self {
+        Self {
+            location: (0.0, 0.0),
+            translation: (0.0, 0.0),
+            scale: 1.0,
+            rotation: 0.0,
+            tap_count: 0,
+        }
+    }
+}
+
+/// State change of a recognizer, as decided by the arbiter
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureTransition {
+    /// Recognizer id
+    pub id: String,
+    /// New state
+    pub state: RecognizerState,
+    /// Recognizer values at the time of the transition
+    pub values: GestureValues,
+}
+
+/// Gesture recognizer driven by a [`GestureArbiter`]
+///
+/// Recognizers report the state they would like to move to; the arbiter
+/// decides whether that transition is allowed.
+pub trait GestureRecognizer {
+    /// Process an input event received at `now_ms`
+    fn handle_event(&mut self, event: &GestureEvent, now_ms: u64) -> RecognizerState;
+
+    /// Advance time without input, e.g. to fail on a multi-tap timeout
+    fn tick(&mut self, _now_ms: u64) -> Option<RecognizerState> {
+        None
+    }
+
+    /// Current recognizer values
+    fn values(&self) -> GestureValues;
+
+    /// Reset for the next gesture
+    fn reset(&mut self);
+}
+
+/// Touches currently on the surface, keyed by id
+#[derive(Debug, Clone, Default)]
+struct ActiveTouches {
+    touches: BTreeMap<u64, (f64, f64)>,
+}
+
+impl ActiveTouches {
+    /// Apply an input event; returns false for events without touch data
+    fn apply(&mut self, event: &GestureEvent) -> bool {
+        match event {
+            GestureEvent::TouchStart { touches } | GestureEvent::TouchMove { touches } => {
+                self.insert(touches);
+                true
+            }
+            GestureEvent::TouchEnd { touches } => {
+                for touch in touches {
+                    self.touches.remove(&touch.id);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn insert(&mut self, touches: &[TouchPoint]) {
+        for touch in touches {
+            self.touches.insert(touch.id, (touch.x, touch.y));
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.touches.len()
+    }
+
+    fn centroid(&self) -> Option<(f64, f64)> {
+        if self.touches.is_empty() {
+            return None;
+        }
+        let count = self.touches.len() as f64;
+        let (sum_x, sum_y) = self
+            .touches
+            .values()
+            .fold((0.0, 0.0), |(x, y), point| (x + point.0, y + point.1));
+        Some((sum_x / count, sum_y / count))
+    }
+
+    /// Distance between the two earliest touches
+    fn span(&self) -> Option<f64> {
+        let mut points = self.touches.values();
+        let first = points.next()?;
+        let second = points.next()?;
+        Some(((second.0 - first.0).powi(2) + (second.1 - first.1).powi(2)).sqrt())
+    }
+
+    /// Angle of the line from the first to the second of the two earliest touches
+    fn angle(&self) -> Option<f64> {
+        let mut points = self.touches.values();
+        let first = points.next()?;
+        let second = points.next()?;
+        Some((second.1 - first.1).atan2(second.0 - first.0))
+    }
+
+    fn clear(&mut self) {
+        self.touches.clear();
+    }
+}
+
+/// Discrete tap recognizer for single and multi taps
+#[derive(Debug, Clone)]
+pub struct TapRecognizer {
+    /// Taps needed to recognize (2 for a double tap)
+    pub taps_required: u32,
+    /// Maximum movement in pixels during a tap
+    pub max_distance: f64,
+    /// Maximum press duration in milliseconds
+    pub max_duration_ms: u64,
+    /// Maximum delay between taps in milliseconds
+    pub multi_tap_timeout_ms: u64,
+    touches: ActiveTouches,
+    press: Option<((f64, f64), u64)>,
+    taps: u32,
+    last_tap_ms: Option<u64>,
+    location: (f64, f64),
+}
+
+impl TapRecognizer {
+    /// Create a single tap recognizer
+    pub fn new() -> Self {
+        Self {
+            taps_required: 1,
+            max_distance: 10.0,
+            max_duration_ms: 300,
+            multi_tap_timeout_ms: 300,
+            touches: ActiveTouches::default(),
+            press: None,
+            taps: 0,
+            last_tap_ms: None,
+            location: (0.0, 0.0),
+        }
+    }
+
+    /// Set the number of taps required
+    pub fn taps_required(mut self, taps: u32) -> Self {
+        self.taps_required = taps.max(1);
+        self
+    }
+
+    /// Set maximum tap movement
+    pub fn max_distance(mut self, distance: f64) -> Self {
+        self.max_distance = distance.max(0.0);
+        self
+    }
+
+    /// Set maximum press duration
+    pub fn max_duration(mut self, duration_ms: u64) -> Self {
+        self.max_duration_ms = duration_ms;
+        self
+    }
+
+    /// Set maximum delay between taps
+    pub fn multi_tap_timeout(mut self, timeout_ms: u64) -> Self {
+        self.multi_tap_timeout_ms = timeout_ms;
+        self
+    }
+}
+
+impl Default for TapRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for TapRecognizer {
+    fn handle_event(&mut self, event: &GestureEvent, now_ms: u64) -> RecognizerState {
+        if !self.touches.apply(event) {
+            return RecognizerState::Possible;
+        }
+
+        match event {
+            GestureEvent::TouchStart { .. } => {
+                if self.touches.len() > 1 {
+                    return RecognizerState::Failed;
+                }
+                if let Some(last) = self.last_tap_ms
+                    && now_ms.saturating_sub(last) > self.multi_tap_timeout_ms
+                {
+                    return RecognizerState::Failed;
+                }
+                if let Some(position) = self.touches.centroid() {
+                    self.press = Some((position, now_ms));
+                }
+                RecognizerState::Possible
+            }
+            GestureEvent::TouchMove { .. } => match (self.press, self.touches.centroid()) {
+                (Some((start, _)), Some(position))
+                    if distance(start, position) > self.max_distance =>
+                {
+                    RecognizerState::Failed
+                }
+                _ => RecognizerState::Possible,
+            },
+            GestureEvent::TouchEnd { .. } => {
+                if self.touches.len() > 0 {
+                    return RecognizerState::Possible;
+                }
+                let Some((start, pressed_at)) = self.press.take() else {
+                    return RecognizerState::Possible;
+                };
+                if now_ms.saturating_sub(pressed_at) > self.max_duration_ms {
+                    return RecognizerState::Failed;
+                }
+
+                self.taps += 1;
+                self.location = start;
+                if self.taps >= self.taps_required {
+                    RecognizerState::Ended
+                } else {
+                    self.last_tap_ms = Some(now_ms);
+                    RecognizerState::Possible
+                }
+            }
+            _ => RecognizerState::Possible,
+        }
+    }
+
+    fn tick(&mut self, now_ms: u64) -> Option<RecognizerState> {
+        let last = self.last_tap_ms?;
+        (self.press.is_none() && now_ms.saturating_sub(last) > self.multi_tap_timeout_ms)
+            .then_some(RecognizerState::Failed)
+    }
+
+    fn values(&self) -> GestureValues {
+        GestureValues {
+            location: self.location,
+            tap_count: self.taps,
+            ..GestureValues::default()
+        }
+    }
+
+    fn reset(&mut self) {
+        self.touches.clear();
+        self.press = None;
+        self.taps = 0;
+        self.last_tap_ms = None;
+    }
+}
+
+/// Continuous pan recognizer
+#[derive(Debug, Clone)]
+pub struct PanRecognizer {
+    /// Movement in pixels before the pan begins
+    pub min_distance: f64,
+    /// Maximum number of touches for the pan to begin
+    pub max_touches: usize,
+    touches: ActiveTouches,
+    anchor: Option<(f64, f64)>,
+    carried: (f64, f64),
+    translation: (f64, f64),
+    location: (f64, f64),
+    began: bool,
+}
+
+impl PanRecognizer {
+    /// Create a single touch pan recognizer
+    pub fn new() -> Self {
+        Self {
+            min_distance: 10.0,
+            max_touches: 1,
+            touches: ActiveTouches::default(),
+            anchor: None,
+            carried: (0.0, 0.0),
+            translation: (0.0, 0.0),
+            location: (0.0, 0.0),
+            began: false,
+        }
+    }
+
+    /// Set movement threshold
+    pub fn min_distance(mut self, distance: f64) -> Self {
+        self.min_distance = distance.max(0.0);
+        self
+    }
+
+    /// Set maximum number of touches
+    pub fn max_touches(mut self, max: usize) -> Self {
+        self.max_touches = max.max(1);
+        self
+    }
+
+    /// Restart measuring from the current centroid, keeping the translation
+    fn reanchor(&mut self) {
+        self.carried = self.translation;
+        self.anchor = self.touches.centroid();
+    }
+}
+
+impl Default for PanRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for PanRecognizer {
+    fn handle_event(&mut self, event: &GestureEvent, _now_ms: u64) -> RecognizerState {
+        if !self.touches.apply(event) {
+            return RecognizerState::Possible;
+        }
+        let active = if self.began {
+            RecognizerState::Changed
+        } else {
+            RecognizerState::Possible
+        };
+
+        match event {
+            GestureEvent::TouchStart { .. } => {
+                if !self.began && self.touches.len() > self.max_touches {
+                    return RecognizerState::Failed;
+                }
+                // Touch count changes move the centroid without moving the pan
+                self.reanchor();
+                active
+            }
+            GestureEvent::TouchMove { .. } => {
+                let (Some(anchor), Some(position)) = (self.anchor, self.touches.centroid()) else {
+                    return active;
+                };
+                self.location = position;
+                self.translation = (
+                    self.carried.0 + position.0 - anchor.0,
+                    self.carried.1 + position.1 - anchor.1,
+                );
+                if !self.began && distance((0.0, 0.0), self.translation) >= self.min_distance {
+                    self.began = true;
+                    return RecognizerState::Began;
+                }
+                active
+            }
+            GestureEvent::TouchEnd { .. } => {
+                if self.touches.len() > 0 {
+                    self.reanchor();
+                    return active;
+                }
+                if self.began {
+                    RecognizerState::Ended
+                } else {
+                    RecognizerState::Failed
+                }
+            }
+            _ => active,
+        }
+    }
+
+    fn values(&self) -> GestureValues {
+        GestureValues {
+            location: self.location,
+            translation: self.translation,
+            ..GestureValues::default()
+        }
+    }
+
+    fn reset(&mut self) {
+        self.touches.clear();
+        self.anchor = None;
+        self.carried = (0.0, 0.0);
+        self.translation = (0.0, 0.0);
+        self.began = false;
+    }
+}
+
+/// Continuous two-finger pinch recognizer
+#[derive(Debug, Clone)]
+pub struct PinchRecognizer {
+    /// Scale change before the pinch begins
+    pub min_scale_delta: f64,
+    touches: ActiveTouches,
+    initial_span: Option<f64>,
+    carried_scale: f64,
+    scale: f64,
+    location: (f64, f64),
+    began: bool,
+}
+
+impl PinchRecognizer {
+    /// Create a pinch recognizer
+    pub fn new() -> Self {
+        Self {
+            min_scale_delta: 0.05,
+            touches: ActiveTouches::default(),
+            initial_span: None,
+            carried_scale: 1.0,
+            scale: 1.0,
+            location: (0.0, 0.0),
+            began: false,
+        }
+    }
+
+    /// Set scale threshold
+    pub fn min_scale_delta(mut self, delta: f64) -> Self {
+        self.min_scale_delta = delta.max(0.0);
+        self
+    }
+}
+
+impl Default for PinchRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for PinchRecognizer {
+    fn handle_event(&mut self, event: &GestureEvent, _now_ms: u64) -> RecognizerState {
+        if !self.touches.apply(event) {
+            return RecognizerState::Possible;
+        }
+        let active = if self.began {
+            RecognizerState::Changed
+        } else {
+            RecognizerState::Possible
+        };
+
+        match event {
+            GestureEvent::TouchStart { .. } => {
+                self.carried_scale = self.scale;
+                self.initial_span = self.touches.span().filter(|span| *span > 0.0);
+                active
+            }
+            GestureEvent::TouchMove { .. } => {
+                let (Some(initial), Some(span)) = (self.initial_span, self.touches.span()) else {
+                    return active;
+                };
+                self.scale = self.carried_scale * span / initial;
+                if let Some(position) = self.touches.centroid() {
+                    self.location = position;
+                }
+                if !self.began && (self.scale - 1.0).abs() >= self.min_scale_delta {
+                    self.began = true;
+                    return RecognizerState::Began;
+                }
+                active
+            }
+            GestureEvent::TouchEnd { .. } => {
+                if self.touches.len() >= 2 {
+                    self.carried_scale = self.scale;
+                    self.initial_span = self.touches.span().filter(|span| *span > 0.0);
+                    return active;
+                }
+                self.initial_span = None;
+                if self.began {
+                    RecognizerState::Ended
+                } else if self.touches.len() == 0 {
+                    RecognizerState::Failed
+                } else {
+                    RecognizerState::Possible
+                }
+            }
+            _ => active,
+        }
+    }
+
+    fn values(&self) -> GestureValues {
+        GestureValues {
+            location: self.location,
+            scale: self.scale,
+            ..GestureValues::default()
+        }
+    }
+
+    fn reset(&mut self) {
+        self.touches.clear();
+        self.initial_span = None;
+        self.carried_scale = 1.0;
+        self.scale = 1.0;
+        self.began = false;
+    }
+}
+
+/// Continuous two-finger rotation recognizer
+#[derive(Debug, Clone)]
+pub struct RotationRecognizer {
+    /// Rotation in radians before the gesture begins
+    pub min_rotation: f64,
+    touches: ActiveTouches,
+    initial_angle: Option<f64>,
+    carried_rotation: f64,
+    rotation: f64,
+    location: (f64, f64),
+    began: bool,
+}
+
+impl RotationRecognizer {
+    /// Create a rotation recognizer
+    pub fn new() -> Self {
+        Self {
+            min_rotation: 0.1,
+            touches: ActiveTouches::default(),
+            initial_angle: None,
+            carried_rotation: 0.0,
+            rotation: 0.0,
+            location: (0.0, 0.0),
+            began: false,
+        }
+    }
+
+    /// Set rotation threshold
+    pub fn min_rotation(mut self, radians: f64) -> Self {
+        self.min_rotation = radians.abs();
+        self
+    }
+
+    /// Restart measuring from the current touches, keeping the rotation
+    fn reanchor(&mut self) {
+        self.carried_rotation = self.rotation;
+        self.initial_angle = self.touches.angle();
+    }
+}
+
+impl Default for RotationRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GestureRecognizer for RotationRecognizer {
+    fn handle_event(&mut self, event: &GestureEvent, _now_ms: u64) -> RecognizerState {
+        if !self.touches.apply(event) {
+            return RecognizerState::Possible;
+        }
+        let active = if self.began {
+            RecognizerState::Changed
+        } else {
+            RecognizerState::Possible
+        };
+
+        match event {
+            GestureEvent::TouchStart { .. } => {
+                self.reanchor();
+                active
+            }
+            GestureEvent::TouchMove { .. } => {
+                let (Some(initial), Some(angle)) = (self.initial_angle, self.touches.angle())
+                else {
+                    return active;
+                };
+                // Keep the change in (-π, π] so crossing the x axis doesn't jump
+                let mut change = angle - initial;
+                if change > std::f64::consts::PI {
+                    change -= std::f64::consts::TAU;
+                } else if change <= -std::f64::consts::PI {
+                    change += std::f64::consts::TAU;
+                }
+                self.rotation = self.carried_rotation + change;
+                if let Some(position) = self.touches.centroid() {
+                    self.location = position;
+                }
+                if !self.began && self.rotation.abs() >= self.min_rotation {
+                    self.began = true;
+                    return RecognizerState::Began;
+                }
+                active
+            }
+            GestureEvent::TouchEnd { .. } => {
+                if self.touches.len() >= 2 {
+                    self.reanchor();
+                    return active;
+                }
+                self.initial_angle = None;
+                if self.began {
+                    RecognizerState::Ended
+                } else if self.touches.len() == 0 {
+                    RecognizerState::Failed
+                } else {
+                    RecognizerState::Possible
+                }
+            }
+            _ => active,
+        }
+    }
+
+    fn values(&self) -> GestureValues {
+        GestureValues {
+            location: self.location,
+            rotation: self.rotation,
+            ..GestureValues::default()
+        }
+    }
+
+    fn reset(&mut self) {
+        self.touches.clear();
+        self.initial_angle = None;
+        self.carried_rotation = 0.0;
+        self.rotation = 0.0;
+        self.began = false;
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt()
+}
+
+/// Recognizer registered with an arbiter
+struct RecognizerEntry {
+    id: String,
+    recognizer: Box<dyn GestureRecognizer>,
+    state: RecognizerState,
+    enabled: bool,
+    /// State held back until required recognizers fail
+    waiting: Option<RecognizerState>,
+}
+
+/// Decides which of several recognizers may recognize the same input
+#[derive(Default)]
+pub struct GestureArbiter {
+    entries: Vec<RecognizerEntry>,
+    simultaneous: HashSet<(String, String)>,
+    exclusive: HashSet<(String, String)>,
+    failure_requirements: HashMap<String, Vec<String>>,
+    callbacks: HashMap<String, TransitionCallback>,
+}
+
+impl GestureArbiter {
+    /// Create an empty arbiter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a recognizer under `id`, replacing any previous one
+    pub fn add<R>(&mut self, id: &str, recognizer: R) -> &mut Self
+    where
+        R: GestureRecognizer + 'static,
+    {
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.push(RecognizerEntry {
+            id: id.to_string(),
+            recognizer: Box::new(recognizer),
+            state: RecognizerState::Possible,
+            enabled: true,
+            waiting: None,
+        });
+        self
+    }
+
+    /// Remove a recognizer and its relationships
+    pub fn remove(&mut self, id: &str) -> bool {
+        let count = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.simultaneous.retain(|(a, b)| a != id && b != id);
+        self.exclusive.retain(|(a, b)| a != id && b != id);
+        self.failure_requirements.remove(id);
+        for required in self.failure_requirements.values_mut() {
+            required.retain(|other| other != id);
+        }
+        self.callbacks.remove(id);
+        self.entries.len() != count
+    }
+
+    /// Allow `a` and `b` to recognize at the same time
+    pub fn simultaneous_with(&mut self, a: &str, b: &str) -> &mut Self {
+        self.simultaneous.insert((a.to_string(), b.to_string()));
+        self.simultaneous.insert((b.to_string(), a.to_string()));
+        self
+    }
+
+    /// Hold `id` back until `other` has failed
+    pub fn require_to_fail(&mut self, id: &str, other: &str) -> &mut Self {
+        let required = self.failure_requirements.entry(id.to_string()).or_default();
+        if !required.iter().any(|existing| existing == other) {
+            required.push(other.to_string());
+        }
+        self
+    }
+
+    /// Give `winner` priority over `loser`
+    ///
+    /// The two never run together: `winner` cancels a running `loser` when it
+    /// begins, and `loser` stays possible-but-blocked while `winner` runs.
+    pub fn exclusive(&mut self, winner: &str, loser: &str) -> &mut Self {
+        self.exclusive
+            .insert((winner.to_string(), loser.to_string()));
+        self
+    }
+
+    /// Register a callback for transitions of recognizer `id`
+    pub fn on_transition<F>(&mut self, id: &str, callback: F) -> &mut Self
+    where
+        F: Fn(&GestureTransition) + Send + Sync + 'static,
+    {
+        self.callbacks.insert(id.to_string(), Box::new(callback));
+        self
+    }
+
+    /// Enable or disable a recognizer
+    ///
+    /// Disabling a running recognizer cancels it. Disabled recognizers count
+    /// as failed for [`require_to_fail`](Self::require_to_fail).
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) -> Vec<GestureTransition> {
+        let mut transitions = Vec::new();
+        let Some(index) = self.index_of(id) else {
+            return transitions;
+        };
+        if self.entries[index].enabled == enabled {
+            return transitions;
+        }
+
+        self.entries[index].enabled = enabled;
+        if !enabled {
+            if self.entries[index].state.is_active() {
+                self.set_state(index, RecognizerState::Cancelled, &mut transitions);
+            }
+            let entry = &mut self.entries[index];
+            entry.state = RecognizerState::Possible;
+            entry.waiting = None;
+            entry.recognizer.reset();
+            self.release_waiters(id, &mut transitions);
+            self.reset_if_finished();
+        }
+        transitions
+    }
+
+    /// Check if a recognizer is enabled
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.index_of(id)
+            .is_some_and(|index| self.entries[index].enabled)
+    }
+
+    /// Current state of a recognizer
+    pub fn state(&self, id: &str) -> Option<RecognizerState> {
+        self.index_of(id).map(|index| self.entries[index].state)
+    }
+
+    /// Ids of the recognizers currently running
+    pub fn active_recognizers(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.state.is_active())
+            .map(|entry| entry.id.as_str())
+            .collect()
+    }
+
+    /// Feed an input event to every recognizer
+    pub fn handle_event(&mut self, event: &GestureEvent, now_ms: u64) -> Vec<GestureTransition> {
+        let mut transitions = Vec::new();
+        for index in 0..self.entries.len() {
+            let entry = &mut self.entries[index];
+            if !entry.enabled || entry.state.is_finished() || entry.waiting.is_some() {
+                continue;
+            }
+            let desired = entry.recognizer.handle_event(event, now_ms);
+            self.request(index, desired, &mut transitions);
+        }
+        self.reset_if_finished();
+        transitions
+    }
+
+    /// Advance time so recognizers can fail on timeouts
+    ///
+    /// Input events don't arrive while a single tap waits for a double tap
+    /// to time out, so this has to be called from a timer for the waiting
+    /// recognizer to fire.
+    pub fn tick(&mut self, now_ms: u64) -> Vec<GestureTransition> {
+        let mut transitions = Vec::new();
+        for index in 0..self.entries.len() {
+            let entry = &mut self.entries[index];
+            if !entry.enabled || entry.state.is_finished() || entry.waiting.is_some() {
+                continue;
+            }
+            if let Some(desired) = entry.recognizer.tick(now_ms) {
+                self.request(index, desired, &mut transitions);
+            }
+        }
+        self.reset_if_finished();
+        transitions
+    }
+
+    /// Cancel running recognizers and reset every recognizer
+    pub fn reset(&mut self) -> Vec<GestureTransition> {
+        let mut transitions = Vec::new();
+        for index in 0..self.entries.len() {
+            if self.entries[index].state.is_active() {
+                self.set_state(index, RecognizerState::Cancelled, &mut transitions);
+            }
+        }
+        self.reset_all();
+        transitions
+    }
+
+    fn index_of(&self, id: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == id)
+    }
+
+    fn can_run_together(&self, a: &str, b: &str) -> bool {
+        let pair = (a.to_string(), b.to_string());
+        self.simultaneous.contains(&pair)
+            && !self.exclusive.contains(&pair)
+            && !self.exclusive.contains(&(pair.1, pair.0))
+    }
+
+    fn has_priority(&self, winner: &str, loser: &str) -> bool {
+        self.exclusive
+            .contains(&(winner.to_string(), loser.to_string()))
+    }
+
+    /// Apply the state a recognizer asked for, if the relationships allow it
+    fn request(
+        &mut self,
+        index: usize,
+        desired: RecognizerState,
+        transitions: &mut Vec<GestureTransition>,
+    ) {
+        let current = self.entries[index].state;
+        match desired {
+            RecognizerState::Possible => {}
+            RecognizerState::Failed | RecognizerState::Cancelled => {
+                let next = if current.is_active() {
+                    RecognizerState::Cancelled
+                } else {
+                    RecognizerState::Failed
+                };
+                self.set_state(index, next, transitions);
+                if next == RecognizerState::Failed {
+                    let id = self.entries[index].id.clone();
+                    self.release_waiters(&id, transitions);
+                }
+            }
+            RecognizerState::Began | RecognizerState::Changed | RecognizerState::Ended
+                if current.is_active() =>
+            {
+                let next = if desired == RecognizerState::Began {
+                    RecognizerState::Changed
+                } else {
+                    desired
+                };
+                self.set_state(index, next, transitions);
+            }
+            RecognizerState::Began | RecognizerState::Changed | RecognizerState::Ended => {
+                self.begin(index, desired, transitions);
+            }
+        }
+    }
+
+    /// Move a possible recognizer to `desired` (began, or ended if discrete)
+    fn begin(
+        &mut self,
+        index: usize,
+        desired: RecognizerState,
+        transitions: &mut Vec<GestureTransition>,
+    ) {
+        let id = self.entries[index].id.clone();
+
+        // Required recognizers must fail first
+        let required = self
+            .failure_requirements
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+        let mut pending = false;
+        for other in &required {
+            let Some(other_index) = self.index_of(other) else {
+                continue;
+            };
+            let other_entry = &self.entries[other_index];
+            if !other_entry.enabled || other_entry.state == RecognizerState::Failed {
+                continue;
+            }
+            if other_entry.state == RecognizerState::Possible {
+                pending = true;
+            } else {
+                self.set_state(index, RecognizerState::Failed, transitions);
+                self.release_waiters(&id, transitions);
+                return;
+            }
+        }
+        if pending {
+            self.entries[index].waiting = Some(desired);
+            return;
+        }
+
+        // Running recognizers block unless they may run together or yield
+        let blocked = self.entries.iter().any(|other| {
+            other.id != id
+                && other.state.is_active()
+                && !self.has_priority(&id, &other.id)
+                && !self.can_run_together(&id, &other.id)
+        });
+        if blocked {
+            self.set_state(index, RecognizerState::Failed, transitions);
+            self.release_waiters(&id, transitions);
+            return;
+        }
+
+        let state = if desired == RecognizerState::Changed {
+            RecognizerState::Began
+        } else {
+            desired
+        };
+        self.entries[index].waiting = None;
+        self.set_state(index, state, transitions);
+
+        for other_index in 0..self.entries.len() {
+            if other_index == index {
+                continue;
+            }
+            let other = &self.entries[other_index];
+            if !other.enabled || other.state.is_finished() {
+                continue;
+            }
+            let other_id = other.id.clone();
+            let waits_for_this = self
+                .failure_requirements
+                .get(&other_id)
+                .is_some_and(|required| required.contains(&id));
+
+            if other.state.is_active() {
+                if self.has_priority(&id, &other_id) {
+                    self.set_state(other_index, RecognizerState::Cancelled, transitions);
+                }
+            } else if waits_for_this
+                || (!self.can_run_together(&id, &other_id) && !self.has_priority(&other_id, &id))
+            {
+                self.entries[other_index].waiting = None;
+                self.set_state(other_index, RecognizerState::Failed, transitions);
+                self.release_waiters(&other_id, transitions);
+            }
+        }
+    }
+
+    /// Let recognizers waiting on `failed_id` proceed once all their requirements failed
+    fn release_waiters(&mut self, failed_id: &str, transitions: &mut Vec<GestureTransition>) {
+        let waiters: Vec<usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry.waiting.is_some()
+                    && self
+                        .failure_requirements
+                        .get(&entry.id)
+                        .is_some_and(|required| required.iter().any(|id| id == failed_id))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        for index in waiters {
+            if let Some(desired) = self.entries[index].waiting.take() {
+                self.begin(index, desired, transitions);
+            }
+        }
+    }
+
+    fn set_state(
+        &mut self,
+        index: usize,
+        state: RecognizerState,
+        transitions: &mut Vec<GestureTransition>,
+    ) {
+        let entry = &mut self.entries[index];
+        if entry.state == state && state != RecognizerState::Changed {
+            return;
+        }
+        entry.state = state;
+
+        let transition = GestureTransition {
+            id: entry.id.clone(),
+            state,
+            values: entry.recognizer.values(),
+        };
+        if let Some(callback) = self.callbacks.get(&transition.id) {
+            callback(&transition);
+        }
+        transitions.push(transition);
+    }
+
+    /// Start over once no enabled recognizer is still possible or running
+    fn reset_if_finished(&mut self) {
+        let finished = self
+            .entries
+            .iter()
+            .filter(|entry| entry.enabled)
+            .all(|entry| entry.state.is_finished() && entry.waiting.is_none());
+        if finished && !self.entries.is_empty() {
+            self.reset_all();
+        }
+    }
+
+    fn reset_all(&mut self) {
+        for entry in &mut self.entries {
+            entry.state = RecognizerState::Possible;
+            entry.waiting = None;
+            entry.recognizer.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, x: f64, y: f64) -> TouchPoint {
+        TouchPoint {
+            id,
+            x,
+            y,
+            pressure: 1.0,
+            timestamp: 0,
+        }
+    }
+
+    fn start(touches: Vec<TouchPoint>) -> GestureEvent {
+        GestureEvent::TouchStart { touches }
+    }
+
+    fn moved(touches: Vec<TouchPoint>) -> GestureEvent {
+        GestureEvent::TouchMove { touches }
+    }
+
+    fn end(touches: Vec<TouchPoint>) -> GestureEvent {
+        GestureEvent::TouchEnd { touches }
+    }
+
+    fn states(transitions: &[GestureTransition], id: &str) -> Vec<RecognizerState> {
+        transitions
+            .iter()
+            .filter(|transition| transition.id == id)
+            .map(|transition| transition.state)
+            .collect()
+    }
+
+    fn tap(arbiter: &mut GestureArbiter, at: u64) -> Vec<GestureTransition> {
+        let mut transitions = arbiter.handle_event(&start(vec![touch(1, 10.0, 10.0)]), at);
+        transitions.extend(arbiter.handle_event(&end(vec![touch(1, 10.0, 10.0)]), at + 50));
+        transitions
+    }
+
+    #[test]
+    fn test_pan_states() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter.add("pan", PanRecognizer::new());
+
+        arbiter.handle_event(&start(vec![touch(1, 0.0, 0.0)]), 0);
+        assert_eq!(arbiter.state("pan"), Some(RecognizerState::Possible));
+
+        let began = arbiter.handle_event(&moved(vec![touch(1, 20.0, 0.0)]), 16);
+        assert_eq!(states(&began, "pan"), vec![RecognizerState::Began]);
+        assert_eq!(began[0].values.translation, (20.0, 0.0));
+
+        let changed = arbiter.handle_event(&moved(vec![touch(1, 30.0, 5.0)]), 32);
+        assert_eq!(states(&changed, "pan"), vec![RecognizerState::Changed]);
+
+        let ended = arbiter.handle_event(&end(vec![touch(1, 30.0, 5.0)]), 48);
+        assert_eq!(states(&ended, "pan"), vec![RecognizerState::Ended]);
+
+        // Every recognizer finished, so the arbiter is ready for the next gesture
+        assert_eq!(arbiter.state("pan"), Some(RecognizerState::Possible));
+    }
+
+    #[test]
+    fn test_first_recognizer_to_begin_wins() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("pan", PanRecognizer::new().max_touches(2))
+            .add("pinch", PinchRecognizer::new());
+
+        arbiter.handle_event(&start(vec![touch(1, 0.0, 0.0), touch(2, 100.0, 0.0)]), 0);
+        let transitions =
+            arbiter.handle_event(&moved(vec![touch(1, 20.0, 0.0), touch(2, 120.0, 0.0)]), 16);
+
+        // A two finger pan without scaling must not also report a pinch
+        assert_eq!(states(&transitions, "pan"), vec![RecognizerState::Began]);
+        assert_eq!(states(&transitions, "pinch"), vec![RecognizerState::Failed]);
+    }
+
+    #[test]
+    fn test_simultaneous_recognizers() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("pan", PanRecognizer::new().max_touches(2))
+            .add("pinch", PinchRecognizer::new())
+            .simultaneous_with("pan", "pinch");
+
+        arbiter.handle_event(&start(vec![touch(1, 0.0, 0.0), touch(2, 100.0, 0.0)]), 0);
+        let transitions =
+            arbiter.handle_event(&moved(vec![touch(1, 0.0, 0.0), touch(2, 150.0, 0.0)]), 16);
+
+        assert_eq!(states(&transitions, "pan"), vec![RecognizerState::Began]);
+        assert_eq!(states(&transitions, "pinch"), vec![RecognizerState::Began]);
+        assert_eq!(arbiter.active_recognizers(), vec!["pan", "pinch"]);
+    }
+
+    #[test]
+    fn test_exclusive_recognizer_takes_over() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("pan", PanRecognizer::new())
+            .add("pinch", PinchRecognizer::new())
+            .exclusive("pinch", "pan");
+
+        arbiter.handle_event(&start(vec![touch(1, 0.0, 0.0)]), 0);
+        arbiter.handle_event(&moved(vec![touch(1, 20.0, 0.0)]), 16);
+        assert_eq!(arbiter.state("pan"), Some(RecognizerState::Began));
+        assert_eq!(arbiter.state("pinch"), Some(RecognizerState::Possible));
+
+        // A second finger lands and starts pinching
+        arbiter.handle_event(&start(vec![touch(2, 120.0, 0.0)]), 32);
+        let transitions = arbiter.handle_event(&moved(vec![touch(2, 200.0, 0.0)]), 48);
+
+        assert_eq!(states(&transitions, "pinch"), vec![RecognizerState::Began]);
+        assert!(states(&transitions, "pan").contains(&RecognizerState::Cancelled));
+        assert_eq!(arbiter.active_recognizers(), vec!["pinch"]);
+    }
+
+    #[test]
+    fn test_single_tap_waits_for_double_tap_to_fail() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("tap", TapRecognizer::new())
+            .add("double_tap", TapRecognizer::new().taps_required(2))
+            .require_to_fail("tap", "double_tap");
+
+        let transitions = tap(&mut arbiter, 0);
+        assert!(states(&transitions, "tap").is_empty());
+
+        // The double tap times out, releasing the single tap
+        let transitions = arbiter.tick(500);
+        assert_eq!(
+            states(&transitions, "double_tap"),
+            vec![RecognizerState::Failed]
+        );
+        assert_eq!(states(&transitions, "tap"), vec![RecognizerState::Ended]);
+    }
+
+    #[test]
+    fn test_double_tap_fails_single_tap() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("tap", TapRecognizer::new())
+            .add("double_tap", TapRecognizer::new().taps_required(2))
+            .require_to_fail("tap", "double_tap");
+
+        tap(&mut arbiter, 0);
+        let transitions = tap(&mut arbiter, 150);
+
+        assert_eq!(
+            states(&transitions, "double_tap"),
+            vec![RecognizerState::Ended]
+        );
+        assert_eq!(transitions[0].values.tap_count, 2);
+        assert_eq!(states(&transitions, "tap"), vec![RecognizerState::Failed]);
+    }
+
+    #[test]
+    fn test_disabled_recognizer() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("tap", TapRecognizer::new())
+            .add("double_tap", TapRecognizer::new().taps_required(2))
+            .require_to_fail("tap", "double_tap");
+        arbiter.set_enabled("double_tap", false);
+        assert!(!arbiter.is_enabled("double_tap"));
+
+        // Disabled requirements count as failed, so the tap fires immediately
+        let transitions = tap(&mut arbiter, 0);
+        assert_eq!(states(&transitions, "tap"), vec![RecognizerState::Ended]);
+        assert!(states(&transitions, "double_tap").is_empty());
+    }
+
+    #[test]
+    fn test_disabling_running_recognizer_cancels_it() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter.add("pan", PanRecognizer::new());
+
+        arbiter.handle_event(&start(vec![touch(1, 0.0, 0.0)]), 0);
+        arbiter.handle_event(&moved(vec![touch(1, 20.0, 0.0)]), 16);
+
+        let transitions = arbiter.set_enabled("pan", false);
+        assert_eq!(
+            states(&transitions, "pan"),
+            vec![RecognizerState::Cancelled]
+        );
+        assert!(arbiter.active_recognizers().is_empty());
+    }
+
+    #[test]
+    fn test_rotation_recognizer() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("pinch", PinchRecognizer::new())
+            .add("rotation", RotationRecognizer::new())
+            .simultaneous_with("pinch", "rotation");
+
+        arbiter.handle_event(&start(vec![touch(1, 0.0, 0.0), touch(2, 100.0, 0.0)]), 0);
+        // Quarter turn around the first touch without changing the span
+        let transitions = arbiter.handle_event(&moved(vec![touch(2, 0.0, 100.0)]), 16);
+
+        assert_eq!(
+            states(&transitions, "rotation"),
+            vec![RecognizerState::Began]
+        );
+        assert!(states(&transitions, "pinch").is_empty());
+        let rotation = transitions[0].values.rotation;
+        assert!((rotation - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transition_callbacks() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_callback = seen.clone();
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add("tap", TapRecognizer::new())
+            .on_transition("tap", move |transition| {
+                seen_callback.lock().unwrap().push(transition.state);
+            });
+
+        tap(&mut arbiter, 0);
+        assert_eq!(*seen.lock().unwrap(), vec![RecognizerState::Ended]);
+    }
+}
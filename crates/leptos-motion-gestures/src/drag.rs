@@ -1,5 +1,6 @@
 //! Drag gesture implementation
 
+use crate::arbitration::{GestureRecognizer, GestureValues, RecognizerState};
 use crate::{GestureEvent, GestureHandler, GestureResult};
 use std::time::{Duration, Instant};
 
@@ -178,6 +179,54 @@ impl GestureHandler for DragGesture {
     }
 }
 
+impl GestureRecognizer for DragGesture {
+    fn handle_event(&mut self, event: &GestureEvent, _now_ms: u64) -> RecognizerState {
+        let recognized = self.active && self.exceeds_threshold();
+        match event {
+            GestureEvent::TouchStart { .. } if self.active => {
+                if recognized {
+                    RecognizerState::Changed
+                } else {
+                    RecognizerState::Possible
+                }
+            }
+            GestureEvent::TouchStart { .. } => {
+                self.handle_gesture(event.clone());
+                RecognizerState::Possible
+            }
+            GestureEvent::TouchMove { .. } if self.active => {
+                self.handle_gesture(event.clone());
+                match (recognized, self.exceeds_threshold()) {
+                    (false, true) => RecognizerState::Began,
+                    (true, _) => RecognizerState::Changed,
+                    (false, false) => RecognizerState::Possible,
+                }
+            }
+            GestureEvent::TouchEnd { .. } if self.active => {
+                self.handle_gesture(event.clone());
+                if recognized {
+                    RecognizerState::Ended
+                } else {
+                    RecognizerState::Failed
+                }
+            }
+            _ => RecognizerState::Possible,
+        }
+    }
+
+    fn values(&self) -> GestureValues {
+        GestureValues {
+            location: self.current_position.unwrap_or_default(),
+            translation: self.get_delta(),
+            ..GestureValues::default()
+        }
+    }
+
+    fn reset(&mut self) {
+        GestureHandler::reset(self);
+    }
+}
+
 impl Default for DragGesture {
     fn default() -> Self {
         Self::new()
@@ -188,6 +237,7 @@ impl Default for DragGesture {
 mod tests {
     use super::*;
     use crate::TouchPoint;
+    use crate::arbitration::GestureArbiter;
 
     fn create_touch_point(x: f64, y: f64) -> TouchPoint {
         TouchPoint {
@@ -240,4 +290,41 @@ mod tests {
         let _result = drag.handle_gesture(end_event);
         assert!(!drag.active);
     }
+
+    #[test]
+    fn test_drag_gesture_begins_in_arbiter_past_threshold() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter.add("drag", DragGesture::new().threshold(5.0));
+
+        arbiter.handle_event(
+            &GestureEvent::TouchStart {
+                touches: vec![create_touch_point(100.0, 100.0)],
+            },
+            0,
+        );
+        let transitions = arbiter.handle_event(
+            &GestureEvent::TouchMove {
+                touches: vec![create_touch_point(102.0, 100.0)],
+            },
+            16,
+        );
+        assert!(transitions.is_empty());
+
+        let transitions = arbiter.handle_event(
+            &GestureEvent::TouchMove {
+                touches: vec![create_touch_point(110.0, 100.0)],
+            },
+            32,
+        );
+        assert_eq!(transitions[0].state, RecognizerState::Began);
+        assert_eq!(transitions[0].values.translation, (10.0, 0.0));
+
+        let transitions = arbiter.handle_event(
+            &GestureEvent::TouchEnd {
+                touches: vec![create_touch_point(110.0, 100.0)],
+            },
+            48,
+        );
+        assert_eq!(transitions[0].state, RecognizerState::Ended);
+    }
 }
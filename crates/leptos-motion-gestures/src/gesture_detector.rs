@@ -1,8 +1,10 @@
 //! Main gesture detector that coordinates all gesture types
 
-use crate::drag::DragGesture;
+use crate::arbitration::{
+    GestureArbiter, GestureTransition, PanRecognizer, PinchRecognizer, RecognizerState,
+    RotationRecognizer, TapRecognizer,
+};
 use crate::hover::HoverGesture;
-use crate::tap::TapGesture;
 use crate::{
     GestureConfig, GestureEvent, GestureHandler, GestureResult, MultiTouchGestureDetector,
     MultiTouchGestureType, MultiTouchState, TouchPoint,
@@ -12,21 +14,27 @@ use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 use web_sys::{Element, MouseEvent, PointerEvent, TouchEvent};
 
+/// Arbiter id of the recognizer gating the pinch callback
+pub const PINCH_RECOGNIZER: &str = "pinch";
+
+/// Arbiter id of the recognizer gating the rotation callback
+pub const ROTATION_RECOGNIZER: &str = "rotation";
+
+/// Arbiter id of the single tap recognizer
+pub const TAP_RECOGNIZER: &str = "tap";
+
+/// Arbiter id of the single touch pan recognizer, which drags start from
+pub const PAN_RECOGNIZER: &str = "pan";
+
 /// Main gesture detector that coordinates all gesture types
 pub struct GestureDetector {
     /// Configuration for all gestures
     config: GestureConfig,
     /// Multi-touch gesture detector
     multi_touch: MultiTouchGestureDetector,
-    /// Drag gesture detector
-    #[allow(dead_code)]
-    drag: DragGesture,
     /// Hover gesture detector
     #[allow(dead_code)]
     hover: HoverGesture,
-    /// Tap gesture detector
-    #[allow(dead_code)]
-    tap: TapGesture,
     /// Element this detector is attached to
     element: Option<Element>,
     /// Active gesture callbacks
@@ -35,21 +43,42 @@ pub struct GestureDetector {
     current_gesture: Option<MultiTouchState>,
     /// Gesture event history
     event_history: Vec<GestureEvent>,
+    /// Recognizers arbitrated against each other
+    arbiter: GestureArbiter,
+    /// Timestamp of the last event with touch data
+    last_event_time: u64,
 }
 
 impl GestureDetector {
     /// Create a new gesture detector
+    ///
+    /// Taps, pans, pinches and rotations are arbitrated as
+    /// [`TAP_RECOGNIZER`], [`PAN_RECOGNIZER`], [`PINCH_RECOGNIZER`] and
+    /// [`ROTATION_RECOGNIZER`]. Pinch and rotation may run together; any
+    /// other pair competes, the first to begin failing the rest.
     pub fn new(config: GestureConfig) -> Self {
+        let mut arbiter = GestureArbiter::new();
+        arbiter
+            .add(TAP_RECOGNIZER, TapRecognizer::new())
+            .add(
+                PAN_RECOGNIZER,
+                PanRecognizer::new().min_distance(config.min_distance),
+            )
+            .add(PINCH_RECOGNIZER, PinchRecognizer::new())
+            .add(ROTATION_RECOGNIZER, RotationRecognizer::new())
+            .simultaneous_with(PINCH_RECOGNIZER, ROTATION_RECOGNIZER);
+        Self::enable_recognizers(&mut arbiter, &config);
+
         Self {
             config: config.clone(),
             multi_touch: MultiTouchGestureDetector::new(config),
-            drag: DragGesture::new(),
             hover: HoverGesture::new(),
-            tap: TapGesture::new(),
             element: None,
             callbacks: HashMap::new(),
             current_gesture: None,
             event_history: Vec::new(),
+            arbiter,
+            last_event_time: 0,
         }
     }
 
@@ -151,12 +180,57 @@ impl GestureDetector {
             .insert("rotation".to_string(), Box::new(wrapped_callback));
     }
 
+    /// Register a callback for taps
+    ///
+    /// Fires with the tap location once [`TAP_RECOGNIZER`] recognizes, and
+    /// replaces any [`GestureArbiter::on_transition`] callback of it.
+    pub fn on_tap<F>(&mut self, callback: F)
+    where
+        F: Fn((f64, f64)) + Send + Sync + 'static,
+    {
+        self.arbiter
+            .on_transition(TAP_RECOGNIZER, move |transition| {
+                if transition.state == RecognizerState::Ended {
+                    callback(transition.values.location);
+                }
+            });
+    }
+
+    /// Register a callback for pans
+    ///
+    /// Fires with the translation since the pan started while
+    /// [`PAN_RECOGNIZER`] runs and when it ends, and replaces any
+    /// [`GestureArbiter::on_transition`] callback of it.
+    pub fn on_pan<F>(&mut self, callback: F)
+    where
+        F: Fn((f64, f64)) + Send + Sync + 'static,
+    {
+        self.arbiter
+            .on_transition(PAN_RECOGNIZER, move |transition| {
+                if matches!(
+                    transition.state,
+                    RecognizerState::Began | RecognizerState::Changed | RecognizerState::Ended
+                ) {
+                    callback(transition.values.translation);
+                }
+            });
+    }
+
     /// Update the gesture configuration
     pub fn update_config(&mut self, config: GestureConfig) {
+        Self::enable_recognizers(&mut self.arbiter, &config);
         self.config = config.clone();
         self.multi_touch.update_config(config);
     }
 
+    /// Enable the built-in recognizers `config` asks for
+    fn enable_recognizers(arbiter: &mut GestureArbiter, config: &GestureConfig) {
+        arbiter.set_enabled(TAP_RECOGNIZER, config.basic_gestures);
+        arbiter.set_enabled(PAN_RECOGNIZER, config.basic_gestures);
+        arbiter.set_enabled(PINCH_RECOGNIZER, config.pinch_to_zoom);
+        arbiter.set_enabled(ROTATION_RECOGNIZER, config.rotation);
+    }
+
     /// Get the current multi-touch state
     pub fn get_multi_touch_state(&self) -> Option<&MultiTouchState> {
         self.current_gesture.as_ref()
@@ -171,6 +245,34 @@ impl GestureDetector {
         }
     }
 
+    /// Recognizers run against every processed event
+    pub fn arbiter(&self) -> &GestureArbiter {
+        &self.arbiter
+    }
+
+    /// Register recognizers and their relationships
+    ///
+    /// Recognizers added here compete with the built-in ones: a two finger
+    /// pan that begins first fails the pinch and holds back the pinch
+    /// callback, unless the two are made
+    /// [`simultaneous_with`](GestureArbiter::simultaneous_with) each other.
+    pub fn arbiter_mut(&mut self) -> &mut GestureArbiter {
+        &mut self.arbiter
+    }
+
+    /// Advance time to `now_ms` without input
+    ///
+    /// Recognizers waiting on a timeout, like a single tap waiting for a
+    /// double tap to fail, only fire when time advances. Events do that
+    /// too, but none arrive after the last tap, so call this from a timer,
+    /// e.g. every animation frame while [`GestureArbiter::state`] reports a
+    /// recognizer still possible. Times are in the clock of the touch
+    /// timestamps.
+    pub fn tick(&mut self, now_ms: u64) -> Vec<GestureTransition> {
+        self.last_event_time = self.last_event_time.max(now_ms);
+        self.arbiter.tick(self.last_event_time)
+    }
+
     /// Set up touch event listeners
     fn setup_touch_events(&self, element: &Element) -> Result<(), JsValue> {
         let touchstart_callback = Closure::wrap(Box::new(move |event: TouchEvent| {
@@ -331,6 +433,20 @@ impl GestureDetector {
             self.event_history.remove(0);
         }
 
+        // Arbitrate registered recognizers; their callbacks fire from the arbiter
+        if let GestureEvent::TouchStart { touches }
+        | GestureEvent::TouchMove { touches }
+        | GestureEvent::TouchEnd { touches } = &event
+            && let Some(timestamp) = touches.iter().map(|touch| touch.timestamp).max()
+        {
+            self.last_event_time = self.last_event_time.max(timestamp);
+        }
+        // Time out waiting recognizers before the new input reaches them
+        let mut transitions = self.arbiter.tick(self.last_event_time);
+        transitions.extend(self.arbiter.handle_event(&event, self.last_event_time));
+        let pinch_allowed = self.is_recognized(PINCH_RECOGNIZER, &transitions);
+        let rotation_allowed = self.is_recognized(ROTATION_RECOGNIZER, &transitions);
+
         // Process with multi-touch detector
         let result = self.multi_touch.handle_gesture(event.clone());
 
@@ -342,27 +458,24 @@ impl GestureDetector {
             self.current_gesture = Some(multi_touch_state);
         }
 
-        // Trigger callbacks based on gesture type
-        match result.gesture_type {
-            MultiTouchGestureType::Pinch => {
-                if let Some(callback) = self.callbacks.get("pinch") {
-                    callback(result.clone());
-                }
-            }
-            MultiTouchGestureType::Rotation => {
-                if let Some(callback) = self.callbacks.get("rotation") {
-                    callback(result.clone());
-                }
-            }
-            MultiTouchGestureType::PinchAndRotate => {
-                if let Some(callback) = self.callbacks.get("pinch") {
-                    callback(result.clone());
-                }
-                if let Some(callback) = self.callbacks.get("rotation") {
-                    callback(result.clone());
-                }
-            }
-            _ => {}
+        // Trigger callbacks based on gesture type, if the arbiter let it recognize
+        let (pinch, rotation) = match result.gesture_type {
+            MultiTouchGestureType::Pinch => (true, false),
+            MultiTouchGestureType::Rotation => (false, true),
+            MultiTouchGestureType::PinchAndRotate => (true, true),
+            _ => (false, false),
+        };
+        if pinch
+            && pinch_allowed
+            && let Some(callback) = self.callbacks.get("pinch")
+        {
+            callback(result.clone());
+        }
+        if rotation
+            && rotation_allowed
+            && let Some(callback) = self.callbacks.get("rotation")
+        {
+            callback(result.clone());
         }
 
         // Always trigger multi-touch callback if registered
@@ -376,6 +489,20 @@ impl GestureDetector {
         }
     }
 
+    /// Check if recognizer `id` is running or recognized in `transitions`
+    fn is_recognized(&self, id: &str, transitions: &[GestureTransition]) -> bool {
+        self.arbiter
+            .state(id)
+            .is_some_and(|state| state.is_active())
+            || transitions.iter().any(|transition| {
+                transition.id == id
+                    && matches!(
+                        transition.state,
+                        RecognizerState::Began | RecognizerState::Changed | RecognizerState::Ended
+                    )
+            })
+    }
+
     /// Get gesture event history
     pub fn get_event_history(&self) -> &[GestureEvent] {
         &self.event_history
@@ -421,6 +548,7 @@ impl GestureHandler for GestureDetector {
 
     fn reset(&mut self) {
         self.multi_touch.reset();
+        self.arbiter.reset();
         self.current_gesture = None;
         self.event_history.clear();
     }
@@ -509,17 +637,56 @@ impl Default for GestureDetectorBuilder {
 mod tests {
     use super::*;
     use crate::TouchPoint;
+    use crate::arbitration::{PanRecognizer, TapRecognizer};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     fn create_touch_point(id: u64, x: f64, y: f64) -> TouchPoint {
+        create_touch_point_at(id, x, y, 0)
+    }
+
+    fn create_touch_point_at(id: u64, x: f64, y: f64, timestamp: u64) -> TouchPoint {
         TouchPoint {
             id,
             x,
             y,
             pressure: 1.0,
-            timestamp: 0,
+            timestamp,
         }
     }
 
+    /// Spread two fingers from 100px to 200px apart, after a two finger pan
+    fn pan_then_spread(detector: &mut GestureDetector) {
+        let frames = [
+            (0.0, 100.0),
+            (20.0, 120.0),
+            (40.0, 140.0),
+            (40.0, 240.0),
+            (40.0, 280.0),
+        ];
+        for (index, (left, right)) in frames.into_iter().enumerate() {
+            let touches = vec![
+                create_touch_point_at(1, left, 0.0, index as u64 * 16),
+                create_touch_point_at(2, right, 0.0, index as u64 * 16),
+            ];
+            let event = if index == 0 {
+                GestureEvent::TouchStart { touches }
+            } else {
+                GestureEvent::TouchMove { touches }
+            };
+            detector.handle_gesture(event);
+        }
+    }
+
+    fn count_pinches(detector: &mut GestureDetector) -> Arc<AtomicUsize> {
+        let pinches = Arc::new(AtomicUsize::new(0));
+        let counter = pinches.clone();
+        detector.on_pinch(move |_scale| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        pinches
+    }
+
     #[test]
     fn test_gesture_detector_creation() {
         let detector = GestureDetector::default();
@@ -558,4 +725,112 @@ mod tests {
         // Should recognize the gesture
         assert!(result.recognized);
     }
+
+    #[test]
+    fn test_pinch_callback_fires_when_arbitrated() {
+        let mut detector = GestureDetector::default();
+        let pinches = count_pinches(&mut detector);
+
+        pan_then_spread(&mut detector);
+
+        assert!(pinches.load(Ordering::SeqCst) > 0);
+    }
+
+    #[test]
+    fn test_pan_that_began_first_holds_back_pinch() {
+        let mut detector = GestureDetector::default();
+        detector
+            .arbiter_mut()
+            .add("pan", PanRecognizer::new().max_touches(2));
+        let pinches = count_pinches(&mut detector);
+
+        pan_then_spread(&mut detector);
+
+        assert_eq!(
+            detector.arbiter().state("pan"),
+            Some(RecognizerState::Changed)
+        );
+        assert_eq!(
+            detector.arbiter().state(PINCH_RECOGNIZER),
+            Some(RecognizerState::Failed)
+        );
+        assert_eq!(pinches.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_tick_fires_single_tap_after_double_tap_times_out() {
+        let taps = Arc::new(AtomicUsize::new(0));
+        let counter = taps.clone();
+        let mut detector = GestureDetector::default();
+        detector
+            .arbiter_mut()
+            .add("tap", TapRecognizer::new())
+            .add("double_tap", TapRecognizer::new().taps_required(2))
+            .require_to_fail("tap", "double_tap")
+            .on_transition("tap", move |transition| {
+                if transition.state == RecognizerState::Ended {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+
+        detector.handle_gesture(GestureEvent::TouchStart {
+            touches: vec![create_touch_point_at(1, 10.0, 10.0, 0)],
+        });
+        detector.handle_gesture(GestureEvent::TouchEnd {
+            touches: vec![create_touch_point_at(1, 10.0, 10.0, 50)],
+        });
+        assert_eq!(taps.load(Ordering::SeqCst), 0);
+
+        // Still within the double tap timeout
+        assert!(detector.tick(200).is_empty());
+        assert_eq!(taps.load(Ordering::SeqCst), 0);
+
+        let transitions = detector.tick(500);
+        assert!(
+            transitions
+                .iter()
+                .any(|transition| transition.id == "tap"
+                    && transition.state == RecognizerState::Ended)
+        );
+        assert_eq!(taps.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_drag_fails_the_builtin_tap() {
+        let taps = Arc::new(AtomicUsize::new(0));
+        let tap_counter = taps.clone();
+        let pans = Arc::new(AtomicUsize::new(0));
+        let pan_counter = pans.clone();
+        let mut detector = GestureDetector::default();
+        detector.on_tap(move |_location| {
+            tap_counter.fetch_add(1, Ordering::SeqCst);
+        });
+        detector.on_pan(move |_translation| {
+            pan_counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        detector.handle_gesture(GestureEvent::TouchStart {
+            touches: vec![create_touch_point_at(1, 0.0, 0.0, 0)],
+        });
+        detector.handle_gesture(GestureEvent::TouchMove {
+            touches: vec![create_touch_point_at(1, 40.0, 0.0, 16)],
+        });
+        assert_eq!(
+            detector.arbiter().state(TAP_RECOGNIZER),
+            Some(RecognizerState::Failed)
+        );
+        detector.handle_gesture(GestureEvent::TouchEnd {
+            touches: vec![create_touch_point_at(1, 40.0, 0.0, 32)],
+        });
+        assert_eq!(taps.load(Ordering::SeqCst), 0);
+        assert_eq!(pans.load(Ordering::SeqCst), 2);
+
+        detector.handle_gesture(GestureEvent::TouchStart {
+            touches: vec![create_touch_point_at(1, 0.0, 0.0, 100)],
+        });
+        detector.handle_gesture(GestureEvent::TouchEnd {
+            touches: vec![create_touch_point_at(1, 0.0, 0.0, 150)],
+        });
+        assert_eq!(taps.load(Ordering::SeqCst), 1);
+    }
 }
@@ -2,6 +2,7 @@
 
 #![warn(missing_docs)]
 
+pub mod arbitration;
 pub mod drag;
 pub mod gesture_detector;
 pub mod hover;
@@ -238,7 +239,13 @@ pub trait GestureHandler {
     fn reset(&mut self);
 }
 
-pub use gesture_detector::GestureDetector;
+pub use arbitration::{
+    GestureArbiter, GestureRecognizer, GestureTransition, GestureValues, PanRecognizer,
+    PinchRecognizer, RecognizerState, RotationRecognizer, TapRecognizer,
+};
+pub use gesture_detector::{
+    GestureDetector, PAN_RECOGNIZER, PINCH_RECOGNIZER, ROTATION_RECOGNIZER, TAP_RECOGNIZER,
+};
 /// Re-export commonly used types
 pub use multi_touch::MultiTouchGestureDetector;
 
@@ -1,5 +1,6 @@
 //! Tap gesture implementation
 
+use crate::arbitration::{GestureRecognizer, GestureValues, RecognizerState};
 use crate::{GestureEvent, GestureHandler, GestureResult};
 use std::time::{Duration, Instant};
 
@@ -269,6 +270,61 @@ impl GestureHandler for TapGesture {
     }
 }
 
+impl GestureRecognizer for TapGesture {
+    fn handle_event(&mut self, event: &GestureEvent, _now_ms: u64) -> RecognizerState {
+        let pressed = self.active;
+        match event {
+            GestureEvent::TouchStart { touches } if pressed || touches.len() > 1 => {
+                RecognizerState::Failed
+            }
+            GestureEvent::TouchMove { .. } if pressed => {
+                self.handle_gesture(event.clone());
+                if self
+                    .get_distance()
+                    .is_some_and(|distance| distance > self.max_distance)
+                {
+                    RecognizerState::Failed
+                } else {
+                    RecognizerState::Possible
+                }
+            }
+            GestureEvent::TouchEnd { .. } if pressed => {
+                self.handle_gesture(event.clone());
+                if self.is_valid_tap() {
+                    RecognizerState::Ended
+                } else {
+                    RecognizerState::Failed
+                }
+            }
+            GestureEvent::TouchStart { .. } => {
+                self.handle_gesture(event.clone());
+                RecognizerState::Possible
+            }
+            _ => RecognizerState::Possible,
+        }
+    }
+
+    fn values(&self) -> GestureValues {
+        GestureValues {
+            location: self
+                .end_position
+                .or(self.start_position)
+                .unwrap_or_default(),
+            tap_count: self.tap_count,
+            ..GestureValues::default()
+        }
+    }
+
+    /// Clear the press, keeping the tap history for multi-tap counting
+    fn reset(&mut self) {
+        self.active = false;
+        self.start_position = None;
+        self.end_position = None;
+        self.start_time = None;
+        self.end_time = None;
+    }
+}
+
 impl Default for TapGesture {
     fn default() -> Self {
         Self::new()
@@ -279,6 +335,7 @@ impl Default for TapGesture {
 mod tests {
     use super::*;
     use crate::TouchPoint;
+    use crate::arbitration::GestureArbiter;
 
     fn create_touch_point(x: f64, y: f64) -> TouchPoint {
         TouchPoint {
@@ -328,4 +385,46 @@ mod tests {
         tap.end_position = Some((10.0, 10.0));
         assert!(!tap.is_valid_tap());
     }
+
+    #[test]
+    fn test_tap_gesture_fails_in_arbiter_once_moved_too_far() {
+        let mut arbiter = GestureArbiter::new();
+        arbiter.add("tap", TapGesture::new().max_distance(5.0));
+
+        arbiter.handle_event(
+            &GestureEvent::TouchStart {
+                touches: vec![create_touch_point(0.0, 0.0)],
+            },
+            0,
+        );
+        let transitions = arbiter.handle_event(
+            &GestureEvent::TouchMove {
+                touches: vec![create_touch_point(20.0, 0.0)],
+            },
+            16,
+        );
+        assert_eq!(transitions[0].state, RecognizerState::Failed);
+
+        arbiter.handle_event(
+            &GestureEvent::TouchEnd {
+                touches: vec![create_touch_point(20.0, 0.0)],
+            },
+            32,
+        );
+        arbiter.handle_event(
+            &GestureEvent::TouchStart {
+                touches: vec![create_touch_point(0.0, 0.0)],
+            },
+            100,
+        );
+        let transitions = arbiter.handle_event(
+            &GestureEvent::TouchEnd {
+                touches: vec![create_touch_point(1.0, 1.0)],
+            },
+            150,
+        );
+        assert_eq!(transitions[0].state, RecognizerState::Ended);
+        assert_eq!(transitions[0].values.location, (1.0, 1.0));
+        assert_eq!(transitions[0].values.tap_count, 1);
+    }
 }
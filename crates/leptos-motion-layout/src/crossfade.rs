@@ -0,0 +1,327 @@
+//! Crossfading shared element transitions
+//!
+//! Scaling one element between a thumbnail and a full card stretches its
+//! content. A crossfade instead flies copies of both elements together
+//! above the page: their box moves from one layout to the other, reflowing
+//! the content as it goes, while the outgoing copy fades out and the
+//! incoming one fades in, each on a curve of its own.
+
+use crate::LayoutInfo;
+use crate::shared_elements::{SharedElementConfig, elevated_copy};
+use leptos_motion_core::{Easing, run_frames};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
+
+/// Opacity curves of a crossfading shared element transition
+///
+/// The curves run over the whole transition but apart from its easing, so
+/// the fade can lead or trail the movement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossfadeConfig {
+    /// Easing of the outgoing element fading out
+    pub fade_out: Easing,
+    /// Easing of the incoming element fading in
+    pub fade_in: Easing,
+}
+
+impl Default for CrossfadeConfig {
+    fn default() -> Self {
+        Self {
+            fade_out: Easing::EaseOut,
+            fade_in: Easing::EaseIn,
+        }
+    }
+}
+
+/// State of a crossfade at one point of its transition
+#[derive(Debug, Clone)]
+pub struct CrossfadeFrame {
+    /// Viewport box both elements are laid out at
+    pub layout: LayoutInfo,
+    /// Opacity of the outgoing element
+    pub outgoing_opacity: f64,
+    /// Opacity of the incoming element
+    pub incoming_opacity: f64,
+}
+
+impl CrossfadeConfig {
+    /// Frame of a crossfade from `from` to `to` at `progress`, moving along `easing`
+    pub fn frame(
+        &self,
+        from: &LayoutInfo,
+        to: &LayoutInfo,
+        easing: &Easing,
+        progress: f64,
+    ) -> CrossfadeFrame {
+        let moved = easing.evaluate(progress);
+        let lerp = |from: f64, to: f64| from + (to - from) * moved;
+        CrossfadeFrame {
+            layout: LayoutInfo::new(
+                lerp(from.x, to.x),
+                lerp(from.y, to.y),
+                lerp(from.width, to.width),
+                lerp(from.height, to.height),
+            ),
+            outgoing_opacity: 1.0 - self.fade_out.evaluate(progress),
+            incoming_opacity: self.fade_in.evaluate(progress),
+        }
+    }
+}
+
+/// Elements of a running crossfade and their copies
+struct CrossfadeState {
+    /// Outgoing and incoming elements, with the inline visibility they had
+    elements: [(HtmlElement, String); 2],
+    /// Copies of the outgoing and incoming elements flying above the page
+    copies: [HtmlElement; 2],
+    /// Boxes the copies fly from and to
+    from: LayoutInfo,
+    to: LayoutInfo,
+    /// Box the copies are at now
+    layout: LayoutInfo,
+    /// Opacities of the outgoing and incoming copies at progress 0 and now
+    start_opacity: [f64; 2],
+    opacity: [f64; 2],
+    curves: CrossfadeConfig,
+    easing: Easing,
+    /// Timestamp the frame clock of [`Crossfade::start`] counts from
+    clock_start: Option<f64>,
+    running: bool,
+}
+
+impl CrossfadeState {
+    /// Lay the copies out at `progress` of the way
+    fn apply(&mut self, progress: f64) {
+        let frame = self
+            .curves
+            .frame(&self.from, &self.to, &self.easing, progress);
+        let [outgoing, incoming] = self.start_opacity;
+        self.opacity = [
+            outgoing * frame.outgoing_opacity,
+            incoming + (1.0 - incoming) * frame.incoming_opacity,
+        ];
+        for (copy, opacity) in self.copies.iter().zip(self.opacity) {
+            let style = copy.style();
+            for (property, value) in [
+                ("left", format!("{}px", frame.layout.x)),
+                ("top", format!("{}px", frame.layout.y)),
+                ("width", format!("{}px", frame.layout.width)),
+                ("height", format!("{}px", frame.layout.height)),
+                ("opacity", opacity.to_string()),
+            ] {
+                let _ = style.set_property(property, &value);
+            }
+        }
+        self.layout = frame.layout;
+    }
+
+    /// Remove the copies and show the elements again
+    fn finish(&mut self) {
+        if !self.running {
+            return;
+        }
+        self.running = false;
+        for copy in &self.copies {
+            copy.remove();
+        }
+        for (element, visibility) in &self.elements {
+            let style = element.style();
+            let _ = if visibility.is_empty() {
+                style.remove_property("visibility").map(|_| ())
+            } else {
+                style.set_property("visibility", visibility)
+            };
+        }
+    }
+}
+
+/// Crossfade between two elements
+///
+/// Either runs on animation frames over its configured duration, or
+/// follows the progress of the transition driving it. Both elements stay
+/// hidden while their copies fly, so dropping the handle doesn't stop the
+/// crossfade, cancelling it does.
+#[derive(Clone)]
+pub struct Crossfade {
+    state: Rc<RefCell<CrossfadeState>>,
+}
+
+impl Crossfade {
+    /// Crossfade `outgoing` laid out at `from` into `incoming` laid out at `to`
+    ///
+    /// Moves along the duration and easing of `config`, fading with its
+    /// [`SharedElementConfig::crossfade`] curves or the default ones.
+    pub fn start(
+        outgoing: &Element,
+        incoming: &Element,
+        from: &LayoutInfo,
+        to: &LayoutInfo,
+        config: &SharedElementConfig,
+    ) -> Result<Self, String> {
+        let crossfade = Self::new(outgoing, incoming, from, to, config)?;
+        let duration_ms = config.duration * 1000.0;
+        let frame_state = crossfade.state.clone();
+        let started = run_frames(move |timestamp| {
+            let mut state = frame_state.borrow_mut();
+            if !state.running {
+                return false;
+            }
+            let start = *state.clock_start.get_or_insert(timestamp);
+            let progress = if duration_ms > 0.0 {
+                ((timestamp - start) / duration_ms).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            if progress >= 1.0 {
+                state.finish();
+                return false;
+            }
+            state.apply(progress);
+            true
+        });
+        if !started {
+            crossfade.cancel();
+            return Err("Failed to request animation frame".to_string());
+        }
+        Ok(crossfade)
+    }
+
+    /// Lay out copies of `outgoing` and `incoming` at `from`, ready to fly to `to`
+    ///
+    /// Nothing moves until [`set_progress`](Self::set_progress) is called,
+    /// for crossfades following a transition driven elsewhere.
+    pub fn new(
+        outgoing: &Element,
+        incoming: &Element,
+        from: &LayoutInfo,
+        to: &LayoutInfo,
+        config: &SharedElementConfig,
+    ) -> Result<Self, String> {
+        let outgoing_copy = elevated_copy(outgoing, from).ok_or("Failed to copy element")?;
+        let Some(incoming_copy) = elevated_copy(incoming, from) else {
+            outgoing_copy.remove();
+            return Err("Failed to copy element".to_string());
+        };
+        let elements = [outgoing, incoming].map(|element| {
+            let element: HtmlElement = element.clone().unchecked_into();
+            let style = element.style();
+            let visibility = style.get_property_value("visibility").unwrap_or_default();
+            let _ = style.set_property("visibility", "hidden");
+            (element, visibility)
+        });
+        let mut state = CrossfadeState {
+            elements,
+            copies: [outgoing_copy, incoming_copy],
+            from: from.clone(),
+            to: to.clone(),
+            layout: from.clone(),
+            start_opacity: [1.0, 0.0],
+            opacity: [1.0, 0.0],
+            curves: config.crossfade.clone().unwrap_or_default(),
+            easing: config.easing.clone(),
+            clock_start: None,
+            running: true,
+        };
+        state.apply(0.0);
+        Ok(Self {
+            state: Rc::new(RefCell::new(state)),
+        })
+    }
+
+    /// Lay the copies out at `progress` of the way, from 0 to 1
+    pub fn set_progress(&self, progress: f64) {
+        let mut state = self.state.borrow_mut();
+        if state.running {
+            state.apply(progress.clamp(0.0, 1.0));
+        }
+    }
+
+    /// Fly the copies back to where they came from, starting where they are
+    ///
+    /// The incoming copy fades back out and the outgoing one back in from
+    /// their current opacities, with progress counting from 0 again.
+    pub fn reverse(&self) {
+        let mut state = self.state.borrow_mut();
+        if !state.running {
+            return;
+        }
+        let state = &mut *state;
+        state.copies.swap(0, 1);
+        state.elements.swap(0, 1);
+        state.to = std::mem::replace(&mut state.from, state.layout.clone());
+        state.start_opacity = [state.opacity[1], state.opacity[0]];
+        state.clock_start = None;
+        state.apply(0.0);
+    }
+
+    /// Whether the copies are still flying
+    pub fn is_running(&self) -> bool {
+        self.state.borrow().running
+    }
+
+    /// Boxes the copies fly from, to and are at now, while they are flying
+    pub fn layouts(&self) -> Option<(LayoutInfo, LayoutInfo, LayoutInfo)> {
+        let state = self.state.borrow();
+        state
+            .running
+            .then(|| (state.from.clone(), state.to.clone(), state.layout.clone()))
+    }
+
+    /// Stop the crossfade, showing both elements where they are laid out
+    ///
+    /// The pending frame sees the crossfade stopped and releases itself.
+    pub fn cancel(&self) {
+        self.state.borrow_mut().finish();
+    }
+}
+
+impl std::fmt::Debug for Crossfade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Crossfade")
+            .field("running", &self.is_running())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crossfade_frame_interpolates_bounds() {
+        let curves = CrossfadeConfig {
+            fade_out: Easing::Linear,
+            fade_in: Easing::Linear,
+        };
+        let from = LayoutInfo::new(0.0, 0.0, 100.0, 100.0);
+        let to = LayoutInfo::new(100.0, 50.0, 300.0, 200.0);
+
+        let frame = curves.frame(&from, &to, &Easing::Linear, 0.5);
+        assert_eq!(frame.layout.x, 50.0);
+        assert_eq!(frame.layout.y, 25.0);
+        assert_eq!(frame.layout.width, 200.0);
+        assert_eq!(frame.layout.height, 150.0);
+        assert_eq!(frame.outgoing_opacity, 0.5);
+        assert_eq!(frame.incoming_opacity, 0.5);
+
+        let end = curves.frame(&from, &to, &Easing::EaseInOut, 1.0);
+        assert_eq!(end.layout.width, 300.0);
+        assert_eq!(end.outgoing_opacity, 0.0);
+        assert_eq!(end.incoming_opacity, 1.0);
+    }
+
+    #[test]
+    fn test_crossfade_curves_are_independent_of_movement() {
+        let curves = CrossfadeConfig::default();
+        let from = LayoutInfo::new(0.0, 0.0, 100.0, 100.0);
+        let to = LayoutInfo::new(0.0, 0.0, 200.0, 100.0);
+
+        let frame = curves.frame(&from, &to, &Easing::Linear, 0.25);
+        assert_eq!(frame.layout.width, 125.0);
+        // The outgoing element fades out early, the incoming one comes in late
+        assert!(frame.outgoing_opacity < 0.75);
+        assert!(frame.incoming_opacity < 0.25);
+    }
+}
@@ -0,0 +1,597 @@
+//! Debugging overlay for layout animations
+//!
+//! [`LayoutDebugOverlay`] draws what a [`LayoutObserver`] measured and is
+//! animating on top of the page. For every animating element it shows the
+//! box the animation started from (dashed), the box the element is laid out
+//! at (solid), the FLIP delta between the two and the box the element is
+//! projected to right now (filled), labelled with its scale and the
+//! corrected corner and shadow styles. Boxes take the color of the
+//! [`PerformanceImpact`] of the layout change between the two boxes.
+//!
+//! One overlay can watch several observers. Animations that are not played
+//! by an observer, such as shared layout animations, are drawn from the
+//! snapshots of sources added with [`LayoutDebugOverlay::add_source`].
+//!
+//! The overlay is opt-in and meant for development builds: a button in the
+//! corner of the page shows and hides it.
+//!
+//! ```rust,ignore
+//! let observer = LayoutObserver::new(&scope)?;
+//! observer.animate_changes(LayoutAnimationConfig::default());
+//! let overlay = LayoutDebugOverlay::attach(&observer)?;
+//! overlay.set_visible(true);
+//! ```
+
+use crate::LayoutInfo;
+use crate::flip::TransformValues;
+use crate::layout_tracker::{LayoutObserver, PerformanceImpact, layout_change_impact};
+use leptos_motion_core::FrameLoop;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use web_sys::{Document, DomRect, Element};
+
+/// Attribute marking the elements of the overlay
+const OVERLAY_ATTRIBUTE: &str = "data-motion-layout-debug";
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Color of boxes whose element has no recorded layout change
+const UNKNOWN_COLOR: &str = "#9e9e9e";
+
+/// Color of the boxes of an element whose last layout change had `impact`
+pub fn impact_color(impact: Option<&PerformanceImpact>) -> &'static str {
+    match impact {
+        Some(PerformanceImpact::Low) => "#2e7d32",
+        Some(PerformanceImpact::Medium) => "#f9a825",
+        Some(PerformanceImpact::High) => "#ef6c00",
+        Some(PerformanceImpact::Critical) => "#c62828",
+        None => UNKNOWN_COLOR,
+    }
+}
+
+/// Box an element laid out at `last` is seen at under a FLIP `transform`
+///
+/// FLIP animations scale from the top left corner, so the corner moves by
+/// the translation and the size scales.
+pub fn projected_layout(last: &LayoutInfo, transform: &TransformValues) -> LayoutInfo {
+    LayoutInfo::new(
+        last.x + transform.translate_x,
+        last.y + transform.translate_y,
+        last.width * transform.scale_x,
+        last.height * transform.scale_y,
+    )
+}
+
+/// What the overlay draws for one animating element
+///
+/// Boxes are in viewport coordinates.
+#[derive(Debug, Clone)]
+pub struct AnimationSnapshot {
+    /// Label of the element
+    pub id: String,
+    /// Box the animation started from
+    pub first: LayoutInfo,
+    /// Box the element is laid out at
+    pub last: LayoutInfo,
+    /// FLIP transform from `last` to where the element is seen now
+    pub transform: TransformValues,
+    /// Corrected styles at the current scale
+    pub corrections: Vec<(&'static str, String)>,
+    /// Performance impact of the layout change from `first` to `last`
+    pub impact: PerformanceImpact,
+}
+
+impl AnimationSnapshot {
+    /// Snapshot of an element animating from `first` to `last`, seen at `current`
+    pub fn between(id: &str, first: LayoutInfo, last: LayoutInfo, current: &LayoutInfo) -> Self {
+        let scale = |current: f64, last: f64| if last > 0.0 { current / last } else { 1.0 };
+        let transform = TransformValues::new(
+            current.x - last.x,
+            current.y - last.y,
+            scale(current.width, last.width),
+            scale(current.height, last.height),
+            0.0,
+        );
+        Self {
+            id: id.to_string(),
+            impact: layout_change_impact(&first, &last),
+            first,
+            last,
+            transform,
+            corrections: Vec::new(),
+        }
+    }
+
+    /// Lines labelling the element: id, FLIP delta, scale and corrections
+    fn labels(&self) -> Vec<String> {
+        let mut labels = vec![
+            self.id.clone(),
+            format!(
+                "Δ {:.1}, {:.1}  scale {:.3} × {:.3}",
+                self.first.x - self.last.x,
+                self.first.y - self.last.y,
+                self.transform.scale_x,
+                self.transform.scale_y,
+            ),
+        ];
+        labels.extend(
+            self.corrections
+                .iter()
+                .map(|(property, value)| format!("{property}: {value}")),
+        );
+        labels
+    }
+}
+
+/// Box of a `DomRect`
+fn rect_layout(rect: &DomRect) -> LayoutInfo {
+    LayoutInfo::new(rect.x(), rect.y(), rect.width(), rect.height())
+}
+
+/// Source of snapshots of animations no observer plays
+type SnapshotSource = Box<dyn Fn() -> Vec<AnimationSnapshot>>;
+
+/// Overlay elements and what it knows about the observed layout
+struct OverlayState {
+    observers: Vec<LayoutObserver>,
+    sources: Vec<SnapshotSource>,
+    document: Document,
+    svg: Element,
+    layer: Element,
+    toggle: Element,
+    visible: bool,
+}
+
+/// Callbacks handed to the browser, alive as long as a handle is
+struct OverlayCallbacks {
+    frame: FrameLoop,
+    _toggle: Closure<dyn FnMut()>,
+}
+
+/// Overlay drawing the layout animations of [`LayoutObserver`]s
+///
+/// Keeps the observers it watches running while attached. The overlay and
+/// its toggle button are removed once the last handle is dropped.
+#[derive(Clone)]
+pub struct LayoutDebugOverlay {
+    state: Rc<RefCell<OverlayState>>,
+    callbacks: Rc<OverlayCallbacks>,
+}
+
+impl LayoutDebugOverlay {
+    /// Add the overlay, hidden, and its toggle button to the page of `observer`
+    pub fn attach(observer: &LayoutObserver) -> Result<Self, String> {
+        let overlay = Self::new()?;
+        overlay.watch(observer);
+        Ok(overlay)
+    }
+
+    /// Add the overlay, hidden, and its toggle button to the page, watching nothing yet
+    pub fn new() -> Result<Self, String> {
+        let window = web_sys::window().ok_or("No window")?;
+        let document = window.document().ok_or("No document")?;
+        let body = document.body().ok_or("No document body")?;
+
+        let layer = document
+            .create_element("div")
+            .map_err(|_| "Failed to create overlay")?;
+        let _ = layer.set_attribute(OVERLAY_ATTRIBUTE, "layer");
+        let _ = layer.set_attribute(
+            "style",
+            "position: fixed; inset: 0; pointer-events: none; z-index: 2147483646; display: none",
+        );
+        let svg = svg_element(&document, "svg", &[("width", "100%"), ("height", "100%")])
+            .ok_or("Failed to create overlay")?;
+        if let Some(defs) = arrow_marker(&document) {
+            let _ = svg.append_child(&defs);
+        }
+        let _ = layer.append_child(&svg);
+
+        let toggle = document
+            .create_element("button")
+            .map_err(|_| "Failed to create overlay toggle")?;
+        let _ = toggle.set_attribute(OVERLAY_ATTRIBUTE, "toggle");
+        let _ = toggle.set_attribute("type", "button");
+        let _ = toggle.set_attribute(
+            "style",
+            "position: fixed; right: 12px; bottom: 12px; z-index: 2147483647; \
+             padding: 4px 8px; font: 12px monospace; opacity: 0.8",
+        );
+        toggle.set_text_content(Some("Layout debug"));
+
+        body.append_child(&layer)
+            .map_err(|_| "Failed to add overlay")?;
+        body.append_child(&toggle)
+            .map_err(|_| "Failed to add overlay toggle")?;
+
+        let state = Rc::new(RefCell::new(OverlayState {
+            observers: Vec::new(),
+            sources: Vec::new(),
+            document,
+            svg,
+            layer,
+            toggle: toggle.clone(),
+            visible: false,
+        }));
+
+        let frame_state = Rc::downgrade(&state);
+        let frame = FrameLoop::new(move |_timestamp| {
+            let Some(state) = frame_state.upgrade() else {
+                return false;
+            };
+            let state = state.borrow();
+            if state.visible {
+                draw(&state);
+            }
+            state.visible
+        });
+
+        let toggle_state = Rc::downgrade(&state);
+        let toggle_frame = frame.clone();
+        let on_toggle = Closure::wrap(Box::new(move || {
+            if let Some(state) = toggle_state.upgrade() {
+                let visible = state.borrow().visible;
+                set_visible(&state, &toggle_frame, !visible);
+            }
+        }) as Box<dyn FnMut()>);
+        toggle
+            .add_event_listener_with_callback("click", on_toggle.as_ref().unchecked_ref())
+            .map_err(|_| "Failed to listen to overlay toggle")?;
+
+        Ok(Self {
+            state,
+            callbacks: Rc::new(OverlayCallbacks {
+                frame,
+                _toggle: on_toggle,
+            }),
+        })
+    }
+
+    /// Also draw the animations of `observer`
+    pub fn watch(&self, observer: &LayoutObserver) {
+        if self.state.borrow().observers.contains(observer) {
+            return;
+        }
+        self.state.borrow_mut().observers.push(observer.clone());
+    }
+
+    /// Stop drawing the animations of `observer`
+    pub fn unwatch(&self, observer: &LayoutObserver) {
+        self.state
+            .borrow_mut()
+            .observers
+            .retain(|watched| watched != observer);
+    }
+
+    /// Also draw the animations `source` returns snapshots of on every frame
+    pub fn add_source(&self, source: impl Fn() -> Vec<AnimationSnapshot> + 'static) {
+        self.state.borrow_mut().sources.push(Box::new(source));
+    }
+
+    /// Show or hide the overlay
+    pub fn set_visible(&self, visible: bool) {
+        set_visible(&self.state, &self.callbacks.frame, visible);
+    }
+
+    /// Whether the overlay is shown
+    pub fn is_visible(&self) -> bool {
+        self.state.borrow().visible
+    }
+
+    /// Show the overlay if hidden, hide it if shown
+    pub fn toggle(&self) {
+        self.set_visible(!self.is_visible());
+    }
+}
+
+impl Drop for LayoutDebugOverlay {
+    fn drop(&mut self) {
+        // The browser must not call the callbacks once the last handle is gone
+        if Rc::strong_count(&self.callbacks) == 1 {
+            self.callbacks.frame.cancel();
+            let state = self.state.borrow();
+            state.layer.remove();
+            state.toggle.remove();
+        }
+    }
+}
+
+impl std::fmt::Debug for LayoutDebugOverlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayoutDebugOverlay")
+            .field("visible", &self.is_visible())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Show or hide the overlay, drawing on every frame while it is shown
+fn set_visible(state: &RefCell<OverlayState>, frame: &FrameLoop, visible: bool) {
+    {
+        let mut state = state.borrow_mut();
+        state.visible = visible;
+        if let Some(layer) = state.layer.dyn_ref::<web_sys::HtmlElement>() {
+            let display = if visible { "block" } else { "none" };
+            let _ = layer.style().set_property("display", display);
+        }
+    }
+    if visible {
+        frame.request();
+    }
+}
+
+/// Redraw the running animations of the observers and sources
+fn draw(state: &OverlayState) {
+    // Observers measure layouts relative to the page
+    let (scroll_x, scroll_y) = web_sys::window()
+        .map(|window| {
+            (
+                window.scroll_x().unwrap_or(0.0),
+                window.scroll_y().unwrap_or(0.0),
+            )
+        })
+        .unwrap_or((0.0, 0.0));
+    let on_screen = |layout: &LayoutInfo| {
+        LayoutInfo::new(
+            layout.x - scroll_x,
+            layout.y - scroll_y,
+            layout.width,
+            layout.height,
+        )
+    };
+    let mut snapshots: Vec<AnimationSnapshot> = Vec::new();
+    for observer in &state.observers {
+        observer.with_animator(|animator| {
+            snapshots.extend(animator.animations().filter_map(|animation| {
+                let transform = animator.current_transform(&animation.id)?;
+                let corrections = animation
+                    .correction
+                    .styles(transform.scale_x, transform.scale_y);
+                let first = on_screen(&rect_layout(&animation.state.first));
+                let last = on_screen(&rect_layout(&animation.state.last));
+                Some(AnimationSnapshot {
+                    id: animation.id.clone(),
+                    impact: layout_change_impact(&first, &last),
+                    first,
+                    last,
+                    transform,
+                    corrections,
+                })
+            }))
+        });
+    }
+    for source in &state.sources {
+        snapshots.extend(source());
+    }
+
+    // Keep the arrow marker, replace everything else
+    let svg = &state.svg;
+    while let Some(child) = svg.last_element_child() {
+        if child.tag_name() == "defs" {
+            break;
+        }
+        child.remove();
+    }
+    let document = &state.document;
+    for snapshot in &snapshots {
+        let color = impact_color(Some(&snapshot.impact));
+        let projected = projected_layout(&snapshot.last, &snapshot.transform);
+        let boxes = [
+            (&snapshot.first, "none", "4 3"),
+            (&snapshot.last, "none", "none"),
+            (&projected, color, "none"),
+        ];
+        for (layout, fill, dash) in boxes {
+            if let Some(rect) = svg_rect(document, layout, color, fill, dash) {
+                let _ = svg.append_child(&rect);
+            }
+        }
+        if let Some(line) = svg_element(
+            document,
+            "line",
+            &[
+                ("x1", &snapshot.first.x.to_string()),
+                ("y1", &snapshot.first.y.to_string()),
+                ("x2", &snapshot.last.x.to_string()),
+                ("y2", &snapshot.last.y.to_string()),
+                ("stroke", color),
+                ("stroke-width", "2"),
+                ("marker-end", "url(#motion-layout-debug-arrow)"),
+            ],
+        ) {
+            let _ = svg.append_child(&line);
+        }
+        let labels = snapshot.labels();
+        for (index, label) in labels.iter().enumerate() {
+            let y = projected.y - 4.0 - 12.0 * (labels.len() - 1 - index) as f64;
+            if let Some(text) = svg_element(
+                document,
+                "text",
+                &[
+                    ("x", &projected.x.to_string()),
+                    ("y", &y.to_string()),
+                    ("fill", color),
+                    ("font-family", "monospace"),
+                    ("font-size", "11"),
+                ],
+            ) {
+                text.set_text_content(Some(label));
+                let _ = svg.append_child(&text);
+            }
+        }
+    }
+}
+
+/// SVG element `tag` with `attributes`
+fn svg_element(document: &Document, tag: &str, attributes: &[(&str, &str)]) -> Option<Element> {
+    let element = document.create_element_ns(Some(SVG_NAMESPACE), tag).ok()?;
+    for (name, value) in attributes {
+        let _ = element.set_attribute(name, value);
+    }
+    Some(element)
+}
+
+/// Outline of `layout`, translucently filled with `fill` unless it is `none`
+fn svg_rect(
+    document: &Document,
+    layout: &LayoutInfo,
+    stroke: &str,
+    fill: &str,
+    dash: &str,
+) -> Option<Element> {
+    svg_element(
+        document,
+        "rect",
+        &[
+            ("x", &layout.x.to_string()),
+            ("y", &layout.y.to_string()),
+            ("width", &layout.width.max(0.0).to_string()),
+            ("height", &layout.height.max(0.0).to_string()),
+            ("stroke", stroke),
+            ("stroke-width", "1.5"),
+            ("stroke-dasharray", dash),
+            ("fill", fill),
+            ("fill-opacity", "0.15"),
+        ],
+    )
+}
+
+/// Arrowhead ending the FLIP delta lines
+fn arrow_marker(document: &Document) -> Option<Element> {
+    let defs = svg_element(document, "defs", &[])?;
+    let marker = svg_element(
+        document,
+        "marker",
+        &[
+            ("id", "motion-layout-debug-arrow"),
+            ("viewBox", "0 0 10 10"),
+            ("refX", "9"),
+            ("refY", "5"),
+            ("markerWidth", "6"),
+            ("markerHeight", "6"),
+            ("orient", "auto-start-reverse"),
+        ],
+    )?;
+    let path = svg_element(
+        document,
+        "path",
+        &[("d", "M 0 0 L 10 5 L 0 10 z"), ("fill", "context-stroke")],
+    )?;
+    let _ = marker.append_child(&path);
+    let _ = defs.append_child(&marker);
+    Some(defs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[test]
+    fn test_impact_colors_are_distinct() {
+        let colors = [
+            impact_color(Some(&PerformanceImpact::Low)),
+            impact_color(Some(&PerformanceImpact::Medium)),
+            impact_color(Some(&PerformanceImpact::High)),
+            impact_color(Some(&PerformanceImpact::Critical)),
+            impact_color(None),
+        ];
+        for (index, color) in colors.iter().enumerate() {
+            assert!(!colors[index + 1..].contains(color));
+        }
+    }
+
+    #[test]
+    fn test_projected_layout_scales_from_top_left() {
+        let last = LayoutInfo::new(100.0, 50.0, 200.0, 100.0);
+        let transform = TransformValues::new(-20.0, 10.0, 0.5, 2.0, 0.0);
+        let projected = projected_layout(&last, &transform);
+        assert_eq!(projected.x, 80.0);
+        assert_eq!(projected.y, 60.0);
+        assert_eq!(projected.width, 100.0);
+        assert_eq!(projected.height, 200.0);
+    }
+
+    #[test]
+    fn test_snapshot_between_boxes() {
+        let snapshot = AnimationSnapshot::between(
+            "card",
+            LayoutInfo::new(0.0, 0.0, 100.0, 100.0),
+            LayoutInfo::new(50.0, 20.0, 200.0, 100.0),
+            &LayoutInfo::new(25.0, 10.0, 150.0, 100.0),
+        );
+        assert_eq!(snapshot.transform.translate_x, -25.0);
+        assert_eq!(snapshot.transform.translate_y, -10.0);
+        assert_eq!(snapshot.transform.scale_x, 0.75);
+        assert_eq!(snapshot.transform.scale_y, 1.0);
+        let projected = projected_layout(&snapshot.last, &snapshot.transform);
+        assert_eq!(projected.x, 25.0);
+        assert_eq!(projected.width, 150.0);
+        // Doubling the area is a critical change
+        assert_eq!(snapshot.impact, PerformanceImpact::Critical);
+    }
+
+    #[test]
+    fn test_animation_labels() {
+        let snapshot = AnimationSnapshot {
+            id: "card".to_string(),
+            first: LayoutInfo::new(0.0, 0.0, 100.0, 100.0),
+            last: LayoutInfo::new(50.0, 20.0, 200.0, 100.0),
+            transform: TransformValues::new(-25.0, -10.0, 0.75, 1.0, 0.0),
+            corrections: vec![("border-top-left-radius", "10.667px 8px".to_string())],
+            impact: PerformanceImpact::Low,
+        };
+        assert_eq!(
+            snapshot.labels(),
+            vec![
+                "card".to_string(),
+                "Δ -50.0, -20.0  scale 0.750 × 1.000".to_string(),
+                "border-top-left-radius: 10.667px 8px".to_string(),
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_overlay_toggles_and_draws_sources() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let part = |name: &str| {
+            document
+                .query_selector(&format!("[{OVERLAY_ATTRIBUTE}={name}]"))
+                .unwrap()
+        };
+        let overlay = LayoutDebugOverlay::new().unwrap();
+        overlay.add_source(|| {
+            vec![AnimationSnapshot::between(
+                "card",
+                LayoutInfo::new(0.0, 0.0, 100.0, 100.0),
+                LayoutInfo::new(50.0, 20.0, 100.0, 100.0),
+                &LayoutInfo::new(25.0, 10.0, 100.0, 100.0),
+            )]
+        });
+        assert!(!overlay.is_visible());
+
+        // The toggle button shows the overlay
+        part("toggle")
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+        assert!(overlay.is_visible());
+
+        draw(&overlay.state.borrow());
+        let svg = overlay.state.borrow().svg.clone();
+        assert_eq!(svg.query_selector_all("rect").unwrap().length(), 3);
+        assert_eq!(svg.query_selector_all("line").unwrap().length(), 1);
+        assert_eq!(svg.query_selector_all("text").unwrap().length(), 2);
+
+        overlay.toggle();
+        assert!(!overlay.is_visible());
+
+        // Dropping the last handle removes the overlay from the page
+        drop(overlay);
+        assert!(part("layer").is_none());
+        assert!(part("toggle").is_none());
+    }
+}
@@ -5,8 +5,20 @@
 //! 2. Last: Record the final position
 //! 3. Invert: Apply transforms to make it appear in the initial position
 //! 4. Play: Animate the transforms to their natural values
+//!
+//! Scaling an element also scales its corners, shadow and children. While a
+//! FLIP animation plays, [`ScaleCorrection`] rescales `border-radius` and
+//! `box-shadow` every frame and gives descendants marked with
+//! [`LAYOUT_ATTRIBUTE`] the inverse scale on top of their own transform, so
+//! they look constant.
+//!
+//! [`FLIPAnimator::animate_height`] resizes an element's `height` instead of
+//! scaling it, so expanding or collapsing content is revealed or clipped
+//! rather than stretched.
 
 use crate::LayoutAnimationConfig;
+use leptos_motion_core::spring::MAX_SPRING_DURATION;
+use leptos_motion_core::{Easing, SpringConfig, SpringSimulator, StaggerResolver, inline_style};
 use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 use web_sys::{DomRect, Element};
@@ -85,6 +97,11 @@ impl TransformValues {
     pub fn rotation(degrees: f64) -> Self {
         Self::new(0.0, 0.0, 1.0, 1.0, degrees)
     }
+
+    /// All components zero, such as a velocity at rest
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0, 0.0)
+    }
 }
 
 /// FLIP animation instance
@@ -103,69 +120,439 @@ pub struct FLIPAnimation {
     /// Duration
     pub duration: f64,
     /// Easing function
-    pub easing: EasingFunction,
+    pub easing: Easing,
+    /// Rate of change of the transform per second when the animation started
+    ///
+    /// Only springs take it into account, so an interrupted spring keeps its momentum.
+    pub velocity: TransformValues,
+    /// Part of the layout change that is animated
+    pub mode: LayoutMode,
+    /// Styles keeping the element looking unscaled
+    pub correction: ScaleCorrection,
+    /// Inline styles of the element and its corrected children before the animation
+    pub saved_styles: InlineStyles,
+    /// Height the element is resized through instead of being transformed
+    pub height: Option<HeightAnimation>,
 }
 
-/// Easing function for FLIP animations
-#[derive(Debug, Clone, Default)]
-pub enum EasingFunction {
-    /// Linear easing
-    Linear,
-    /// Ease-in
-    EaseIn,
-    /// Ease-out
-    #[default]
-    EaseOut,
-    /// Ease-in-out
-    EaseInOut,
-    /// Custom cubic-bezier
-    CubicBezier(f64, f64, f64, f64),
-    /// Spring physics
-    Spring {
-        /// Spring tension
-        tension: f64,
-        /// Spring friction
-        friction: f64,
-    },
+/// Height an element is animated through by [`FLIPAnimator::animate_height`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeightAnimation {
+    /// Height in pixels at the start
+    pub from: f64,
+    /// Height in pixels at the end
+    pub to: f64,
+    /// Whether the height goes back to `auto` once the animation ends
+    pub auto: bool,
 }
 
-impl EasingFunction {
-    /// Evaluate the easing function at given progress (0.0 to 1.0)
-    pub fn evaluate(&self, t: f64) -> f64 {
-        let t = t.clamp(0.0, 1.0);
-        match self {
-            EasingFunction::Linear => t,
-            EasingFunction::EaseIn => t * t,
-            EasingFunction::EaseOut => 1.0 - (1.0 - t).powi(2),
-            EasingFunction::EaseInOut => {
-                if t < 0.5 {
-                    2.0 * t * t
+/// Property of an element holding its inline `overflow` while it is collapsed
+const COLLAPSED_OVERFLOW_KEY: &str = "__motionCollapsedOverflow";
+
+impl HeightAnimation {
+    /// Height in pixels at `progress`
+    pub fn at(&self, progress: f64) -> f64 {
+        self.from + (self.to - self.from) * progress
+    }
+
+    /// Size `element` at `progress`, clipping its content
+    fn apply(&self, element: &Element, progress: f64) {
+        exclude_from_transition(element, &["height", "overflow"]);
+        let style = inline_style(element);
+        let _ = style.set_property("height", &format!("{}px", self.at(progress)));
+        let _ = style.set_property("overflow", "hidden");
+    }
+
+    /// Leave `element` at its end height once its saved styles are restored
+    ///
+    /// A collapsed element keeps its content clipped, its own `overflow`
+    /// held until it goes back to `auto`.
+    pub(crate) fn finish(&self, element: &Element) {
+        let style = inline_style(element);
+        let key = JsValue::from_str(COLLAPSED_OVERFLOW_KEY);
+        let collapsed_overflow = js_sys::Reflect::get(element, &key)
+            .ok()
+            .and_then(|overflow| overflow.as_string());
+        if self.auto {
+            let _ = style.set_property("height", "auto");
+            if let Some(overflow) = collapsed_overflow {
+                let _ = if overflow.is_empty() {
+                    style.remove_property("overflow").map(|_| ())
                 } else {
-                    1.0 - 2.0 * (1.0 - t).powi(2)
-                }
+                    style.set_property("overflow", &overflow)
+                };
+                let _ = js_sys::Reflect::delete_property(
+                    element.unchecked_ref::<js_sys::Object>(),
+                    &key,
+                );
             }
-            EasingFunction::CubicBezier(p1, _p2, p3, _p4) => {
-                // Simplified cubic bezier evaluation
-                let u = 1.0 - t;
-                u * u * u * 0.0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p3 + t * t * t * 1.0
+        } else {
+            let _ = style.set_property("height", &format!("{}px", self.to));
+            if collapsed_overflow.is_none() {
+                let overflow = style.get_property_value("overflow").unwrap_or_default();
+                let _ = js_sys::Reflect::set(element, &key, &overflow.into());
             }
-            EasingFunction::Spring { tension, friction } => {
-                // Simplified spring physics
-                let damping = friction / (2.0 * (tension).sqrt());
-                let frequency = (tension).sqrt();
-
-                if damping < 1.0 {
-                    let damped_freq = frequency * (1.0 - damping * damping).sqrt();
-                    let decay = (-damping * frequency * t).exp();
-                    1.0 - decay * (damped_freq * t + damping * frequency * t / damped_freq).cos()
-                } else {
-                    1.0 - (-frequency * t).exp()
-                }
+            let _ = style.set_property("overflow", "hidden");
+        }
+    }
+}
+
+/// Attribute marking elements with layout animations, its value the [`LayoutMode`]
+pub const LAYOUT_ATTRIBUTE: &str = "data-motion-layout";
+
+/// Corner radius properties, in the order of the `border-radius` shorthand
+const RADIUS_PROPERTIES: [&str; 4] = [
+    "border-top-left-radius",
+    "border-top-right-radius",
+    "border-bottom-right-radius",
+    "border-bottom-left-radius",
+];
+
+/// Which part of a layout change is animated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// Animate position and size (`layout`)
+    #[default]
+    Both,
+    /// Animate the position, the size changes instantly (`layout="position"`)
+    Position,
+    /// Animate the size, the position changes instantly (`layout="size"`)
+    Size,
+}
+
+impl LayoutMode {
+    /// Mode of a `layout` prop or [`LAYOUT_ATTRIBUTE`] value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "" | "true" => Some(Self::Both),
+            "position" => Some(Self::Position),
+            "size" => Some(Self::Size),
+            _ => None,
+        }
+    }
+
+    /// Value of [`LAYOUT_ATTRIBUTE`] for this mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Both => "true",
+            Self::Position => "position",
+            Self::Size => "size",
+        }
+    }
+
+    /// Keep the part of an inverted transform this mode animates
+    ///
+    /// Transforms originate at the top left corner, so dropping the
+    /// translation anchors a size animation at the new position.
+    pub fn restrict(&self, inverted: TransformValues) -> TransformValues {
+        match self {
+            Self::Both => inverted,
+            Self::Position => TransformValues {
+                scale_x: 1.0,
+                scale_y: 1.0,
+                ..inverted
+            },
+            Self::Size => TransformValues {
+                translate_x: 0.0,
+                translate_y: 0.0,
+                ..inverted
+            },
+        }
+    }
+}
+
+/// Corner radius `value` (`"12px"` or `"12px 6px"`) as it must be set to look
+/// unchanged under a `scale_x` by `scale_y` scale
+///
+/// Percentages scale with the box already, so they need no correction and
+/// give `None`.
+pub fn correct_radius(value: &str, scale_x: f64, scale_y: f64) -> Option<String> {
+    let mut lengths = value.split_whitespace().map(|length| {
+        length
+            .strip_suffix("px")
+            .and_then(|number| number.parse::<f64>().ok())
+    });
+    let x = lengths.next()??;
+    let y = match lengths.next() {
+        Some(y) => y?,
+        None => x,
+    };
+    if x == 0.0 && y == 0.0 {
+        return None;
+    }
+    Some(format!(
+        "{}px {}px",
+        x / scale_x.max(f64::EPSILON),
+        y / scale_y.max(f64::EPSILON)
+    ))
+}
+
+/// Box shadow `value` as it must be set to look unchanged under a `scale_x`
+/// by `scale_y` scale
+///
+/// Offsets are divided by the scale of their axis, blur and spread by the
+/// average scale. Colors and `inset` are kept as they are.
+pub fn correct_box_shadow(value: &str, scale_x: f64, scale_y: f64) -> Option<String> {
+    if value.trim().is_empty() || value.trim() == "none" {
+        return None;
+    }
+    let scale_x = scale_x.max(f64::EPSILON);
+    let scale_y = scale_y.max(f64::EPSILON);
+    let average = (scale_x + scale_y) / 2.0;
+
+    let shadows: Vec<String> = split_top_level(value, ',')
+        .into_iter()
+        .map(|shadow| {
+            let mut length_index = 0;
+            split_top_level(shadow.trim(), ' ')
+                .into_iter()
+                .filter(|token| !token.is_empty())
+                .map(|token| {
+                    let Some(length) = token
+                        .strip_suffix("px")
+                        .and_then(|number| number.parse::<f64>().ok())
+                        .or_else(|| (token == "0").then_some(0.0))
+                    else {
+                        return token.to_string();
+                    };
+                    let scale = match length_index {
+                        0 => scale_x,
+                        1 => scale_y,
+                        _ => average,
+                    };
+                    length_index += 1;
+                    format!("{}px", length / scale)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    Some(shadows.join(", "))
+}
+
+/// Split `value` at `separator`, ignoring separators inside parentheses
+fn split_top_level(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, ch) in value.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ch if ch == separator && depth == 0 => {
+                parts.push(&value[start..index]);
+                start = index + ch.len_utf8();
             }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Scales closer to 0 than this are left uncorrected, as inverting them
+/// would blow corners, shadows and children up
+const MIN_CORRECTED_SCALE: f64 = 1e-3;
+
+/// Whether a `scale_x` by `scale_y` scale can be corrected
+fn is_correctable(scale_x: f64, scale_y: f64) -> bool {
+    [scale_x, scale_y]
+        .into_iter()
+        .all(|scale| scale.is_finite() && scale.abs() >= MIN_CORRECTED_SCALE)
+}
+
+/// Styles that keep a scaled element's corners, shadow and layout children
+/// looking unscaled
+///
+/// The element is expected to scale from its top left corner, as FLIP
+/// animations set `transform-origin: 0 0`.
+#[derive(Debug, Clone, Default)]
+pub struct ScaleCorrection {
+    /// Computed corner radii, by property
+    pub radii: Vec<(&'static str, String)>,
+    /// Computed box shadow
+    pub box_shadow: Option<String>,
+    /// Closest descendants marked with [`LAYOUT_ATTRIBUTE`] and their
+    /// computed transform origins in pixels
+    pub children: Vec<(Element, (f64, f64))>,
+}
+
+impl ScaleCorrection {
+    /// Read the computed corners and shadow of `element` and find its layout children
+    pub fn measure(element: &Element) -> Self {
+        let computed =
+            web_sys::window().and_then(|window| window.get_computed_style(element).ok()?);
+        let value = |property: &str| {
+            computed
+                .as_ref()
+                .and_then(|style| style.get_property_value(property).ok())
+                .unwrap_or_default()
+        };
+        let radii = RADIUS_PROPERTIES
+            .iter()
+            .map(|property| (*property, value(property)))
+            .filter(|(_, radius)| correct_radius(radius, 1.0, 1.0).is_some())
+            .collect();
+        let box_shadow = Some(value("box-shadow"))
+            .filter(|shadow| correct_box_shadow(shadow, 1.0, 1.0).is_some());
+        Self {
+            radii,
+            box_shadow,
+            children: layout_children(element),
+        }
+    }
+
+    /// Inline styles for the element under a `scale_x` by `scale_y` scale
+    ///
+    /// Empty for scales too close to 0 to correct.
+    pub fn styles(&self, scale_x: f64, scale_y: f64) -> Vec<(&'static str, String)> {
+        if !is_correctable(scale_x, scale_y) {
+            return Vec::new();
+        }
+        let mut styles: Vec<(&'static str, String)> = self
+            .radii
+            .iter()
+            .filter_map(|(property, radius)| {
+                Some((*property, correct_radius(radius, scale_x, scale_y)?))
+            })
+            .collect();
+        if let Some(shadow) = self
+            .box_shadow
+            .as_ref()
+            .and_then(|shadow| correct_box_shadow(shadow, scale_x, scale_y))
+        {
+            styles.push(("box-shadow", shadow));
+        }
+        styles
+    }
+
+    /// `translate` and `scale` of a layout child transformed around `origin`
+    /// cancelling a `scale_x` by `scale_y` scale of its parent
+    ///
+    /// The parent scales from its top left corner, so the child is also
+    /// moved back by the way its origin drifts under the counter-scale.
+    /// Set as the individual transform properties, they apply on top of the
+    /// child's own `transform`, such as its own FLIP animation.
+    pub fn child_correction(origin: (f64, f64), scale_x: f64, scale_y: f64) -> (String, String) {
+        let (inverse_x, inverse_y) = (1.0 / scale_x, 1.0 / scale_y);
+        (
+            format!(
+                "{}px {}px",
+                origin.0 * inverse_x - origin.0,
+                origin.1 * inverse_y - origin.1
+            ),
+            format!("{inverse_x} {inverse_y}"),
+        )
+    }
+
+    /// Correct `element` and its layout children for a `scale_x` by `scale_y` scale
+    ///
+    /// Scales too close to 0 to correct leave the previous correction.
+    pub fn apply(&self, element: &Element, scale_x: f64, scale_y: f64) {
+        if !is_correctable(scale_x, scale_y) {
+            return;
+        }
+        let style = inline_style(element);
+        for (property, value) in self.styles(scale_x, scale_y) {
+            let _ = style.set_property(property, &value);
+        }
+        for (child, origin) in &self.children {
+            let (translate, scale) = Self::child_correction(*origin, scale_x, scale_y);
+            exclude_from_transition(child, &["translate", "scale"]);
+            let style = inline_style(child);
+            let _ = style.set_property("translate", &translate);
+            let _ = style.set_property("scale", &scale);
         }
     }
 }
 
+/// Inline styles overridden by a layout animation, restored once it ends
+#[derive(Debug, Clone, Default)]
+pub struct InlineStyles(Vec<(Element, &'static str, String)>);
+
+impl InlineStyles {
+    /// Save the transform of `element` and the styles `correction` overrides
+    pub fn save(element: &Element, correction: &ScaleCorrection) -> Self {
+        let mut properties = vec!["transform", "transform-origin"];
+        properties.extend(correction.radii.iter().map(|(property, _)| *property));
+        if correction.box_shadow.is_some() {
+            properties.push("box-shadow");
+        }
+        let mut saved = Self::of(element, properties);
+        for (child, _) in &correction.children {
+            saved.0.extend(Self::of(child, ["translate", "scale"]).0);
+        }
+        saved
+    }
+
+    /// Save the inline `properties` of `element`
+    pub fn of(element: &Element, properties: impl IntoIterator<Item = &'static str>) -> Self {
+        let style = inline_style(element);
+        Self(
+            properties
+                .into_iter()
+                .map(|property| {
+                    let value = style.get_property_value(property).unwrap_or_default();
+                    (element.clone(), property, value)
+                })
+                .collect(),
+        )
+    }
+
+    /// Put the saved styles back
+    pub fn restore(&self) {
+        for (element, property, value) in &self.0 {
+            let style = inline_style(element);
+            let _ = if value.is_empty() {
+                style.remove_property(property).map(|_| ())
+            } else {
+                style.set_property(property, value)
+            };
+        }
+    }
+}
+
+/// Descendants marked with [`LAYOUT_ATTRIBUTE`] without a marked element in
+/// between, with their computed transform origins
+fn layout_children(element: &Element) -> Vec<(Element, (f64, f64))> {
+    let selector = format!("[{LAYOUT_ATTRIBUTE}]");
+    let Ok(nodes) = element.query_selector_all(&selector) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index)?.dyn_into::<Element>().ok())
+        .filter(|child| {
+            child
+                .parent_element()
+                .and_then(|parent| parent.closest(&selector).ok().flatten())
+                .is_none_or(|closest| closest == *element)
+        })
+        .map(|child| {
+            let origin = transform_origin(&child);
+            (child, origin)
+        })
+        .collect()
+}
+
+/// Computed `transform-origin` of `element` in pixels, `(0, 0)` if unknown
+fn transform_origin(element: &Element) -> (f64, f64) {
+    let value = web_sys::window()
+        .and_then(|window| window.get_computed_style(element).ok()?)
+        .and_then(|style| style.get_property_value("transform-origin").ok())
+        .unwrap_or_default();
+    parse_transform_origin(&value).unwrap_or((0.0, 0.0))
+}
+
+/// X and Y of a computed `transform-origin` such as `"50px 20px 0px"`
+fn parse_transform_origin(value: &str) -> Option<(f64, f64)> {
+    let mut lengths = value.split_whitespace().map(|length| {
+        length
+            .strip_suffix("px")
+            .and_then(|number| number.parse::<f64>().ok())
+    });
+    Some((lengths.next()??, lengths.next()??))
+}
+
 /// FLIP animator for managing layout transitions
 pub struct FLIPAnimator {
     /// Active FLIP animations
@@ -212,6 +599,9 @@ impl FLIPAnimator {
     }
 
     /// Start a FLIP animation
+    ///
+    /// An animation of the same `id` that is still running is replaced, its
+    /// velocity carried over into the new one.
     pub fn animate(
         &mut self,
         id: String,
@@ -220,7 +610,60 @@ impl FLIPAnimator {
         last: DomRect,
         config: LayoutAnimationConfig,
     ) -> Result<(), String> {
-        let inverted = self.calculate_transform_values(&first, &last);
+        let velocity = self.velocity(&id).unwrap_or_else(TransformValues::zero);
+        self.cancel(&id);
+        self.animate_with_velocity(id, element, first, last, config, velocity)
+    }
+
+    /// Start a FLIP animation whose box at `first` is already moving
+    ///
+    /// `velocity` is in the terms of [`Self::velocity`]: the translation in
+    /// pixels per second, the scale as the rate the size of the box grows
+    /// relative to itself.
+    pub fn animate_with_velocity(
+        &mut self,
+        id: String,
+        element: Element,
+        first: DomRect,
+        last: DomRect,
+        config: LayoutAnimationConfig,
+        velocity: TransformValues,
+    ) -> Result<(), String> {
+        let mode = element
+            .get_attribute(LAYOUT_ATTRIBUTE)
+            .and_then(|value| LayoutMode::parse(&value))
+            .unwrap_or_default();
+        let inverted = mode.restrict(self.calculate_transform_values(&first, &last));
+        let (translates, scales) = (mode != LayoutMode::Size, mode != LayoutMode::Position);
+        let velocity = TransformValues::new(
+            if translates {
+                velocity.translate_x
+            } else {
+                0.0
+            },
+            if translates {
+                velocity.translate_y
+            } else {
+                0.0
+            },
+            if scales {
+                velocity.scale_x * inverted.scale_x
+            } else {
+                0.0
+            },
+            if scales {
+                velocity.scale_y * inverted.scale_y
+            } else {
+                0.0
+            },
+            0.0,
+        );
+        let correction = if inverted.scale_x != 1.0 || inverted.scale_y != 1.0 {
+            ScaleCorrection::measure(&element)
+        } else {
+            ScaleCorrection::default()
+        };
+        let saved_styles = InlineStyles::save(&element, &correction);
 
         let state = FLIPState {
             first,
@@ -238,6 +681,73 @@ impl FLIPAnimator {
             start_time: self.get_current_time(),
             duration: config.duration,
             easing: config.easing,
+            velocity,
+            mode,
+            correction,
+            saved_styles,
+            height: None,
+        };
+
+        self.active_animations.insert(id, animation);
+        self.performance_metrics.total_animations += 1;
+
+        Ok(())
+    }
+
+    /// Start FLIP animations of several elements together, each delayed by
+    /// `stagger`
+    ///
+    /// Every element is inverted as soon as the animator updates, so staggered
+    /// elements wait in their first box until their turn comes.
+    pub fn animate_staggered(
+        &mut self,
+        animations: Vec<(String, Element, DomRect, DomRect)>,
+        config: LayoutAnimationConfig,
+        stagger: &StaggerResolver,
+    ) -> Result<(), String> {
+        let delays = stagger.delays(animations.len());
+        for ((id, element, first, last), delay) in animations.into_iter().zip(delays) {
+            self.animate(id.clone(), element, first, last, config.clone())?;
+            self.delay(&id, delay);
+        }
+        Ok(())
+    }
+
+    /// Animate the `height` of `element` rather than transforming it
+    ///
+    /// Its content is clipped, not stretched, while the box grows or shrinks.
+    /// An animation of the same `id` that is still running is replaced.
+    pub fn animate_height(
+        &mut self,
+        id: String,
+        element: Element,
+        height: HeightAnimation,
+        config: LayoutAnimationConfig,
+    ) -> Result<(), String> {
+        self.cancel(&id);
+        let rect = element.get_bounding_client_rect();
+        let saved_styles = InlineStyles::of(&element, ["height", "overflow"]);
+        let state = FLIPState {
+            first: rect.clone(),
+            last: rect,
+            inverted: TransformValues::default(),
+            progress: 0.0,
+            active: true,
+        };
+
+        let animation = FLIPAnimation {
+            id: id.clone(),
+            element,
+            state,
+            config: config.clone(),
+            start_time: self.get_current_time(),
+            duration: config.duration,
+            easing: config.easing,
+            velocity: TransformValues::zero(),
+            mode: LayoutMode::Size,
+            correction: ScaleCorrection::default(),
+            saved_styles,
+            height: Some(height),
         };
 
         self.active_animations.insert(id, animation);
@@ -246,6 +756,40 @@ impl FLIPAnimator {
         Ok(())
     }
 
+    /// Start the animation `id` `delay` seconds later
+    pub fn delay(&mut self, id: &str, delay: f64) {
+        if let Some(animation) = self.active_animations.get_mut(id) {
+            animation.start_time += delay * 1000.0;
+        }
+    }
+
+    /// Velocity of the box animated by `id`
+    ///
+    /// The translation is in pixels per second. The scale is the rate the
+    /// size of the box grows relative to its current size, so it still holds
+    /// once the box is animated towards a different layout.
+    pub fn velocity(&self, id: &str) -> Option<TransformValues> {
+        const STEP: f64 = 0.001;
+        let animation = self.active_animations.get(id)?;
+        let elapsed = ((self.get_current_time() - animation.start_time) / 1000.0).max(STEP);
+        let (now, _, _) = Self::transform_at(animation, elapsed);
+        let (before, _, _) = Self::transform_at(animation, elapsed - STEP);
+        let relative = |now: f64, before: f64| {
+            if now == 0.0 {
+                0.0
+            } else {
+                (now - before) / STEP / now
+            }
+        };
+        Some(TransformValues::new(
+            (now.translate_x - before.translate_x) / STEP,
+            (now.translate_y - before.translate_y) / STEP,
+            relative(now.scale_x, before.scale_x),
+            relative(now.scale_y, before.scale_y),
+            0.0,
+        ))
+    }
+
     /// Calculate transform values from first to last positions
     fn calculate_transform_values(&self, first: &DomRect, last: &DomRect) -> TransformValues {
         let translate_x = first.x() - last.x();
@@ -276,37 +820,22 @@ impl FLIPAnimator {
 
         for (id, animation) in &mut self.active_animations {
             let elapsed = (current_time - animation.start_time) / 1000.0; // Convert to seconds
-            let progress = (elapsed / animation.duration).clamp(0.0, 1.0);
-            let eased_progress = animation.easing.evaluate(progress);
+            let (transform, progress, finished) = Self::transform_at(animation, elapsed);
 
-            animation.state.progress = eased_progress;
+            animation.state.progress = progress;
 
-            if progress >= 1.0 {
+            if finished {
+                // Hand the element back to its own styles
                 animation.state.active = false;
                 completed_ids.push(id.clone());
-            }
-
-            // Apply transform inline to avoid borrow checker issues
-            let inverted = &animation.state.inverted;
-            let current_x = inverted.translate_x * (1.0 - eased_progress);
-            let current_y = inverted.translate_y * (1.0 - eased_progress);
-            let current_scale_x = 1.0 + (inverted.scale_x - 1.0) * (1.0 - eased_progress);
-            let current_scale_y = 1.0 + (inverted.scale_y - 1.0) * (1.0 - eased_progress);
-
-            let transform = format!(
-                "translateX({}px) translateY({}px) scaleX({}) scaleY({})",
-                current_x, current_y, current_scale_x, current_scale_y
-            );
-
-            if animation
-                .element
-                .dyn_ref::<web_sys::HtmlElement>()
-                .unwrap()
-                .style()
-                .set_property("transform", &transform)
-                .is_ok()
-            {
-                // Transform applied successfully
+                animation.saved_styles.restore();
+                if let Some(height) = &animation.height {
+                    height.finish(&animation.element);
+                }
+            } else if let Some(height) = &animation.height {
+                height.apply(&animation.element, progress);
+            } else {
+                Self::apply_transform(animation, &transform);
             }
         }
 
@@ -316,29 +845,68 @@ impl FLIPAnimator {
         }
     }
 
-    /// Apply transform to element based on progress
-    #[allow(dead_code)]
-    fn apply_transform(&self, animation: &FLIPAnimation, progress: f64) {
+    /// Transform `elapsed` seconds into the animation, its eased progress and whether it finished
+    ///
+    /// Springs are simulated per transform component by [`spring_transform_at`]
+    /// and finish once all of them, and the height, rest. Other easings play
+    /// over the configured duration.
+    fn transform_at(animation: &FLIPAnimation, elapsed: f64) -> (TransformValues, f64, bool) {
         let inverted = &animation.state.inverted;
-        let current_x = inverted.translate_x * (1.0 - progress);
-        let current_y = inverted.translate_y * (1.0 - progress);
-        let current_scale_x = 1.0 + (inverted.scale_x - 1.0) * (1.0 - progress);
-        let current_scale_y = 1.0 + (inverted.scale_y - 1.0) * (1.0 - progress);
+        if let Easing::Spring(spring) = &animation.easing {
+            let last = &animation.state.last;
+            return spring_transform_at(
+                spring,
+                animation.duration,
+                (last.width(), last.height()),
+                inverted,
+                &animation.velocity,
+                animation.height.as_ref(),
+                elapsed,
+            );
+        }
+
+        let progress = if animation.duration > 0.0 {
+            (elapsed / animation.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let eased = animation.easing.evaluate(progress);
+        let transform = TransformValues::new(
+            inverted.translate_x * (1.0 - eased),
+            inverted.translate_y * (1.0 - eased),
+            1.0 + (inverted.scale_x - 1.0) * (1.0 - eased),
+            1.0 + (inverted.scale_y - 1.0) * (1.0 - eased),
+            0.0,
+        );
+        (transform, eased, progress >= 1.0)
+    }
 
-        let transform = format!(
+    /// Apply transform and scale corrections to element
+    fn apply_transform(animation: &FLIPAnimation, transform: &TransformValues) {
+        let css = format!(
             "translateX({}px) translateY({}px) scaleX({}) scaleY({})",
-            current_x, current_y, current_scale_x, current_scale_y
+            transform.translate_x, transform.translate_y, transform.scale_x, transform.scale_y
         );
 
-        if let Ok(_style) = animation
-            .element
-            .dyn_ref::<web_sys::HtmlElement>()
-            .unwrap()
-            .style()
-            .set_property("transform", &transform)
-        {
-            // Transform applied successfully
-        }
+        exclude_from_transition(&animation.element, &["transform", "transform-origin"]);
+        let style = inline_style(&animation.element);
+        let _ = style.set_property("transform-origin", "0 0");
+        let _ = style.set_property("transform", &css);
+        animation
+            .correction
+            .apply(&animation.element, transform.scale_x, transform.scale_y);
+    }
+
+    /// Running animations, in no particular order
+    pub fn animations(&self) -> impl Iterator<Item = &FLIPAnimation> {
+        self.active_animations.values()
+    }
+
+    /// Transform the animation `id` applies to its element right now
+    pub fn current_transform(&self, id: &str) -> Option<TransformValues> {
+        let animation = self.active_animations.get(id)?;
+        let elapsed = ((self.get_current_time() - animation.start_time) / 1000.0).max(0.0);
+        Some(Self::transform_at(animation, elapsed).0)
     }
 
     /// Get active animation count
@@ -353,21 +921,29 @@ impl FLIPAnimator {
 
     /// Cancel all animations
     pub fn cancel_all(&mut self) {
-        self.active_animations.clear();
+        for (_, animation) in self.active_animations.drain() {
+            animation.saved_styles.restore();
+        }
     }
 
     /// Cancel specific animation
     pub fn cancel(&mut self, id: &str) -> bool {
-        self.active_animations.remove(id).is_some()
+        match self.active_animations.remove(id) {
+            Some(animation) => {
+                animation.saved_styles.restore();
+                true
+            }
+            None => false,
+        }
     }
 
     #[allow(dead_code)]
-    fn parse_easing_function(&self, easing: &str) -> Result<EasingFunction, String> {
+    fn parse_easing_function(&self, easing: &str) -> Result<Easing, String> {
         match easing {
-            "linear" => Ok(EasingFunction::Linear),
-            "ease-in" => Ok(EasingFunction::EaseIn),
-            "ease-out" => Ok(EasingFunction::EaseOut),
-            "ease-in-out" => Ok(EasingFunction::EaseInOut),
+            "linear" => Ok(Easing::Linear),
+            "ease-in" => Ok(Easing::EaseIn),
+            "ease-out" => Ok(Easing::EaseOut),
+            "ease-in-out" => Ok(Easing::EaseInOut),
             _ => {
                 if easing.starts_with("cubic-bezier(") && easing.ends_with(')') {
                     // Parse cubic-bezier values
@@ -376,9 +952,7 @@ impl FLIPAnimator {
                         values_str.split(',').map(|s| s.trim().parse()).collect();
 
                     match values {
-                        Ok(v) if v.len() == 4 => {
-                            Ok(EasingFunction::CubicBezier(v[0], v[1], v[2], v[3]))
-                        }
+                        Ok(v) if v.len() == 4 => Ok(Easing::Bezier(v[0], v[1], v[2], v[3])),
                         _ => Err("Invalid cubic-bezier format".to_string()),
                     }
                 } else if easing.starts_with("spring(") && easing.ends_with(')') {
@@ -388,10 +962,11 @@ impl FLIPAnimator {
                         values_str.split(',').map(|s| s.trim().parse()).collect();
 
                     match values {
-                        Ok(v) if v.len() == 2 => Ok(EasingFunction::Spring {
-                            tension: v[0],
-                            friction: v[1],
-                        }),
+                        Ok(v) if v.len() == 2 => Ok(Easing::Spring(SpringConfig {
+                            stiffness: v[0],
+                            damping: v[1],
+                            ..Default::default()
+                        })),
                         _ => Err("Invalid spring format".to_string()),
                     }
                 } else {
@@ -414,6 +989,101 @@ impl FLIPAnimator {
     }
 }
 
+/// Spring transform `elapsed` seconds into a layout animation lasting
+/// `duration`, its progress and whether it finished
+///
+/// Time is scaled so the spring settles over `duration`, as it does in the
+/// CSS `linear()` curve of [`Easing::to_css`]. Scales are simulated as the
+/// last box's `size` in pixels, so the spring rests within `rest_delta` of a
+/// pixel rather than of a scale factor and doesn't snap at the end. A
+/// `height` rests likewise.
+fn spring_transform_at(
+    spring: &SpringConfig,
+    duration: f64,
+    size: (f64, f64),
+    inverted: &TransformValues,
+    velocity: &TransformValues,
+    height: Option<&HeightAnimation>,
+    elapsed: f64,
+) -> (TransformValues, f64, bool) {
+    let Ok(simulator) = SpringSimulator::new(spring.clone()) else {
+        return (TransformValues::default(), 1.0, true);
+    };
+    let rate = simulator.settling_duration() / duration;
+    let time = elapsed * rate;
+    if duration <= 0.0 || rate <= 0.0 || time >= MAX_SPRING_DURATION {
+        return (TransformValues::default(), 1.0, true);
+    }
+
+    let (width, box_height) = (size.0.max(1.0), size.1.max(1.0));
+    let components = [
+        (inverted.translate_x, 0.0, velocity.translate_x, 1.0),
+        (inverted.translate_y, 0.0, velocity.translate_y, 1.0),
+        (inverted.scale_x, 1.0, velocity.scale_x, width),
+        (inverted.scale_y, 1.0, velocity.scale_y, box_height),
+    ];
+    let mut resting = true;
+    let mut values = [0.0; 4];
+    for (value, (from, to, velocity, unit)) in values.iter_mut().zip(components) {
+        let component = SpringSimulator::new(SpringConfig {
+            velocity: velocity * unit / rate,
+            ..spring.clone()
+        });
+        *value = match component {
+            Ok(component) => {
+                resting &= component.is_settled(from * unit, to * unit, time);
+                component.position(from * unit, to * unit, time) / unit
+            }
+            Err(_) => to,
+        };
+    }
+    if let Some(height) = height {
+        resting &= simulator.is_settled(height.from, height.to, time);
+    }
+    let transform = TransformValues::new(values[0], values[1], values[2], values[3], 0.0);
+    (transform, simulator.position(0.0, 1.0, time), resting)
+}
+
+/// Transition list without `properties`, if it named any of them
+///
+/// A list left empty becomes `none` so a stylesheet transition doesn't take
+/// over.
+pub fn without_transition_properties(list: &str, properties: &[&str]) -> Option<String> {
+    let named: Vec<&str> = list
+        .split(',')
+        .map(str::trim)
+        .filter(|property| !property.is_empty())
+        .collect();
+    let remaining: Vec<&str> = named
+        .iter()
+        .copied()
+        .filter(|property| !properties.contains(property))
+        .collect();
+    if remaining.len() == named.len() {
+        return None;
+    }
+    Some(if remaining.is_empty() {
+        "none".to_string()
+    } else {
+        remaining.join(", ")
+    })
+}
+
+/// Stop `properties` of `element` from transitioning
+///
+/// Animations writing a property on every frame call this first, so a CSS
+/// transition set by another animation of the element doesn't smear their
+/// writes.
+pub fn exclude_from_transition(element: &Element, properties: &[&str]) {
+    let style = inline_style(element);
+    let list = style
+        .get_property_value("transition-property")
+        .unwrap_or_default();
+    if let Some(list) = without_transition_properties(&list, properties) {
+        let _ = style.set_property("transition-property", &list);
+    }
+}
+
 impl Default for FLIPAnimator {
     fn default() -> Self {
         Self::new()
@@ -478,89 +1148,209 @@ mod tests {
     }
 
     #[test]
-    fn test_easing_function_linear() {
-        let easing = EasingFunction::Linear;
-        assert_eq!(easing.evaluate(0.0), 0.0);
-        assert_eq!(easing.evaluate(0.5), 0.5);
-        assert_eq!(easing.evaluate(1.0), 1.0);
+    fn test_flip_animator_creation() {
+        let animator = FLIPAnimator::new();
+        assert_eq!(animator.active_count(), 0);
+        assert_eq!(animator.performance_metrics().total_animations, 0);
     }
 
     #[test]
-    fn test_easing_function_ease_in() {
-        let easing = EasingFunction::EaseIn;
-        assert_eq!(easing.evaluate(0.0), 0.0);
-        assert_eq!(easing.evaluate(0.5), 0.25);
-        assert_eq!(easing.evaluate(1.0), 1.0);
+    fn test_flip_animator_default() {
+        let animator = FLIPAnimator::default();
+        assert_eq!(animator.active_count(), 0);
     }
 
     #[test]
-    fn test_easing_function_ease_out() {
-        let easing = EasingFunction::EaseOut;
-        assert_eq!(easing.evaluate(0.0), 0.0);
-        assert_eq!(easing.evaluate(0.5), 0.75);
-        assert_eq!(easing.evaluate(1.0), 1.0);
+    fn test_performance_metrics_default() {
+        let metrics = FLIPPerformanceMetrics::default();
+        assert_eq!(metrics.total_animations, 0);
+        assert_eq!(metrics.average_duration, 0.0);
+        assert_eq!(metrics.failed_animations, 0);
+        assert_eq!(metrics.performance_score, 1.0);
     }
 
     #[test]
-    fn test_easing_function_ease_in_out() {
-        let easing = EasingFunction::EaseInOut;
-        assert_eq!(easing.evaluate(0.0), 0.0);
-        assert_eq!(easing.evaluate(0.25), 0.125);
-        assert_eq!(easing.evaluate(0.75), 0.875);
-        assert_eq!(easing.evaluate(1.0), 1.0);
+    fn test_layout_mode_parse() {
+        assert_eq!(LayoutMode::parse("true"), Some(LayoutMode::Both));
+        assert_eq!(LayoutMode::parse(""), Some(LayoutMode::Both));
+        assert_eq!(LayoutMode::parse("position"), Some(LayoutMode::Position));
+        assert_eq!(LayoutMode::parse("size"), Some(LayoutMode::Size));
+        assert_eq!(LayoutMode::parse("grid"), None);
+        assert_eq!(
+            LayoutMode::parse(LayoutMode::Size.as_str()),
+            Some(LayoutMode::Size)
+        );
+    }
+
+    #[test]
+    fn test_layout_mode_restrict() {
+        let inverted = TransformValues::new(-50.0, -25.0, 0.5, 2.0, 0.0);
+
+        let position = LayoutMode::Position.restrict(inverted.clone());
+        assert_eq!(position.translate_x, -50.0);
+        assert_eq!(position.scale_x, 1.0);
+        assert_eq!(position.scale_y, 1.0);
+
+        let size = LayoutMode::Size.restrict(inverted.clone());
+        assert_eq!(size.translate_x, 0.0);
+        assert_eq!(size.translate_y, 0.0);
+        assert_eq!(size.scale_y, 2.0);
+
+        let both = LayoutMode::Both.restrict(inverted);
+        assert_eq!(both.translate_y, -25.0);
+        assert_eq!(both.scale_x, 0.5);
+    }
+
+    #[test]
+    fn test_spring_transform_follows_the_css_time_mapping() {
+        let spring = SpringConfig::default();
+        let simulator = SpringSimulator::new(spring.clone()).unwrap();
+        let inverted = TransformValues::new(-100.0, 0.0, 1.0, 1.0, 0.0);
+        for duration in [0.6, 1.2] {
+            let (transform, progress, finished) = spring_transform_at(
+                &spring,
+                duration,
+                (200.0, 100.0),
+                &inverted,
+                &TransformValues::zero(),
+                None,
+                duration / 2.0,
+            );
+            assert!((progress - simulator.progress_at(0.5)).abs() < 1e-9);
+            assert!((transform.translate_x + 100.0 * (1.0 - progress)).abs() < 1e-9);
+            assert!(!finished);
+        }
     }
 
     #[test]
-    fn test_easing_function_cubic_bezier() {
-        let easing = EasingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0);
-        let result = easing.evaluate(0.5);
-        assert!(result > 0.0 && result < 1.0);
+    fn test_spring_transform_rests_within_a_pixel() {
+        let spring = SpringConfig::default();
+        let inverted = TransformValues::new(0.0, 0.0, 0.5, 1.0, 0.0);
+        let frame = |elapsed| {
+            spring_transform_at(
+                &spring,
+                0.5,
+                (400.0, 100.0),
+                &inverted,
+                &TransformValues::zero(),
+                None,
+                elapsed,
+            )
+        };
+        let (transform, _, _) = (1..)
+            .map(|step| frame(step as f64 / 60.0))
+            .find(|(_, _, finished)| *finished)
+            .unwrap();
+        assert!((transform.scale_x - 1.0).abs() * 400.0 < spring.rest_delta);
     }
 
     #[test]
-    fn test_easing_function_spring() {
-        let easing = EasingFunction::Spring {
-            tension: 120.0,
-            friction: 14.0,
+    fn test_spring_height_rests_at_its_end_height() {
+        let spring = SpringConfig::default();
+        let height = HeightAnimation {
+            from: 0.0,
+            to: 300.0,
+            auto: true,
         };
-        let result = easing.evaluate(0.5);
-        // Spring animations can temporarily exceed bounds due to oscillation
-        assert!(
-            (-0.5..=1.5).contains(&result),
-            "Spring result was: {}",
-            result
+        let frame = |elapsed| {
+            spring_transform_at(
+                &spring,
+                0.5,
+                (200.0, 300.0),
+                &TransformValues::default(),
+                &TransformValues::zero(),
+                Some(&height),
+                elapsed,
+            )
+        };
+        assert!(!frame(0.25).2);
+        let (_, progress, _) = (1..)
+            .map(|step| frame(step as f64 / 60.0))
+            .find(|(_, _, finished)| *finished)
+            .unwrap();
+        assert!((height.at(progress) - 300.0).abs() < spring.rest_delta);
+    }
+
+    #[test]
+    fn test_without_transition_properties() {
+        assert_eq!(
+            without_transition_properties("opacity, transform", &["transform"]),
+            Some("opacity".to_string())
+        );
+        assert_eq!(
+            without_transition_properties("transform", &["transform", "transform-origin"]),
+            Some("none".to_string())
+        );
+        assert_eq!(
+            without_transition_properties("opacity", &["transform"]),
+            None
         );
+        assert_eq!(without_transition_properties("", &["transform"]), None);
     }
 
     #[test]
-    fn test_easing_function_default() {
-        let easing = EasingFunction::default();
-        match easing {
-            EasingFunction::EaseOut => {}
-            _ => panic!("Default should be EaseOut"),
-        }
+    fn test_correct_radius() {
+        assert_eq!(
+            correct_radius("12px", 2.0, 0.5),
+            Some("6px 24px".to_string())
+        );
+        assert_eq!(
+            correct_radius("8px 4px", 2.0, 2.0),
+            Some("4px 2px".to_string())
+        );
+        // Percentages already follow the box, square corners need nothing
+        assert_eq!(correct_radius("50%", 2.0, 2.0), None);
+        assert_eq!(correct_radius("0px", 2.0, 2.0), None);
     }
 
     #[test]
-    fn test_flip_animator_creation() {
-        let animator = FLIPAnimator::new();
-        assert_eq!(animator.active_count(), 0);
-        assert_eq!(animator.performance_metrics().total_animations, 0);
+    fn test_correct_box_shadow() {
+        assert_eq!(
+            correct_box_shadow("rgba(0, 0, 0, 0.2) 0px 4px 8px 0px", 2.0, 0.5),
+            Some("rgba(0, 0, 0, 0.2) 0px 8px 6.4px 0px".to_string())
+        );
+        assert_eq!(
+            correct_box_shadow("red 2px 2px, inset blue 0px 0px 4px", 2.0, 2.0),
+            Some("red 1px 1px, inset blue 0px 0px 2px".to_string())
+        );
+        assert_eq!(correct_box_shadow("none", 2.0, 2.0), None);
     }
 
     #[test]
-    fn test_flip_animator_default() {
-        let animator = FLIPAnimator::default();
-        assert_eq!(animator.active_count(), 0);
+    fn test_scale_correction_styles() {
+        let correction = ScaleCorrection {
+            radii: vec![("border-top-left-radius", "10px".to_string())],
+            box_shadow: Some("black 0px 2px 4px".to_string()),
+            children: Vec::new(),
+        };
+        assert_eq!(
+            correction.styles(2.0, 1.0),
+            vec![
+                ("border-top-left-radius", "5px 10px".to_string()),
+                (
+                    "box-shadow",
+                    "black 0px 2px 2.6666666666666665px".to_string()
+                ),
+            ]
+        );
+        // Too flat to correct
+        assert!(correction.styles(0.0, 1.0).is_empty());
+        assert!(correction.styles(1.0, f64::EPSILON).is_empty());
     }
 
     #[test]
-    fn test_performance_metrics_default() {
-        let metrics = FLIPPerformanceMetrics::default();
-        assert_eq!(metrics.total_animations, 0);
-        assert_eq!(metrics.average_duration, 0.0);
-        assert_eq!(metrics.failed_animations, 0);
-        assert_eq!(metrics.performance_score, 1.0);
+    fn test_child_correction_counters_the_parent_origin() {
+        // A child scaled around its center at (50, 20) stays put under a 2x by 0.5x parent
+        assert_eq!(
+            ScaleCorrection::child_correction((50.0, 20.0), 2.0, 0.5),
+            ("-25px 20px".to_string(), "0.5 2".to_string())
+        );
+        assert_eq!(
+            ScaleCorrection::child_correction((0.0, 0.0), 4.0, 1.0),
+            ("0px 0px".to_string(), "0.25 1".to_string())
+        );
+        assert_eq!(parse_transform_origin("50px 20px 0px"), Some((50.0, 20.0)));
+        assert_eq!(parse_transform_origin(""), None);
     }
 
     #[wasm_bindgen_test]
@@ -582,32 +1372,33 @@ mod tests {
 
         assert!(matches!(
             animator.parse_easing_function("linear"),
-            Ok(EasingFunction::Linear)
+            Ok(Easing::Linear)
         ));
         assert!(matches!(
             animator.parse_easing_function("ease-in"),
-            Ok(EasingFunction::EaseIn)
+            Ok(Easing::EaseIn)
         ));
         assert!(matches!(
             animator.parse_easing_function("ease-out"),
-            Ok(EasingFunction::EaseOut)
+            Ok(Easing::EaseOut)
         ));
         assert!(matches!(
             animator.parse_easing_function("ease-in-out"),
-            Ok(EasingFunction::EaseInOut)
+            Ok(Easing::EaseInOut)
         ));
 
         assert!(matches!(
             animator.parse_easing_function("cubic-bezier(0.25, 0.1, 0.25, 1.0)"),
-            Ok(EasingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0))
+            Ok(Easing::Bezier(0.25, 0.1, 0.25, 1.0))
         ));
 
         assert!(matches!(
             animator.parse_easing_function("spring(120, 14)"),
-            Ok(EasingFunction::Spring {
-                tension: 120.0,
-                friction: 14.0
-            })
+            Ok(Easing::Spring(SpringConfig {
+                stiffness: 120.0,
+                damping: 14.0,
+                ..
+            }))
         ));
 
         assert!(animator.parse_easing_function("invalid").is_err());
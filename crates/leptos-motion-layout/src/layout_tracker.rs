@@ -2,9 +2,19 @@
 //!
 //! This module provides functionality for tracking layout changes,
 //! monitoring performance metrics, and optimizing layout animations.
-
-use std::collections::HashMap;
-use std::time::Instant;
+//!
+//! [`LayoutTracker`] measures its elements whenever it is updated, while
+//! [`LayoutObserver`] lets the browser report which elements may have
+//! moved and measures only those, so idle pages cost nothing.
+
+use crate::flip::{FLIPAnimator, LAYOUT_ATTRIBUTE, TransformValues};
+use crate::{LayoutAnimationConfig, LayoutInfo};
+use leptos_motion_core::FrameLoop;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
 use web_sys::{DomRect, Element};
 
 /// Layout change record
@@ -12,6 +22,8 @@ use web_sys::{DomRect, Element};
 pub struct LayoutChange {
     /// Element ID
     pub element_id: String,
+    /// DOM `id` of the element, empty when it has none
+    pub label: String,
     /// Previous layout
     pub previous_layout: DomRect,
     /// Current layout
@@ -84,14 +96,16 @@ pub struct LayoutTracker {
 pub struct TrackedElement {
     /// Element ID
     pub id: String,
+    /// DOM `id` of the element, empty when it has none
+    pub label: String,
     /// Element reference
     pub element: Element,
     /// Current layout
     pub current_layout: DomRect,
     /// Previous layout
     pub previous_layout: Option<DomRect>,
-    /// Last update time
-    pub last_update: Instant,
+    /// Last update timestamp in milliseconds
+    pub last_update: f64,
     /// Change count
     pub change_count: usize,
 }
@@ -131,25 +145,23 @@ impl LayoutTracker {
 
     /// Start tracking an element
     pub fn track_element(&mut self, element: Element) -> Result<(), String> {
-        let element_id = element.id();
-
-        // Get current layout
         let current_layout = element.get_bounding_client_rect();
+        self.insert_element(element.id(), element, current_layout);
+        Ok(())
+    }
 
-        // Create tracked element
+    /// Track `element` under `element_id`, starting from `current_layout`
+    fn insert_element(&mut self, element_id: String, element: Element, current_layout: DomRect) {
         let tracked_element = TrackedElement {
             id: element_id.clone(),
-            element: element.clone(),
+            label: element.id(),
+            element,
             current_layout,
             previous_layout: None,
-            last_update: Instant::now(),
+            last_update: js_sys::Date::now(),
             change_count: 0,
         };
-
-        // Add to tracking
         self.tracked_elements.insert(element_id, tracked_element);
-
-        Ok(())
     }
 
     /// Stop tracking an element
@@ -167,53 +179,23 @@ impl LayoutTracker {
             return Ok(Vec::new());
         }
 
-        let mut changes = Vec::new();
         let current_time = js_sys::Date::now();
-
-        // Check each tracked element for changes
-        let element_ids: Vec<String> = self.tracked_elements.keys().cloned().collect();
-
-        for element_id in element_ids {
-            // Get current layout first
-            let new_layout = if let Some(tracked_element) = self.tracked_elements.get(&element_id) {
-                tracked_element.element.get_bounding_client_rect()
-            } else {
-                continue;
-            };
-
-            // Check if layout changed
-            let current_layout =
-                if let Some(tracked_element) = self.tracked_elements.get(&element_id) {
-                    tracked_element.current_layout.clone()
-                } else {
-                    continue;
-                };
-
-            if self.has_layout_changed(&current_layout, &new_layout) {
-                // Create change record
-                let change = LayoutChange {
-                    element_id: element_id.clone(),
-                    previous_layout: current_layout.clone(),
-                    current_layout: new_layout.clone(),
-                    timestamp: current_time,
-                    change_type: LayoutChangeType::PositionAndSize, // Default to both
-                    performance_impact: PerformanceImpact::Low,     // Default to low impact
-                };
-
-                changes.push(change.clone());
-
-                // Update tracked element (now we can borrow mutably)
-                if let Some(tracked_element) = self.tracked_elements.get_mut(&element_id) {
-                    tracked_element.previous_layout = Some(current_layout);
-                    tracked_element.current_layout = new_layout;
-                    tracked_element.last_update = Instant::now();
-                    tracked_element.change_count += 1;
-                }
-
-                // Add to history
-                self.change_history.push(change);
-            }
-        }
+        let measurements: Vec<(String, DomRect)> = self
+            .tracked_elements
+            .values()
+            .map(|tracked| {
+                (
+                    tracked.id.clone(),
+                    tracked.element.get_bounding_client_rect(),
+                )
+            })
+            .collect();
+        let changes: Vec<LayoutChange> = measurements
+            .into_iter()
+            .filter_map(|(element_id, new_layout)| {
+                self.record_change(&element_id, new_layout, current_time)
+            })
+            .collect();
 
         // Update performance monitoring
         self.performance_monitor.update()?;
@@ -227,6 +209,48 @@ impl LayoutTracker {
         Ok(changes)
     }
 
+    /// Record a new measurement of a tracked element
+    ///
+    /// Returns the change when the element moved or resized since its last
+    /// measurement; the change is also added to the history and statistics.
+    pub fn record_layout(
+        &mut self,
+        element_id: &str,
+        new_layout: DomRect,
+        timestamp: f64,
+    ) -> Option<LayoutChange> {
+        if !self.enabled {
+            return None;
+        }
+        let change = self.record_change(element_id, new_layout, timestamp)?;
+        self.update_stats(std::slice::from_ref(&change));
+        self.cleanup_history();
+        Some(change)
+    }
+
+    /// Store a measurement and its change in the history, without updating statistics
+    fn record_change(
+        &mut self,
+        element_id: &str,
+        new_layout: DomRect,
+        timestamp: f64,
+    ) -> Option<LayoutChange> {
+        let tracked_element = self.tracked_elements.get(element_id)?;
+        if !self.has_layout_changed(&tracked_element.current_layout, &new_layout) {
+            return None;
+        }
+        let change = self.create_layout_change(tracked_element, &new_layout, timestamp);
+
+        let tracked_element = self.tracked_elements.get_mut(element_id)?;
+        tracked_element.previous_layout = Some(change.previous_layout.clone());
+        tracked_element.current_layout = new_layout;
+        tracked_element.last_update = timestamp;
+        tracked_element.change_count += 1;
+
+        self.change_history.push(change.clone());
+        Some(change)
+    }
+
     /// Get layout for a specific element
     pub fn get_element_layout(&self, element: &Element) -> Result<DomRect, String> {
         Ok(element.get_bounding_client_rect())
@@ -282,13 +306,12 @@ impl LayoutTracker {
     }
 
     /// Create a layout change record
-    #[allow(dead_code)]
     fn create_layout_change(
         &self,
         tracked_element: &TrackedElement,
         new_layout: &DomRect,
         timestamp: f64,
-    ) -> Result<LayoutChange, String> {
+    ) -> LayoutChange {
         let previous_layout = tracked_element.current_layout.clone();
 
         // Determine change type
@@ -297,18 +320,18 @@ impl LayoutTracker {
         // Calculate performance impact
         let performance_impact = self.calculate_performance_impact(&previous_layout, new_layout);
 
-        Ok(LayoutChange {
+        LayoutChange {
             element_id: tracked_element.id.clone(),
+            label: tracked_element.label.clone(),
             previous_layout,
             current_layout: new_layout.clone(),
             timestamp,
             change_type,
             performance_impact,
-        })
+        }
     }
 
     /// Determine the type of layout change
-    #[allow(dead_code)]
     fn determine_change_type(&self, old: &DomRect, new: &DomRect) -> LayoutChangeType {
         let position_changed =
             (old.left() - new.left()).abs() > 0.1 || (old.top() - new.top()).abs() > 0.1;
@@ -324,20 +347,10 @@ impl LayoutTracker {
     }
 
     /// Calculate performance impact of layout change
-    #[allow(dead_code)]
     fn calculate_performance_impact(&self, old: &DomRect, new: &DomRect) -> PerformanceImpact {
-        let area_change = (new.width() * new.height() - old.width() * old.height()).abs();
-        let position_change =
-            ((new.left() - old.left()).powi(2) + (new.top() - old.top()).powi(2)).sqrt();
-
-        let total_change = area_change + position_change;
-
-        match total_change {
-            change if change < 100.0 => PerformanceImpact::Low,
-            change if change < 1000.0 => PerformanceImpact::Medium,
-            change if change < 10000.0 => PerformanceImpact::High,
-            _ => PerformanceImpact::Critical,
-        }
+        let layout =
+            |rect: &DomRect| LayoutInfo::new(rect.left(), rect.top(), rect.width(), rect.height());
+        layout_change_impact(&layout(old), &layout(new))
     }
 
     /// Update statistics
@@ -445,6 +458,21 @@ impl PerformanceMonitor {
     }
 }
 
+/// Performance impact of a layout change from `old` to `new`
+///
+/// Grows with the change in area and the distance moved.
+pub(crate) fn layout_change_impact(old: &LayoutInfo, new: &LayoutInfo) -> PerformanceImpact {
+    let area_change = (new.width * new.height - old.width * old.height).abs();
+    let position_change = ((new.x - old.x).powi(2) + (new.y - old.y).powi(2)).sqrt();
+
+    match area_change + position_change {
+        change if change < 100.0 => PerformanceImpact::Low,
+        change if change < 1000.0 => PerformanceImpact::Medium,
+        change if change < 10000.0 => PerformanceImpact::High,
+        _ => PerformanceImpact::Critical,
+    }
+}
+
 impl Default for LayoutTracker {
     fn default() -> Self {
         Self::new()
@@ -469,6 +497,453 @@ impl Default for LayoutStats {
     }
 }
 
+/// Listener receiving the layout changes of one read phase
+type ChangeListener = Box<dyn FnMut(&[LayoutChange])>;
+
+/// Layout tracking driven by browser observers instead of polling
+///
+/// A `ResizeObserver` reports size changes of the scope and the tracked
+/// elements, and a `MutationObserver` reports children being added, removed
+/// or reordered anywhere inside the scope. Observers only mark elements as
+/// dirty; the dirty elements are then measured together in one read phase
+/// and subscribers receive the resulting [`LayoutChange`]s. Size changes are
+/// measured right in the resize callback, after layout and before paint,
+/// while DOM changes are measured on the next animation frame.
+///
+/// Elements marked with [`LAYOUT_ATTRIBUTE`] inside the scope are tracked
+/// automatically as they come and go; others can be tracked explicitly.
+/// A size change of any tracked element may shift the others, so it gets
+/// them all measured, as do class changes and DOM changes in the scope.
+/// Layouts are measured relative to the page, so scrolling is not a change.
+#[derive(Clone)]
+pub struct LayoutObserver {
+    state: Rc<RefCell<ObserverState>>,
+    callbacks: Rc<ObserverCallbacks>,
+}
+
+/// Bookkeeping shared by the observer callbacks
+struct ObserverState {
+    tracker: LayoutTracker,
+    scope: Element,
+    resize_observer: Option<web_sys::ResizeObserver>,
+    dirty: HashSet<String>,
+    auto_track: bool,
+    rescan: bool,
+    next_key: usize,
+    animator: FLIPAnimator,
+    animation: Option<LayoutAnimationConfig>,
+    element_animations: HashMap<String, LayoutAnimationConfig>,
+    listeners: Vec<ChangeListener>,
+}
+
+/// Callbacks handed to the browser, alive as long as a handle is
+struct ObserverCallbacks {
+    mutation_observer: web_sys::MutationObserver,
+    _resize: Closure<dyn FnMut(js_sys::Array)>,
+    _mutation: Closure<dyn FnMut(js_sys::Array)>,
+    frame: FrameLoop,
+}
+
+impl LayoutObserver {
+    /// Observe the layout of `scope` and the marked elements inside it
+    pub fn new(scope: &Element) -> Result<Self, String> {
+        Self::observe(scope, true)
+    }
+
+    /// Observe the layout of `scope` for the elements tracked explicitly
+    ///
+    /// Marked elements are left to the observer their owner tracks them with,
+    /// which lets groups nest without animating an element twice.
+    pub fn for_group(scope: &Element) -> Result<Self, String> {
+        Self::observe(scope, false)
+    }
+
+    fn observe(scope: &Element, auto_track: bool) -> Result<Self, String> {
+        let state = Rc::new(RefCell::new(ObserverState {
+            tracker: LayoutTracker::new(),
+            scope: scope.clone(),
+            resize_observer: None,
+            dirty: HashSet::new(),
+            auto_track,
+            rescan: false,
+            next_key: 0,
+            animator: FLIPAnimator::new(),
+            animation: None,
+            element_animations: HashMap::new(),
+            listeners: Vec::new(),
+        }));
+
+        let frame_state = Rc::downgrade(&state);
+        let frame = FrameLoop::new(move |_timestamp| {
+            let Some(state) = frame_state.upgrade() else {
+                return false;
+            };
+            flush(&state);
+            let mut state = state.borrow_mut();
+            state.animator.update();
+            state.animator.active_count() > 0
+        });
+
+        let resize_state = Rc::downgrade(&state);
+        let resize_frame = frame.clone();
+        let resize = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+            let Some(state) = resize_state.upgrade() else {
+                return;
+            };
+            {
+                let mut state = state.borrow_mut();
+                for entry in entries.iter() {
+                    let target = entry
+                        .unchecked_into::<web_sys::ResizeObserverEntry>()
+                        .target();
+                    state.mark_dirty(&target);
+                }
+            }
+            // Layout is already up to date here, so reading it is cheap
+            flush(&state);
+            if state.borrow().animator.active_count() > 0 {
+                resize_frame.request();
+            }
+        }) as Box<dyn FnMut(js_sys::Array)>);
+
+        let mutation_state = Rc::downgrade(&state);
+        let mutation_frame = frame.clone();
+        let mutation = Closure::wrap(Box::new(move |_records: js_sys::Array| {
+            let Some(state) = mutation_state.upgrade() else {
+                return;
+            };
+            {
+                let mut state = state.borrow_mut();
+                state.rescan = true;
+                state.mark_all_dirty();
+            }
+            mutation_frame.request();
+        }) as Box<dyn FnMut(js_sys::Array)>);
+
+        let resize_observer = web_sys::ResizeObserver::new(resize.as_ref().unchecked_ref())
+            .map_err(|_| "ResizeObserver is not supported".to_string())?;
+        let mutation_observer = web_sys::MutationObserver::new(mutation.as_ref().unchecked_ref())
+            .map_err(|_| "MutationObserver is not supported".to_string())?;
+        let options = web_sys::MutationObserverInit::new();
+        options.set_child_list(true);
+        options.set_subtree(true);
+        options.set_attribute_filter(&js_sys::Array::of2(
+            &LAYOUT_ATTRIBUTE.into(),
+            &"class".into(),
+        ));
+        mutation_observer
+            .observe_with_options(scope, &options)
+            .map_err(|_| "Failed to observe layout scope".to_string())?;
+        resize_observer.observe(scope);
+
+        {
+            let mut state = state.borrow_mut();
+            state.resize_observer = Some(resize_observer);
+            state.rescan();
+        }
+        Ok(Self {
+            state,
+            callbacks: Rc::new(ObserverCallbacks {
+                mutation_observer,
+                _resize: resize,
+                _mutation: mutation,
+                frame,
+            }),
+        })
+    }
+
+    /// Track `element`, returning the id its changes are reported under
+    ///
+    /// Elements get a key of their own, so elements sharing a DOM `id` are
+    /// tracked apart; the DOM `id` is reported as the change's label.
+    pub fn track(&self, element: &Element) -> String {
+        self.state.borrow_mut().track(element)
+    }
+
+    /// Track `element` and play a FLIP animation with `config` when it changes
+    ///
+    /// `config` takes precedence over the one set by [`Self::animate_changes`].
+    pub fn track_animated(&self, element: &Element, config: LayoutAnimationConfig) -> String {
+        let mut state = self.state.borrow_mut();
+        let element_id = state.track(element);
+        state.element_animations.insert(element_id.clone(), config);
+        element_id
+    }
+
+    /// Id the changes of a tracked element are reported under
+    pub fn element_id(&self, element: &Element) -> Option<String> {
+        self.state.borrow().element_id(element)
+    }
+
+    /// Stop tracking the element reported under `element_id`
+    pub fn untrack(&self, element_id: &str) -> Result<(), String> {
+        self.state.borrow_mut().untrack(element_id)
+    }
+
+    /// Call `listener` with the changes of every read phase that found any
+    pub fn subscribe(&self, listener: impl FnMut(&[LayoutChange]) + 'static) {
+        self.state.borrow_mut().listeners.push(Box::new(listener));
+    }
+
+    /// Play a FLIP animation with `config` for every change from now on
+    ///
+    /// Elements already animating start again from where they are on screen.
+    pub fn animate_changes(&self, config: LayoutAnimationConfig) {
+        self.state.borrow_mut().animation = config.enabled.then_some(config);
+    }
+
+    /// Number of tracked elements
+    pub fn tracked_count(&self) -> usize {
+        self.state.borrow().tracker.tracked_elements.len()
+    }
+
+    /// Read the underlying tracker, e.g. for its history and statistics
+    pub fn with_tracker<R>(&self, read: impl FnOnce(&LayoutTracker) -> R) -> R {
+        read(&self.state.borrow().tracker)
+    }
+
+    /// Read the animator playing the FLIP animations of tracked elements
+    pub fn with_animator<R>(&self, read: impl FnOnce(&FLIPAnimator) -> R) -> R {
+        read(&self.state.borrow().animator)
+    }
+
+    /// Stop observing and finish running animations in place
+    pub fn disconnect(&self) {
+        self.callbacks.mutation_observer.disconnect();
+        let mut state = self.state.borrow_mut();
+        if let Some(resize_observer) = &state.resize_observer {
+            resize_observer.disconnect();
+        }
+        self.callbacks.frame.cancel();
+        state.dirty.clear();
+        state.animator.cancel_all();
+    }
+}
+
+impl PartialEq for LayoutObserver {
+    fn eq(&self, other: &Self) -> bool {
+        // Handles of the same observer are equal
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Drop for LayoutObserver {
+    fn drop(&mut self) {
+        // The browser must not call the callbacks once the last handle is gone
+        if Rc::strong_count(&self.callbacks) == 1 {
+            self.disconnect();
+        }
+    }
+}
+
+impl ObserverState {
+    /// Id of a tracked element
+    fn element_id(&self, element: &Element) -> Option<String> {
+        self.tracker
+            .tracked_elements
+            .values()
+            .find(|tracked| &tracked.element == element)
+            .map(|tracked| tracked.id.clone())
+    }
+
+    /// Start tracking `element` from its current layout
+    fn track(&mut self, element: &Element) -> String {
+        if let Some(element_id) = self.element_id(element) {
+            return element_id;
+        }
+        self.next_key += 1;
+        let element_id = format!("layout-{}", self.next_key);
+        self.tracker
+            .insert_element(element_id.clone(), element.clone(), page_layout(element));
+        if let Some(resize_observer) = &self.resize_observer {
+            resize_observer.observe(element);
+        }
+        element_id
+    }
+
+    /// Stop tracking the element under `element_id`
+    fn untrack(&mut self, element_id: &str) -> Result<(), String> {
+        if let (Some(tracked), Some(resize_observer)) = (
+            self.tracker.tracked_elements.get(element_id),
+            &self.resize_observer,
+        ) {
+            resize_observer.unobserve(&tracked.element);
+        }
+        self.dirty.remove(element_id);
+        self.element_animations.remove(element_id);
+        self.animator.cancel(element_id);
+        self.tracker.untrack_element(element_id)
+    }
+
+    /// Track marked elements that appeared in the scope and drop those that left
+    fn rescan(&mut self) {
+        let removed: Vec<String> = self
+            .tracker
+            .tracked_elements
+            .values()
+            .filter(|tracked| !self.scope.contains(Some(&tracked.element)))
+            .map(|tracked| tracked.id.clone())
+            .collect();
+        for element_id in removed {
+            let _ = self.untrack(&element_id);
+        }
+        if !self.auto_track {
+            return;
+        }
+
+        let Ok(marked) = self
+            .scope
+            .query_selector_all(&format!("[{}]", LAYOUT_ATTRIBUTE))
+        else {
+            return;
+        };
+        for index in 0..marked.length() {
+            if let Some(element) = marked
+                .item(index)
+                .and_then(|node| node.dyn_into::<Element>().ok())
+            {
+                self.track(&element);
+            }
+        }
+    }
+
+    /// Mark every tracked element for measuring
+    fn mark_all_dirty(&mut self) {
+        let ids: Vec<String> = self.tracker.tracked_elements.keys().cloned().collect();
+        self.dirty.extend(ids);
+    }
+
+    /// Mark everything for measuring when the scope or a tracked element resized
+    fn mark_dirty(&mut self, target: &Element) {
+        if target == &self.scope || self.element_id(target).is_some() {
+            self.mark_all_dirty();
+        }
+    }
+
+    /// Measure every dirty element in one read phase, starting FLIP animations
+    ///
+    /// Elements in the middle of a FLIP animation are read twice: once where
+    /// they are on screen, so a new animation starts from there, and once
+    /// without their transform for their actual layout.
+    fn measure_dirty(&mut self) -> Vec<LayoutChange> {
+        if self.rescan {
+            self.rescan = false;
+            self.rescan();
+        }
+        if self.dirty.is_empty() {
+            return Vec::new();
+        }
+        let dirty: Vec<(String, Element)> = self
+            .dirty
+            .drain()
+            .filter_map(|element_id| {
+                let element = self
+                    .tracker
+                    .tracked_elements
+                    .get(&element_id)?
+                    .element
+                    .clone();
+                Some((element_id, element))
+            })
+            .collect();
+
+        let visual: Vec<DomRect> = dirty
+            .iter()
+            .map(|(_, element)| page_layout(element))
+            .collect();
+        // Interrupted animations start from where the element is seen, moving as it was
+        let interrupted: Vec<Option<TransformValues>> = dirty
+            .iter()
+            .map(|(element_id, _)| {
+                let velocity = self.animator.velocity(element_id);
+                self.animator.cancel(element_id);
+                velocity
+            })
+            .collect();
+        let layouts: Vec<DomRect> = dirty
+            .iter()
+            .map(|(_, element)| page_layout(element))
+            .collect();
+
+        let timestamp = js_sys::Date::now();
+        let mut changes = Vec::new();
+        for (((element_id, element), visual), (layout, interrupted)) in dirty
+            .into_iter()
+            .zip(visual)
+            .zip(layouts.into_iter().zip(interrupted))
+        {
+            let Some(change) = self.tracker.record_layout(&element_id, layout, timestamp) else {
+                continue;
+            };
+            let config = self
+                .element_animations
+                .get(&element_id)
+                .or(self.animation.as_ref())
+                .filter(|config| config.enabled);
+            if let Some(config) = config {
+                let (first, velocity) = match interrupted {
+                    Some(velocity) => (visual, velocity),
+                    None => (change.previous_layout.clone(), TransformValues::zero()),
+                };
+                let _ = self.animator.animate_with_velocity(
+                    element_id,
+                    element,
+                    first,
+                    change.current_layout.clone(),
+                    config.clone(),
+                    velocity,
+                );
+            }
+            changes.push(change);
+        }
+        // Invert new animations before the browser paints the new layout
+        if self.animator.active_count() > 0 {
+            self.animator.update();
+        }
+        changes
+    }
+}
+
+/// Layout of `element` relative to the page rather than the viewport
+fn page_layout(element: &Element) -> DomRect {
+    let rect = element.get_bounding_client_rect();
+    let (scroll_x, scroll_y) = web_sys::window()
+        .map(|window| {
+            (
+                window.scroll_x().unwrap_or(0.0),
+                window.scroll_y().unwrap_or(0.0),
+            )
+        })
+        .unwrap_or((0.0, 0.0));
+    DomRect::new_with_x_and_y_and_width_and_height(
+        rect.x() + scroll_x,
+        rect.y() + scroll_y,
+        rect.width(),
+        rect.height(),
+    )
+    .unwrap_or(rect)
+}
+
+/// Run a read phase and hand its changes to the listeners
+fn flush(state: &RefCell<ObserverState>) {
+    let (changes, mut listeners) = {
+        let mut state = state.borrow_mut();
+        let changes = state.measure_dirty();
+        if changes.is_empty() {
+            return;
+        }
+        (changes, std::mem::take(&mut state.listeners))
+    };
+
+    // Listeners may track or subscribe, so they run without the borrow
+    for listener in &mut listeners {
+        listener(&changes);
+    }
+    let mut state = state.borrow_mut();
+    listeners.append(&mut state.listeners);
+    state.listeners = listeners;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -535,4 +1010,87 @@ mod tests {
         let impact = tracker.calculate_performance_impact(&old, &large_change);
         assert!(matches!(impact, PerformanceImpact::Low));
     }
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> DomRect {
+        DomRect::new_with_x_and_y_and_width_and_height(x, y, width, height).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn test_record_layout_reports_change_type() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let element = document.create_element("div").unwrap();
+        let mut tracker = LayoutTracker::new();
+        tracker.insert_element("card".to_string(), element, rect(0.0, 0.0, 100.0, 50.0));
+
+        assert!(
+            tracker
+                .record_layout("card", rect(0.0, 0.0, 100.0, 50.0), 1.0)
+                .is_none()
+        );
+        let change = tracker
+            .record_layout("card", rect(20.0, 0.0, 100.0, 50.0), 2.0)
+            .unwrap();
+        assert!(matches!(change.change_type, LayoutChangeType::Position));
+        let change = tracker
+            .record_layout("card", rect(20.0, 0.0, 100.0, 80.0), 3.0)
+            .unwrap();
+        assert!(matches!(change.change_type, LayoutChangeType::Size));
+        assert_eq!(change.previous_layout.height(), 50.0);
+        assert_eq!(tracker.get_element_changes("card").len(), 2);
+        assert_eq!(tracker.get_stats().total_changes, 2);
+        assert!(
+            tracker
+                .record_layout("missing", rect(0.0, 0.0, 1.0, 1.0), 4.0)
+                .is_none()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_layout_observer_tracks_marked_elements() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let scope = document.create_element("div").unwrap();
+        let marked = document.create_element("div").unwrap();
+        marked.set_attribute(LAYOUT_ATTRIBUTE, "").unwrap();
+        let unmarked = document.create_element("div").unwrap();
+        scope.append_child(&marked).unwrap();
+        scope.append_child(&unmarked).unwrap();
+        document.body().unwrap().append_child(&scope).unwrap();
+
+        let observer = LayoutObserver::new(&scope).unwrap();
+        assert_eq!(observer.tracked_count(), 1);
+        let element_id = observer.track(&unmarked);
+        assert_eq!(observer.track(&unmarked), element_id);
+        assert_eq!(observer.tracked_count(), 2);
+        observer.untrack(&element_id).unwrap();
+        assert_eq!(observer.tracked_count(), 1);
+
+        observer.disconnect();
+        scope.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_layout_observer_tracks_elements_sharing_an_id_apart() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let scope = document.create_element("div").unwrap();
+        let first = document.create_element("div").unwrap();
+        let second = document.create_element("div").unwrap();
+        first.set_id("card");
+        second.set_id("card");
+        scope.append_child(&first).unwrap();
+        scope.append_child(&second).unwrap();
+        document.body().unwrap().append_child(&scope).unwrap();
+
+        let observer = LayoutObserver::for_group(&scope).unwrap();
+        let first_id = observer.track(&first);
+        let second_id = observer.track(&second);
+        assert_ne!(first_id, second_id);
+        assert_eq!(observer.tracked_count(), 2);
+        observer.with_tracker(|tracker| {
+            assert_eq!(tracker.tracked_elements[&first_id].label, "card");
+            assert_eq!(tracker.tracked_elements[&second_id].label, "card");
+        });
+
+        observer.disconnect();
+        scope.remove();
+    }
 }
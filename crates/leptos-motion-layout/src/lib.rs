@@ -6,21 +6,48 @@
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
+pub mod crossfade;
+pub mod debug_overlay;
 pub mod flip;
 pub mod layout_tracker;
+pub mod presets;
 pub mod shared_elements;
 pub mod simplified_layout_api;
+pub mod view_transitions;
+
+use leptos_motion_core::{Easing, Transition};
 
 // Re-export main types
-pub use flip::{EasingFunction, FLIPAnimation, FLIPAnimator, FLIPState, TransformValues};
-pub use layout_tracker::{LayoutChange, LayoutChangeType, LayoutTracker, PerformanceImpact};
-pub use shared_elements::{SharedElementConfig, SharedElementManager, ZIndexStrategy};
+pub use crossfade::{Crossfade, CrossfadeConfig, CrossfadeFrame};
+pub use debug_overlay::{AnimationSnapshot, LayoutDebugOverlay, impact_color, projected_layout};
+pub use flip::{
+    FLIPAnimation, FLIPAnimator, FLIPState, HeightAnimation, InlineStyles, LAYOUT_ATTRIBUTE,
+    LayoutMode, ScaleCorrection, TransformValues, correct_box_shadow, correct_radius,
+    exclude_from_transition, without_transition_properties,
+};
+pub use layout_tracker::{
+    LayoutChange, LayoutChangeType, LayoutObserver, LayoutTracker, PerformanceImpact,
+};
+pub use leptos_motion_core::{computed_size, natural_size};
+pub use presets::{
+    Breakpoint, BreakpointObserver, LayoutPreset, LayoutPresetManager, ParameterType,
+    PresetParameter, PresetType,
+};
+pub use shared_elements::{
+    InterruptionPolicy, LAYOUT_ID_ATTRIBUTE, LayoutAnimationSlot, LayoutSnapshot,
+    SharedElementConfig, SharedElementManager, SharedLayoutEnter, TransitionPriority,
+    ZIndexStrategy, element_layout, elevated_copy, shared_layout_at, shared_layout_transform,
+    transform_css_at,
+};
 
 // Re-export simplified layout API (new public API)
 pub use simplified_layout_api::{
-    SimplifiedAnimationStatus, SimplifiedEasing, SimplifiedLayoutConfig, SimplifiedLayoutManager,
+    SimplifiedAnimationStatus, SimplifiedLayoutConfig, SimplifiedLayoutManager,
     SimplifiedPerformanceMetrics,
 };
+pub use view_transitions::{
+    VIEW_TRANSITION_NAME_PREFIX, ViewTransitionEngine, view_transition_name,
+};
 
 /// Layout information for FLIP animations
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -90,8 +117,8 @@ pub struct LayoutAnimationConfig {
     pub enabled: bool,
     /// Animation duration in seconds
     pub duration: f64,
-    /// Easing function
-    pub easing: EasingFunction,
+    /// Easing function, springs are simulated with their velocity
+    pub easing: Easing,
     /// Whether to use hardware acceleration
     pub hardware_accelerated: bool,
 }
@@ -101,7 +128,7 @@ impl Default for LayoutAnimationConfig {
         Self {
             enabled: true,
             duration: 0.3,
-            easing: EasingFunction::default(),
+            easing: Easing::EaseOut,
             hardware_accelerated: true,
         }
     }
@@ -120,7 +147,7 @@ impl LayoutAnimationConfig {
     }
 
     /// Set easing function
-    pub fn with_easing(mut self, easing: EasingFunction) -> Self {
+    pub fn with_easing(mut self, easing: Easing) -> Self {
         self.easing = easing;
         self
     }
@@ -138,9 +165,25 @@ impl LayoutAnimationConfig {
     }
 }
 
+impl From<&Transition> for LayoutAnimationConfig {
+    /// Layout animation following a motion transition, so layout and
+    /// property animations with the same transition move alike
+    ///
+    /// A spring without a duration plays over its settling duration.
+    fn from(transition: &Transition) -> Self {
+        let config = Self::default();
+        Self {
+            duration: transition.effective_duration().unwrap_or(config.duration),
+            easing: transition.ease.clone(),
+            ..config
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use leptos_motion_core::{SpringConfig, SpringSimulator};
 
     #[test]
     fn test_layout_info_new() {
@@ -237,13 +280,46 @@ mod tests {
 
     #[test]
     fn test_layout_animation_config_with_easing() {
-        let config = LayoutAnimationConfig::new().with_easing(EasingFunction::Linear);
+        let config = LayoutAnimationConfig::new().with_easing(Easing::Linear);
 
         match config.easing {
-            EasingFunction::Linear => {}
+            Easing::Linear => {}
             _ => panic!("Expected Linear easing function"),
         }
     }
+
+    #[test]
+    fn test_layout_animation_config_from_transition() {
+        let spring = SpringConfig {
+            stiffness: 300.0,
+            damping: 20.0,
+            ..Default::default()
+        };
+        let config = LayoutAnimationConfig::from(&Transition {
+            duration: Some(0.6),
+            ease: Easing::Spring(spring.clone()),
+            ..Default::default()
+        });
+        assert!(config.enabled);
+        assert_eq!(config.duration, 0.6);
+        assert_eq!(config.easing, Easing::Spring(spring.clone()));
+
+        let config = LayoutAnimationConfig::from(&Transition {
+            duration: None,
+            ..Default::default()
+        });
+        assert_eq!(config.duration, 0.3);
+
+        let settling = SpringSimulator::new(spring.clone())
+            .unwrap()
+            .settling_duration();
+        let config = LayoutAnimationConfig::from(&Transition {
+            duration: None,
+            ease: Easing::Spring(spring),
+            ..Default::default()
+        });
+        assert_eq!(config.duration, settling);
+    }
 }
 
 // Include simplified layout tests
@@ -3,10 +3,22 @@
 //! This module provides predefined animation configurations for
 //! common layout changes like list reordering, grid resizing,
 //! responsive breakpoints, modal expansion, and card flipping.
+//!
+//! Presets compute the boxes items take once applied from their parameters,
+//! such as the columns and gap of a grid, without measuring the page again.
+//! [`LayoutPresetManager::animate`] applies a preset to the children of a
+//! container and plays the change as staggered FLIP animations, or as height
+//! animations for `modal-expand`.
 
-use crate::LayoutAnimationConfig;
+use crate::flip::{FLIPAnimator, HeightAnimation};
+use crate::{LayoutAnimationConfig, LayoutInfo};
+use leptos_motion_core::{
+    Easing, StaggerConfig, StaggerFrom, StaggerResolver, computed_size, inline_style, natural_size,
+};
 use std::collections::HashMap;
-use web_sys::DomRect;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use web_sys::{DomRect, Element, MediaQueryList};
 
 /// Layout animation preset
 #[derive(Debug, Clone)]
@@ -19,6 +31,8 @@ pub struct LayoutPreset {
     pub preset_type: PresetType,
     /// Default configuration
     pub default_config: LayoutAnimationConfig,
+    /// Delay between the animations of consecutive items, in seconds
+    pub stagger: f64,
     /// Required parameters
     pub required_parameters: Vec<PresetParameter>,
     /// Optional parameters
@@ -101,13 +115,10 @@ impl LayoutPresetManager {
             name: "list-reorder".to_string(),
             description: "Smooth animation for reordering list items".to_string(),
             preset_type: PresetType::ListReorder,
-            default_config: LayoutAnimationConfig {
-                duration: 300.0,
-                easing: "ease-out".to_string(),
-                delay: 0.0,
-                stagger: 50.0,
-                ..Default::default()
-            },
+            default_config: LayoutAnimationConfig::new()
+                .with_duration(0.3)
+                .with_easing(Easing::EaseOut),
+            stagger: 0.05,
             required_parameters: vec![PresetParameter {
                 name: "direction".to_string(),
                 description: "Reorder direction (horizontal/vertical)".to_string(),
@@ -115,13 +126,30 @@ impl LayoutPresetManager {
                 default_value: Some("vertical".to_string()),
                 required: false,
             }],
-            optional_parameters: vec![PresetParameter {
-                name: "stagger".to_string(),
-                description: "Delay between item animations".to_string(),
-                parameter_type: ParameterType::Duration,
-                default_value: Some("50".to_string()),
-                required: false,
-            }],
+            optional_parameters: vec![
+                PresetParameter {
+                    name: "stagger".to_string(),
+                    description: "Delay between item animations".to_string(),
+                    parameter_type: ParameterType::Duration,
+                    default_value: Some("50".to_string()),
+                    required: false,
+                },
+                PresetParameter {
+                    name: "order".to_string(),
+                    description: "Current indices of the items in their new order, comma separated"
+                        .to_string(),
+                    parameter_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                },
+                PresetParameter {
+                    name: "gap".to_string(),
+                    description: "Space between items in pixels".to_string(),
+                    parameter_type: ParameterType::Number,
+                    default_value: Some("0".to_string()),
+                    required: false,
+                },
+            ],
         };
 
         // Grid resize preset
@@ -129,13 +157,10 @@ impl LayoutPresetManager {
             name: "grid-resize".to_string(),
             description: "Animated grid column/row changes".to_string(),
             preset_type: PresetType::GridResize,
-            default_config: LayoutAnimationConfig {
-                duration: 400.0,
-                easing: "ease-in-out".to_string(),
-                delay: 0.0,
-                stagger: 0.0,
-                ..Default::default()
-            },
+            default_config: LayoutAnimationConfig::new()
+                .with_duration(0.4)
+                .with_easing(Easing::EaseInOut),
+            stagger: 0.0,
             required_parameters: vec![PresetParameter {
                 name: "columns".to_string(),
                 description: "Target number of columns".to_string(),
@@ -143,13 +168,24 @@ impl LayoutPresetManager {
                 default_value: None,
                 required: true,
             }],
-            optional_parameters: vec![PresetParameter {
-                name: "maintain_aspect".to_string(),
-                description: "Maintain item aspect ratios".to_string(),
-                parameter_type: ParameterType::Boolean,
-                default_value: Some("true".to_string()),
-                required: false,
-            }],
+            optional_parameters: vec![
+                PresetParameter {
+                    name: "maintain_aspect".to_string(),
+                    description: "Items keep their aspect ratios as their width changes"
+                        .to_string(),
+                    parameter_type: ParameterType::Boolean,
+                    default_value: Some("true".to_string()),
+                    required: false,
+                },
+                PresetParameter {
+                    name: "gap".to_string(),
+                    description: "Space between cells in pixels, the container's own by default"
+                        .to_string(),
+                    parameter_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                },
+            ],
         };
 
         // Responsive breakpoint preset
@@ -157,27 +193,44 @@ impl LayoutPresetManager {
             name: "responsive-breakpoint".to_string(),
             description: "Smooth transition between responsive layouts".to_string(),
             preset_type: PresetType::ResponsiveBreakpoint,
-            default_config: LayoutAnimationConfig {
-                duration: 500.0,
-                easing: "ease-in-out".to_string(),
-                delay: 0.0,
-                stagger: 0.0,
-                ..Default::default()
-            },
-            required_parameters: vec![PresetParameter {
-                name: "breakpoint".to_string(),
-                description: "Target breakpoint (sm/md/lg/xl)".to_string(),
-                parameter_type: ParameterType::String,
-                default_value: None,
-                required: true,
-            }],
-            optional_parameters: vec![PresetParameter {
-                name: "preserve_content".to_string(),
-                description: "Preserve content during transition".to_string(),
-                parameter_type: ParameterType::Boolean,
-                default_value: Some("true".to_string()),
-                required: false,
-            }],
+            default_config: LayoutAnimationConfig::new()
+                .with_duration(0.5)
+                .with_easing(Easing::EaseInOut),
+            stagger: 0.0,
+            required_parameters: vec![],
+            optional_parameters: vec![
+                PresetParameter {
+                    name: "breakpoint".to_string(),
+                    description:
+                        "Target breakpoint (sm/md/lg/xl), the matching media query by default"
+                            .to_string(),
+                    parameter_type: ParameterType::String,
+                    default_value: None,
+                    required: false,
+                },
+                PresetParameter {
+                    name: "preserve_content".to_string(),
+                    description: "Preserve content during transition".to_string(),
+                    parameter_type: ParameterType::Boolean,
+                    default_value: Some("true".to_string()),
+                    required: false,
+                },
+                PresetParameter {
+                    name: "columns".to_string(),
+                    description: "Columns at the breakpoint, overriding its default".to_string(),
+                    parameter_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                },
+                PresetParameter {
+                    name: "gap".to_string(),
+                    description: "Space between cells in pixels, the container's own by default"
+                        .to_string(),
+                    parameter_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                },
+            ],
         };
 
         // Modal expand preset
@@ -185,13 +238,10 @@ impl LayoutPresetManager {
             name: "modal-expand".to_string(),
             description: "Expand/collapse modal with backdrop".to_string(),
             preset_type: PresetType::ModalExpand,
-            default_config: LayoutAnimationConfig {
-                duration: 250.0,
-                easing: "ease-out".to_string(),
-                delay: 0.0,
-                stagger: 0.0,
-                ..Default::default()
-            },
+            default_config: LayoutAnimationConfig::new()
+                .with_duration(0.25)
+                .with_easing(Easing::EaseOut),
+            stagger: 0.0,
             required_parameters: vec![PresetParameter {
                 name: "action".to_string(),
                 description: "Action to perform (expand/collapse)".to_string(),
@@ -199,13 +249,30 @@ impl LayoutPresetManager {
                 default_value: None,
                 required: true,
             }],
-            optional_parameters: vec![PresetParameter {
-                name: "backdrop".to_string(),
-                description: "Animate backdrop separately".to_string(),
-                parameter_type: ParameterType::Boolean,
-                default_value: Some("true".to_string()),
-                required: false,
-            }],
+            optional_parameters: vec![
+                PresetParameter {
+                    name: "backdrop".to_string(),
+                    description: "Animate backdrop separately".to_string(),
+                    parameter_type: ParameterType::Boolean,
+                    default_value: Some("true".to_string()),
+                    required: false,
+                },
+                PresetParameter {
+                    name: "natural_height".to_string(),
+                    description: "Height of the expanded content, measured when animated"
+                        .to_string(),
+                    parameter_type: ParameterType::Number,
+                    default_value: None,
+                    required: false,
+                },
+                PresetParameter {
+                    name: "collapsed_height".to_string(),
+                    description: "Height in pixels once collapsed".to_string(),
+                    parameter_type: ParameterType::Number,
+                    default_value: Some("0".to_string()),
+                    required: false,
+                },
+            ],
         };
 
         // Card flip preset
@@ -213,13 +280,10 @@ impl LayoutPresetManager {
             name: "card-flip".to_string(),
             description: "3D card flip animation".to_string(),
             preset_type: PresetType::CardFlip,
-            default_config: LayoutAnimationConfig {
-                duration: 600.0,
-                easing: "ease-in-out".to_string(),
-                delay: 0.0,
-                stagger: 0.0,
-                ..Default::default()
-            },
+            default_config: LayoutAnimationConfig::new()
+                .with_duration(0.6)
+                .with_easing(Easing::EaseInOut),
+            stagger: 0.0,
             required_parameters: vec![PresetParameter {
                 name: "axis".to_string(),
                 description: "Flip axis (x/y)".to_string(),
@@ -298,63 +362,246 @@ impl LayoutPresetManager {
         self.custom_presets.keys().collect()
     }
 
-    /// Calculate target layout for a preset
-    pub fn calculate_target_layout(
+    /// Layouts `items` take inside `container` once `preset` is applied
+    ///
+    /// Grids are laid out from their columns, gap and the item index, rows
+    /// as tall as their tallest item. Lists stack their items in the new
+    /// order from where the first one starts. Expanding gives an item its
+    /// natural height and collapsing its collapsed height, while card flips
+    /// and custom presets leave the layout unchanged.
+    pub fn calculate_target_layouts(
         &self,
         preset: &LayoutPreset,
-        current_layout: &DomRect,
+        container: &LayoutInfo,
+        items: &[LayoutInfo],
         parameters: &HashMap<String, String>,
-    ) -> Result<DomRect, String> {
+    ) -> Result<Vec<LayoutInfo>, String> {
         match &preset.preset_type {
             PresetType::ListReorder => {
-                // For list reorder, layout remains the same
-                Ok(current_layout.clone())
-            }
-            PresetType::GridResize => {
-                // For grid resize, calculate new dimensions
-                let column_count = parameters
-                    .get("columns")
-                    .ok_or("Missing required parameter: columns")?
-                    .parse::<f64>()
-                    .map_err(|_| "Invalid column count")?;
-
-                // For now, just return current layout as a placeholder
-                // In a real implementation, this would calculate new grid dimensions
-                let _new_width = current_layout.width() * column_count;
-                Ok(current_layout.clone())
+                let order = list_order(parameters, items.len())?;
+                let gap = parameter(parameters, "gap", Some(0.0))?;
+                let horizontal = parameter(parameters, "direction", Some("vertical".to_string()))?
+                    == "horizontal";
+                Ok(list_layouts(items, &order, gap, horizontal))
             }
-            PresetType::ResponsiveBreakpoint => {
-                // For responsive breakpoint, layout changes based on breakpoint
-                let breakpoint = parameters
-                    .get("breakpoint")
-                    .ok_or("Missing required parameter: breakpoint")?;
-
-                // For now, just return current layout as a placeholder
-                // In a real implementation, this would calculate new responsive layout
-                log::info!("Calculating layout for breakpoint: {}", breakpoint);
-                Ok(current_layout.clone())
+            PresetType::GridResize | PresetType::ResponsiveBreakpoint => {
+                let columns = grid_columns(preset, parameters)?;
+                let gap = parameter(parameters, "gap", Some(0.0))?;
+                let maintain_aspect = parameter(parameters, "maintain_aspect", Some(true))?;
+                Ok(grid_layouts(
+                    container,
+                    items,
+                    columns,
+                    gap,
+                    maintain_aspect,
+                ))
             }
             PresetType::ModalExpand => {
-                // For modal expand, layout changes based on action
-                let action = parameters
-                    .get("action")
-                    .ok_or("Missing required parameter: action")?;
-
-                // For now, just return current layout as a placeholder
-                // In a real implementation, this would calculate new modal dimensions
-                log::info!("Calculating layout for modal action: {}", action);
-                Ok(current_layout.clone())
+                let action = parameter::<String>(parameters, "action", None)?;
+                let expand = match action.as_str() {
+                    "expand" => true,
+                    "collapse" => false,
+                    _ => return Err(format!("Unknown modal action: {}", action)),
+                };
+                let collapsed_height = parameter(parameters, "collapsed_height", Some(0.0))?;
+                items
+                    .iter()
+                    .map(|item| {
+                        let height = if expand {
+                            parameter(parameters, "natural_height", Some(item.height))?
+                        } else {
+                            collapsed_height
+                        };
+                        Ok(LayoutInfo::new(item.x, item.y, item.width, height))
+                    })
+                    .collect()
             }
-            PresetType::CardFlip => {
-                // For card flip, layout remains the same
-                Ok(current_layout.clone())
+            PresetType::CardFlip | PresetType::Custom(_) => Ok(items.to_vec()),
+        }
+    }
+
+    /// Apply `preset` to the `items` of `container` and animate them into place
+    ///
+    /// The items are measured, the preset applied to the page and each item
+    /// animated from its old box into the one it is laid out at afterwards,
+    /// staggered by the preset's stagger. `modal-expand` instead animates the
+    /// height of each item to its natural height or its collapsed height, so
+    /// the content is revealed rather than stretched. Returns the layouts
+    /// computed for the items, which the page may round or constrain
+    /// differently. The animations start before returning; keep calling
+    /// [`FLIPAnimator::update`] every frame to play them.
+    pub fn animate(
+        &self,
+        preset: &LayoutPreset,
+        container: &Element,
+        items: &[Element],
+        parameters: &HashMap<String, String>,
+        animator: &mut FLIPAnimator,
+    ) -> Result<Vec<LayoutInfo>, String> {
+        let first: Vec<DomRect> = items
+            .iter()
+            .map(Element::get_bounding_client_rect)
+            .collect();
+        let layouts: Vec<LayoutInfo> = first.iter().map(rect_layout).collect();
+        let content = content_box(container);
+        let stagger = self.stagger_resolver(preset, parameters)?;
+
+        if matches!(preset.preset_type, PresetType::ModalExpand) {
+            return self.animate_heights(preset, items, &layouts, parameters, &stagger, animator);
+        }
+
+        // Grids without a gap given keep the container's own
+        let mut layout_parameters = parameters.clone();
+        if matches!(
+            preset.preset_type,
+            PresetType::GridResize | PresetType::ResponsiveBreakpoint
+        ) && !parameters.contains_key("gap")
+        {
+            let gap = computed_size(container, "column-gap").unwrap_or(0.0);
+            layout_parameters.insert("gap".to_string(), gap.to_string());
+        }
+        let targets =
+            self.calculate_target_layouts(preset, &content, &layouts, &layout_parameters)?;
+
+        self.apply(preset, container, items, parameters)?;
+
+        let mut animations = Vec::with_capacity(items.len());
+        for (index, (item, first)) in items.iter().zip(first).enumerate() {
+            let id = item_id(preset, item, index);
+            // Measure the new layout without the transform of an earlier run
+            animator.cancel(&id);
+            let last = item.get_bounding_client_rect();
+            animations.push((id, item.clone(), first, last));
+        }
+        animator.animate_staggered(animations, preset.default_config.clone(), &stagger)?;
+        animator.update();
+
+        Ok(targets)
+    }
+
+    /// Stagger of the items of `preset`, across the grid for grid presets
+    fn stagger_resolver(
+        &self,
+        preset: &LayoutPreset,
+        parameters: &HashMap<String, String>,
+    ) -> Result<StaggerResolver, String> {
+        let delay = parameter(parameters, "stagger", Some(preset.stagger * 1000.0))? / 1000.0;
+        let resolver = StaggerResolver::new(StaggerConfig {
+            delay,
+            from: StaggerFrom::First,
+        });
+        Ok(match preset.preset_type {
+            PresetType::GridResize | PresetType::ResponsiveBreakpoint => {
+                resolver.with_grid(grid_columns(preset, parameters)?)
+            }
+            _ => resolver,
+        })
+    }
+
+    /// Expand or collapse the `items` of a `modal-expand` preset by animating their height
+    ///
+    /// Expanded items measure their natural height and go back to
+    /// `height: auto` at the end.
+    fn animate_heights(
+        &self,
+        preset: &LayoutPreset,
+        items: &[Element],
+        layouts: &[LayoutInfo],
+        parameters: &HashMap<String, String>,
+        stagger: &StaggerResolver,
+        animator: &mut FLIPAnimator,
+    ) -> Result<Vec<LayoutInfo>, String> {
+        let expand = parameter::<String>(parameters, "action", None)? == "expand";
+        let collapsed_height: f64 = parameter(parameters, "collapsed_height", Some(0.0))?;
+        let delays = stagger.delays(items.len());
+        let mut targets = Vec::with_capacity(items.len());
+        for (index, (item, layout)) in items.iter().zip(layouts).enumerate() {
+            // Heights are in CSS pixels, without what the box adds around them
+            let current = computed_size(item, "height").unwrap_or(layout.height);
+            let around = layout.height - current;
+            let height = if !expand {
+                collapsed_height
+            } else if parameters.contains_key("natural_height") {
+                parameter::<f64>(parameters, "natural_height", None)? - around
+            } else {
+                natural_size(item, "height").unwrap_or(item.scroll_height() as f64 - around)
+            };
+            let mut parameters = parameters.clone();
+            parameters.insert("natural_height".to_string(), (height + around).to_string());
+            targets.extend(self.calculate_target_layouts(
+                preset,
+                &LayoutInfo::default(),
+                std::slice::from_ref(layout),
+                &parameters,
+            )?);
+
+            let id = item_id(preset, item, index);
+            animator.animate_height(
+                id.clone(),
+                item.clone(),
+                HeightAnimation {
+                    from: current,
+                    to: height.max(0.0),
+                    auto: expand,
+                },
+                preset.default_config.clone(),
+            )?;
+            animator.delay(&id, delays[index]);
+        }
+        animator.update();
+
+        Ok(targets)
+    }
+
+    /// Change the page so the items take the layouts computed for `preset`
+    ///
+    /// Grid presets only change the columns of a grid container, and its gap
+    /// when one is given, leaving the rest of its styles alone.
+    fn apply(
+        &self,
+        preset: &LayoutPreset,
+        container: &Element,
+        items: &[Element],
+        parameters: &HashMap<String, String>,
+    ) -> Result<(), String> {
+        match &preset.preset_type {
+            PresetType::ListReorder => {
+                for index in list_order(parameters, items.len())? {
+                    container
+                        .append_child(&items[index])
+                        .map_err(|_| "Failed to reorder list item".to_string())?;
+                }
             }
-            PresetType::Custom(name) => {
-                // For custom presets, return current layout
-                log::info!("Calculating layout for custom preset: {}", name);
-                Ok(current_layout.clone())
+            PresetType::GridResize | PresetType::ResponsiveBreakpoint => {
+                let computed = web_sys::window()
+                    .and_then(|window| window.get_computed_style(container).ok().flatten())
+                    .ok_or_else(|| "Failed to read the container's styles".to_string())?;
+                let display = computed.get_property_value("display").unwrap_or_default();
+                if display != "grid" && display != "inline-grid" {
+                    return Err(format!(
+                        "Grid presets need a grid container, not display: {}",
+                        display
+                    ));
+                }
+                let columns = grid_columns(preset, parameters)?.max(1);
+                let tracks = computed
+                    .get_property_value("grid-template-columns")
+                    .unwrap_or_default();
+                let style = inline_style(container);
+                if track_count(&tracks) != columns {
+                    let _ = style.set_property(
+                        "grid-template-columns",
+                        &format!("repeat({}, minmax(0, 1fr))", columns),
+                    );
+                }
+                if parameters.contains_key("gap") {
+                    let gap: f64 = parameter(parameters, "gap", None)?;
+                    let _ = style.set_property("gap", &format!("{}px", gap));
+                }
             }
+            PresetType::ModalExpand | PresetType::CardFlip | PresetType::Custom(_) => {}
         }
+        Ok(())
     }
 
     /// Validate a preset configuration
@@ -418,6 +665,286 @@ impl Default for LayoutPresetManager {
     }
 }
 
+/// Responsive breakpoint, from the narrowest screens up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Breakpoint {
+    /// Narrow screens such as phones
+    Sm,
+    /// Screens at least 768px wide
+    Md,
+    /// Screens at least 1024px wide
+    Lg,
+    /// Screens at least 1280px wide
+    Xl,
+}
+
+impl Breakpoint {
+    /// All breakpoints, narrowest first
+    pub const ALL: [Self; 4] = [Self::Sm, Self::Md, Self::Lg, Self::Xl];
+
+    /// Breakpoint of a `breakpoint` parameter
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sm" => Some(Self::Sm),
+            "md" => Some(Self::Md),
+            "lg" => Some(Self::Lg),
+            "xl" => Some(Self::Xl),
+            _ => None,
+        }
+    }
+
+    /// Narrowest viewport width of the breakpoint in pixels
+    pub fn min_width(&self) -> f64 {
+        match self {
+            Self::Sm => 0.0,
+            Self::Md => 768.0,
+            Self::Lg => 1024.0,
+            Self::Xl => 1280.0,
+        }
+    }
+
+    /// Grid columns at the breakpoint unless a preset is given its own
+    pub fn columns(&self) -> usize {
+        match self {
+            Self::Sm => 1,
+            Self::Md => 2,
+            Self::Lg => 3,
+            Self::Xl => 4,
+        }
+    }
+
+    /// Media query matching viewports at least as wide as the breakpoint
+    pub fn media_query(&self) -> String {
+        format!("(min-width: {}px)", self.min_width())
+    }
+
+    /// Widest breakpoint whose media query matches the viewport
+    pub fn current() -> Self {
+        let Some(window) = web_sys::window() else {
+            return Self::Sm;
+        };
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|breakpoint| {
+                window
+                    .match_media(&breakpoint.media_query())
+                    .ok()
+                    .flatten()
+                    .is_some_and(|query| query.matches())
+            })
+            .unwrap_or(Self::Sm)
+    }
+}
+
+/// Reports the new [`Breakpoint`] whenever the viewport crosses one
+///
+/// The browser notifies each breakpoint's `matchMedia` query as it starts
+/// or stops matching, so nothing runs while the viewport is resized within
+/// a breakpoint. Listening stops when the observer is dropped.
+pub struct BreakpointObserver {
+    queries: Vec<MediaQueryList>,
+    listener: Closure<dyn FnMut()>,
+}
+
+impl BreakpointObserver {
+    /// Call `on_change` with the breakpoint the viewport enters
+    pub fn new(mut on_change: impl FnMut(Breakpoint) + 'static) -> Result<Self, String> {
+        let window = web_sys::window().ok_or("No window available")?;
+        let listener = Closure::<dyn FnMut()>::new(move || on_change(Breakpoint::current()));
+        let mut queries = Vec::new();
+        // The narrowest breakpoint matches every viewport
+        for breakpoint in &Breakpoint::ALL[1..] {
+            let query = window
+                .match_media(&breakpoint.media_query())
+                .ok()
+                .flatten()
+                .ok_or("Media queries are not supported")?;
+            query
+                .add_event_listener_with_callback("change", listener.as_ref().unchecked_ref())
+                .map_err(|_| "Failed to observe media query".to_string())?;
+            queries.push(query);
+        }
+        Ok(Self { queries, listener })
+    }
+}
+
+impl Drop for BreakpointObserver {
+    fn drop(&mut self) {
+        for query in &self.queries {
+            let _ = query.remove_event_listener_with_callback(
+                "change",
+                self.listener.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+/// Parameter `name` parsed, `default` when it is not given
+fn parameter<T: std::str::FromStr>(
+    parameters: &HashMap<String, String>,
+    name: &str,
+    default: Option<T>,
+) -> Result<T, String> {
+    match parameters.get(name) {
+        Some(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid value for parameter {}: {}", name, value)),
+        None => default.ok_or_else(|| format!("Missing required parameter: {}", name)),
+    }
+}
+
+/// Columns of a grid preset, given or those of its breakpoint
+fn grid_columns(
+    preset: &LayoutPreset,
+    parameters: &HashMap<String, String>,
+) -> Result<usize, String> {
+    match preset.preset_type {
+        PresetType::ResponsiveBreakpoint => {
+            let breakpoint = match parameters.get("breakpoint") {
+                Some(name) => Breakpoint::parse(name)
+                    .ok_or_else(|| format!("Unknown breakpoint: {}", name))?,
+                None => Breakpoint::current(),
+            };
+            parameter(parameters, "columns", Some(breakpoint.columns()))
+        }
+        _ => parameter(parameters, "columns", None),
+    }
+}
+
+/// Current indices of `count` list items in their new order
+fn list_order(parameters: &HashMap<String, String>, count: usize) -> Result<Vec<usize>, String> {
+    let Some(order) = parameters.get("order") else {
+        return Ok((0..count).collect());
+    };
+    let order: Vec<usize> = order
+        .split(',')
+        .map(|index| index.trim().parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("Invalid list order: {}", order))?;
+    let mut sorted = order.clone();
+    sorted.sort_unstable();
+    if sorted != (0..count).collect::<Vec<_>>() {
+        return Err(format!(
+            "List order must list each of the {} items once",
+            count
+        ));
+    }
+    Ok(order)
+}
+
+/// Cells of `items` in a grid of `columns` filling `container`
+///
+/// Rows are as tall as their tallest item. Items keeping their aspect ratio
+/// sit at the top of their row, the others stretch to its height.
+fn grid_layouts(
+    container: &LayoutInfo,
+    items: &[LayoutInfo],
+    columns: usize,
+    gap: f64,
+    maintain_aspect: bool,
+) -> Vec<LayoutInfo> {
+    let columns = columns.max(1);
+    let cell_width = ((container.width - gap * (columns - 1) as f64) / columns as f64).max(0.0);
+    let heights: Vec<f64> = items
+        .iter()
+        .map(|item| {
+            if maintain_aspect && item.width > 0.0 {
+                cell_width * item.height / item.width
+            } else {
+                item.height
+            }
+        })
+        .collect();
+
+    let mut layouts = Vec::with_capacity(items.len());
+    let mut y = container.y;
+    for row in heights.chunks(columns) {
+        let row_height = row.iter().copied().fold(0.0, f64::max);
+        for (column, height) in row.iter().enumerate() {
+            let height = if maintain_aspect { *height } else { row_height };
+            let x = container.x + column as f64 * (cell_width + gap);
+            layouts.push(LayoutInfo::new(x, y, cell_width, height));
+        }
+        y += row_height + gap;
+    }
+    layouts
+}
+
+/// Boxes of `items` stacked in `order` from where the first of them starts
+fn list_layouts(
+    items: &[LayoutInfo],
+    order: &[usize],
+    gap: f64,
+    horizontal: bool,
+) -> Vec<LayoutInfo> {
+    let start = items
+        .iter()
+        .map(|item| if horizontal { item.x } else { item.y })
+        .fold(f64::INFINITY, f64::min);
+    let mut layouts = items.to_vec();
+    let mut offset = start;
+    for &index in order {
+        let item = &items[index];
+        let layout = &mut layouts[index];
+        if horizontal {
+            layout.x = offset;
+            offset += item.width + gap;
+        } else {
+            layout.y = offset;
+            offset += item.height + gap;
+        }
+    }
+    layouts
+}
+
+/// Id of the animation of the `item` at `index`, its element id if it has one
+fn item_id(preset: &LayoutPreset, item: &Element, index: usize) -> String {
+    if item.id().is_empty() {
+        format!("{}-{}", preset.name, index)
+    } else {
+        item.id()
+    }
+}
+
+/// Number of columns in a computed `grid-template-columns`, such as `"80px 80px"`
+fn track_count(tracks: &str) -> usize {
+    if tracks == "none" {
+        return 0;
+    }
+    tracks
+        .split_whitespace()
+        .filter(|track| !track.starts_with('[') && !track.ends_with(']'))
+        .count()
+}
+
+/// Box of the content of `element`, inside its border and padding
+fn content_box(element: &Element) -> LayoutInfo {
+    let rect = element.get_bounding_client_rect();
+    let style =
+        web_sys::window().and_then(|window| window.get_computed_style(element).ok().flatten());
+    let padding = |side: &str| {
+        style
+            .as_ref()
+            .and_then(|style| style.get_property_value(&format!("padding-{}", side)).ok())
+            .and_then(|value| value.trim_end_matches("px").parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    let (left, right, top) = (padding("left"), padding("right"), padding("top"));
+    LayoutInfo::new(
+        rect.x() + element.client_left() as f64 + left,
+        rect.y() + element.client_top() as f64 + top,
+        element.client_width() as f64 - left - right,
+        rect.height(),
+    )
+}
+
+/// Layout of a measured `rect`
+fn rect_layout(rect: &DomRect) -> LayoutInfo {
+    LayoutInfo::new(rect.x(), rect.y(), rect.width(), rect.height())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,6 +985,7 @@ mod tests {
             description: "Test custom preset".to_string(),
             preset_type: PresetType::Custom("test".to_string()),
             default_config: LayoutAnimationConfig::default(),
+            stagger: 0.0,
             required_parameters: vec![],
             optional_parameters: vec![],
         };
@@ -479,10 +1007,262 @@ mod tests {
     fn test_target_layout_calculation() {
         let manager = LayoutPresetManager::new();
         let preset = manager.get_built_in_preset("list-reorder").unwrap();
-        let current_layout = DomRect::new().unwrap();
+        let items = vec![LayoutInfo::new(0.0, 0.0, 100.0, 40.0)];
         let parameters = HashMap::new();
 
-        let result = manager.calculate_target_layout(preset, &current_layout, &parameters);
+        let result =
+            manager.calculate_target_layouts(preset, &LayoutInfo::default(), &items, &parameters);
         assert!(result.is_ok());
     }
+
+    fn parameters(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn bounds(layout: &LayoutInfo) -> (f64, f64, f64, f64) {
+        (layout.x, layout.y, layout.width, layout.height)
+    }
+
+    #[test]
+    fn test_grid_resize_reflows_items_into_columns() {
+        let manager = LayoutPresetManager::new();
+        let preset = manager.get_built_in_preset("grid-resize").unwrap();
+        let container = LayoutInfo::new(10.0, 20.0, 320.0, 0.0);
+        let items = vec![LayoutInfo::new(0.0, 0.0, 50.0, 25.0); 3];
+
+        let layouts = manager
+            .calculate_target_layouts(
+                preset,
+                &container,
+                &items,
+                &parameters(&[("columns", "2"), ("gap", "20")]),
+            )
+            .unwrap();
+        assert_eq!(bounds(&layouts[0]), (10.0, 20.0, 150.0, 75.0));
+        assert_eq!(bounds(&layouts[1]), (180.0, 20.0, 150.0, 75.0));
+        assert_eq!(bounds(&layouts[2]), (10.0, 115.0, 150.0, 75.0));
+
+        let missing = manager.calculate_target_layouts(preset, &container, &items, &HashMap::new());
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_grid_rows_without_aspect_stretch_to_tallest_item() {
+        let manager = LayoutPresetManager::new();
+        let preset = manager.get_built_in_preset("grid-resize").unwrap();
+        let container = LayoutInfo::new(0.0, 0.0, 200.0, 0.0);
+        let items = vec![
+            LayoutInfo::new(0.0, 0.0, 50.0, 30.0),
+            LayoutInfo::new(0.0, 0.0, 50.0, 60.0),
+            LayoutInfo::new(0.0, 0.0, 50.0, 10.0),
+        ];
+
+        let layouts = manager
+            .calculate_target_layouts(
+                preset,
+                &container,
+                &items,
+                &parameters(&[("columns", "2"), ("maintain_aspect", "false")]),
+            )
+            .unwrap();
+        assert_eq!(bounds(&layouts[0]), (0.0, 0.0, 100.0, 60.0));
+        assert_eq!(bounds(&layouts[1]), (100.0, 0.0, 100.0, 60.0));
+        assert_eq!(bounds(&layouts[2]), (0.0, 60.0, 100.0, 10.0));
+    }
+
+    #[test]
+    fn test_breakpoint_columns() {
+        let manager = LayoutPresetManager::new();
+        let preset = manager
+            .get_built_in_preset("responsive-breakpoint")
+            .unwrap();
+        let container = LayoutInfo::new(0.0, 0.0, 300.0, 0.0);
+        let items = vec![LayoutInfo::new(0.0, 0.0, 10.0, 10.0); 3];
+
+        let layouts = manager
+            .calculate_target_layouts(
+                preset,
+                &container,
+                &items,
+                &parameters(&[("breakpoint", "lg")]),
+            )
+            .unwrap();
+        assert_eq!(layouts[2].x, 200.0);
+        assert_eq!(layouts[2].y, 0.0);
+
+        let layouts = manager
+            .calculate_target_layouts(
+                preset,
+                &container,
+                &items,
+                &parameters(&[("breakpoint", "lg"), ("columns", "1")]),
+            )
+            .unwrap();
+        assert_eq!(layouts[2].x, 0.0);
+        assert_eq!(layouts[2].y, 600.0);
+
+        assert_eq!(Breakpoint::parse("md"), Some(Breakpoint::Md));
+        assert_eq!(Breakpoint::Md.media_query(), "(min-width: 768px)");
+        assert!(
+            manager
+                .calculate_target_layouts(
+                    preset,
+                    &container,
+                    &items,
+                    &parameters(&[("breakpoint", "xxl")])
+                )
+                .is_err()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_animate_flips_into_measured_layout() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let container = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&container).unwrap();
+        // Margins the preset doesn't know about move the items further apart
+        let items: Vec<Element> = (0..2)
+            .map(|_| {
+                let item = document.create_element("div").unwrap();
+                item.set_attribute("style", "height: 20px; margin-bottom: 7px")
+                    .unwrap();
+                container.append_child(&item).unwrap();
+                item
+            })
+            .collect();
+        let top = items[0].get_bounding_client_rect().y();
+
+        let manager = LayoutPresetManager::new();
+        let preset = manager.get_built_in_preset("list-reorder").unwrap();
+        let mut animator = FLIPAnimator::new();
+        let targets = manager
+            .animate(
+                preset,
+                &container,
+                &items,
+                &parameters(&[("order", "1, 0")]),
+                &mut animator,
+            )
+            .unwrap();
+
+        assert_eq!(targets[0].y, top + 20.0);
+        let last = animator
+            .animations()
+            .find(|animation| animation.element == items[0])
+            .map(|animation| animation.state.last.y())
+            .unwrap();
+        assert_eq!(last, top + 27.0);
+        container.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_modal_expand_animates_height_and_hands_back_auto() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let container = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&container).unwrap();
+        let item = document.create_element("div").unwrap();
+        item.set_attribute("style", "height: 0px").unwrap();
+        item.set_inner_html("<div style=\"height: 80px\"></div>");
+        container.append_child(&item).unwrap();
+
+        let manager = LayoutPresetManager::new();
+        let preset = manager.get_built_in_preset("modal-expand").unwrap();
+        let mut animator = FLIPAnimator::new();
+        let targets = manager
+            .animate(
+                preset,
+                &container,
+                std::slice::from_ref(&item),
+                &parameters(&[("action", "expand")]),
+                &mut animator,
+            )
+            .unwrap();
+        assert_eq!(targets[0].height, 80.0);
+        let animation = animator.animations().next().unwrap();
+        assert_eq!(
+            animation.height,
+            Some(HeightAnimation {
+                from: 0.0,
+                to: 80.0,
+                auto: true,
+            })
+        );
+        let style = inline_style(&item);
+        let height = style.get_property_value("height").unwrap();
+        assert!(height.trim_end_matches("px").parse::<f64>().unwrap() < 1.0);
+        assert_eq!(style.get_property_value("overflow").unwrap(), "hidden");
+        assert!(style.get_property_value("transform").unwrap().is_empty());
+
+        animation.height.as_ref().unwrap().finish(&item);
+        assert_eq!(style.get_property_value("height").unwrap(), "auto");
+        container.remove();
+    }
+
+    #[test]
+    fn test_track_count() {
+        assert_eq!(track_count("none"), 0);
+        assert_eq!(track_count("80px 80px 80px"), 3);
+        assert_eq!(track_count("[start] 80px [middle] 120px [end]"), 2);
+    }
+
+    #[test]
+    fn test_list_reorder_stacks_items_in_new_order() {
+        let manager = LayoutPresetManager::new();
+        let preset = manager.get_built_in_preset("list-reorder").unwrap();
+        let items = vec![
+            LayoutInfo::new(0.0, 100.0, 200.0, 40.0),
+            LayoutInfo::new(0.0, 150.0, 200.0, 20.0),
+            LayoutInfo::new(0.0, 180.0, 200.0, 30.0),
+        ];
+
+        let layouts = manager
+            .calculate_target_layouts(
+                preset,
+                &LayoutInfo::default(),
+                &items,
+                &parameters(&[("order", "2, 0, 1"), ("gap", "10")]),
+            )
+            .unwrap();
+        assert_eq!(layouts[2].y, 100.0);
+        assert_eq!(layouts[0].y, 140.0);
+        assert_eq!(layouts[1].y, 190.0);
+
+        let invalid = manager.calculate_target_layouts(
+            preset,
+            &LayoutInfo::default(),
+            &items,
+            &parameters(&[("order", "0, 0, 1")]),
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_modal_expand_and_collapse_heights() {
+        let manager = LayoutPresetManager::new();
+        let preset = manager.get_built_in_preset("modal-expand").unwrap();
+        let items = vec![LayoutInfo::new(5.0, 5.0, 300.0, 0.0)];
+
+        let expanded = manager
+            .calculate_target_layouts(
+                preset,
+                &LayoutInfo::default(),
+                &items,
+                &parameters(&[("action", "expand"), ("natural_height", "240")]),
+            )
+            .unwrap();
+        assert_eq!(bounds(&expanded[0]), (5.0, 5.0, 300.0, 240.0));
+
+        let collapsed = manager
+            .calculate_target_layouts(
+                preset,
+                &LayoutInfo::default(),
+                &expanded,
+                &parameters(&[("action", "collapse")]),
+            )
+            .unwrap();
+        assert_eq!(collapsed[0].height, 0.0);
+    }
 }
@@ -3,36 +3,75 @@
 //! This module provides functionality for creating smooth transitions
 //! where elements appear to move between different views or states.
 
+use crate::LayoutInfo;
+use crate::crossfade::{Crossfade, CrossfadeConfig};
+use crate::flip::TransformValues;
+use leptos_motion_core::{Easing, Transition};
 use std::collections::{HashMap, VecDeque};
 use wasm_bindgen::prelude::*;
 use web_sys::Element;
 
+/// How long the box of an unmounted `layout_id` element stays available, in
+/// milliseconds
+///
+/// The element replacing it usually mounts in the same update, so this only
+/// needs to cover the time until its first effects run.
+pub const LAYOUT_SNAPSHOT_LIFETIME_MS: f64 = 100.0;
+
+/// Attribute giving plain elements a `layout_id`, its value
+///
+/// Route transitions measure tagged elements as a page leaves and animate
+/// their counterparts on the next page from there.
+pub const LAYOUT_ID_ATTRIBUTE: &str = "data-layout-id";
+
+/// Attribute of the overlay layer elevated shared elements fly in
+const OVERLAY_ATTRIBUTE: &str = "data-motion-overlay";
+
 /// Shared element configuration
 #[derive(Debug, Clone)]
 pub struct SharedElementConfig {
     /// Transition duration in seconds
     pub duration: f64,
     /// Easing function
-    pub easing: crate::flip::EasingFunction,
+    pub easing: Easing,
     /// Whether to maintain aspect ratio
     pub maintain_aspect_ratio: bool,
     /// Whether to use hardware acceleration
     pub hardware_accelerated: bool,
+    /// Crossfade copies of both elements instead of scaling one
+    ///
+    /// Suits elements whose content changes along with their size, such as
+    /// a thumbnail opening into a card. Applies to the transitions of a
+    /// [`SharedElementManager`] and to route shared elements whose
+    /// counterpart is still in the page.
+    pub crossfade: Option<CrossfadeConfig>,
 }
 
 impl Default for SharedElementConfig {
     fn default() -> Self {
         Self {
             duration: 0.3,
-            easing: crate::flip::EasingFunction::EaseOut,
+            easing: Easing::EaseOut,
             maintain_aspect_ratio: false,
             hardware_accelerated: true,
+            crossfade: None,
+        }
+    }
+}
+
+impl From<&SharedElementConfig> for Transition {
+    /// Transition of the motion elements flying as shared elements
+    fn from(config: &SharedElementConfig) -> Self {
+        Self {
+            duration: Some(config.duration),
+            ease: config.easing.clone(),
+            ..Default::default()
         }
     }
 }
 
 /// Z-index strategy for shared elements
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ZIndexStrategy {
     /// Use fixed z-index values
     Fixed {
@@ -53,7 +92,10 @@ pub enum ZIndexStrategy {
         /// CSS custom property name
         property: String,
     },
-    /// Elevate element during transition
+    /// Fly a copy of the element in an overlay layer above the page
+    ///
+    /// The element itself stays hidden until the copy lands on it, so it
+    /// isn't clipped or covered by the pages it moves between.
     Elevate,
     /// Custom z-index value
     Custom(i32),
@@ -71,7 +113,7 @@ impl Default for ZIndexStrategy {
 }
 
 /// Shared element transition
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SharedElementTransition {
     /// Unique transition ID
     pub id: String,
@@ -87,6 +129,53 @@ pub struct SharedElementTransition {
     pub active: bool,
     /// Start time
     pub start_time: f64,
+    /// Priority the transition was created with
+    pub priority: TransitionPriority,
+    /// Opacity of the source and target elements when the transition started
+    ///
+    /// Fully shown and hidden for a new transition; a transition replacing
+    /// or reversing an interrupted one starts from where it left them.
+    pub start_opacity: (f64, f64),
+    /// Whether the transition is playing back an interrupted one
+    pub reversed: bool,
+    /// Duration of a whole run between the elements in seconds
+    ///
+    /// `config.duration` is shorter once the transition is reversed part
+    /// of the way through.
+    pub full_duration: f64,
+}
+
+impl SharedElementTransition {
+    /// Opacity of the source and target elements at `progress`
+    pub fn opacity_at(&self, progress: f64) -> (f64, f64) {
+        let (source, target) = self.start_opacity;
+        (
+            source * (1.0 - progress),
+            target + (1.0 - target) * progress,
+        )
+    }
+
+    /// Whether the transition animates `element`
+    fn involves(&self, element: &Element) -> bool {
+        &self.source_element == element || &self.target_element == element
+    }
+}
+
+/// What happens to a transition started on elements that are already transitioning
+///
+/// The policy is decided by priority: a transition never interrupts a more
+/// important one and queues behind it, otherwise the policy configured for
+/// its own priority applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptionPolicy {
+    /// Start once the running transition completes
+    Queue,
+    /// Cancel the running transition and start from the elements' current state
+    Replace,
+    /// Play the running transition backward from its current progress instead
+    Reverse,
+    /// Drop the new transition
+    Ignore,
 }
 
 /// Manager for shared element transitions
@@ -96,15 +185,95 @@ pub struct SharedElementManager {
     /// Transition queue
     transition_queue: VecDeque<QueuedTransition>,
     /// Z-index strategy
-    #[allow(dead_code)]
     z_index_strategy: ZIndexStrategy,
     /// Performance tracking
     performance_metrics: SharedElementMetrics,
+    /// Mounted elements and last boxes of each `layout_id`
+    layout_ids: HashMap<String, LayoutIdStack>,
+    /// Elements registered with their own configuration
+    registered: Vec<(Element, SharedElementConfig)>,
+    /// Navigation keeping snapshots available past their lifetime
+    hold: Option<SnapshotHold>,
+    /// Interruption policy of each priority, indexed by priority
+    interruption_policies: [InterruptionPolicy; 4],
+    /// Transitions created so far, keeping ids unique
+    transition_count: usize,
+    /// Inline opacity of the transitioning elements before their first transition
+    saved_opacity: Vec<(Element, String)>,
+    /// Crossfades of the transitions configured with one, by transition id
+    crossfades: HashMap<String, Crossfade>,
+}
+
+/// Navigation keeping the boxes of unmounted `layout_id` elements available
+#[derive(Debug, Clone)]
+struct SnapshotHold {
+    /// When the navigation started, in milliseconds
+    since: f64,
+    /// When the snapshots expire, in milliseconds
+    until: f64,
+    /// Stacking of elements animating from the held snapshots
+    z_index: ZIndexStrategy,
+}
+
+impl SnapshotHold {
+    /// Whether a snapshot taken at `time` is held at `now`
+    ///
+    /// Snapshots taken just before the navigation started count, as the
+    /// elements of the leaving page are measured before it reports leaving.
+    fn holds(&self, time: f64, now: f64) -> bool {
+        now <= self.until && time >= self.since - LAYOUT_SNAPSHOT_LIFETIME_MS
+    }
+}
+
+/// Elements sharing a `layout_id`, the last mounted one leading
+#[derive(Debug, Default)]
+struct LayoutIdStack {
+    /// Mounted elements, in mount order
+    members: Vec<Element>,
+    /// Box of the last lead that unmounted
+    snapshot: Option<LayoutSnapshot>,
+    /// Start and end in milliseconds and priority of the latest shared layout animation
+    animation: Option<(f64, f64, TransitionPriority)>,
+}
+
+/// Box a `layout_id` element was last seen at
+#[derive(Debug, Clone)]
+pub struct LayoutSnapshot {
+    /// Viewport box of the element
+    pub layout: LayoutInfo,
+    /// The element, or the presence ghost standing in for it while it exits
+    pub element: Element,
+    /// When the element unmounted, in milliseconds
+    pub time: f64,
+}
+
+/// Shared layout animation of the element leading a `layout_id`
+#[derive(Debug, Clone)]
+pub struct SharedLayoutEnter {
+    /// Viewport box to animate from
+    pub from: LayoutInfo,
+    /// Element showing the previous state, faded out while the lead fades in
+    pub crossfade: Option<Element>,
+    /// Fade the lead in, as it was hidden behind another member or is replacing one
+    pub fade_in: bool,
+    /// Stacking of the lead while it animates
+    pub z_index: ZIndexStrategy,
+}
+
+/// When and for how long a shared layout animation plays, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutAnimationSlot {
+    /// Time to wait before starting, behind a running animation
+    pub delay: f64,
+    /// Duration of the animation
+    pub duration: f64,
 }
 
 /// Queued transition waiting to start
 #[derive(Debug)]
 pub struct QueuedTransition {
+    /// Transition ID, kept when it starts
+    pub id: String,
     /// Source element
     pub source_element: Element,
     /// Target element
@@ -156,6 +325,10 @@ pub struct SharedElementMetrics {
     pub average_duration: f64,
     /// Frame rate during transitions
     pub frame_rate: f64,
+    /// Transitions cut short by a [`InterruptionPolicy::Replace`] or [`InterruptionPolicy::Reverse`]
+    pub interrupted_transitions: usize,
+    /// Transitions dropped by [`InterruptionPolicy::Ignore`] or cancelled while queued
+    pub dropped_transitions: usize,
 }
 
 impl SharedElementManager {
@@ -166,31 +339,283 @@ impl SharedElementManager {
             transition_queue: VecDeque::new(),
             z_index_strategy,
             performance_metrics: SharedElementMetrics::default(),
+            layout_ids: HashMap::new(),
+            registered: Vec::new(),
+            hold: None,
+            interruption_policies: [
+                InterruptionPolicy::Queue,
+                InterruptionPolicy::Replace,
+                InterruptionPolicy::Replace,
+                InterruptionPolicy::Replace,
+            ],
+            transition_count: 0,
+            saved_opacity: Vec::new(),
+            crossfades: HashMap::new(),
+        }
+    }
+
+    /// Let transitions of `priority` interrupt running ones following `policy`
+    ///
+    /// By default low priority transitions queue and the others replace the
+    /// running transition, so rapidly switching tabs keeps retargeting the
+    /// elements from where they are instead of stacking transitions.
+    pub fn set_interruption_policy(
+        &mut self,
+        priority: TransitionPriority,
+        policy: InterruptionPolicy,
+    ) {
+        self.interruption_policies[priority as usize] = policy;
+    }
+
+    /// How a transition of `incoming` priority treats a running one of `running` priority
+    pub fn interruption_policy(
+        &self,
+        running: &TransitionPriority,
+        incoming: &TransitionPriority,
+    ) -> InterruptionPolicy {
+        if incoming < running {
+            InterruptionPolicy::Queue
+        } else {
+            self.interruption_policies[incoming.clone() as usize]
+        }
+    }
+
+    /// Z-index strategy of shared layouts outside route transitions
+    pub fn z_index_strategy(&self) -> &ZIndexStrategy {
+        &self.z_index_strategy
+    }
+
+    /// Keep the snapshots of elements unmounting around `now` for `duration` milliseconds
+    ///
+    /// Called when navigating, so elements of the next page mounting after
+    /// its data loaded still find their counterparts. They animate with
+    /// `z_index` instead of the manager's strategy.
+    pub fn hold_layout_snapshots(&mut self, now: f64, duration: f64, z_index: ZIndexStrategy) {
+        self.hold = Some(SnapshotHold {
+            since: now,
+            until: now + duration,
+            z_index,
+        });
+    }
+
+    /// Make `element` the lead of `layout_id`
+    ///
+    /// Returns how it should animate in: from the current lead, which stays
+    /// mounted and is crossfaded out, or from the box of a lead that
+    /// unmounted within [`LAYOUT_SNAPSHOT_LIFETIME_MS`] of `now`.
+    pub fn mount_layout_id(
+        &mut self,
+        layout_id: &str,
+        element: &Element,
+        now: f64,
+    ) -> Option<SharedLayoutEnter> {
+        let stack = self.layout_ids.entry(layout_id.to_string()).or_default();
+        let snapshot = stack.snapshot.take();
+        let enter = match (stack.members.last(), snapshot) {
+            (Some(lead), _) => Some(SharedLayoutEnter {
+                from: element_layout(lead),
+                crossfade: Some(lead.clone()),
+                fade_in: true,
+                z_index: self.z_index_strategy.clone(),
+            }),
+            (None, Some(snapshot)) => {
+                snapshot_enter(snapshot, now, self.hold.as_ref(), &self.z_index_strategy)
+            }
+            _ => None,
+        };
+        stack.members.retain(|member| member != element);
+        stack.members.push(element.clone());
+        if enter.is_some() {
+            self.performance_metrics.total_transitions += 1;
+        }
+        enter
+    }
+
+    /// Remove `element` from `layout_id`, last seen at `layout`
+    ///
+    /// When it was leading and another member is still mounted, that member
+    /// takes over and is returned with the animation back from `layout`.
+    /// Otherwise the box is kept for an element mounting right after. An
+    /// element that could no longer be measured (`None`) leaves without
+    /// handing on its box.
+    pub fn unmount_layout_id(
+        &mut self,
+        layout_id: &str,
+        element: &Element,
+        layout: Option<LayoutInfo>,
+        now: f64,
+    ) -> Option<(Element, SharedLayoutEnter)> {
+        let stack = self.layout_ids.get_mut(layout_id)?;
+        let was_lead = stack.members.last() == Some(element);
+        stack.members.retain(|member| member != element);
+        let layout = layout?;
+        if !was_lead {
+            return None;
+        }
+        match stack.members.last() {
+            Some(lead) => {
+                self.performance_metrics.total_transitions += 1;
+                Some((
+                    lead.clone(),
+                    SharedLayoutEnter {
+                        from: layout,
+                        crossfade: None,
+                        fade_in: true,
+                        z_index: self.z_index_strategy.clone(),
+                    },
+                ))
+            }
+            None => {
+                stack.snapshot = Some(LayoutSnapshot {
+                    layout,
+                    element: element.clone(),
+                    time: now,
+                });
+                None
+            }
+        }
+    }
+
+    /// Remember `element`, tagged with `layout_id` but not mounted as a member, at `layout`
+    ///
+    /// The next element entering `layout_id` animates from there.
+    pub fn snapshot_layout_id(
+        &mut self,
+        layout_id: &str,
+        element: &Element,
+        layout: LayoutInfo,
+        now: f64,
+    ) {
+        self.layout_ids
+            .entry(layout_id.to_string())
+            .or_default()
+            .snapshot = Some(LayoutSnapshot {
+            layout,
+            element: element.clone(),
+            time: now,
+        });
+    }
+
+    /// Animation of an element tagged with `layout_id` appearing at `now`
+    ///
+    /// Like [`mount_layout_id`](Self::mount_layout_id) without making the
+    /// element a member, for elements that don't report unmounting.
+    pub fn enter_layout_id(&mut self, layout_id: &str, now: f64) -> Option<SharedLayoutEnter> {
+        let snapshot = self.layout_ids.get_mut(layout_id)?.snapshot.take()?;
+        let enter = snapshot_enter(snapshot, now, self.hold.as_ref(), &self.z_index_strategy);
+        if enter.is_some() {
+            self.performance_metrics.total_transitions += 1;
+        }
+        enter
+    }
+
+    /// Let `replacement` stand in for `element`, e.g. a presence ghost playing its exit
+    pub fn replace_layout_element(&mut self, element: &Element, replacement: &Element) {
+        for stack in self.layout_ids.values_mut() {
+            if let Some(snapshot) = &mut stack.snapshot
+                && snapshot.element == *element
+            {
+                snapshot.element = replacement.clone();
+            }
+        }
+    }
+
+    /// Number of mounted elements with `layout_id`
+    pub fn layout_id_member_count(&self, layout_id: &str) -> usize {
+        self.layout_ids
+            .get(layout_id)
+            .map_or(0, |stack| stack.members.len())
+    }
+
+    /// Schedule a shared layout animation of `layout_id` and `priority` starting at `now`
+    ///
+    /// While the previous animation of the `layout_id` runs, the
+    /// [`interruption_policy`](Self::interruption_policy) between the two
+    /// priorities applies: the new animation waits for it, replaces it or
+    /// plays back for the time the previous one ran. When ignored, the new
+    /// lead appears in place at once.
+    ///
+    /// The new lead starts from the box the previous one was caught in, so
+    /// a reversed animation heads back from there, but it eases over the
+    /// shortened duration from the start rather than retracing the curve.
+    pub fn schedule_layout_animation(
+        &mut self,
+        layout_id: &str,
+        now: f64,
+        duration: f64,
+        priority: TransitionPriority,
+    ) -> LayoutAnimationSlot {
+        let running = self
+            .layout_ids
+            .get(layout_id)
+            .and_then(|stack| stack.animation.clone())
+            .filter(|(_, end, _)| *end > now);
+        let policy = running
+            .as_ref()
+            .map(|(_, _, running_priority)| self.interruption_policy(running_priority, &priority));
+        let stack = self.layout_ids.entry(layout_id.to_string()).or_default();
+        let slot = match (running.as_ref(), policy) {
+            (None, _) | (_, None) | (Some(_), Some(InterruptionPolicy::Replace)) => {
+                LayoutAnimationSlot {
+                    delay: 0.0,
+                    duration,
+                }
+            }
+            (Some((_, end, _)), Some(InterruptionPolicy::Queue)) => LayoutAnimationSlot {
+                delay: end - now,
+                duration,
+            },
+            (Some((start, end, _)), Some(InterruptionPolicy::Reverse)) => LayoutAnimationSlot {
+                delay: 0.0,
+                duration: (now - start).clamp(0.0, end - start),
+            },
+            (Some(_), Some(InterruptionPolicy::Ignore)) => {
+                self.performance_metrics.dropped_transitions += 1;
+                return LayoutAnimationSlot {
+                    delay: 0.0,
+                    duration: 0.0,
+                };
+            }
+        };
+        if running.is_some() && slot.delay == 0.0 {
+            self.performance_metrics.interrupted_transitions += 1;
         }
+        let start = now + slot.delay;
+        stack.animation = Some((start, start + slot.duration, priority));
+        slot
     }
 
-    /// Register a shared element
+    /// Register a shared element, animating it with `config` when it enters
     pub fn register_element(
         &mut self,
-        _element: &Element,
+        element: &Element,
         config: &SharedElementConfig,
     ) -> Result<(), String> {
-        // For now, just log the registration
-        // In a real implementation, this would set up element tracking
-        log::info!("Registering shared element with config: {:?}", config);
-
+        self.registered
+            .retain(|(registered, _)| registered != element);
+        self.registered.push((element.clone(), config.clone()));
         Ok(())
     }
 
     /// Unregister a shared element
-    pub fn unregister_element(&mut self, _element: &Element) -> Result<(), String> {
-        // For now, just log the unregistration
-        // In a real implementation, this would clean up element tracking
-        log::info!("Unregistering shared element");
-
+    pub fn unregister_element(&mut self, element: &Element) -> Result<(), String> {
+        let count = self.registered.len();
+        self.registered
+            .retain(|(registered, _)| registered != element);
+        if self.registered.len() == count {
+            return Err("Element is not registered".to_string());
+        }
         Ok(())
     }
 
+    /// Configuration `element` was registered with
+    pub fn element_config(&self, element: &Element) -> Option<&SharedElementConfig> {
+        self.registered
+            .iter()
+            .find(|(registered, _)| registered == element)
+            .map(|(_, config)| config)
+    }
+
     /// Create a shared element transition
     pub fn create_transition(
         &mut self,
@@ -198,48 +623,166 @@ impl SharedElementManager {
         target_element: &Element,
         config: &SharedElementConfig,
     ) -> Result<String, String> {
-        // Generate transition ID
-        let id = self.generate_transition_id();
+        self.create_transition_with_priority(
+            source_element,
+            target_element,
+            config,
+            TransitionPriority::default(),
+        )
+    }
 
-        // Create queued transition
+    /// Create a shared element transition of `priority`
+    ///
+    /// When either element is already transitioning, the
+    /// [`Self::interruption_policy`] decides what happens. Returns the id of
+    /// the transition now animating the elements: the new one, or the
+    /// running one when it was reversed or the new one ignored.
+    pub fn create_transition_with_priority(
+        &mut self,
+        source_element: &Element,
+        target_element: &Element,
+        config: &SharedElementConfig,
+        priority: TransitionPriority,
+    ) -> Result<String, String> {
         let queued = QueuedTransition {
+            id: self.generate_transition_id(),
             source_element: source_element.clone(),
             target_element: target_element.clone(),
             config: config.clone(),
-            priority: TransitionPriority::Normal,
+            priority,
         };
 
-        // Add to queue
-        self.transition_queue.push_back(queued);
+        let running = self
+            .active_transitions
+            .values()
+            .filter(|transition| {
+                transition.involves(source_element) || transition.involves(target_element)
+            })
+            .max_by_key(|transition| transition.priority.clone())
+            .map(|transition| (transition.id.clone(), transition.priority.clone()));
+        let Some((running_id, running_priority)) = running else {
+            let id = queued.id.clone();
+            self.start_queued(queued, (1.0, 0.0), None)?;
+            return Ok(id);
+        };
 
-        // Try to start transitions
-        self.process_transition_queue()?;
+        match self.interruption_policy(&running_priority, &queued.priority) {
+            InterruptionPolicy::Queue => {
+                let id = queued.id.clone();
+                // Behind the queued transitions of the same or higher priority
+                let index = self
+                    .transition_queue
+                    .iter()
+                    .position(|waiting| waiting.priority < queued.priority)
+                    .unwrap_or(self.transition_queue.len());
+                self.transition_queue.insert(index, queued);
+                Ok(id)
+            }
+            InterruptionPolicy::Replace => {
+                let mut start_opacity = (1.0, 0.0);
+                let mut crossfade_from = None;
+                let interrupted: Vec<String> = self
+                    .active_transitions
+                    .values()
+                    .filter(|transition| {
+                        transition.involves(source_element) || transition.involves(target_element)
+                    })
+                    .map(|transition| transition.id.clone())
+                    .collect();
+                for id in interrupted {
+                    let Some(transition) = self.active_transitions.remove(&id) else {
+                        continue;
+                    };
+                    if let Some((_, _, layout)) =
+                        self.crossfades.get(&id).and_then(Crossfade::layouts)
+                    {
+                        crossfade_from = Some(layout);
+                    }
+                    self.stop_crossfade(&id);
+                    let (source, target) = transition.opacity_at(transition.progress);
+                    for (element, opacity) in [
+                        (&transition.source_element, source),
+                        (&transition.target_element, target),
+                    ] {
+                        if element == source_element {
+                            start_opacity.0 = opacity;
+                        } else if element == target_element {
+                            start_opacity.1 = opacity;
+                        } else {
+                            self.reset_element_transforms(element)?;
+                            self.restore_opacity(element)?;
+                        }
+                    }
+                    self.performance_metrics.interrupted_transitions += 1;
+                }
+                let id = queued.id.clone();
+                self.start_queued(queued, start_opacity, crossfade_from)?;
+                Ok(id)
+            }
+            InterruptionPolicy::Reverse => {
+                self.reverse_transition(&running_id)?;
+                Ok(running_id)
+            }
+            InterruptionPolicy::Ignore => {
+                self.performance_metrics.dropped_transitions += 1;
+                Ok(running_id)
+            }
+        }
+    }
 
-        Ok(id)
+    /// Play a running transition back to its source from its current progress
+    ///
+    /// The transition keeps its id and restarts at progress 0, running for
+    /// as long as it takes to cover the way back at its full duration's pace.
+    /// Returns that duration in seconds, also set as its `config.duration`,
+    /// for the caller driving [`update_transition`](Self::update_transition).
+    pub fn reverse_transition(&mut self, transition_id: &str) -> Result<f64, String> {
+        let transition = self
+            .active_transitions
+            .get_mut(transition_id)
+            .ok_or("Transition not found")?;
+        let (source, target) = transition.opacity_at(transition.progress);
+        std::mem::swap(
+            &mut transition.source_element,
+            &mut transition.target_element,
+        );
+        // The way left of this run is the way back, the rest of a whole run
+        let remaining = (1.0 - transition.progress) * transition.config.duration;
+        transition.start_opacity = (target, source);
+        transition.config.duration = transition.full_duration - remaining;
+        transition.progress = 0.0;
+        transition.reversed = !transition.reversed;
+        transition.start_time = js_sys::Date::now();
+        let duration = transition.config.duration;
+        self.performance_metrics.interrupted_transitions += 1;
+        if let Some(crossfade) = self.crossfades.get(transition_id) {
+            crossfade.reverse();
+        }
+        Ok(duration)
     }
 
     /// Start a shared element transition
     pub fn start_transition(&mut self, transition_id: &str) -> Result<(), String> {
+        self.begin_transition(transition_id, None)
+    }
+
+    /// Start a transition, crossfading from `crossfade_from` rather than its source box
+    fn begin_transition(
+        &mut self,
+        transition_id: &str,
+        crossfade_from: Option<LayoutInfo>,
+    ) -> Result<(), String> {
         // Find and start the transition
         if let Some(transition) = self.active_transitions.get_mut(transition_id) {
             transition.active = true;
             transition.start_time = js_sys::Date::now();
 
             // Clone transition to avoid borrow checker issues
-            let transition_clone = SharedElementTransition {
-                id: transition.id.clone(),
-                source_element: transition.source_element.clone(),
-                target_element: transition.target_element.clone(),
-                config: transition.config.clone(),
-                progress: transition.progress,
-                active: transition.active,
-                start_time: transition.start_time,
-            };
+            let transition_clone = transition.clone();
 
             // Apply initial transforms
             self.apply_initial_transforms(&transition_clone)?;
-
-            Ok(())
+            self.start_crossfade(&transition_clone, crossfade_from)
         } else {
             Err("Transition not found".to_string())
         }
@@ -251,15 +794,7 @@ impl SharedElementManager {
             transition.progress = progress.clamp(0.0, 1.0);
 
             // Clone transition to avoid borrow checker issues
-            let transition_clone = SharedElementTransition {
-                id: transition.id.clone(),
-                source_element: transition.source_element.clone(),
-                target_element: transition.target_element.clone(),
-                config: transition.config.clone(),
-                progress: transition.progress,
-                active: transition.active,
-                start_time: transition.start_time,
-            };
+            let transition_clone = transition.clone();
 
             // Apply transforms based on progress
             self.apply_transition_transforms(&transition_clone)?;
@@ -278,13 +813,19 @@ impl SharedElementManager {
     /// Cancel a transition
     pub fn cancel_transition(&mut self, transition_id: &str) -> Result<(), String> {
         if let Some(transition) = self.active_transitions.remove(transition_id) {
+            self.stop_crossfade(transition_id);
             // Reset element transforms
-            self.reset_element_transforms(&transition.source_element)?;
-            self.reset_element_transforms(&transition.target_element)?;
+            for element in [&transition.source_element, &transition.target_element] {
+                self.reset_element_transforms(element)?;
+                self.restore_opacity(element)?;
+            }
 
             // Update metrics
             self.performance_metrics.total_transitions += 1;
 
+            // Start the transitions waiting for these elements
+            self.process_transition_queue()?;
+
             Ok(())
         } else {
             Err("Transition not found".to_string())
@@ -293,37 +834,68 @@ impl SharedElementManager {
 
     /// Cancel all active transitions
     pub fn cancel_all_transitions(&mut self) {
+        self.performance_metrics.dropped_transitions += self.transition_queue.len();
         self.active_transitions.clear();
         self.transition_queue.clear();
+        self.saved_opacity.clear();
+        for (_, crossfade) in self.crossfades.drain() {
+            crossfade.cancel();
+        }
     }
 
-    /// Process the transition queue
+    /// Start the queued transitions whose elements are no longer transitioning
     fn process_transition_queue(&mut self) -> Result<(), String> {
-        while let Some(queued) = self.transition_queue.pop_front() {
-            // Create transition
-            let transition = SharedElementTransition {
-                id: self.generate_transition_id(),
-                source_element: queued.source_element.clone(),
-                target_element: queued.target_element.clone(),
-                config: queued.config.clone(),
-                progress: 0.0,
-                active: false,
-                start_time: 0.0,
-            };
-
-            // Add to active transitions
-            let id = transition.id.clone();
-            self.active_transitions.insert(id.clone(), transition);
-
-            // Start the transition
-            self.start_transition(&id)?;
+        let mut index = 0;
+        while index < self.transition_queue.len() {
+            let waiting = &self.transition_queue[index];
+            let busy = self.active_transitions.values().any(|transition| {
+                transition.involves(&waiting.source_element)
+                    || transition.involves(&waiting.target_element)
+            });
+            if busy {
+                index += 1;
+            } else if let Some(queued) = self.transition_queue.remove(index) {
+                self.start_queued(queued, (1.0, 0.0), None)?;
+            }
         }
 
         Ok(())
     }
 
+    /// Make `queued` active and start it with the elements at `start_opacity`
+    ///
+    /// Its crossfade, if any, starts at `crossfade_from`, where the copies
+    /// of an interrupted crossfade were.
+    fn start_queued(
+        &mut self,
+        queued: QueuedTransition,
+        start_opacity: (f64, f64),
+        crossfade_from: Option<LayoutInfo>,
+    ) -> Result<(), String> {
+        let transition = SharedElementTransition {
+            id: queued.id,
+            source_element: queued.source_element,
+            target_element: queued.target_element,
+            full_duration: queued.config.duration,
+            config: queued.config,
+            progress: 0.0,
+            active: false,
+            start_time: 0.0,
+            priority: queued.priority,
+            start_opacity,
+            reversed: false,
+        };
+
+        let id = transition.id.clone();
+        self.active_transitions.insert(id.clone(), transition);
+        self.begin_transition(&id, crossfade_from)
+    }
+
     /// Apply initial transforms for transition
-    fn apply_initial_transforms(&self, transition: &SharedElementTransition) -> Result<(), String> {
+    fn apply_initial_transforms(
+        &mut self,
+        transition: &SharedElementTransition,
+    ) -> Result<(), String> {
         // Set up initial transform state for both elements
         self.setup_transition_element(&transition.source_element, &transition.config)?;
         self.setup_transition_element(&transition.target_element, &transition.config)?;
@@ -343,12 +915,15 @@ impl SharedElementManager {
     ) -> Result<(), String> {
         // Interpolate transforms based on progress
         let progress = transition.progress;
-        let inverse_progress = 1.0 - progress;
+        let (source_opacity, target_opacity) = transition.opacity_at(progress);
+        if let Some(crossfade) = self.crossfades.get(&transition.id) {
+            crossfade.set_progress(progress);
+        }
 
         // Apply transforms to both source and target elements
         if let Some(source_html) = transition.source_element.dyn_ref::<web_sys::HtmlElement>() {
             let style = source_html.style();
-            let opacity = inverse_progress;
+            let opacity = source_opacity;
             style
                 .set_property("opacity", &opacity.to_string())
                 .map_err(|_| "Failed to set source opacity")?;
@@ -356,7 +931,7 @@ impl SharedElementManager {
 
         if let Some(target_html) = transition.target_element.dyn_ref::<web_sys::HtmlElement>() {
             let style = target_html.style();
-            let opacity = progress;
+            let opacity = target_opacity;
             style
                 .set_property("opacity", &opacity.to_string())
                 .map_err(|_| "Failed to set target opacity")?;
@@ -373,12 +948,16 @@ impl SharedElementManager {
 
     /// Set up an element for transition
     fn setup_transition_element(
-        &self,
+        &mut self,
         element: &Element,
         _config: &SharedElementConfig,
     ) -> Result<(), String> {
         if let Some(html_element) = element.dyn_ref::<web_sys::HtmlElement>() {
             let style = html_element.style();
+            if !self.saved_opacity.iter().any(|(saved, _)| saved == element) {
+                let opacity = style.get_property_value("opacity").unwrap_or_default();
+                self.saved_opacity.push((element.clone(), opacity));
+            }
             style
                 .set_property("will-change", "transform, opacity")
                 .map_err(|_| "Failed to set will-change")?;
@@ -397,14 +976,20 @@ impl SharedElementManager {
     /// Complete a transition
     fn complete_transition(&mut self, transition_id: &str) -> Result<(), String> {
         if let Some(transition) = self.active_transitions.remove(transition_id) {
-            // Reset element transforms
+            self.stop_crossfade(transition_id);
+            // Reset element transforms, the elements keep their final opacity
             self.reset_element_transforms(&transition.source_element)?;
             self.reset_element_transforms(&transition.target_element)?;
+            self.saved_opacity
+                .retain(|(element, _)| !transition.involves(element));
 
             // Update performance metrics
             let duration = js_sys::Date::now() - transition.start_time;
             self.update_performance_metrics(duration);
 
+            // Start the transitions waiting for these elements
+            self.process_transition_queue()?;
+
             Ok(())
         } else {
             Err("Transition not found".to_string())
@@ -433,9 +1018,70 @@ impl SharedElementManager {
         Ok(())
     }
 
+    /// Lay out crossfading copies of the elements of `transition` if it is configured to
+    ///
+    /// The copies move from `from`, or the box of the source element, to the
+    /// box of the target element as [`Self::update_transition`] advances.
+    fn start_crossfade(
+        &mut self,
+        transition: &SharedElementTransition,
+        from: Option<LayoutInfo>,
+    ) -> Result<(), String> {
+        if transition.config.crossfade.is_none() {
+            return Ok(());
+        }
+        let crossfade = Crossfade::new(
+            &transition.source_element,
+            &transition.target_element,
+            &from.unwrap_or_else(|| element_layout(&transition.source_element)),
+            &element_layout(&transition.target_element),
+            &transition.config,
+        )?;
+        self.crossfades.insert(transition.id.clone(), crossfade);
+        Ok(())
+    }
+
+    /// Stop the crossfade of a transition, showing its elements again
+    fn stop_crossfade(&mut self, transition_id: &str) {
+        if let Some(crossfade) = self.crossfades.remove(transition_id) {
+            crossfade.cancel();
+        }
+    }
+
+    /// Give `element` back the inline opacity it had before transitioning
+    fn restore_opacity(&mut self, element: &Element) -> Result<(), String> {
+        let Some(index) = self
+            .saved_opacity
+            .iter()
+            .position(|(saved, _)| saved == element)
+        else {
+            return Ok(());
+        };
+        let (_, opacity) = self.saved_opacity.remove(index);
+        let style = element
+            .dyn_ref::<web_sys::HtmlElement>()
+            .ok_or("Element is not an HtmlElement")?
+            .style();
+        if opacity.is_empty() {
+            style
+                .remove_property("opacity")
+                .map_err(|_| "Failed to remove opacity")?;
+        } else {
+            style
+                .set_property("opacity", &opacity)
+                .map_err(|_| "Failed to restore opacity")?;
+        }
+        Ok(())
+    }
+
     /// Generate unique transition ID
-    fn generate_transition_id(&self) -> String {
-        format!("shared_trans_{}", js_sys::Date::now())
+    fn generate_transition_id(&mut self) -> String {
+        self.transition_count += 1;
+        format!(
+            "shared_trans_{}_{}",
+            js_sys::Date::now(),
+            self.transition_count
+        )
     }
 
     /// Update performance metrics
@@ -466,6 +1112,118 @@ impl SharedElementManager {
     }
 }
 
+/// Animation from the box of an unmounted element, if it is still available at `now`
+fn snapshot_enter(
+    snapshot: LayoutSnapshot,
+    now: f64,
+    hold: Option<&SnapshotHold>,
+    z_index: &ZIndexStrategy,
+) -> Option<SharedLayoutEnter> {
+    let z_index = match hold {
+        Some(hold) if hold.holds(snapshot.time, now) => hold.z_index.clone(),
+        _ if now - snapshot.time <= LAYOUT_SNAPSHOT_LIFETIME_MS => z_index.clone(),
+        _ => return None,
+    };
+    let crossfade = snapshot.element.is_connected().then_some(snapshot.element);
+    Some(SharedLayoutEnter {
+        from: snapshot.layout,
+        fade_in: crossfade.is_some(),
+        crossfade,
+        z_index,
+    })
+}
+
+/// Viewport box of `element`, including running transforms
+pub fn element_layout(element: &Element) -> LayoutInfo {
+    let rect = element.get_bounding_client_rect();
+    LayoutInfo::new(rect.x(), rect.y(), rect.width(), rect.height())
+}
+
+/// Fixed layer above the page that elevated shared elements fly in, created on first use
+fn overlay_layer(document: &web_sys::Document) -> Option<Element> {
+    if let Ok(Some(layer)) = document.query_selector(&format!("[{OVERLAY_ATTRIBUTE}]")) {
+        return Some(layer);
+    }
+    let layer = document.create_element("div").ok()?;
+    let _ = layer.set_attribute(OVERLAY_ATTRIBUTE, "");
+    let _ = layer.set_attribute(
+        "style",
+        "position: fixed; inset: 0; overflow: visible; pointer-events: none; z-index: 2147483647",
+    );
+    document.body()?.append_child(&layer).ok()?;
+    Some(layer)
+}
+
+/// Copy of `element` laid out at the viewport box `layout` in a layer above the page
+///
+/// The copy is not clipped or covered by the containers of `element`, and
+/// is removed by whoever animates it once it lands.
+pub fn elevated_copy(element: &Element, layout: &LayoutInfo) -> Option<web_sys::HtmlElement> {
+    let document = element.owner_document()?;
+    let copy: web_sys::HtmlElement = element.clone_node_with_deep(true).ok()?.unchecked_into();
+    let _ = copy.remove_attribute("id");
+    let _ = copy.remove_attribute(LAYOUT_ID_ATTRIBUTE);
+    let style = copy.style();
+    for (property, value) in [
+        ("position", "absolute".to_string()),
+        ("margin", "0".to_string()),
+        ("box-sizing", "border-box".to_string()),
+        ("left", format!("{}px", layout.x)),
+        ("top", format!("{}px", layout.y)),
+        ("width", format!("{}px", layout.width)),
+        ("height", format!("{}px", layout.height)),
+    ] {
+        let _ = style.set_property(property, &value);
+    }
+    overlay_layer(&document)?.append_child(&copy).ok()?;
+    Some(copy)
+}
+
+/// Transform placing an element laid out at `to` over the box `from`
+///
+/// Translation is measured between the box centers, so it suits the default
+/// `transform-origin` of HTML elements.
+pub fn shared_layout_transform(from: &LayoutInfo, to: &LayoutInfo) -> TransformValues {
+    let scale = |from: f64, to: f64| if to > 0.0 { from / to } else { 1.0 };
+    let (from_x, from_y) = from.center();
+    let (to_x, to_y) = to.center();
+    TransformValues::new(
+        from_x - to_x,
+        from_y - to_y,
+        scale(from.width, to.width),
+        scale(from.height, to.height),
+        0.0,
+    )
+}
+
+/// CSS transform part of the way from `transform` to none, at eased `progress`
+pub fn transform_css_at(transform: &TransformValues, progress: f64) -> String {
+    let remaining = 1.0 - progress;
+    // Adding zero turns -0 into 0
+    format!(
+        "translate({}px, {}px) scale({}, {})",
+        transform.translate_x * remaining + 0.0,
+        transform.translate_y * remaining + 0.0,
+        1.0 + (transform.scale_x - 1.0) * remaining,
+        1.0 + (transform.scale_y - 1.0) * remaining,
+    )
+}
+
+/// Box an element laid out at `to` is seen at part of the way from `transform`
+/// to none, at eased `progress`
+pub fn shared_layout_at(to: &LayoutInfo, transform: &TransformValues, progress: f64) -> LayoutInfo {
+    let remaining = 1.0 - progress;
+    let width = to.width * (1.0 + (transform.scale_x - 1.0) * remaining);
+    let height = to.height * (1.0 + (transform.scale_y - 1.0) * remaining);
+    let (center_x, center_y) = to.center();
+    LayoutInfo::new(
+        center_x + transform.translate_x * remaining - width / 2.0,
+        center_y + transform.translate_y * remaining - height / 2.0,
+        width,
+        height,
+    )
+}
+
 impl Default for SharedElementManager {
     fn default() -> Self {
         Self::new(ZIndexStrategy::default())
@@ -479,6 +1237,8 @@ impl Default for SharedElementMetrics {
             successful_transitions: 0,
             average_duration: 0.0,
             frame_rate: 60.0,
+            interrupted_transitions: 0,
+            dropped_transitions: 0,
         }
     }
 }
@@ -500,12 +1260,10 @@ mod tests {
     fn test_shared_element_config_default() {
         let config = SharedElementConfig::default();
         assert_eq!(config.duration, 0.3);
-        assert!(matches!(
-            config.easing,
-            crate::flip::EasingFunction::EaseOut
-        ));
+        assert_eq!(config.easing, Easing::EaseOut);
         assert!(!config.maintain_aspect_ratio);
         assert!(config.hardware_accelerated);
+        assert_eq!(config.crossfade, None);
     }
 
     #[test]
@@ -627,6 +1385,45 @@ mod tests {
         assert_eq!(metrics.successful_transitions, 0);
         assert_eq!(metrics.average_duration, 0.0);
         assert_eq!(metrics.frame_rate, 60.0);
+        assert_eq!(metrics.interrupted_transitions, 0);
+        assert_eq!(metrics.dropped_transitions, 0);
+    }
+
+    #[test]
+    fn test_interruption_policy_by_priority() {
+        let mut manager = SharedElementManager::default();
+        let (low, normal, high) = (
+            TransitionPriority::Low,
+            TransitionPriority::Normal,
+            TransitionPriority::High,
+        );
+        // Never interrupts a more important transition
+        assert_eq!(
+            manager.interruption_policy(&high, &normal),
+            InterruptionPolicy::Queue
+        );
+        assert_eq!(
+            manager.interruption_policy(&normal, &normal),
+            InterruptionPolicy::Replace
+        );
+        assert_eq!(
+            manager.interruption_policy(&normal, &high),
+            InterruptionPolicy::Replace
+        );
+        assert_eq!(
+            manager.interruption_policy(&low, &low),
+            InterruptionPolicy::Queue
+        );
+
+        manager.set_interruption_policy(TransitionPriority::Normal, InterruptionPolicy::Reverse);
+        assert_eq!(
+            manager.interruption_policy(&low, &normal),
+            InterruptionPolicy::Reverse
+        );
+        assert_eq!(
+            manager.interruption_policy(&high, &normal),
+            InterruptionPolicy::Queue
+        );
     }
 
     #[test]
@@ -647,6 +1444,78 @@ mod tests {
         assert!(record.success);
     }
 
+    #[test]
+    fn test_shared_layout_transform_between_centers() {
+        let from = LayoutInfo::new(0.0, 0.0, 100.0, 50.0);
+        let to = LayoutInfo::new(200.0, 100.0, 200.0, 100.0);
+        let transform = shared_layout_transform(&from, &to);
+        assert_eq!(transform.translate_x, -250.0);
+        assert_eq!(transform.translate_y, -125.0);
+        assert_eq!(transform.scale_x, 0.5);
+        assert_eq!(transform.scale_y, 0.5);
+
+        assert_eq!(
+            transform_css_at(&transform, 0.0),
+            "translate(-250px, -125px) scale(0.5, 0.5)"
+        );
+        assert_eq!(
+            transform_css_at(&transform, 0.5),
+            "translate(-125px, -62.5px) scale(0.75, 0.75)"
+        );
+        assert_eq!(
+            transform_css_at(&transform, 1.0),
+            "translate(0px, 0px) scale(1, 1)"
+        );
+
+        let halfway = shared_layout_at(&to, &transform, 0.5);
+        assert_eq!(halfway.x, 100.0);
+        assert_eq!(halfway.y, 50.0);
+        assert_eq!(halfway.width, 150.0);
+        assert_eq!(halfway.height, 75.0);
+        let start = shared_layout_at(&to, &transform, 0.0);
+        assert_eq!(
+            (start.x, start.y, start.width, start.height),
+            (0.0, 0.0, 100.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn test_shared_layout_transform_of_empty_box() {
+        let from = LayoutInfo::new(10.0, 10.0, 20.0, 20.0);
+        let to = LayoutInfo::new(10.0, 10.0, 0.0, 0.0);
+        let transform = shared_layout_transform(&from, &to);
+        assert_eq!(transform.scale_x, 1.0);
+        assert_eq!(transform.scale_y, 1.0);
+    }
+
+    #[test]
+    fn test_shared_element_config_transition() {
+        let config = SharedElementConfig {
+            duration: 0.5,
+            easing: Easing::EaseInOut,
+            ..Default::default()
+        };
+        let transition = Transition::from(&config);
+        assert_eq!(transition.duration, Some(0.5));
+        assert_eq!(transition.ease, Easing::EaseInOut);
+        assert_eq!(transition.delay, None);
+    }
+
+    #[test]
+    fn test_snapshot_hold_covers_navigation() {
+        let hold = SnapshotHold {
+            since: 1000.0,
+            until: 2000.0,
+            z_index: ZIndexStrategy::Elevate,
+        };
+        // Measured as the leaving page unmounted, just before the hold
+        assert!(hold.holds(990.0, 1500.0));
+        assert!(hold.holds(1000.0, 2000.0));
+        // Expired hold, or a snapshot from an earlier page
+        assert!(!hold.holds(1000.0, 2001.0));
+        assert!(!hold.holds(1000.0 - LAYOUT_SNAPSHOT_LIFETIME_MS - 1.0, 1500.0));
+    }
+
     #[wasm_bindgen_test]
     fn test_shared_element_manager_creation() {
         let manager = SharedElementManager::new(ZIndexStrategy::default());
@@ -683,7 +1552,7 @@ mod tests {
 
     #[wasm_bindgen_test]
     fn test_transition_id_generation() {
-        let manager = SharedElementManager::new(ZIndexStrategy::default());
+        let mut manager = SharedElementManager::new(ZIndexStrategy::default());
         let id1 = manager.generate_transition_id();
         let id2 = manager.generate_transition_id();
 
@@ -692,6 +1561,260 @@ mod tests {
         assert!(id2.starts_with("shared_trans_"));
     }
 
+    /// Three detached elements to transition between
+    fn tabs() -> (Element, Element, Element) {
+        let document = web_sys::window().unwrap().document().unwrap();
+        (
+            document.create_element("div").unwrap(),
+            document.create_element("div").unwrap(),
+            document.create_element("div").unwrap(),
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn test_replace_retargets_from_current_opacity() {
+        let (first, second, third) = tabs();
+        let config = SharedElementConfig::default();
+        let mut manager = SharedElementManager::default();
+
+        let running = manager.create_transition(&first, &second, &config).unwrap();
+        manager.update_transition(&running, 0.25).unwrap();
+        // Switching back mid-way starts from the faded state instead of jumping
+        let back = manager.create_transition(&second, &first, &config).unwrap();
+        assert_ne!(back, running);
+        assert_eq!(manager.get_active_transition_count(), 1);
+        assert_eq!(manager.get_performance_metrics().interrupted_transitions, 1);
+        let transition = &manager.active_transitions[&back];
+        assert_eq!(transition.start_opacity, (0.25, 0.75));
+        assert_eq!(transition.opacity_at(0.5), (0.125, 0.875));
+
+        // Unrelated elements start fully shown and hidden
+        let other = manager.create_transition(&third, &second, &config).unwrap();
+        assert_eq!(
+            manager.active_transitions[&other].start_opacity,
+            (1.0, 0.25)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_reverse_plays_back_from_progress() {
+        let (first, second, _) = tabs();
+        let config = SharedElementConfig::default();
+        let mut manager = SharedElementManager::default();
+        manager.set_interruption_policy(TransitionPriority::Normal, InterruptionPolicy::Reverse);
+
+        // A third of the way through
+        let running = manager.create_transition(&first, &second, &config).unwrap();
+        manager.update_transition(&running, 1.0 / 3.0).unwrap();
+        let reversed = manager.create_transition(&second, &first, &config).unwrap();
+        assert_eq!(reversed, running);
+
+        let transition = &manager.active_transitions[&running];
+        assert!(transition.reversed);
+        assert_eq!(transition.target_element, first);
+        assert_eq!(transition.progress, 0.0);
+        assert!((transition.config.duration - 0.1).abs() < 1e-9);
+        assert_eq!(transition.opacity_at(1.0), (0.0, 1.0));
+
+        // Turning around half way back covers the rest of a whole run
+        manager.update_transition(&running, 0.5).unwrap();
+        let duration = manager.reverse_transition(&running).unwrap();
+        assert!((duration - 0.25).abs() < 1e-9);
+        let transition = &manager.active_transitions[&running];
+        assert!(!transition.reversed);
+        assert_eq!(transition.config.duration, duration);
+        assert_eq!(transition.full_duration, 0.3);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_replace_restores_opacity_of_dropped_elements() {
+        let (first, second, third) = tabs();
+        let config = SharedElementConfig::default();
+        let mut manager = SharedElementManager::default();
+        let first_style = first.unchecked_ref::<web_sys::HtmlElement>().style();
+        first_style.set_property("opacity", "0.8").unwrap();
+
+        let running = manager.create_transition(&first, &second, &config).unwrap();
+        manager.update_transition(&running, 0.5).unwrap();
+        manager.create_transition(&third, &second, &config).unwrap();
+        assert_eq!(first_style.get_property_value("opacity").unwrap(), "0.8");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_crossfade_transition_flies_copies() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let body = document.body().unwrap();
+        let (thumbnail, card, _) = tabs();
+        body.append_child(&thumbnail).unwrap();
+        body.append_child(&card).unwrap();
+        let config = SharedElementConfig {
+            crossfade: Some(CrossfadeConfig::default()),
+            ..Default::default()
+        };
+        let mut manager = SharedElementManager::default();
+        let visibility = |element: &Element| {
+            element
+                .unchecked_ref::<web_sys::HtmlElement>()
+                .style()
+                .get_property_value("visibility")
+                .unwrap()
+        };
+
+        let id = manager
+            .create_transition(&thumbnail, &card, &config)
+            .unwrap();
+        assert!(manager.crossfades[&id].is_running());
+        assert_eq!(visibility(&thumbnail), "hidden");
+        assert_eq!(visibility(&card), "hidden");
+
+        manager.cancel_transition(&id).unwrap();
+        assert!(manager.crossfades.is_empty());
+        assert_eq!(visibility(&card), "");
+        thumbnail.remove();
+        card.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_crossfade_follows_transition_progress() {
+        let body = web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .body()
+            .unwrap();
+        let (thumbnail, card, other) = tabs();
+        for (element, left) in [(&thumbnail, "0px"), (&card, "100px"), (&other, "300px")] {
+            let style = element.unchecked_ref::<web_sys::HtmlElement>().style();
+            for (property, value) in [
+                ("position", "absolute"),
+                ("top", "0px"),
+                ("left", left),
+                ("width", "100px"),
+                ("height", "100px"),
+            ] {
+                style.set_property(property, value).unwrap();
+            }
+            body.append_child(element).unwrap();
+        }
+        let config = SharedElementConfig {
+            easing: Easing::Linear,
+            crossfade: Some(CrossfadeConfig::default()),
+            ..Default::default()
+        };
+        let mut manager = SharedElementManager::default();
+        let copies_x = |manager: &SharedElementManager, id: &str| {
+            let (_, _, layout) = manager.crossfades[id].layouts().unwrap();
+            layout.x
+        };
+
+        let id = manager
+            .create_transition(&thumbnail, &card, &config)
+            .unwrap();
+        assert_eq!(copies_x(&manager, &id), 0.0);
+        manager.update_transition(&id, 0.5).unwrap();
+        assert_eq!(copies_x(&manager, &id), 50.0);
+
+        // Reversing flies back from where the copies are
+        manager.reverse_transition(&id).unwrap();
+        let (from, to, _) = manager.crossfades[&id].layouts().unwrap();
+        assert_eq!((from.x, to.x), (50.0, 0.0));
+
+        // Replacing starts the new copies there too
+        let replaced = manager.create_transition(&card, &other, &config).unwrap();
+        assert!(!manager.crossfades.contains_key(&id));
+        let (from, to, _) = manager.crossfades[&replaced].layouts().unwrap();
+        assert_eq!((from.x, to.x), (50.0, 300.0));
+
+        manager.update_transition(&replaced, 1.0).unwrap();
+        assert!(manager.crossfades.is_empty());
+        for element in [thumbnail, card, other] {
+            element.remove();
+        }
+    }
+
+    #[test]
+    fn test_layout_animation_interruption_policies() {
+        let mut manager = SharedElementManager::default();
+        let normal = TransitionPriority::Normal;
+        let slot = manager.schedule_layout_animation("card", 0.0, 300.0, normal.clone());
+        assert_eq!((slot.delay, slot.duration), (0.0, 300.0));
+
+        // Replaced by default
+        let slot = manager.schedule_layout_animation("card", 100.0, 300.0, normal.clone());
+        assert_eq!(slot.delay, 0.0);
+        assert_eq!(manager.get_performance_metrics().interrupted_transitions, 1);
+
+        manager.set_interruption_policy(TransitionPriority::Normal, InterruptionPolicy::Queue);
+        let slot = manager.schedule_layout_animation("card", 200.0, 300.0, normal.clone());
+        assert_eq!((slot.delay, slot.duration), (200.0, 300.0));
+
+        // Reversing 100ms into an animation plays back for those 100ms
+        manager.set_interruption_policy(TransitionPriority::Normal, InterruptionPolicy::Reverse);
+        let slot = manager.schedule_layout_animation("card", 500.0, 300.0, normal.clone());
+        assert_eq!((slot.delay, slot.duration), (0.0, 100.0));
+
+        manager.set_interruption_policy(TransitionPriority::Normal, InterruptionPolicy::Ignore);
+        let slot = manager.schedule_layout_animation("card", 550.0, 300.0, normal.clone());
+        assert_eq!(slot.duration, 0.0);
+        assert_eq!(manager.get_performance_metrics().dropped_transitions, 1);
+
+        // Other ids and finished animations aren't interrupted
+        let slot = manager.schedule_layout_animation("tab", 850.0, 300.0, normal.clone());
+        assert_eq!(slot.duration, 300.0);
+        let slot = manager.schedule_layout_animation("card", 1000.0, 300.0, normal.clone());
+        assert_eq!(slot.duration, 300.0);
+
+        // Less important animations wait for the running one
+        let slot =
+            manager.schedule_layout_animation("card", 1100.0, 300.0, TransitionPriority::Low);
+        assert_eq!((slot.delay, slot.duration), (200.0, 300.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_queue_waits_for_running_transition() {
+        let (first, second, third) = tabs();
+        let config = SharedElementConfig::default();
+        let mut manager = SharedElementManager::default();
+
+        let running = manager
+            .create_transition_with_priority(&first, &second, &config, TransitionPriority::High)
+            .unwrap();
+        let queued = manager.create_transition(&second, &third, &config).unwrap();
+        assert_eq!(manager.get_active_transition_count(), 1);
+        assert_eq!(manager.get_queued_transition_count(), 1);
+
+        manager.update_transition(&running, 1.0).unwrap();
+        assert_eq!(manager.get_queued_transition_count(), 0);
+        assert!(manager.active_transitions.contains_key(&queued));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_ignore_drops_transition() {
+        let (first, second, _) = tabs();
+        let config = SharedElementConfig::default();
+        let mut manager = SharedElementManager::default();
+        manager.set_interruption_policy(TransitionPriority::Low, InterruptionPolicy::Ignore);
+
+        let running = manager.create_transition(&first, &second, &config).unwrap();
+        let ignored = manager
+            .create_transition_with_priority(&second, &first, &config, TransitionPriority::Low)
+            .unwrap();
+        // Lower priority queues regardless of its policy
+        assert_ne!(ignored, running);
+        assert_eq!(manager.get_queued_transition_count(), 1);
+
+        manager.cancel_all_transitions();
+        let running = manager
+            .create_transition_with_priority(&first, &second, &config, TransitionPriority::Low)
+            .unwrap();
+        let ignored = manager
+            .create_transition_with_priority(&second, &first, &config, TransitionPriority::Low)
+            .unwrap();
+        assert_eq!(ignored, running);
+        assert_eq!(manager.get_active_transition_count(), 1);
+        assert_eq!(manager.get_performance_metrics().dropped_transitions, 2);
+    }
+
     #[wasm_bindgen_test]
     fn test_manager_cancel_all_transitions() {
         let mut manager = SharedElementManager::new(ZIndexStrategy::default());
@@ -699,4 +1822,111 @@ mod tests {
         assert_eq!(manager.get_active_transition_count(), 0);
         assert_eq!(manager.get_queued_transition_count(), 0);
     }
+
+    #[wasm_bindgen_test]
+    fn test_layout_id_hands_over_between_members() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let card = document.create_element("div").unwrap();
+        let modal = document.create_element("div").unwrap();
+        let mut manager = SharedElementManager::default();
+
+        // First member has nothing to animate from
+        assert!(manager.mount_layout_id("card", &card, 0.0).is_none());
+
+        // Second member animates from the card, which is crossfaded out
+        let enter = manager.mount_layout_id("card", &modal, 10.0).unwrap();
+        assert_eq!(enter.crossfade.as_ref(), Some(&card));
+        assert!(enter.fade_in);
+        assert_eq!(manager.layout_id_member_count("card"), 2);
+
+        // Closing the modal hands the lead back to the card
+        let layout = LayoutInfo::new(0.0, 0.0, 400.0, 300.0);
+        let (lead, enter) = manager
+            .unmount_layout_id("card", &modal, Some(layout), 20.0)
+            .unwrap();
+        assert_eq!(lead, card);
+        assert_eq!(enter.from.width, 400.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_layout_id_snapshot_expires() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let tab = document.create_element("div").unwrap();
+        let next_tab = document.create_element("div").unwrap();
+        let mut manager = SharedElementManager::default();
+        manager.mount_layout_id("underline", &tab, 0.0);
+        let layout = LayoutInfo::new(0.0, 40.0, 80.0, 2.0);
+        assert!(
+            manager
+                .unmount_layout_id("underline", &tab, Some(layout.clone()), 0.0)
+                .is_none()
+        );
+
+        let enter = manager
+            .mount_layout_id("underline", &next_tab, 16.0)
+            .unwrap();
+        assert_eq!(enter.from.x, 0.0);
+        // The unmounted tab isn't in the document, so there is nothing to crossfade
+        assert!(enter.crossfade.is_none());
+        assert!(!enter.fade_in);
+
+        manager.unmount_layout_id("underline", &next_tab, Some(layout), 100.0);
+        let late =
+            manager.mount_layout_id("underline", &tab, 100.0 + LAYOUT_SNAPSHOT_LIFETIME_MS + 1.0);
+        assert!(late.is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_detached_unmount_keeps_no_snapshot() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let tab = document.create_element("div").unwrap();
+        let next_tab = document.create_element("div").unwrap();
+        let mut manager = SharedElementManager::default();
+        manager.mount_layout_id("underline", &tab, 0.0);
+
+        assert!(
+            manager
+                .unmount_layout_id("underline", &tab, None, 0.0)
+                .is_none()
+        );
+        assert_eq!(manager.layout_id_member_count("underline"), 0);
+        assert!(
+            manager
+                .mount_layout_id("underline", &next_tab, 16.0)
+                .is_none()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_route_hold_keeps_tagged_snapshot() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let thumbnail = document.create_element("img").unwrap();
+        let mut manager = SharedElementManager::default();
+        let layout = LayoutInfo::new(20.0, 300.0, 120.0, 80.0);
+        manager.snapshot_layout_id("photo-1", &thumbnail, layout, 0.0);
+        manager.hold_layout_snapshots(5.0, 1000.0, ZIndexStrategy::Elevate);
+
+        // The detail page rendered after loading its data
+        let enter = manager.enter_layout_id("photo-1", 600.0).unwrap();
+        assert_eq!(enter.from.y, 300.0);
+        assert_eq!(enter.z_index, ZIndexStrategy::Elevate);
+        assert!(manager.enter_layout_id("photo-1", 650.0).is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_register_element_config() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let hero = document.create_element("div").unwrap();
+        let mut manager = SharedElementManager::default();
+        let config = SharedElementConfig {
+            duration: 0.6,
+            ..Default::default()
+        };
+        manager.register_element(&hero, &config).unwrap();
+        assert_eq!(manager.element_config(&hero).unwrap().duration, 0.6);
+
+        manager.unregister_element(&hero).unwrap();
+        assert!(manager.element_config(&hero).is_none());
+        assert!(manager.unregister_element(&hero).is_err());
+    }
 }
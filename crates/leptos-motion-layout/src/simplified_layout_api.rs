@@ -4,6 +4,7 @@
 //! that hides complexity and provides a clean interface.
 
 use crate::*;
+use leptos_motion_core::Easing;
 use std::collections::HashMap;
 use std::time::Instant;
 use web_sys::{DomRect, Element};
@@ -64,38 +65,21 @@ impl MockSharedElementManager {
     }
 }
 
-/// Simplified easing functions
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SimplifiedEasing {
-    /// Linear easing
-    Linear,
-    /// Ease in
-    EaseIn,
-    /// Ease out
-    EaseOut,
-    /// Ease in out
-    EaseInOut,
-    /// Ease in cubic
-    EaseInCubic,
-    /// Ease out cubic
-    EaseOutCubic,
-    /// Ease in out cubic
-    EaseInOutCubic,
-}
-
 /// Simplified layout configuration
 #[derive(Debug, Clone, PartialEq)]
 pub struct SimplifiedLayoutConfig {
     /// Animation duration in seconds
     pub duration: f64,
     /// Easing function
-    pub easing: SimplifiedEasing,
+    pub easing: Easing,
     /// Whether to use hardware acceleration
     pub hardware_accelerated: bool,
     /// Whether to enable FLIP animations
     pub enable_flip: bool,
     /// Whether to enable shared element transitions
     pub enable_shared_elements: bool,
+    /// Crossfade shared elements instead of scaling them
+    pub crossfade: Option<CrossfadeConfig>,
 }
 
 /// Simplified animation status
@@ -158,6 +142,9 @@ pub struct SimplifiedLayoutManager {
     active_animations: HashMap<String, SimplifiedAnimationStatus>,
     /// Performance metrics
     performance_metrics: SimplifiedPerformanceMetrics,
+    /// Running crossfades, by both their outgoing and incoming elements
+    #[cfg(target_arch = "wasm32")]
+    crossfades: HashMap<String, Crossfade>,
 }
 
 impl SimplifiedLayoutManager {
@@ -184,6 +171,7 @@ impl SimplifiedLayoutManager {
                     memory_usage: 0,
                     cpu_usage: 0.0,
                 },
+                crossfades: HashMap::new(),
             }
         }
         #[cfg(not(target_arch = "wasm32"))]
@@ -264,7 +252,7 @@ impl SimplifiedLayoutManager {
         let animation_config = LayoutAnimationConfig {
             enabled: true,
             duration: self.config.duration,
-            easing: self.convert_easing(self.config.easing),
+            easing: self.config.easing.clone(),
             hardware_accelerated: self.config.hardware_accelerated,
         };
 
@@ -309,12 +297,15 @@ impl SimplifiedLayoutManager {
     }
 
     /// Perform shared element transition
+    ///
+    /// With [`SimplifiedLayoutConfig::crossfade`] set, copies of both
+    /// elements move from `from_layout` to `to_layout` while crossfading.
     pub fn shared_element_transition(
         &mut self,
         from_element_id: &str,
         to_element_id: &str,
-        _from_layout: &LayoutInfo,
-        _to_layout: &LayoutInfo,
+        from_layout: &LayoutInfo,
+        to_layout: &LayoutInfo,
     ) -> Result<(), String> {
         if !self.config.enable_shared_elements {
             return Err("Shared element transitions are disabled".to_string());
@@ -331,20 +322,28 @@ impl SimplifiedLayoutManager {
             return Err(format!("Element '{}' is not being tracked", to_element_id));
         }
 
-        let _from_element = self.tracked_elements.get(from_element_id).unwrap();
-        let _to_element = self.tracked_elements.get(to_element_id).unwrap();
-
         // Create shared element transition using the correct API
-        let _config = SharedElementConfig {
+        let config = SharedElementConfig {
             maintain_aspect_ratio: true,
             hardware_accelerated: self.config.hardware_accelerated,
             duration: self.config.duration,
-            easing: self.convert_easing(self.config.easing),
+            easing: self.config.easing.clone(),
+            crossfade: self.config.crossfade.clone(),
         };
 
-        let _ = self
-            .internal_shared_manager
-            .start_transition("shared-transition");
+        if config.crossfade.is_some() {
+            self.start_crossfade(
+                from_element_id,
+                to_element_id,
+                from_layout,
+                to_layout,
+                &config,
+            )?;
+        } else {
+            let _ = self
+                .internal_shared_manager
+                .start_transition("shared-transition");
+        }
 
         // Update animation status for both elements
         let status = SimplifiedAnimationStatus {
@@ -366,6 +365,56 @@ impl SimplifiedLayoutManager {
         Ok(())
     }
 
+    /// Crossfade two tracked elements, replacing the crossfades they are part of
+    #[cfg(target_arch = "wasm32")]
+    fn start_crossfade(
+        &mut self,
+        from_element_id: &str,
+        to_element_id: &str,
+        from_layout: &LayoutInfo,
+        to_layout: &LayoutInfo,
+        config: &SharedElementConfig,
+    ) -> Result<(), String> {
+        for element_id in [from_element_id, to_element_id] {
+            self.stop_crossfade(element_id);
+        }
+        let crossfade = Crossfade::start(
+            &self.tracked_elements[from_element_id],
+            &self.tracked_elements[to_element_id],
+            from_layout,
+            to_layout,
+            config,
+        )?;
+        self.crossfades
+            .insert(from_element_id.to_string(), crossfade.clone());
+        self.crossfades.insert(to_element_id.to_string(), crossfade);
+        Ok(())
+    }
+
+    /// Stop the crossfade `element_id` is part of, showing both its elements again
+    #[cfg(target_arch = "wasm32")]
+    fn stop_crossfade(&mut self, element_id: &str) {
+        if let Some(crossfade) = self.crossfades.remove(element_id) {
+            crossfade.cancel();
+            // Drop the entry of its other element too
+            self.crossfades
+                .retain(|_, crossfade| crossfade.is_running());
+        }
+    }
+
+    /// Crossfades need the DOM, so there is nothing to run on native targets
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_crossfade(
+        &mut self,
+        _from_element_id: &str,
+        _to_element_id: &str,
+        _from_layout: &LayoutInfo,
+        _to_layout: &LayoutInfo,
+        _config: &SharedElementConfig,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
     /// Batch start tracking multiple elements
     pub fn batch_start_tracking(&mut self, elements: Vec<(&str, &Element)>) -> Result<(), String> {
         for (element_id, element) in elements {
@@ -427,6 +476,8 @@ impl SimplifiedLayoutManager {
 
     /// Cancel animation for an element
     pub fn cancel_animation(&mut self, element_id: &str) -> Result<(), String> {
+        #[cfg(target_arch = "wasm32")]
+        self.stop_crossfade(element_id);
         if let Some(status) = self.active_animations.get_mut(element_id) {
             status.is_animating = false;
             status.is_paused = false;
@@ -442,6 +493,10 @@ impl SimplifiedLayoutManager {
     pub fn clear_all(&mut self) {
         self.tracked_elements.clear();
         self.active_animations.clear();
+        #[cfg(target_arch = "wasm32")]
+        for (_, crossfade) in self.crossfades.drain() {
+            crossfade.cancel();
+        }
         // Clear tracking and animations (methods may not exist, so we'll handle gracefully)
         // self.internal_tracker.clear_all();
         // self.internal_flip_animator.clear_all();
@@ -480,21 +535,6 @@ impl SimplifiedLayoutManager {
             .filter(|s| s.is_animating)
             .count()
     }
-
-    /// Convert simplified easing to internal easing
-    fn convert_easing(&self, easing: SimplifiedEasing) -> EasingFunction {
-        match easing {
-            SimplifiedEasing::Linear => EasingFunction::Linear,
-            SimplifiedEasing::EaseIn => EasingFunction::EaseIn,
-            SimplifiedEasing::EaseOut => EasingFunction::EaseOut,
-            SimplifiedEasing::EaseInOut => EasingFunction::EaseInOut,
-            SimplifiedEasing::EaseInCubic => EasingFunction::CubicBezier(0.55, 0.055, 0.675, 0.19),
-            SimplifiedEasing::EaseOutCubic => EasingFunction::CubicBezier(0.215, 0.61, 0.355, 1.0),
-            SimplifiedEasing::EaseInOutCubic => {
-                EasingFunction::CubicBezier(0.645, 0.045, 0.355, 1.0)
-            }
-        }
-    }
 }
 
 impl Default for SimplifiedLayoutManager {
@@ -515,6 +555,7 @@ impl Clone for SimplifiedLayoutManager {
                 tracked_elements: HashMap::new(), // Can't clone DOM elements
                 active_animations: HashMap::new(),
                 performance_metrics: self.performance_metrics.clone(),
+                crossfades: HashMap::new(),
             }
         }
         #[cfg(not(target_arch = "wasm32"))]
@@ -546,10 +587,11 @@ impl Default for SimplifiedLayoutConfig {
     fn default() -> Self {
         Self {
             duration: 0.3,
-            easing: SimplifiedEasing::EaseInOut,
+            easing: Easing::EaseInOut,
             hardware_accelerated: true,
             enable_flip: true,
             enable_shared_elements: true,
+            crossfade: None,
         }
     }
 }
@@ -567,7 +609,7 @@ impl SimplifiedLayoutConfig {
     }
 
     /// Set easing function
-    pub fn easing(mut self, easing: SimplifiedEasing) -> Self {
+    pub fn easing(mut self, easing: Easing) -> Self {
         self.easing = easing;
         self
     }
@@ -589,6 +631,12 @@ impl SimplifiedLayoutConfig {
         self.enable_shared_elements = enabled;
         self
     }
+
+    /// Crossfade shared elements with `crossfade` curves
+    pub fn crossfade(mut self, crossfade: CrossfadeConfig) -> Self {
+        self.crossfade = Some(crossfade);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -607,7 +655,7 @@ mod tests {
     fn test_simplified_layout_manager_with_config() {
         let config = SimplifiedLayoutConfig::new()
             .duration(0.5)
-            .easing(SimplifiedEasing::EaseOut);
+            .easing(Easing::EaseOut);
 
         let manager = SimplifiedLayoutManager::with_config(config);
         assert!(!manager.is_tracking());
@@ -618,16 +666,18 @@ mod tests {
     fn test_simplified_layout_config_fluent_api() {
         let config = SimplifiedLayoutConfig::new()
             .duration(0.5)
-            .easing(SimplifiedEasing::EaseOut)
+            .easing(Easing::EaseOut)
             .hardware_accelerated(false)
             .enable_flip(false)
-            .enable_shared_elements(false);
+            .enable_shared_elements(false)
+            .crossfade(CrossfadeConfig::default());
 
         assert_eq!(config.duration, 0.5);
-        assert_eq!(config.easing, SimplifiedEasing::EaseOut);
+        assert_eq!(config.easing, Easing::EaseOut);
         assert!(!config.hardware_accelerated);
         assert!(!config.enable_flip);
         assert!(!config.enable_shared_elements);
+        assert_eq!(config.crossfade, Some(CrossfadeConfig::default()));
     }
 
     #[test]
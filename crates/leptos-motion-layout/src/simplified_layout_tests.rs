@@ -4,6 +4,7 @@
 // that hides complexity and provides a clean, simple interface.
 
 use crate::*;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -23,7 +24,7 @@ fn simple_layout_info() -> LayoutInfo {
 fn simple_layout_config() -> SimplifiedLayoutConfig {
     SimplifiedLayoutConfig::new()
         .duration(0.5)
-        .easing(SimplifiedEasing::EaseInOut)
+        .easing(Easing::EaseInOut)
         .hardware_accelerated(true)
         .enable_flip(true)
         .enable_shared_elements(true)
@@ -126,6 +127,43 @@ fn test_simplified_layout_manager_shared_element_transition() {
     assert_eq!(manager.animation_count(), 1);
 }
 
+#[wasm_bindgen_test]
+fn test_simplified_layout_manager_crossfade_transition() {
+    // Test crossfading shared element transition
+    let config = SimplifiedLayoutConfig::new().crossfade(CrossfadeConfig::default());
+    let mut manager = SimplifiedLayoutManager::with_config(config);
+    let thumbnail = mock_element();
+    let card = mock_element();
+    let body = web_sys::window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .body()
+        .unwrap();
+    body.append_child(&thumbnail).unwrap();
+    body.append_child(&card).unwrap();
+
+    manager.start_tracking("thumbnail", &thumbnail).unwrap();
+    manager.start_tracking("card", &card).unwrap();
+
+    let from_layout = simple_layout_info();
+    let to_layout = LayoutInfo::new(200.0, 300.0, 400.0, 500.0);
+
+    let result = manager.shared_element_transition("thumbnail", "card", &from_layout, &to_layout);
+    assert!(result.is_ok());
+    // Copies fly while the elements are hidden
+    let card_style = card.unchecked_ref::<web_sys::HtmlElement>().style();
+    assert_eq!(
+        card_style.get_property_value("visibility").unwrap(),
+        "hidden"
+    );
+
+    manager.cancel_animation("card").unwrap();
+    assert_eq!(card_style.get_property_value("visibility").unwrap(), "");
+    thumbnail.remove();
+    card.remove();
+}
+
 #[wasm_bindgen_test]
 fn test_simplified_layout_manager_batch_operations() {
     // Test batch operations
@@ -353,7 +391,7 @@ fn test_simplified_layout_config_creation() {
     // Test layout config creation
     let config = SimplifiedLayoutConfig::new();
     assert_eq!(config.duration, 0.3);
-    assert_eq!(config.easing, SimplifiedEasing::EaseInOut);
+    assert_eq!(config.easing, Easing::EaseInOut);
     assert!(config.hardware_accelerated);
     assert!(config.enable_flip);
     assert!(config.enable_shared_elements);
@@ -364,13 +402,13 @@ fn test_simplified_layout_config_fluent_api() {
     // Test layout config fluent API
     let config = SimplifiedLayoutConfig::new()
         .duration(0.5)
-        .easing(SimplifiedEasing::EaseOut)
+        .easing(Easing::EaseOut)
         .hardware_accelerated(false)
         .enable_flip(false)
         .enable_shared_elements(false);
 
     assert_eq!(config.duration, 0.5);
-    assert_eq!(config.easing, SimplifiedEasing::EaseOut);
+    assert_eq!(config.easing, Easing::EaseOut);
     assert!(!config.hardware_accelerated);
     assert!(!config.enable_flip);
     assert!(!config.enable_shared_elements);
@@ -381,7 +419,7 @@ fn test_simplified_layout_config_clone() {
     // Test that simplified layout config can be cloned
     let config1 = SimplifiedLayoutConfig::new()
         .duration(0.5)
-        .easing(SimplifiedEasing::EaseOut);
+        .easing(Easing::EaseOut);
 
     let config2 = config1.clone();
 
@@ -394,7 +432,7 @@ fn test_simplified_layout_config_debug() {
     // Test debug formatting
     let config = SimplifiedLayoutConfig::new()
         .duration(0.5)
-        .easing(SimplifiedEasing::EaseOut);
+        .easing(Easing::EaseOut);
 
     let debug_str = format!("{:?}", config);
     assert!(debug_str.contains("SimplifiedLayoutConfig"));
@@ -407,7 +445,7 @@ fn test_simplified_layout_config_default() {
     // Test default implementation
     let config = SimplifiedLayoutConfig::default();
     assert_eq!(config.duration, 0.3);
-    assert_eq!(config.easing, SimplifiedEasing::EaseInOut);
+    assert_eq!(config.easing, Easing::EaseInOut);
     assert!(config.hardware_accelerated);
     assert!(config.enable_flip);
     assert!(config.enable_shared_elements);
@@ -0,0 +1,490 @@
+//! Shared element transitions through the browser's View Transitions API
+//!
+//! [`ViewTransitionEngine`] runs a state update inside
+//! `document.startViewTransition`. Elements tagged with
+//! [`LAYOUT_ID_ATTRIBUTE`] get a `view-transition-name` derived from their
+//! id before and after the update, so the browser morphs each of them into
+//! its counterpart, and the `::view-transition-*` animations follow motion
+//! [`Transition`]s. Browsers without the API get the same transitions from
+//! layout snapshots and FLIP animations instead.
+//!
+//! ```rust,ignore
+//! let engine = ViewTransitionEngine::new(Transition::default())
+//!     .with_transition("hero", hero_transition);
+//! engine.start(move || set_selected.set(Some(photo)))?;
+//! ```
+
+use crate::LayoutAnimationConfig;
+use crate::flip::FLIPAnimator;
+use crate::shared_elements::{LAYOUT_ID_ATTRIBUTE, SharedElementManager, element_layout};
+use leptos_motion_core::{FrameLoop, Transition};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, DomRect, Element, HtmlElement};
+
+/// Prefix of the `view-transition-name`s given to tagged elements
+pub const VIEW_TRANSITION_NAME_PREFIX: &str = "motion-";
+
+/// Attribute marking the style element holding the generated animations
+const STYLE_ATTRIBUTE: &str = "data-motion-view-transitions";
+
+/// `view-transition-name` of the elements tagged with `layout_id`
+///
+/// Characters other than ASCII letters, digits and `-` are escaped as
+/// `_<hex>_`, so distinct ids always get distinct names.
+pub fn view_transition_name(layout_id: &str) -> String {
+    let mut name = String::from(VIEW_TRANSITION_NAME_PREFIX);
+    for character in layout_id.chars() {
+        if character.is_ascii_alphanumeric() || character == '-' {
+            name.push(character);
+        } else {
+            name.push_str(&format!("_{:x}_", character as u32));
+        }
+    }
+    name
+}
+
+/// Rules animating the view transition pseudo-elements of `name` with `transition`
+fn transition_rules(name: &str, transition: &Transition) -> String {
+    let duration = transition
+        .effective_duration()
+        .unwrap_or_else(|| LayoutAnimationConfig::default().duration);
+    format!(
+        "::view-transition-group({name}),\n\
+         ::view-transition-old({name}),\n\
+         ::view-transition-new({name}) {{\n  \
+         animation-duration: {duration}s;\n  \
+         animation-delay: {delay}s;\n  \
+         animation-timing-function: {ease};\n\
+         }}\n",
+        delay = transition.delay.unwrap_or(0.0),
+        ease = transition.ease.to_css(),
+    )
+}
+
+/// Snapshots and animations of browsers without view transitions
+struct Fallback {
+    manager: SharedElementManager,
+    animator: FLIPAnimator,
+}
+
+/// Shared element transitions backed by `document.startViewTransition`
+///
+/// An alternative to animating shared elements from JavaScript: the browser
+/// captures the page before and after the update and animates between the
+/// captures, which keeps working while the main thread is busy. Where the
+/// API is missing, [`Self::start`] falls back to a [`SharedElementManager`]
+/// snapshot of the tagged elements and FLIP animations after the update.
+#[derive(Clone)]
+pub struct ViewTransitionEngine {
+    transition: Transition,
+    transitions: HashMap<String, Transition>,
+    fallback: Rc<RefCell<Fallback>>,
+    frame: FrameLoop,
+}
+
+impl ViewTransitionEngine {
+    /// Engine animating every shared element, and the page itself, with `transition`
+    pub fn new(transition: Transition) -> Self {
+        let fallback = Rc::new(RefCell::new(Fallback {
+            manager: SharedElementManager::default(),
+            animator: FLIPAnimator::new(),
+        }));
+        // Animate the fallback on the next frames until its animations finish
+        let frame_fallback = Rc::downgrade(&fallback);
+        let frame = FrameLoop::new(move |_timestamp| {
+            let Some(fallback) = frame_fallback.upgrade() else {
+                return false;
+            };
+            let mut fallback = fallback.borrow_mut();
+            fallback.animator.update();
+            fallback.animator.active_count() > 0
+        });
+        Self {
+            transition,
+            transitions: HashMap::new(),
+            fallback,
+            frame,
+        }
+    }
+
+    /// Animate the elements tagged with `layout_id` with their own `transition`
+    pub fn with_transition(mut self, layout_id: impl Into<String>, transition: Transition) -> Self {
+        self.transitions.insert(layout_id.into(), transition);
+        self
+    }
+
+    /// Transition of the elements tagged with `layout_id`
+    pub fn transition(&self, layout_id: &str) -> &Transition {
+        self.transitions.get(layout_id).unwrap_or(&self.transition)
+    }
+
+    /// Whether the browser implements `document.startViewTransition`
+    pub fn is_supported() -> bool {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .is_some_and(|document| {
+                js_sys::Reflect::get(&document, &JsValue::from_str("startViewTransition"))
+                    .is_ok_and(|start| start.is_function())
+            })
+    }
+
+    /// Stylesheet animating the view transition pseudo-elements
+    ///
+    /// The default transition applies to every name, including the root
+    /// crossfade; named rules are more specific and win for their ids.
+    pub fn css(&self) -> String {
+        let mut layout_ids: Vec<&String> = self.transitions.keys().collect();
+        layout_ids.sort();
+        let mut css = transition_rules("*", &self.transition);
+        for layout_id in layout_ids {
+            css.push_str(&transition_rules(
+                &view_transition_name(layout_id),
+                &self.transitions[layout_id],
+            ));
+        }
+        css
+    }
+
+    /// Run `update` inside a view transition of the tagged elements
+    ///
+    /// The new state is captured once the reactive system has rendered the
+    /// update. Without view transitions, `update` runs right away and the
+    /// tagged elements animate from where their counterparts were.
+    pub fn start(&self, update: impl FnOnce() + 'static) -> Result<(), String> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or("No document")?;
+        if Self::is_supported() {
+            self.start_view_transition(&document, update)
+        } else {
+            self.start_fallback(&document, update);
+            Ok(())
+        }
+    }
+
+    fn start_view_transition(
+        &self,
+        document: &Document,
+        update: impl FnOnce() + 'static,
+    ) -> Result<(), String> {
+        install_css(document, &self.css())?;
+        let old_names = name_tagged_elements(document);
+
+        let callback_document = document.clone();
+        let new_names: Rc<RefCell<Vec<HtmlElement>>> = Rc::default();
+        let callback_names = new_names.clone();
+        let callback = Closure::once_into_js(move || -> JsValue {
+            update();
+            // Effects render the update in a microtask; capture after them
+            js_sys::Promise::new(&mut |resolve, _reject| {
+                let document = callback_document.clone();
+                let names = callback_names.clone();
+                let old_names = old_names.clone();
+                let capture = Closure::once_into_js(move || {
+                    clear_names(&old_names);
+                    *names.borrow_mut() = name_tagged_elements(&document);
+                    let _ = resolve.call0(&JsValue::UNDEFINED);
+                });
+                if let Some(window) = web_sys::window() {
+                    let _ = window.set_timeout_with_callback(capture.unchecked_ref());
+                }
+            })
+            .into()
+        });
+
+        let start = js_sys::Reflect::get(document, &JsValue::from_str("startViewTransition"))
+            .map_err(|_| "No startViewTransition")?
+            .dyn_into::<js_sys::Function>()
+            .map_err(|_| "startViewTransition is not a function")?;
+        let transition = start
+            .call1(document, &callback)
+            .map_err(|_| "startViewTransition failed")?;
+
+        // Names left on the elements would join later, unrelated transitions
+        let finished = js_sys::Reflect::get(&transition, &JsValue::from_str("finished"))
+            .map_err(|_| "No finished promise")?;
+        let finally = js_sys::Reflect::get(&finished, &JsValue::from_str("finally"))
+            .ok()
+            .and_then(|finally| finally.dyn_into::<js_sys::Function>().ok());
+        if let Some(finally) = finally {
+            let clear = Closure::once_into_js(move || clear_names(&new_names.borrow()));
+            let _ = finally.call1(&finished, &clear);
+        }
+        Ok(())
+    }
+
+    fn start_fallback(&self, document: &Document, update: impl FnOnce() + 'static) {
+        let now = js_sys::Date::now();
+        {
+            let mut fallback = self.fallback.borrow_mut();
+            for (element, layout_id) in tagged_elements(document) {
+                let layout = element_layout(&element);
+                fallback
+                    .manager
+                    .snapshot_layout_id(&layout_id, &element, layout, now);
+            }
+        }
+        update();
+
+        let engine = self.clone();
+        let document = document.clone();
+        let animate = Closure::once_into_js(move || engine.animate_fallback(&document));
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback(animate.unchecked_ref());
+        }
+    }
+
+    /// Animate the tagged elements of the updated page from their snapshots
+    fn animate_fallback(&self, document: &Document) {
+        let now = js_sys::Date::now();
+        let mut seen = HashSet::new();
+        {
+            let mut fallback = self.fallback.borrow_mut();
+            for (element, layout_id) in tagged_elements(document) {
+                if !seen.insert(layout_id.clone()) {
+                    continue;
+                }
+                let Some(enter) = fallback.manager.enter_layout_id(&layout_id, now) else {
+                    continue;
+                };
+                let from = enter.from;
+                let Ok(first) = DomRect::new_with_x_and_y_and_width_and_height(
+                    from.x,
+                    from.y,
+                    from.width,
+                    from.height,
+                ) else {
+                    continue;
+                };
+                let last = element.get_bounding_client_rect();
+                let config = LayoutAnimationConfig::from(self.transition(&layout_id));
+                let _ = fallback.animator.animate(
+                    format!("view-transition-{layout_id}"),
+                    element,
+                    first,
+                    last,
+                    config,
+                );
+            }
+        }
+        self.frame.request();
+    }
+}
+
+impl Default for ViewTransitionEngine {
+    fn default() -> Self {
+        Self::new(Transition::default())
+    }
+}
+
+impl std::fmt::Debug for ViewTransitionEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ViewTransitionEngine")
+            .field("transition", &self.transition)
+            .field("transitions", &self.transitions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Elements of `document` tagged with [`LAYOUT_ID_ATTRIBUTE`] and their ids
+fn tagged_elements(document: &Document) -> Vec<(Element, String)> {
+    let Ok(nodes) = document.query_selector_all(&format!("[{LAYOUT_ID_ATTRIBUTE}]")) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index)?.dyn_into::<Element>().ok())
+        .filter_map(|element| {
+            let layout_id = element.get_attribute(LAYOUT_ID_ATTRIBUTE)?;
+            Some((element, layout_id))
+        })
+        .collect()
+}
+
+/// Give the first element tagged with each id its `view-transition-name`
+///
+/// Names must be unique while the browser captures the page, so later
+/// elements with the same id are left out.
+fn name_tagged_elements(document: &Document) -> Vec<HtmlElement> {
+    let mut seen = HashSet::new();
+    tagged_elements(document)
+        .into_iter()
+        .filter(|(_, layout_id)| seen.insert(layout_id.clone()))
+        .filter_map(|(element, layout_id)| {
+            let element = element.dyn_into::<HtmlElement>().ok()?;
+            element
+                .style()
+                .set_property("view-transition-name", &view_transition_name(&layout_id))
+                .ok()?;
+            Some(element)
+        })
+        .collect()
+}
+
+fn clear_names(elements: &[HtmlElement]) {
+    for element in elements {
+        let _ = element.style().remove_property("view-transition-name");
+    }
+}
+
+/// Put `css` in the engine's style element, creating it on first use
+fn install_css(document: &Document, css: &str) -> Result<(), String> {
+    let style = match document
+        .query_selector(&format!("style[{STYLE_ATTRIBUTE}]"))
+        .ok()
+        .flatten()
+    {
+        Some(style) => style,
+        None => {
+            let style = document
+                .create_element("style")
+                .map_err(|_| "Failed to create style element")?;
+            let _ = style.set_attribute(STYLE_ATTRIBUTE, "");
+            let head = document.head().ok_or("No document head")?;
+            head.append_child(&style)
+                .map_err(|_| "Failed to add style element")?;
+            style
+        }
+    };
+    style.set_text_content(Some(css));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos_motion_core::Easing;
+    use std::cell::Cell;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Tagged element laid out at `left`, added to the body
+    fn tagged(document: &Document, layout_id: &str, left: &str) -> HtmlElement {
+        let element: HtmlElement = document.create_element("div").unwrap().unchecked_into();
+        element
+            .set_attribute(LAYOUT_ID_ATTRIBUTE, layout_id)
+            .unwrap();
+        let style = element.style();
+        for (property, value) in [
+            ("position", "absolute"),
+            ("top", "0px"),
+            ("left", left),
+            ("width", "100px"),
+            ("height", "100px"),
+        ] {
+            style.set_property(property, value).unwrap();
+        }
+        document.body().unwrap().append_child(&element).unwrap();
+        element
+    }
+
+    /// Replace `document.startViewTransition` for the duration of a test
+    fn set_start_view_transition(document: &Document, start: &JsValue) {
+        js_sys::Reflect::set(document, &JsValue::from_str("startViewTransition"), start).unwrap();
+    }
+
+    #[test]
+    fn test_view_transition_name_escapes_id() {
+        assert_eq!(view_transition_name("hero-1"), "motion-hero-1");
+        assert_eq!(view_transition_name("photo 7"), "motion-photo_20_7");
+        assert_ne!(view_transition_name("a_20_"), view_transition_name("a "));
+    }
+
+    #[test]
+    fn test_view_transition_css_follows_transitions() {
+        let engine = ViewTransitionEngine::new(Transition {
+            duration: Some(0.4),
+            ease: Easing::EaseOut,
+            ..Default::default()
+        })
+        .with_transition(
+            "hero",
+            Transition {
+                duration: Some(0.6),
+                delay: Some(0.1),
+                ease: Easing::Linear,
+                ..Default::default()
+            },
+        );
+
+        let css = engine.css();
+        assert!(css.starts_with("::view-transition-group(*),"));
+        assert!(css.contains("animation-duration: 0.4s;"));
+        assert!(css.contains("animation-timing-function: ease-out;"));
+        assert!(css.contains("::view-transition-new(motion-hero) {"));
+        assert!(css.contains("animation-duration: 0.6s;"));
+        assert!(css.contains("animation-delay: 0.1s;"));
+        assert!(css.contains("animation-timing-function: linear;"));
+        assert_eq!(engine.transition("other").duration, Some(0.4));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_start_runs_update_inside_view_transition() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        // Runs the update right away, like a browser with nothing to capture
+        let start = js_sys::Function::new_with_args(
+            "update",
+            "update(); return { finished: Promise.resolve() };",
+        );
+        set_start_view_transition(&document, &start);
+        let thumbnail = tagged(&document, "hero", "0px");
+        let engine = ViewTransitionEngine::default();
+        assert!(ViewTransitionEngine::is_supported());
+
+        let updated = Rc::new(Cell::new(false));
+        let update_flag = updated.clone();
+        engine.start(move || update_flag.set(true)).unwrap();
+        assert!(updated.get());
+        // Named for the capture of the old state, cleared once the new one renders
+        assert_eq!(
+            thumbnail
+                .style()
+                .get_property_value("view-transition-name")
+                .unwrap(),
+            "motion-hero"
+        );
+        let style = document
+            .query_selector(&format!("style[{STYLE_ATTRIBUTE}]"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(style.text_content(), Some(engine.css()));
+
+        js_sys::Reflect::delete_property(&document, &JsValue::from_str("startViewTransition"))
+            .unwrap();
+        thumbnail.remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_start_falls_back_to_flip_animations() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        set_start_view_transition(&document, &JsValue::UNDEFINED);
+        assert!(!ViewTransitionEngine::is_supported());
+        let thumbnail = tagged(&document, "hero", "0px");
+        let engine = ViewTransitionEngine::default();
+
+        let update_document = document.clone();
+        let update_thumbnail = thumbnail.clone();
+        let card = Rc::new(RefCell::new(None));
+        let update_card = card.clone();
+        engine
+            .start(move || {
+                update_thumbnail.remove();
+                *update_card.borrow_mut() = Some(tagged(&update_document, "hero", "200px"));
+            })
+            .unwrap();
+        let card = card.borrow_mut().take().unwrap();
+
+        // The card plays from where the thumbnail was
+        engine.animate_fallback(&document);
+        assert_eq!(engine.fallback.borrow().animator.active_count(), 1);
+
+        js_sys::Reflect::delete_property(&document, &JsValue::from_str("startViewTransition"))
+            .unwrap();
+        card.remove();
+    }
+}
@@ -0,0 +1,366 @@
+//! Motion components for HTML and SVG elements
+//!
+//! Every generated component accepts the same props, which it forwards to
+//! `leptos_motion_dom::motion_element::MotionElement` before rendering its
+//! element with the resulting style and event handlers.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, Token, braced};
+
+/// Kind of element, deciding its constructor module and whether it has children
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ElementKind {
+    /// HTML element with children
+    Html,
+    /// Self-closing HTML element
+    Void,
+    /// SVG element
+    Svg,
+}
+
+/// Input of `create_motion_elements!`: tags grouped by kind
+///
+/// ```text
+/// html { div, span }
+/// void { img }
+/// svg { svg, path }
+/// ```
+pub struct MotionElements {
+    elements: Vec<(ElementKind, Ident)>,
+}
+
+impl Parse for MotionElements {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut elements = Vec::new();
+        while !input.is_empty() {
+            let group: Ident = input.parse()?;
+            let kind = match group.to_string().as_str() {
+                "html" => ElementKind::Html,
+                "void" => ElementKind::Void,
+                "svg" => ElementKind::Svg,
+                _ => {
+                    return Err(syn::Error::new(
+                        group.span(),
+                        "expected `html`, `void` or `svg`",
+                    ));
+                }
+            };
+            let content;
+            braced!(content in input);
+            let tags =
+                Punctuated::<Ident, Token![,]>::parse_terminated_with(&content, Ident::parse_any)?;
+            elements.extend(tags.into_iter().map(|tag| (kind, tag)));
+        }
+        Ok(Self { elements })
+    }
+}
+
+/// Type name of an element, following the `leptos::html`/`leptos::svg` naming
+fn camel_case(tag: &str) -> String {
+    let mut name = String::new();
+    let mut previous = '_';
+    for ch in tag.chars() {
+        if ch != '_' {
+            if previous == '_' {
+                name.extend(ch.to_uppercase());
+            } else if previous.is_uppercase() {
+                name.extend(ch.to_lowercase());
+            } else {
+                name.push(ch);
+            }
+        }
+        previous = ch;
+    }
+    name
+}
+
+/// Expand `create_motion_elements!`
+pub fn expand(input: MotionElements) -> TokenStream {
+    let mut components = Vec::new();
+    let mut aliases = Vec::new();
+    for (kind, tag) in &input.elements {
+        let tag_name = tag.unraw().to_string();
+        let element_name = format_ident!("{}", camel_case(&tag_name));
+        let component = format_ident!("Motion{}", element_name);
+        components.push(motion_component(
+            *kind,
+            tag,
+            &tag_name,
+            &element_name,
+            &component,
+        ));
+        aliases.push(quote! { #component as #element_name });
+    }
+
+    quote! {
+        #(#components)*
+
+        /// Motion components named after their element, e.g. `motion::Div`
+        pub mod motion {
+            pub use super::{#(#aliases),*};
+        }
+    }
+}
+
+fn motion_component(
+    kind: ElementKind,
+    tag: &Ident,
+    tag_name: &str,
+    element_name: &Ident,
+    component: &Ident,
+) -> TokenStream {
+    let (module, element_doc) = match kind {
+        ElementKind::Html | ElementKind::Void => (quote!(::leptos::html), "HTML"),
+        ElementKind::Svg => (quote!(::leptos::svg), "SVG"),
+    };
+    let doc = format!("Motion component for animated `<{tag_name}>` {element_doc} elements");
+    let (children_prop, children) = match kind {
+        ElementKind::Void => (quote!(), quote!()),
+        ElementKind::Html | ElementKind::Svg => (
+            quote! {
+                /// Children elements
+                children: ::leptos::children::Children,
+            },
+            quote!(.child(children())),
+        ),
+    };
+
+    quote! {
+        #[doc = #doc]
+        #[::leptos::component]
+        pub fn #component(
+            /// CSS class name
+            #[prop(optional)]
+            class: Option<::std::string::String>,
+            /// CSS styles
+            #[prop(optional)]
+            style: Option<::std::string::String>,
+            /// Node reference for animation engine integration
+            #[prop(optional)]
+            node_ref: Option<::leptos::prelude::NodeRef<#module::#element_name>>,
+            /// Key for React-like re-rendering
+            #[prop(optional)]
+            key: Option<::std::string::String>,
+            /// Initial animation state
+            #[prop(optional)]
+            initial: Option<::leptos_motion_dom::AnimationTarget>,
+            /// Target animation state
+            #[prop(optional)]
+            animate: Option<::leptos_motion_dom::AnimationTarget>,
+            /// State animated to before unmounting inside an `AnimatePresence`
+            #[prop(optional)]
+            exit: Option<::leptos_motion_dom::AnimationTarget>,
+            /// Transition configuration
+            #[prop(optional)]
+            transition: Option<::leptos_motion_dom::Transition>,
+            /// Named animation states, selected by variant labels
+            #[prop(optional)]
+            variants: Option<::leptos_motion_dom::variants::Variants>,
+            /// Label of the initial variant, inherited from the parent if unset
+            #[prop(optional, into)]
+            initial_variant: Option<::std::string::String>,
+            /// Label of the animated variant, inherited from the parent if unset
+            #[prop(optional, into)]
+            animate_variant: Option<::leptos::prelude::Signal<::std::string::String>>,
+            /// Label of the variant animated to before unmounting, used without `exit`
+            #[prop(optional, into)]
+            exit_variant: Option<::std::string::String>,
+            /// Data passed to dynamic variants
+            #[prop(optional)]
+            custom: Option<::leptos_motion_dom::variants::Custom>,
+            /// How variant animations of children are delayed and staggered
+            #[prop(optional)]
+            orchestration: Option<::leptos_motion_dom::variants::Orchestration>,
+            /// Inherit variant labels from the parent (defaults to true)
+            #[prop(optional)]
+            inherit: Option<bool>,
+            /// State animated to while hovered, as a target or variant label
+            #[prop(optional, into)]
+            while_hover: Option<::leptos_motion_dom::GestureTarget>,
+            /// State animated to while pressed by a pointer or Enter/Space
+            #[prop(optional, into)]
+            while_tap: Option<::leptos_motion_dom::GestureTarget>,
+            /// State animated to while focused
+            #[prop(optional, into)]
+            while_focus: Option<::leptos_motion_dom::GestureTarget>,
+            /// State animated to while dragged
+            #[prop(optional, into)]
+            while_drag: Option<::leptos_motion_dom::GestureTarget>,
+            /// State animated to while inside the viewport
+            #[prop(optional, into)]
+            while_in_view: Option<::leptos_motion_dom::GestureTarget>,
+            /// Fraction of the element that must be visible to be in view (defaults to 0)
+            #[prop(optional)]
+            viewport_amount: Option<f64>,
+            /// Keep the in-view state once the element first enters the viewport
+            #[prop(optional)]
+            viewport_once: Option<bool>,
+            /// Called when a pointer starts hovering the element
+            #[prop(optional, into)]
+            on_hover_start: Option<::leptos::prelude::Callback<()>>,
+            /// Called when a pointer stops hovering the element
+            #[prop(optional, into)]
+            on_hover_end: Option<::leptos::prelude::Callback<()>>,
+            /// Called when a press is released over the element
+            #[prop(optional, into)]
+            on_tap: Option<::leptos::prelude::Callback<()>>,
+            /// Called when a press ends without a tap
+            #[prop(optional, into)]
+            on_tap_cancel: Option<::leptos::prelude::Callback<()>>,
+            /// Called when the element gains focus
+            #[prop(optional, into)]
+            on_focus: Option<::leptos::prelude::Callback<()>>,
+            /// Called when the element loses focus
+            #[prop(optional, into)]
+            on_blur: Option<::leptos::prelude::Callback<()>>,
+            /// Extra recognizers and relationships arbitrated with taps and drags
+            #[prop(optional, into)]
+            gestures: Option<::leptos_motion_dom::GestureSetup>,
+            /// Animate from the previous box whenever a render moves or resizes the element
+            #[prop(optional)]
+            layout: Option<bool>,
+            /// Restrict `layout` animations to the position or the size
+            #[prop(optional)]
+            layout_mode: Option<::leptos_motion_dom::LayoutMode>,
+            /// Animate between elements with the same id as they mount and unmount
+            #[prop(optional, into)]
+            layout_id: Option<::std::string::String>,
+            /// Drag configuration
+            #[prop(optional)]
+            drag: Option<::leptos_motion_dom::DragConfig>,
+            /// Bounds the element can be dragged within, overriding those of `drag`
+            #[prop(optional)]
+            drag_constraints: Option<::leptos_motion_dom::DragConstraints>,
+            /// Element whose bounds constrain the drag, measured at each drag start
+            #[prop(optional, into)]
+            drag_constraints_ref: Option<::leptos_motion_dom::DragConstraintsRef>,
+            /// Return to the origin when the drag ends
+            #[prop(optional)]
+            drag_snap_to_origin: Option<bool>,
+            /// Lock dragging to the first axis the pointer moves along
+            #[prop(optional)]
+            drag_direction_lock: Option<bool>,
+            /// Let drags start inside this element also drag draggable ancestors
+            #[prop(optional)]
+            drag_propagation: Option<bool>,
+            /// Spring used to settle the element when the drag ends
+            #[prop(optional)]
+            drag_transition: Option<::leptos_motion_dom::SpringConfig>,
+            /// Controls that start this element's drag from other elements
+            #[prop(optional)]
+            drag_controls: Option<::leptos_motion_dom::DragControls>,
+            /// Start dragging on this element's own pointerdown (defaults to true)
+            #[prop(optional)]
+            drag_listener: Option<bool>,
+            #children_prop
+        ) -> impl ::leptos::IntoView {
+            use ::leptos::prelude::{
+                ClassAttribute, ElementChild, GlobalAttributes, NodeRefAttribute, OnAttribute,
+                StyleAttribute,
+            };
+
+            let node_ref = node_ref.unwrap_or_default();
+            let motion = ::leptos_motion_dom::motion_element::MotionElement::new(
+                node_ref,
+                ::leptos_motion_dom::motion_element::MotionOptions {
+                    style,
+                    key,
+                    initial,
+                    animate,
+                    exit,
+                    transition,
+                    variants,
+                    initial_variant,
+                    animate_variant,
+                    exit_variant,
+                    custom,
+                    orchestration,
+                    inherit,
+                    while_hover,
+                    while_tap,
+                    while_focus,
+                    while_drag,
+                    while_in_view,
+                    viewport_amount,
+                    viewport_once,
+                    on_hover_start,
+                    on_hover_end,
+                    on_tap,
+                    on_tap_cancel,
+                    on_focus,
+                    on_blur,
+                    gestures,
+                    layout,
+                    layout_mode,
+                    layout_id,
+                    drag,
+                    drag_constraints,
+                    drag_constraints_ref,
+                    drag_snap_to_origin,
+                    drag_direction_lock,
+                    drag_propagation,
+                    drag_transition,
+                    drag_controls,
+                    drag_listener,
+                },
+            );
+
+            #module::#tag()
+                .node_ref(node_ref)
+                .class(class)
+                .style(motion.style)
+                .tabindex(motion.tab_index)
+                .on(::leptos::ev::pointerdown, motion.on_pointer_down)
+                .on(::leptos::ev::pointermove, motion.on_pointer_move)
+                .on(::leptos::ev::pointerup, motion.on_pointer_up)
+                .on(::leptos::ev::pointercancel, motion.on_pointer_cancel)
+                .on(::leptos::ev::pointerenter, motion.on_pointer_enter)
+                .on(::leptos::ev::pointerleave, motion.on_pointer_leave)
+                .on(::leptos::ev::keydown, motion.on_key_down)
+                .on(::leptos::ev::keyup, motion.on_key_up)
+                .on(::leptos::ev::focus, motion.on_focus)
+                .on(::leptos::ev::blur, motion.on_blur)
+                #children
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camel_case_matches_leptos_element_types() {
+        assert_eq!(camel_case("div"), "Div");
+        assert_eq!(camel_case("h1"), "H1");
+        assert_eq!(camel_case("clipPath"), "ClipPath");
+        assert_eq!(camel_case("feGaussianBlur"), "FeGaussianBlur");
+        assert_eq!(camel_case("use"), "Use");
+    }
+
+    #[test]
+    fn test_parse_groups() {
+        let elements: MotionElements =
+            syn::parse_str("html { div, span } void { img } svg { path, r#use, }").unwrap();
+        let parsed: Vec<(ElementKind, String)> = elements
+            .elements
+            .iter()
+            .map(|(kind, tag)| (*kind, tag.unraw().to_string()))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (ElementKind::Html, "div".to_string()),
+                (ElementKind::Html, "span".to_string()),
+                (ElementKind::Void, "img".to_string()),
+                (ElementKind::Svg, "path".to_string()),
+                (ElementKind::Svg, "use".to_string()),
+            ]
+        );
+        assert!(syn::parse_str::<MotionElements>("math { mi }").is_err());
+    }
+}
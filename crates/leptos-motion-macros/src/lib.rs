@@ -4,6 +4,8 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{DeriveInput, parse_macro_input};
 
+mod elements;
+
 /// Derive macro for motion components (placeholder)
 #[proc_macro_derive(MotionComponent)]
 pub fn derive_motion_component(input: TokenStream) -> TokenStream {
@@ -21,10 +23,27 @@ pub fn derive_motion_component(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-/// Macro for creating motion elements (placeholder)
+/// Macro for creating motion components for HTML and SVG elements
+///
+/// Takes the tags to generate components for, grouped by kind, and emits a
+/// `Motion<Tag>` component for each (e.g. `MotionDiv`, `MotionClipPath`)
+/// plus a `motion` module re-exporting them by element name (`motion::Div`).
+///
+/// ```ignore
+/// create_motion_elements! {
+///     html { div, span, button }
+///     void { img, input }
+///     svg { svg, path, r#use }
+/// }
+/// ```
+///
+/// The components forward their props to
+/// `leptos_motion_dom::motion_element::MotionElement`, so the invoking crate
+/// must be able to refer to `leptos` and `leptos_motion_dom`.
 #[proc_macro]
-pub fn create_motion_elements(_input: TokenStream) -> TokenStream {
-    TokenStream::new()
+pub fn create_motion_elements(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as elements::MotionElements);
+    TokenStream::from(elements::expand(input))
 }
 
 /// Macro for creating animation targets
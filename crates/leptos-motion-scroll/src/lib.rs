@@ -4,6 +4,14 @@
 
 use std::collections::HashMap;
 
+pub mod velocity;
+
+pub use velocity::{
+    ScrollVelocityConfig, ScrollVelocityTracker, VelocityDirection, VelocityMapping,
+    VelocitySpring, use_scroll_velocity, use_scroll_velocity_transform,
+    use_scroll_velocity_with_config,
+};
+
 // Include the scroll integration tests
 #[cfg(test)]
 mod scroll_integration_tests;
@@ -73,7 +81,8 @@ pub struct ScrollAnimationState {
     pub previous_scroll_y: f64,
     /// Scroll direction
     pub direction: ScrollDirection,
-    /// Scroll velocity
+    /// Scroll delta of the last update (not time-normalized, see
+    /// [`ScrollVelocityTracker`] for units/sec)
     pub velocity: f64,
 }
 
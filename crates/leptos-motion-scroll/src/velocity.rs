@@ -0,0 +1,586 @@
+//! Scroll velocity motion values
+//!
+//! Time-normalized, smoothed scroll velocity exposed as a [`MotionNumber`],
+//! plus helpers for mapping velocity into transforms (skew, marquee speed)
+//! with spring smoothing.
+
+use leptos::prelude::{StoredValue, UpdateValue, on_cleanup};
+use leptos_motion_core::math::map_range;
+use leptos_motion_core::{FrameLoop, MotionNumber, SpringConfig};
+use std::sync::Arc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+/// Largest integration step used by [`VelocitySpring`] (seconds)
+const MAX_SPRING_STEP: f64 = 1.0 / 120.0;
+
+/// Configuration for scroll velocity tracking
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrollVelocityConfig {
+    /// Smoothing time constant in milliseconds (0 disables smoothing)
+    pub smoothing_ms: f64,
+    /// Velocity (units/sec) below which the value snaps to zero
+    pub rest_threshold: f64,
+    /// Track horizontal scrolling instead of vertical
+    pub horizontal: bool,
+}
+
+impl Default for ScrollVelocityConfig {
+    fn default() -> Self {
+        Self {
+            smoothing_ms: 50.0,
+            rest_threshold: 1.0,
+            horizontal: false,
+        }
+    }
+}
+
+impl ScrollVelocityConfig {
+    /// Set smoothing time constant
+    pub fn with_smoothing(mut self, smoothing_ms: f64) -> Self {
+        self.smoothing_ms = smoothing_ms.max(0.0);
+        self
+    }
+
+    /// Set rest threshold
+    pub fn with_rest_threshold(mut self, rest_threshold: f64) -> Self {
+        self.rest_threshold = rest_threshold.abs();
+        self
+    }
+
+    /// Track horizontal scrolling
+    pub fn with_horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+}
+
+/// Tracks scroll position samples and produces a smoothed velocity in units/sec
+#[derive(Debug, Clone)]
+pub struct ScrollVelocityTracker {
+    config: ScrollVelocityConfig,
+    last_position: Option<f64>,
+    last_time: Option<f64>,
+    velocity: f64,
+}
+
+impl ScrollVelocityTracker {
+    /// Create a new tracker
+    pub fn new(config: ScrollVelocityConfig) -> Self {
+        Self {
+            config,
+            last_position: None,
+            last_time: None,
+            velocity: 0.0,
+        }
+    }
+
+    /// Record a scroll position at `timestamp_ms` and return the smoothed velocity
+    ///
+    /// Sampling the same position again (e.g. on an animation frame with no
+    /// scroll event) decays the velocity towards zero.
+    pub fn sample(&mut self, position: f64, timestamp_ms: f64) -> f64 {
+        let (Some(last_position), Some(last_time)) = (self.last_position, self.last_time) else {
+            self.last_position = Some(position);
+            self.last_time = Some(timestamp_ms);
+            return self.velocity;
+        };
+
+        let dt_ms = timestamp_ms - last_time;
+        if dt_ms <= 0.0 {
+            // Several events in the same frame: keep the latest position
+            self.last_position = Some(position);
+            return self.velocity;
+        }
+
+        let instantaneous = (position - last_position) / (dt_ms / 1000.0);
+        let alpha = if self.config.smoothing_ms > 0.0 {
+            1.0 - (-dt_ms / self.config.smoothing_ms).exp()
+        } else {
+            1.0
+        };
+        self.velocity += (instantaneous - self.velocity) * alpha;
+        if self.velocity.abs() < self.config.rest_threshold {
+            self.velocity = 0.0;
+        }
+
+        self.last_position = Some(position);
+        self.last_time = Some(timestamp_ms);
+        self.velocity
+    }
+
+    /// Current smoothed velocity (units/sec)
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    /// Check if the velocity has settled to zero
+    pub fn is_at_rest(&self) -> bool {
+        self.velocity == 0.0
+    }
+
+    /// Reset all tracked samples
+    pub fn reset(&mut self) {
+        self.last_position = None;
+        self.last_time = None;
+        self.velocity = 0.0;
+    }
+}
+
+impl Default for ScrollVelocityTracker {
+    fn default() -> Self {
+        Self::new(ScrollVelocityConfig::default())
+    }
+}
+
+/// Spring that follows a moving target
+///
+/// Unlike `SpringSimulator`, which solves a fixed `from -> to` animation,
+/// this integrates step by step so the target can change every frame while
+/// velocity carries over.
+#[derive(Debug, Clone)]
+pub struct VelocitySpring {
+    config: SpringConfig,
+    position: f64,
+    velocity: f64,
+}
+
+impl VelocitySpring {
+    /// Create a spring resting at `initial`
+    pub fn new(config: SpringConfig, initial: f64) -> Self {
+        let velocity = config.velocity;
+        Self {
+            config,
+            position: initial,
+            velocity,
+        }
+    }
+
+    /// Advance the spring towards `target` by `dt` seconds and return the new position
+    pub fn step(&mut self, target: f64, dt: f64) -> f64 {
+        let mut remaining = dt.max(0.0);
+        while remaining > 0.0 {
+            let h = remaining.min(MAX_SPRING_STEP);
+            let spring_force = -self.config.stiffness * (self.position - target);
+            let damping_force = -self.config.damping * self.velocity;
+            let acceleration = (spring_force + damping_force) / self.config.mass;
+            self.velocity += acceleration * h;
+            self.position += self.velocity * h;
+            remaining -= h;
+        }
+
+        if (self.position - target).abs() < self.config.rest_delta
+            && self.velocity.abs() < self.config.rest_speed
+        {
+            self.position = target;
+            self.velocity = 0.0;
+        }
+        self.position
+    }
+
+    /// Current position
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    /// Current velocity
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    /// Check if the spring has settled on `target`
+    pub fn is_settled(&self, target: f64) -> bool {
+        self.position == target && self.velocity == 0.0
+    }
+}
+
+/// How a [`VelocityMapping`] treats the direction of the velocity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VelocityDirection {
+    /// Map the velocity as it is, negative when scrolling backwards
+    #[default]
+    Signed,
+    /// Map the speed and give the output the sign of the velocity
+    Symmetric,
+    /// Map the speed, so both directions give the same output
+    Magnitude,
+}
+
+/// Maps a scroll velocity onto an output value
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelocityMapping {
+    /// Input velocity range (units/sec)
+    pub input_range: (f64, f64),
+    /// Output value range
+    pub output_range: (f64, f64),
+    /// Clamp output to `output_range`
+    pub clamp: bool,
+    /// How the direction of the velocity is mapped
+    pub direction: VelocityDirection,
+    /// Spring used to smooth the mapped value (`None` for no smoothing)
+    pub spring: Option<SpringConfig>,
+}
+
+impl VelocityMapping {
+    /// Create a new linear mapping
+    pub fn new(input_range: (f64, f64), output_range: (f64, f64)) -> Self {
+        Self {
+            input_range,
+            output_range,
+            clamp: true,
+            direction: VelocityDirection::Signed,
+            spring: None,
+        }
+    }
+
+    /// Skew in degrees, reaching `max_skew` at `max_velocity` in either direction
+    pub fn skew(max_velocity: f64, max_skew: f64) -> Self {
+        Self::new((-max_velocity, max_velocity), (-max_skew, max_skew)).with_spring(SpringConfig {
+            stiffness: 400.0,
+            damping: 50.0,
+            ..SpringConfig::default()
+        })
+    }
+
+    /// Marquee speed multiplier: `1.0` at rest, `1.0 + boost` at `max_velocity`
+    ///
+    /// Scrolling up produces a negative multiplier so the marquee reverses.
+    pub fn marquee_speed(max_velocity: f64, boost: f64) -> Self {
+        Self::new((0.0, max_velocity), (1.0, 1.0 + boost))
+            .with_direction(VelocityDirection::Symmetric)
+            .with_spring(SpringConfig {
+                stiffness: 300.0,
+                damping: 40.0,
+                ..SpringConfig::default()
+            })
+    }
+
+    /// Blur radius in pixels, reaching `max_blur` at `max_velocity` in either direction
+    pub fn blur(max_velocity: f64, max_blur: f64) -> Self {
+        Self::new((0.0, max_velocity), (0.0, max_blur))
+            .with_direction(VelocityDirection::Magnitude)
+            .with_spring(SpringConfig {
+                stiffness: 300.0,
+                damping: 40.0,
+                ..SpringConfig::default()
+            })
+    }
+
+    /// Set clamping
+    pub fn with_clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Set how the direction of the velocity is mapped
+    ///
+    /// [`VelocityDirection::Symmetric`] and [`VelocityDirection::Magnitude`]
+    /// map the speed over `input_range`; the former negates the output when
+    /// scrolling backwards.
+    pub fn with_direction(mut self, direction: VelocityDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set spring smoothing
+    pub fn with_spring(mut self, spring: SpringConfig) -> Self {
+        self.spring = Some(spring);
+        self
+    }
+
+    /// Disable spring smoothing
+    pub fn without_spring(mut self) -> Self {
+        self.spring = None;
+        self
+    }
+
+    /// Map a velocity onto the output range
+    pub fn map(&self, velocity: f64) -> f64 {
+        let (in_min, in_max) = self.input_range;
+        let (out_min, out_max) = self.output_range;
+
+        let (input, sign) = match self.direction {
+            VelocityDirection::Signed => (velocity, 1.0),
+            VelocityDirection::Symmetric => {
+                (velocity.abs(), if velocity < 0.0 { -1.0 } else { 1.0 })
+            }
+            VelocityDirection::Magnitude => (velocity.abs(), 1.0),
+        };
+
+        let mut output = map_range(input, in_min, in_max, out_min, out_max);
+        if self.clamp {
+            output = output.clamp(out_min.min(out_max), out_min.max(out_max));
+        }
+        output * sign
+    }
+}
+
+/// Hook returning the window's scroll velocity as a motion value (units/sec)
+pub fn use_scroll_velocity() -> Arc<MotionNumber> {
+    use_scroll_velocity_with_config(ScrollVelocityConfig::default())
+}
+
+/// Hook returning the window's scroll velocity with custom tracking options
+///
+/// The value is updated once per animation frame while the page scrolls and
+/// keeps updating until the smoothed velocity settles back to zero.
+pub fn use_scroll_velocity_with_config(config: ScrollVelocityConfig) -> Arc<MotionNumber> {
+    let velocity = Arc::new(MotionNumber::zero());
+
+    let target = velocity.clone();
+    drive_scroll_velocity(config, move |value, _timestamp| {
+        target.set(value);
+        value != 0.0
+    });
+
+    velocity
+}
+
+/// Hook mapping the window's scroll velocity onto a spring-smoothed motion value
+///
+/// ```ignore
+/// let skew = use_scroll_velocity_transform(
+///     ScrollVelocityConfig::default(),
+///     VelocityMapping::skew(2000.0, 10.0),
+/// );
+/// // Blurs while scrolling either way
+/// let blur = use_scroll_velocity_transform(
+///     ScrollVelocityConfig::default(),
+///     VelocityMapping::blur(2000.0, 8.0),
+/// );
+/// ```
+pub fn use_scroll_velocity_transform(
+    config: ScrollVelocityConfig,
+    mapping: VelocityMapping,
+) -> Arc<MotionNumber> {
+    let output = Arc::new(MotionNumber::new(mapping.map(0.0)));
+
+    let mut spring = mapping
+        .spring
+        .clone()
+        .map(|spring_config| VelocitySpring::new(spring_config, output.get()));
+    let mut last_timestamp = None::<f64>;
+
+    let target = output.clone();
+    drive_scroll_velocity(config, move |velocity, timestamp| {
+        let mapped = mapping.map(velocity);
+        let Some(spring) = spring.as_mut() else {
+            target.set(mapped);
+            return velocity != 0.0;
+        };
+
+        let dt = last_timestamp.map_or(0.0, |last| (timestamp - last) / 1000.0);
+        target.set(spring.step(mapped, dt));
+
+        let settled = velocity == 0.0 && spring.is_settled(mapped);
+        last_timestamp = if settled { None } else { Some(timestamp) };
+        !settled
+    });
+
+    output
+}
+
+/// Scroll listener and frame loop driving a velocity hook
+struct ScrollVelocityListener {
+    window: web_sys::Window,
+    scroll_callback: Closure<dyn FnMut()>,
+    frame: FrameLoop,
+}
+
+impl ScrollVelocityListener {
+    /// Remove the scroll listener and cancel the pending frame
+    fn stop(self) {
+        let _ = self.window.remove_event_listener_with_callback(
+            "scroll",
+            self.scroll_callback.as_ref().unchecked_ref(),
+        );
+        self.frame.cancel();
+    }
+}
+
+/// Run `on_frame(velocity, timestamp)` once per animation frame while the
+/// window scrolls, until it returns `false`
+///
+/// Stops listening when the owning component is cleaned up.
+fn drive_scroll_velocity(
+    config: ScrollVelocityConfig,
+    mut on_frame: impl FnMut(f64, f64) -> bool + 'static,
+) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let horizontal = config.horizontal;
+    let read_position = move |window: &web_sys::Window| {
+        if horizontal {
+            window.scroll_x().unwrap_or(0.0)
+        } else {
+            window.scroll_y().unwrap_or(0.0)
+        }
+    };
+
+    let mut tracker = ScrollVelocityTracker::new(config);
+    // Seed the tracker so the first scroll event has a baseline
+    tracker.sample(read_position(&window), now(&window));
+
+    let frame = {
+        let window = window.clone();
+        FrameLoop::new(move |timestamp| {
+            let velocity = tracker.sample(read_position(&window), timestamp);
+            on_frame(velocity, timestamp)
+        })
+    };
+
+    let scroll_callback = {
+        let frame = frame.clone();
+        Closure::wrap(Box::new(move || {
+            frame.request();
+        }) as Box<dyn FnMut()>)
+    };
+
+    let _ =
+        window.add_event_listener_with_callback("scroll", scroll_callback.as_ref().unchecked_ref());
+
+    let listener = StoredValue::new_local(Some(ScrollVelocityListener {
+        window,
+        scroll_callback,
+        frame,
+    }));
+    on_cleanup(move || {
+        listener.try_update_value(|listener| {
+            if let Some(listener) = listener.take() {
+                listener.stop();
+            }
+        });
+    });
+}
+
+fn now(window: &web_sys::Window) -> f64 {
+    window
+        .performance()
+        .map(|performance| performance.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_first_sample_has_no_velocity() {
+        let mut tracker = ScrollVelocityTracker::default();
+        assert_eq!(tracker.sample(100.0, 0.0), 0.0);
+        assert!(tracker.is_at_rest());
+    }
+
+    #[test]
+    fn test_tracker_is_time_normalized() {
+        let config = ScrollVelocityConfig::default().with_smoothing(0.0);
+        let mut tracker = ScrollVelocityTracker::new(config);
+        tracker.sample(0.0, 0.0);
+
+        // 100px over 100ms is 1000px/s
+        let velocity = tracker.sample(100.0, 100.0);
+        assert!((velocity - 1000.0).abs() < 1e-9);
+
+        // Same distance over half the time doubles the velocity
+        let velocity = tracker.sample(200.0, 150.0);
+        assert!((velocity - 2000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tracker_smoothing_lags_and_converges() {
+        let mut tracker = ScrollVelocityTracker::new(ScrollVelocityConfig::default());
+        tracker.sample(0.0, 0.0);
+
+        let first = tracker.sample(16.0, 16.0);
+        assert!(first > 0.0 && first < 1000.0);
+
+        let mut position = 16.0;
+        let mut time = 16.0;
+        for _ in 0..60 {
+            position += 16.0;
+            time += 16.0;
+            tracker.sample(position, time);
+        }
+        assert!((tracker.velocity() - 1000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_tracker_decays_to_rest_when_idle() {
+        let mut tracker = ScrollVelocityTracker::new(ScrollVelocityConfig::default());
+        tracker.sample(0.0, 0.0);
+        tracker.sample(50.0, 16.0);
+        assert!(!tracker.is_at_rest());
+
+        let mut time = 16.0;
+        for _ in 0..120 {
+            time += 16.0;
+            tracker.sample(50.0, time);
+        }
+        assert!(tracker.is_at_rest());
+    }
+
+    #[test]
+    fn test_tracker_ignores_zero_time_delta() {
+        let config = ScrollVelocityConfig::default().with_smoothing(0.0);
+        let mut tracker = ScrollVelocityTracker::new(config);
+        tracker.sample(0.0, 0.0);
+        tracker.sample(10.0, 10.0);
+        let velocity = tracker.velocity();
+
+        assert_eq!(tracker.sample(20.0, 10.0), velocity);
+        assert!(tracker.velocity().is_finite());
+    }
+
+    #[test]
+    fn test_velocity_spring_follows_moving_target() {
+        let mut spring = VelocitySpring::new(SpringConfig::default(), 0.0);
+        spring.step(10.0, 0.1);
+        assert!(spring.position() > 0.0);
+        assert!(spring.velocity() > 0.0);
+
+        for _ in 0..600 {
+            spring.step(10.0, 1.0 / 60.0);
+        }
+        assert!(spring.is_settled(10.0));
+    }
+
+    #[test]
+    fn test_skew_mapping_is_symmetric_and_clamped() {
+        let mapping = VelocityMapping::skew(1000.0, 10.0);
+        assert_eq!(mapping.map(0.0), 0.0);
+        assert_eq!(mapping.map(500.0), 5.0);
+        assert_eq!(mapping.map(-500.0), -5.0);
+        assert_eq!(mapping.map(5000.0), 10.0);
+        assert_eq!(mapping.map(-5000.0), -10.0);
+    }
+
+    #[test]
+    fn test_marquee_mapping_reverses_with_direction() {
+        let mapping = VelocityMapping::marquee_speed(1000.0, 4.0);
+        assert_eq!(mapping.map(0.0), 1.0);
+        assert_eq!(mapping.map(1000.0), 5.0);
+        assert_eq!(mapping.map(-1000.0), -5.0);
+        assert_eq!(mapping.with_clamp(false).map(2000.0), 9.0);
+    }
+
+    #[test]
+    fn test_mapping_from_zero_is_signed_by_default() {
+        let mapping = VelocityMapping::new((0.0, 1000.0), (0.0, 10.0));
+        assert_eq!(mapping.map(500.0), 5.0);
+        assert_eq!(mapping.map(-500.0), 0.0);
+
+        let symmetric = mapping.with_direction(VelocityDirection::Symmetric);
+        assert_eq!(symmetric.map(-500.0), -5.0);
+    }
+
+    #[test]
+    fn test_blur_mapping_uses_speed_in_both_directions() {
+        let blur = VelocityMapping::blur(1000.0, 10.0);
+        assert_eq!(blur.map(0.0), 0.0);
+        assert_eq!(blur.map(500.0), 5.0);
+        assert_eq!(blur.map(-500.0), 5.0);
+        assert_eq!(blur.map(-5000.0), 10.0);
+    }
+}
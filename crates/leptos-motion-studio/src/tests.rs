@@ -266,11 +266,7 @@ fn test_path_morphing_basic() {
     let morpher = PathMorpher::new(path1, path2);
     assert!(morpher.is_ok());
 
-    let morpher = morpher.unwrap();
-    let interpolated = morpher.interpolate(0.5);
-    assert!(interpolated.is_ok());
-
-    let result = interpolated.unwrap();
+    let result = morpher.unwrap().interpolate(0.5);
     assert!(!result.data.is_empty());
 }
 
@@ -295,9 +291,8 @@ fn test_path_morphing_edge_cases() {
     assert!(morpher.is_ok());
 
     // Should handle interpolation gracefully
-    let morpher = morpher.unwrap();
-    let result = morpher.interpolate(0.5);
-    assert!(result.is_ok());
+    let result = morpher.unwrap().interpolate(0.5);
+    assert!(result.data.starts_with("M0 0 C"));
 }
 
 // ============================================================================
@@ -474,34 +469,34 @@ fn test_perspective_creation() {
 #[rstest]
 #[wasm_bindgen_test]
 fn test_easing_functions() {
-    use crate::transforms::EasingFunction;
+    use leptos_motion_core::Easing;
 
     // Test all easing functions
     let easing_functions = [
-        EasingFunction::Linear,
-        EasingFunction::EaseIn,
-        EasingFunction::EaseOut,
-        EasingFunction::EaseInOut,
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
     ];
 
     for easing in easing_functions {
         // Test at t=0
-        let result_0 = easing.apply(0.0);
+        let result_0 = easing.evaluate(0.0);
         assert_eq!(result_0, 0.0);
 
         // Test at t=1
-        let result_1 = easing.apply(1.0);
+        let result_1 = easing.evaluate(1.0);
         assert_eq!(result_1, 1.0);
 
         // Test at t=0.5 (should be between 0 and 1)
-        let result_05 = easing.apply(0.5);
+        let result_05 = easing.evaluate(0.5);
         assert!(result_05 >= 0.0 && result_05 <= 1.0);
 
         // Test clamping
-        let result_negative = easing.apply(-0.5);
+        let result_negative = easing.evaluate(-0.5);
         assert_eq!(result_negative, 0.0);
 
-        let result_positive = easing.apply(1.5);
+        let result_positive = easing.evaluate(1.5);
         assert_eq!(result_positive, 1.0);
     }
 }
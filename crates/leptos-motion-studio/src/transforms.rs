@@ -6,6 +6,7 @@ use leptos::attr::global::ClassAttribute;
 use leptos::html::ElementChild;
 use leptos::prelude::*;
 use leptos::prelude::{NodeRefAttribute, OnAttribute, StyleAttribute};
+use leptos_motion_core::{Easing, Transition};
 use serde::{Deserialize, Serialize};
 
 /// 3D transform component with translation, rotation, and scale
@@ -245,7 +246,7 @@ pub struct Transform3DAnimation {
     /// Animation duration in seconds
     pub duration: f32,
     /// Easing function
-    pub easing: EasingFunction,
+    pub easing: Easing,
     /// Current progress (0.0 to 1.0)
     pub progress: f32,
     /// Is animation playing
@@ -260,12 +261,22 @@ impl Transform3DAnimation {
             from,
             to,
             duration,
-            easing: EasingFunction::EaseInOut,
+            easing: Easing::EaseInOut,
             progress: 0.0,
             is_playing: false,
         }
     }
 
+    /// Follow a motion transition, so the transform moves like elements
+    /// animated with the same transition
+    pub fn with_transition(mut self, transition: &Transition) -> Self {
+        if let Some(duration) = transition.effective_duration() {
+            self.duration = duration as f32;
+        }
+        self.easing = transition.ease.clone();
+        self
+    }
+
     /// Update animation progress
     pub fn update(&mut self, delta_time: f32) -> Transform3D {
         if !self.is_playing {
@@ -284,7 +295,7 @@ impl Transform3DAnimation {
 
     /// Get current interpolated transform
     pub fn current_transform(&self) -> Transform3D {
-        let t = self.easing.apply(self.progress);
+        let t = self.easing.evaluate(self.progress.into()) as f32;
         self.from.lerp(&self.to, t)
     }
 
@@ -310,117 +321,6 @@ impl Transform3DAnimation {
     }
 }
 
-/// Easing functions for 3D animations
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum EasingFunction {
-    Linear,
-    EaseIn,
-    EaseOut,
-    EaseInOut,
-    EaseInBack,
-    EaseOutBack,
-    EaseInOutBack,
-    EaseInElastic,
-    EaseOutElastic,
-    EaseInOutElastic,
-    EaseInBounce,
-    EaseOutBounce,
-    EaseInOutBounce,
-}
-
-impl EasingFunction {
-    /// Apply easing function to input value
-    pub fn apply(&self, t: f32) -> f32 {
-        let t = t.clamp(0.0, 1.0);
-
-        match self {
-            Self::Linear => t,
-            Self::EaseIn => t * t,
-            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
-            Self::EaseInOut => {
-                if t < 0.5 {
-                    2.0 * t * t
-                } else {
-                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
-                }
-            }
-            Self::EaseInBack => {
-                let c1 = 1.70158;
-                let c3 = c1 + 1.0;
-                c3 * t * t * t - c1 * t * t
-            }
-            Self::EaseOutBack => {
-                let c1 = 1.70158;
-                let c3 = c1 + 1.0;
-                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
-            }
-            Self::EaseInOutBack => {
-                let c1 = 1.70158;
-                let c2 = c1 * 1.525;
-                if t < 0.5 {
-                    (2.0 * t).powi(2) * ((c2 + 1.0) * 2.0 * t - c2) / 2.0
-                } else {
-                    ((2.0 * t - 2.0).powi(2) * ((c2 + 1.0) * (t * 2.0 - 2.0) + c2) + 2.0) / 2.0
-                }
-            }
-            Self::EaseInElastic => {
-                let c4 = (2.0 * std::f32::consts::PI) / 3.0;
-                if t == 0.0 {
-                    0.0
-                } else if t == 1.0 {
-                    1.0
-                } else {
-                    -2_f32.powf(10.0 * (t - 1.0))
-                        * ((t - 1.0) * c4 - std::f32::consts::PI / 2.0).sin()
-                }
-            }
-            Self::EaseOutElastic => {
-                let c4 = (2.0 * std::f32::consts::PI) / 3.0;
-                if t == 0.0 {
-                    0.0
-                } else if t == 1.0 {
-                    1.0
-                } else {
-                    2_f32.powf(-10.0 * t) * (t * c4 - std::f32::consts::PI / 2.0).sin() + 1.0
-                }
-            }
-            Self::EaseInOutElastic => {
-                let c5 = (2.0 * std::f32::consts::PI) / 4.5;
-                if t == 0.0 {
-                    0.0
-                } else if t == 1.0 {
-                    1.0
-                } else if t < 0.5 {
-                    -(2_f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * c5).sin()) / 2.0
-                } else {
-                    (2_f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * c5).sin()) / 2.0 + 1.0
-                }
-            }
-            Self::EaseInBounce => 1.0 - Self::EaseOutBounce.apply(1.0 - t),
-            Self::EaseOutBounce => {
-                let n1 = 7.5625;
-                let d1 = 2.75;
-                if t < 1.0 / d1 {
-                    n1 * t * t
-                } else if t < 2.0 / d1 {
-                    n1 * (t - 1.5 / d1) * (t - 1.5 / d1) + 0.75
-                } else if t < 2.5 / d1 {
-                    n1 * (t - 2.25 / d1) * (t - 2.25 / d1) + 0.9375
-                } else {
-                    n1 * (t - 2.625 / d1) * (t - 2.625 / d1) + 0.984375
-                }
-            }
-            Self::EaseInOutBounce => {
-                if t < 0.5 {
-                    (1.0 - Self::EaseOutBounce.apply(1.0 - 2.0 * t)) / 2.0
-                } else {
-                    (1.0 + Self::EaseOutBounce.apply(2.0 * t - 1.0)) / 2.0
-                }
-            }
-        }
-    }
-}
-
 /// 3D Transform editor component
 #[component]
 pub fn Transform3DEditor(
@@ -663,19 +563,19 @@ mod tests {
     #[test]
     fn test_easing_functions() {
         for easing in [
-            EasingFunction::Linear,
-            EasingFunction::EaseIn,
-            EasingFunction::EaseOut,
-            EasingFunction::EaseInOut,
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
         ] {
             // Test boundary conditions
-            assert_relative_eq!(easing.apply(0.0), 0.0, epsilon = 1e-6);
-            assert_relative_eq!(easing.apply(1.0), 1.0, epsilon = 1e-6);
+            assert_relative_eq!(easing.evaluate(0.0), 0.0, epsilon = 1e-6);
+            assert_relative_eq!(easing.evaluate(1.0), 1.0, epsilon = 1e-6);
 
             // Test monotonicity for simple functions
             let t1 = 0.3;
             let t2 = 0.7;
-            assert!(easing.apply(t1) <= easing.apply(t2));
+            assert!(easing.evaluate(t1) <= easing.evaluate(t2));
         }
     }
 
@@ -698,6 +598,24 @@ mod tests {
         assert_relative_eq!(transform.translation.x, 5.0, epsilon = 1e-6);
     }
 
+    #[test]
+    fn test_transform3d_animation_with_transition() {
+        let from = Transform3D::from_translation(Vec3::ZERO);
+        let to = Transform3D::from_translation(Vec3::new(10.0, 0.0, 0.0));
+        let transition = Transition {
+            duration: Some(2.0),
+            ease: Easing::Linear,
+            ..Default::default()
+        };
+
+        let mut animation = Transform3DAnimation::new(from, to, 1.0).with_transition(&transition);
+        assert_eq!(animation.duration, 2.0);
+
+        animation.play();
+        let transform = animation.update(0.5);
+        assert_relative_eq!(transform.translation.x, 2.5, epsilon = 1e-6);
+    }
+
     #[test]
     fn test_matrix_utilities() {
         let matrix = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
@@ -176,14 +176,11 @@ fn test_svg_morphing_integration() {
 
     // Test morphing
     let mut morpher = PathMorpher::new(path1, path2).unwrap();
-    morpher.prepare().unwrap();
+    morpher.prepare();
 
     // Test interpolation at different points
     for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
-        let interpolated = morpher.interpolate(t);
-        assert!(interpolated.is_ok(), "Interpolation failed at t={}", t);
-
-        let result = interpolated.unwrap();
+        let result = morpher.interpolate(t);
         assert!(!result.to_data().is_empty());
 
         if t == 0.0 {
@@ -138,6 +138,9 @@ pub use leptos_motion_dom::{
     PresenceMode,
 };
 
+#[cfg(feature = "router")]
+pub use leptos_motion_dom::{RoutePage, RouteTransitions, ViewTransitionEngine};
+
 // Re-export macros
 pub use leptos_motion_macros::motion_target;
 
@@ -1,6 +1,6 @@
 use leptos::prelude::*;
 use leptos_motion_core::AnimationValue;
-use leptos_motion_dom::ReactiveMotionDiv;
+use leptos_motion_dom::reactive_motion_div::ReactiveMotionDiv;
 use std::collections::HashMap;
 
 /// Advanced 3D Demo Component
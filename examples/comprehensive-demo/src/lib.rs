@@ -5,28 +5,46 @@
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
 
+#[allow(deprecated)]
 mod advanced_3d_demo;
+#[allow(deprecated)]
 mod animate_fn_test;
+#[allow(deprecated)]
 mod animation_3d_demo;
 mod basic_test_demo;
+#[allow(deprecated)]
 mod batched_updates_test;
+#[allow(deprecated)]
 mod fixed_reactive_demo;
+#[allow(deprecated)]
 mod memoization_test;
 mod minimal_comprehensive_demo;
+#[allow(deprecated)]
 mod minimal_motion_test;
 mod minimal_test_demo;
+#[allow(deprecated)]
 mod motion_showcase_demo;
+#[allow(deprecated)]
 mod phase4a_demo;
+#[allow(deprecated)]
 mod reactive_motion_div_test;
+#[allow(deprecated)]
 mod signal_based_comprehensive_demo;
+#[allow(deprecated)]
 mod simple_demo;
 mod simple_div_test;
+#[allow(deprecated)]
 mod simple_phase4a_test;
+#[allow(deprecated)]
 mod simple_working_demo;
+#[allow(deprecated)]
 mod simplified_showcase;
+#[allow(deprecated)]
 mod tdd_reactive_demo;
+#[allow(deprecated)]
 mod transition_config_test;
 mod ultra_simple_demo;
+#[allow(deprecated)]
 mod working_tdd_demo;
 // mod simple_comprehensive_demo; // Commented out
 // mod simplified_comprehensive_demo; // Commented out
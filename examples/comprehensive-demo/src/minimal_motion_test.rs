@@ -1,6 +1,6 @@
 use leptos::prelude::*;
 use leptos_motion_core::{AnimationValue, Easing, RepeatConfig, Transition};
-use leptos_motion_dom::MinimalMotionDiv;
+use leptos_motion_dom::minimal_motion_div::MinimalMotionDiv;
 use std::collections::HashMap;
 
 /// Minimal Motion Test Component
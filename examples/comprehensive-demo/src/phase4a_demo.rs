@@ -6,7 +6,7 @@ use leptos::prelude::*;
 use leptos_motion_core::{AnimationTarget, AnimationValue};
 use std::collections::HashMap;
 
-use leptos_motion_dom::ReactiveMotionDiv;
+use leptos_motion_dom::reactive_motion_div::ReactiveMotionDiv;
 
 /// Helper function to create a simple animation target
 fn create_animation_target(key: &str, value: f64) -> AnimationTarget {
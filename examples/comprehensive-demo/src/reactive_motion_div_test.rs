@@ -3,7 +3,7 @@
 //! A minimal test to see if ReactiveMotionDiv works
 
 use leptos::prelude::*;
-use leptos_motion_dom::ReactiveMotionDiv;
+use leptos_motion_dom::reactive_motion_div::ReactiveMotionDiv;
 
 #[component]
 pub fn ReactiveMotionDivTest() -> impl IntoView {
@@ -51,6 +51,7 @@ pub fn SimpleAnimationTest() -> impl IntoView {
                 AnimationValue::Radians(r) => format!("{}rad", r),
                 AnimationValue::Transform(t) => format!("{:?}", t),
                 AnimationValue::Complex(_) => "complex".to_string(),
+                AnimationValue::Auto => "auto".to_string(),
             };
             styles.push(format!("{}: {}", key, css_value));
         }
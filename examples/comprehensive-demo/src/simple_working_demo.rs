@@ -4,7 +4,7 @@
 
 use leptos::prelude::*;
 use leptos_motion_core::*;
-use leptos_motion_dom::ReactiveMotionDiv;
+use leptos_motion_dom::reactive_motion_div::ReactiveMotionDiv;
 use std::collections::HashMap;
 
 #[component]
@@ -411,7 +411,7 @@ fn LayoutAnimationDemo() -> impl IntoView {
                 {move || (0..6).map(|i| {
                     view! {
                         <MotionDiv
-                            _layout=true
+                            layout=true
                             _transition=Transition {
                                 duration: Some(0.5),
                                 ease: Easing::EaseInOut,
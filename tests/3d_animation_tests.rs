@@ -1,8 +1,9 @@
 #![cfg(test)]
+#![allow(deprecated)]
 
 use leptos::prelude::*;
 use leptos_motion_core::{AnimationValue, Easing, RepeatConfig, Transition};
-use leptos_motion_dom::ReactiveMotionDiv;
+use leptos_motion_dom::reactive_motion_div::ReactiveMotionDiv;
 use std::collections::HashMap;
 use wasm_bindgen_test::*;
 
@@ -168,14 +168,14 @@ fn test_gesture_props_usage() {
     });
 }
 
-/// Test 7: Layout Prop (should be prefixed with _)
-/// Expected: Should work with _layout prop
+/// Test 7: Layout Prop
+/// Expected: Should work with layout prop
 #[test]
 fn test_layout_prop_usage() {
     let _ = create_scope(|cx| {
         let _component = view! { cx,
             <MotionDiv
-                _layout=Some(true)
+                layout=Some(true)
             >
                 "Test Content"
             </MotionDiv>
@@ -219,7 +219,7 @@ fn test_optional_parameters() {
                 transition=None
                 _while_hover=None
                 _while_tap=None
-                _layout=None
+                layout=None
                 drag=None
                 drag_constraints=None
             >
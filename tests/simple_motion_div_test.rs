@@ -51,7 +51,7 @@ fn test_motion_div_all_optional_params() {
                 transition=None
                 _while_hover=None
                 _while_tap=None
-                _layout=None
+                layout=None
                 drag=None
                 drag_constraints=None
             >